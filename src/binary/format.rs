@@ -0,0 +1,150 @@
+//! The fixed-size header in front of every serialized chunk
+
+use crate::binary::BinaryError;
+
+pub const MAGIC_NUMBER: &[u8; 4] = b"NEON";
+
+/// Bump whenever opcodes, their operands, the order of the builtin globals or this header
+/// change. Version 2 added the checksum, version 3 the compression method, version 4 the
+/// `TailCall` instruction, version 5 `Same`, version 6 the destructuring instructions,
+/// version 7 `Tuck`, version 8 enum constants, version 9 the module instructions,
+/// version 10 the `try` instructions, version 11 `Throw` and version 12 `CloseUpvalues`;
+/// older files are rejected as unsupported.
+pub const FORMAT_VERSION: u16 = 12;
+
+const VERSION_OFFSET: usize = MAGIC_NUMBER.len();
+const COMPRESSION_OFFSET: usize = VERSION_OFFSET + 2;
+const CHECKSUM_OFFSET: usize = COMPRESSION_OFFSET + 1;
+
+/// How the chunk following the header is stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Compression {
+    #[default]
+    None,
+    Deflate,
+}
+
+impl Compression {
+    fn to_byte(self) -> u8 {
+        match self {
+            Compression::None => 0,
+            Compression::Deflate => 1,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Result<Self, BinaryError> {
+        match byte {
+            0 => Ok(Compression::None),
+            1 => Ok(Compression::Deflate),
+            _ => Err(BinaryError::Corrupt(format!(
+                "unknown compression method {}",
+                byte
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BinaryHeader {
+    pub version: u16,
+    pub compression: Compression,
+    /// CRC-32 of the stored (possibly compressed) chunk following the header
+    pub checksum: u32,
+}
+
+impl BinaryHeader {
+    /// Magic number, then the little-endian format version, the compression method byte and
+    /// the little-endian checksum
+    pub const LEN: usize = CHECKSUM_OFFSET + 4;
+
+    /// Header of the current format for the given stored chunk
+    pub fn for_body(body: &[u8], compression: Compression) -> Self {
+        BinaryHeader {
+            version: FORMAT_VERSION,
+            compression,
+            checksum: crc32(body),
+        }
+    }
+
+    pub fn write(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(MAGIC_NUMBER);
+        out.extend_from_slice(&self.version.to_le_bytes());
+        out.push(self.compression.to_byte());
+        out.extend_from_slice(&self.checksum.to_le_bytes());
+    }
+
+    /// Splits off the header; data from a format this neon can't read is an error
+    pub fn read(bytes: &[u8]) -> Result<(BinaryHeader, &[u8]), BinaryError> {
+        if !bytes.starts_with(MAGIC_NUMBER) {
+            return Err(BinaryError::BadMagic);
+        }
+        let Some(version) = bytes.get(VERSION_OFFSET..COMPRESSION_OFFSET) else {
+            return Err(BinaryError::Corrupt("truncated header".to_string()));
+        };
+        let version = u16::from_le_bytes([version[0], version[1]]);
+        if !BinaryFormat::is_compatible(version) {
+            return Err(BinaryError::UnsupportedVersion {
+                found: version,
+                expected: FORMAT_VERSION,
+            });
+        }
+
+        let Some(checksum) = bytes.get(CHECKSUM_OFFSET..Self::LEN) else {
+            return Err(BinaryError::Corrupt("truncated header".to_string()));
+        };
+        let compression = Compression::from_byte(bytes[COMPRESSION_OFFSET])?;
+        let checksum = u32::from_le_bytes([checksum[0], checksum[1], checksum[2], checksum[3]]);
+        let header = BinaryHeader {
+            version,
+            compression,
+            checksum,
+        };
+        Ok((header, &bytes[Self::LEN..]))
+    }
+
+    /// Checks that the body is the one the header was written for, not a truncated or damaged one
+    pub fn verify(&self, body: &[u8]) -> Result<(), BinaryError> {
+        if crc32(body) != self.checksum {
+            return Err(BinaryError::ChecksumMismatch);
+        }
+        Ok(())
+    }
+}
+
+pub struct BinaryFormat;
+
+impl BinaryFormat {
+    /// Whether data written with the given format version can be read
+    pub fn is_compatible(version: u16) -> bool {
+        version == FORMAT_VERSION
+    }
+}
+
+/// CRC-32 (IEEE 802.3, the one used by zip and png)
+pub(crate) fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc = CRC32_TABLE[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8);
+    }
+    !crc
+}
+
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                0xedb8_8320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
@@ -0,0 +1,21 @@
+//! Compiled programs on disk
+
+use crate::binary::{deserialize_chunk, serialize_chunk_with, BinaryError, Compression};
+use crate::common::Chunk;
+use std::path::Path;
+
+/// Writes the chunk as a `.nbc` file, deflating it when asked to
+pub fn write_binary_file(
+    path: impl AsRef<Path>,
+    chunk: &Chunk,
+    compression: Compression,
+) -> Result<(), BinaryError> {
+    let bytes = serialize_chunk_with(chunk, compression)?;
+    std::fs::write(path, bytes).map_err(|error| BinaryError::Io(error.to_string()))
+}
+
+/// Reads a `.nbc` file, compressed or not
+pub fn read_binary_file(path: impl AsRef<Path>) -> Result<Chunk, BinaryError> {
+    let bytes = std::fs::read(path).map_err(|error| BinaryError::Io(error.to_string()))?;
+    deserialize_chunk(&bytes)
+}
@@ -0,0 +1,366 @@
+//! Reading and writing compiled programs (`.nbc` files).
+//!
+//! A file starts with a [`BinaryHeader`] (magic number, format version, compression method and
+//! checksum), followed by the bincode-encoded top-level chunk, deflated if the header says so.
+//! Native functions are stored by their registry name rather than their index, so a file stays
+//! valid when methods are added.
+
+use crate::common::method_registry::{get_native_method_index, NATIVE_METHODS};
+use crate::common::{
+    Chunk, Constants, Local, ObjFunction, ObjNativeFunction, Object, SourceLocation, Value,
+};
+use crate::string;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::io::{Read, Write};
+use std::rc::Rc;
+
+mod format;
+mod io;
+#[cfg(test)]
+mod tests;
+
+pub use format::{BinaryFormat, BinaryHeader, Compression, FORMAT_VERSION, MAGIC_NUMBER};
+pub use io::{read_binary_file, write_binary_file};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BinaryError {
+    Io(String),
+    BadMagic,
+    UnsupportedVersion { found: u16, expected: u16 },
+    ChecksumMismatch,
+    Corrupt(String),
+    UnsupportedConstant(String),
+    UnknownNative(String),
+}
+
+impl Display for BinaryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BinaryError::Io(message) => write!(f, "{}", message),
+            BinaryError::BadMagic => write!(f, "not a compiled neon program"),
+            BinaryError::UnsupportedVersion { found, expected } => write!(
+                f,
+                "compiled with bytecode format {}, but this neon runs format {}; recompile the source",
+                found, expected
+            ),
+            BinaryError::ChecksumMismatch => {
+                write!(f, "checksum mismatch, the file is truncated or damaged")
+            }
+            BinaryError::Corrupt(message) => write!(f, "corrupt bytecode: {}", message),
+            BinaryError::UnsupportedConstant(kind) => {
+                write!(f, "{} constants can't be stored in bytecode", kind)
+            }
+            BinaryError::UnknownNative(name) => {
+                write!(f, "program uses '{}', which this neon doesn't provide", name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BinaryError {}
+
+pub fn serialize_chunk(chunk: &Chunk) -> Result<Vec<u8>, BinaryError> {
+    serialize_chunk_with(chunk, Compression::None)
+}
+
+pub fn serialize_chunk_with(
+    chunk: &Chunk,
+    compression: Compression,
+) -> Result<Vec<u8>, BinaryError> {
+    let data = ChunkData::from_chunk(chunk)?;
+    let encoded =
+        bincode::serialize(&data).map_err(|error| BinaryError::Corrupt(error.to_string()))?;
+    let body = match compression {
+        Compression::None => encoded,
+        Compression::Deflate => deflate(&encoded)?,
+    };
+
+    let mut bytes = Vec::with_capacity(BinaryHeader::LEN + body.len());
+    BinaryHeader::for_body(&body, compression).write(&mut bytes);
+    bytes.extend_from_slice(&body);
+    Ok(bytes)
+}
+
+/// Reads data written by either [`serialize_chunk`] or [`serialize_chunk_with`]; the header
+/// says whether the chunk has to be inflated first
+pub fn deserialize_chunk(bytes: &[u8]) -> Result<Chunk, BinaryError> {
+    let (header, body) = BinaryHeader::read(bytes)?;
+    header.verify(body)?;
+    let inflated;
+    let encoded = match header.compression {
+        Compression::None => body,
+        Compression::Deflate => {
+            inflated = inflate(body)?;
+            &inflated
+        }
+    };
+    let data: ChunkData =
+        bincode::deserialize(encoded).map_err(|error| BinaryError::Corrupt(error.to_string()))?;
+    data.into_chunk()
+}
+
+fn deflate(bytes: &[u8]) -> Result<Vec<u8>, BinaryError> {
+    let mut encoder = DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder
+        .write_all(bytes)
+        .and_then(|_| encoder.finish())
+        .map_err(|error| BinaryError::Io(error.to_string()))
+}
+
+fn inflate(bytes: &[u8]) -> Result<Vec<u8>, BinaryError> {
+    let mut inflated = Vec::new();
+    DeflateDecoder::new(bytes)
+        .read_to_end(&mut inflated)
+        .map_err(|error| BinaryError::Corrupt(error.to_string()))?;
+    Ok(inflated)
+}
+
+#[derive(Serialize, Deserialize)]
+struct ChunkData {
+    name: String,
+    constants: Vec<ValueData>,
+    strings: Vec<ValueData>,
+    instructions: Vec<u8>,
+    source_locations: Vec<(usize, u32, u32)>,
+    locals: Vec<(String, i32, bool)>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct FunctionData {
+    name: String,
+    arity: u8,
+    min_arity: u8,
+    variadic: bool,
+    chunk: ChunkData,
+}
+
+/// The values the code generator puts into a chunk's constant and string tables
+#[derive(Serialize, Deserialize)]
+enum ValueData {
+    Number(f64),
+    Int(i64),
+    Boolean(bool),
+    Nil,
+    String(String),
+    Function(FunctionData),
+    /// Registry entry called by index, stored as `(namespace, method)`
+    NativeByIndex {
+        name: String,
+        arity: u8,
+        namespace: String,
+        method: String,
+    },
+    /// Method resolved from the receiver's type at run time
+    NativeByName {
+        name: String,
+        arity: u8,
+        method: String,
+    },
+    Struct {
+        name: String,
+        fields: Vec<String>,
+        defaults: Vec<ValueData>,
+        methods: Vec<(String, FunctionData)>,
+    },
+    Enum {
+        name: String,
+        variants: Vec<String>,
+    },
+}
+
+impl ChunkData {
+    fn from_chunk(chunk: &Chunk) -> Result<Self, BinaryError> {
+        Ok(ChunkData {
+            name: chunk.name.clone(),
+            constants: values_to_data(&chunk.constants.values)?,
+            strings: values_to_data(&chunk.strings.values)?,
+            instructions: chunk.instructions.clone(),
+            source_locations: chunk
+                .source_locations
+                .iter()
+                .map(|location| (location.offset, location.line, location.column))
+                .collect(),
+            locals: chunk
+                .locals
+                .iter()
+                .map(|local| (local.name.clone(), local.depth, local.is_captured))
+                .collect(),
+        })
+    }
+
+    fn into_chunk(self) -> Result<Chunk, BinaryError> {
+        Ok(Chunk {
+            name: self.name,
+            constants: Constants {
+                values: data_to_values(self.constants)?,
+            },
+            strings: Constants {
+                values: data_to_values(self.strings)?,
+            },
+            instructions: self.instructions,
+            source_locations: self
+                .source_locations
+                .into_iter()
+                .map(|(offset, line, column)| SourceLocation {
+                    offset,
+                    line,
+                    column,
+                })
+                .collect(),
+            locals: self
+                .locals
+                .into_iter()
+                .map(|(name, depth, is_captured)| Local {
+                    name,
+                    depth,
+                    is_captured,
+                })
+                .collect(),
+        })
+    }
+}
+
+impl FunctionData {
+    fn from_function(function: &ObjFunction) -> Result<Self, BinaryError> {
+        Ok(FunctionData {
+            name: function.name.clone(),
+            arity: function.arity,
+            min_arity: function.min_arity,
+            variadic: function.variadic,
+            chunk: ChunkData::from_chunk(&function.chunk)?,
+        })
+    }
+
+    fn into_function(self) -> Result<ObjFunction, BinaryError> {
+        Ok(ObjFunction {
+            name: self.name,
+            arity: self.arity,
+            min_arity: self.min_arity,
+            variadic: self.variadic,
+            chunk: Rc::new(self.chunk.into_chunk()?),
+            upvalues: Vec::new(),
+        })
+    }
+}
+
+fn values_to_data(values: &[Value]) -> Result<Vec<ValueData>, BinaryError> {
+    values.iter().map(value_to_data).collect()
+}
+
+fn data_to_values(data: Vec<ValueData>) -> Result<Vec<Value>, BinaryError> {
+    data.into_iter().map(data_to_value).collect()
+}
+
+fn value_to_data(value: &Value) -> Result<ValueData, BinaryError> {
+    let object = match value {
+        Value::Number(n) => return Ok(ValueData::Number(*n)),
+        Value::Int(i) => return Ok(ValueData::Int(*i)),
+        Value::Boolean(b) => return Ok(ValueData::Boolean(*b)),
+        Value::Nil => return Ok(ValueData::Nil),
+        Value::Object(object) => object,
+    };
+    match object.as_ref() {
+        Object::String(s) => Ok(ValueData::String(s.value.to_string())),
+        Object::Function(function) => {
+            Ok(ValueData::Function(FunctionData::from_function(function)?))
+        }
+        Object::NativeFunction(native) => native_to_data(native),
+        Object::Struct(r#struct) => {
+            let mut methods = r#struct
+                .methods
+                .iter()
+                .map(|(name, method)| Ok((name.clone(), FunctionData::from_function(method)?)))
+                .collect::<Result<Vec<_>, BinaryError>>()?;
+            methods.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Ok(ValueData::Struct {
+                name: r#struct.name.clone(),
+                fields: r#struct.fields.clone(),
+                defaults: values_to_data(&r#struct.defaults)?,
+                methods,
+            })
+        }
+        Object::Array(_) => Err(BinaryError::UnsupportedConstant("Array".to_string())),
+        Object::Map(_) => Err(BinaryError::UnsupportedConstant("Map".to_string())),
+        Object::Set(_) => Err(BinaryError::UnsupportedConstant("Set".to_string())),
+        Object::Instance(_) => Err(BinaryError::UnsupportedConstant("Instance".to_string())),
+        Object::File(_) => Err(BinaryError::UnsupportedConstant("File".to_string())),
+        Object::Tuple(_) => Err(BinaryError::UnsupportedConstant("Tuple".to_string())),
+        Object::Frozen(_) => Err(BinaryError::UnsupportedConstant("Frozen".to_string())),
+        Object::Enum(r#enum) => Ok(ValueData::Enum {
+            name: r#enum.name.clone(),
+            variants: r#enum.variants.clone(),
+        }),
+        Object::EnumVariant(..) => Err(BinaryError::UnsupportedConstant("EnumVariant".to_string())),
+        Object::Module(_) => Err(BinaryError::UnsupportedConstant("Module".to_string())),
+        Object::Error(_) => Err(BinaryError::UnsupportedConstant("Error".to_string())),
+        Object::Result(_) => Err(BinaryError::UnsupportedConstant("Result".to_string())),
+        Object::Option(_) => Err(BinaryError::UnsupportedConstant("Option".to_string())),
+    }
+}
+
+fn native_to_data(native: &ObjNativeFunction) -> Result<ValueData, BinaryError> {
+    if native.method_index == u32::MAX {
+        return Ok(ValueData::NativeByName {
+            name: native.name.clone(),
+            arity: native.arity,
+            method: native.method_name.clone(),
+        });
+    }
+    let (namespace, method, _) = NATIVE_METHODS
+        .get(native.method_index as usize)
+        .ok_or_else(|| BinaryError::UnknownNative(native.name.clone()))?;
+    Ok(ValueData::NativeByIndex {
+        name: native.name.clone(),
+        arity: native.arity,
+        namespace: namespace.to_string(),
+        method: method.to_string(),
+    })
+}
+
+fn data_to_value(data: ValueData) -> Result<Value, BinaryError> {
+    Ok(match data {
+        ValueData::Number(n) => Value::Number(n),
+        ValueData::Int(i) => Value::Int(i),
+        ValueData::Boolean(b) => Value::Boolean(b),
+        ValueData::Nil => Value::Nil,
+        ValueData::String(s) => string!(s),
+        ValueData::Function(function) => Value::new_function(function.into_function()?),
+        ValueData::NativeByIndex {
+            name,
+            arity,
+            namespace,
+            method,
+        } => {
+            let index = get_native_method_index(&namespace, &method).ok_or_else(|| {
+                BinaryError::UnknownNative(if namespace.is_empty() {
+                    method.clone()
+                } else {
+                    format!("{}.{}", namespace, method)
+                })
+            })?;
+            Value::new_native_function(name, arity, index as u32, String::new())
+        }
+        ValueData::NativeByName {
+            name,
+            arity,
+            method,
+        } => Value::new_native_function(name, arity, u32::MAX, method),
+        ValueData::Struct {
+            name,
+            fields,
+            defaults,
+            methods,
+        } => {
+            let methods = methods
+                .into_iter()
+                .map(|(name, method)| Ok((name, Rc::new(method.into_function()?))))
+                .collect::<Result<HashMap<_, _>, BinaryError>>()?;
+            Value::new_struct(name, fields, data_to_values(defaults)?, methods)
+        }
+        ValueData::Enum { name, variants } => Value::new_enum(name, variants),
+    })
+}
@@ -0,0 +1,231 @@
+use crate::binary::format::crc32;
+use crate::binary::{
+    deserialize_chunk, read_binary_file, serialize_chunk, serialize_chunk_with, write_binary_file,
+    BinaryError, BinaryFormat, Compression, FORMAT_VERSION, MAGIC_NUMBER,
+};
+use crate::vm::{Result, VirtualMachine};
+
+fn round_trip(source: &str) -> String {
+    let chunk = crate::compile(source).expect("program should compile");
+    let bytes = serialize_chunk(&chunk).expect("chunk should serialize");
+    let restored = deserialize_chunk(&bytes).expect("chunk should deserialize");
+    assert_eq!(chunk.instructions, restored.instructions);
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.execute_chunk(restored));
+    vm.get_output()
+}
+
+#[test]
+fn round_trip_runs_like_the_source() {
+    let output = round_trip(
+        r#"
+        val items = [3, 1, 2]
+        items.sort()
+        print(items, Math.abs(-4), "a,b".split(","))
+        print(7 //2, 1.5, nil, true)
+    "#,
+    );
+    assert_eq!("[1, 2, 3] 4 [a, b]\n3 1.5 nil true", output);
+}
+
+#[test]
+fn round_trip_keeps_static_and_instance_methods_of_the_same_name_apart() {
+    let output = round_trip(
+        r#"
+        print(Array.fill(0, 2), [1, 2].fill(3))
+    "#,
+    );
+    assert_eq!("[0, 0] [3, 3]", output);
+}
+
+#[test]
+fn round_trip_keeps_functions_closures_and_structs() {
+    let output = round_trip(
+        r#"
+        fn counter() {
+            var count = 0
+            return fn() {
+                count = count + 1
+                return count
+            }
+        }
+        struct Point {
+            x
+            y = 0
+            fn sum() {
+                return this.x + this.y
+            }
+        }
+        val next = counter()
+        next()
+        print(next())
+        print(Point(2).sum(), Point(x: 1, y: 5).sum())
+    "#,
+    );
+    assert_eq!("2\n2 6", output);
+}
+
+#[test]
+fn round_trip_keeps_enums() {
+    let output = round_trip(
+        r#"
+        enum Color { Red, Green }
+        print(Color.Green, Color.Red == Color.Red, Color.Red == Color.Green)
+    "#,
+    );
+    assert_eq!("Color.Green true false", output);
+}
+
+#[test]
+fn rejects_data_without_the_magic_number() {
+    assert_eq!(
+        Err(BinaryError::BadMagic),
+        deserialize_chunk(b"print(1)").map(|_| ())
+    );
+    assert_eq!(
+        Err(BinaryError::BadMagic),
+        deserialize_chunk(b"").map(|_| ())
+    );
+}
+
+#[test]
+fn rejects_other_format_versions() {
+    let chunk = crate::compile("print(1)\n").unwrap();
+    let mut bytes = serialize_chunk(&chunk).unwrap();
+    bytes[MAGIC_NUMBER.len()..MAGIC_NUMBER.len() + 2]
+        .copy_from_slice(&(FORMAT_VERSION + 1).to_le_bytes());
+
+    let error = deserialize_chunk(&bytes).map(|_| ()).unwrap_err();
+    assert_eq!(
+        BinaryError::UnsupportedVersion {
+            found: FORMAT_VERSION + 1,
+            expected: FORMAT_VERSION
+        },
+        error
+    );
+    assert!(error.to_string().contains("recompile"));
+}
+
+#[test]
+fn rejects_truncated_data() {
+    let chunk = crate::compile("print(1)\n").unwrap();
+    let bytes = serialize_chunk(&chunk).unwrap();
+
+    let result = deserialize_chunk(&bytes[..bytes.len() - 3]).map(|_| ());
+    assert_eq!(Err(BinaryError::ChecksumMismatch), result);
+}
+
+#[test]
+fn rejects_altered_body() {
+    let chunk = crate::compile("print(1)\n").unwrap();
+    let mut bytes = serialize_chunk(&chunk).unwrap();
+    let last = bytes.len() - 1;
+    bytes[last] ^= 0x01;
+
+    let result = deserialize_chunk(&bytes).map(|_| ());
+    assert_eq!(Err(BinaryError::ChecksumMismatch), result);
+}
+
+#[test]
+fn checksum_is_standard_crc32() {
+    assert_eq!(0, crc32(b""));
+    assert_eq!(0xcbf4_3926, crc32(b"123456789"));
+}
+
+#[test]
+fn rejects_corrupted_magic_number() {
+    let chunk = crate::compile("print(1)\n").unwrap();
+    let mut bytes = serialize_chunk(&chunk).unwrap();
+    bytes[0] = b'X';
+
+    assert_eq!(
+        Err(BinaryError::BadMagic),
+        deserialize_chunk(&bytes).map(|_| ())
+    );
+}
+
+#[test]
+fn rejects_older_format_versions() {
+    let chunk = crate::compile("print(1)\n").unwrap();
+    let mut bytes = serialize_chunk(&chunk).unwrap();
+    bytes[MAGIC_NUMBER.len()..MAGIC_NUMBER.len() + 2]
+        .copy_from_slice(&(FORMAT_VERSION - 1).to_le_bytes());
+
+    assert_eq!(
+        Err(BinaryError::UnsupportedVersion {
+            found: FORMAT_VERSION - 1,
+            expected: FORMAT_VERSION
+        }),
+        deserialize_chunk(&bytes).map(|_| ())
+    );
+}
+
+#[test]
+fn rejects_truncated_header() {
+    let result = deserialize_chunk(&MAGIC_NUMBER[..]).map(|_| ());
+    assert!(matches!(result, Err(BinaryError::Corrupt(_))));
+}
+
+#[test]
+fn only_the_current_format_is_compatible() {
+    assert!(BinaryFormat::is_compatible(FORMAT_VERSION));
+    assert!(!BinaryFormat::is_compatible(FORMAT_VERSION + 1));
+    assert!(!BinaryFormat::is_compatible(FORMAT_VERSION - 1));
+}
+
+#[test]
+fn deflated_round_trip_runs_like_the_source() {
+    let chunk = crate::compile("val total = [1, 2, 3].sum()\nprint(total, \"done\")\n").unwrap();
+    let bytes = serialize_chunk_with(&chunk, Compression::Deflate).unwrap();
+    let restored = deserialize_chunk(&bytes).expect("compressed chunk should deserialize");
+    assert_eq!(chunk.instructions, restored.instructions);
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.execute_chunk(restored));
+    assert_eq!("6 done", vm.get_output());
+}
+
+#[test]
+fn files_round_trip_with_and_without_compression() {
+    let chunk = crate::compile("print(\"hello\")\n").unwrap();
+    let directory = std::env::temp_dir();
+    for (name, compression) in [
+        ("neon_plain_round_trip.nbc", Compression::None),
+        ("neon_deflated_round_trip.nbc", Compression::Deflate),
+    ] {
+        let path = directory.join(format!("{}_{}", std::process::id(), name));
+        write_binary_file(&path, &chunk, compression).unwrap();
+        let restored = read_binary_file(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        let mut vm = VirtualMachine::new();
+        assert_eq!(Result::Ok, vm.execute_chunk(restored.unwrap()));
+        assert_eq!("hello", vm.get_output());
+    }
+}
+
+#[test]
+fn compression_shrinks_repetitive_programs() {
+    let source = "print(\"the same line over and over\")\n".repeat(200);
+    let chunk = crate::compile(&source).unwrap();
+
+    let plain = serialize_chunk(&chunk).unwrap();
+    let deflated = serialize_chunk_with(&chunk, Compression::Deflate).unwrap();
+    assert!(
+        deflated.len() < plain.len() / 2,
+        "{} bytes deflated vs {} plain",
+        deflated.len(),
+        plain.len()
+    );
+}
+
+#[test]
+fn rejects_unknown_compression_method() {
+    let chunk = crate::compile("print(1)\n").unwrap();
+    let mut bytes = serialize_chunk(&chunk).unwrap();
+    bytes[MAGIC_NUMBER.len() + 2] = 9;
+
+    let result = deserialize_chunk(&bytes).map(|_| ());
+    assert!(matches!(result, Err(BinaryError::Corrupt(_))));
+}
@@ -137,6 +137,15 @@ impl Chunk {
         self.instructions.len() as u32 - 4
     }
 
+    /// Jump taken when the caller supplied the argument for parameter `param`.
+    /// The parameter index precedes the jump offset, so `patch_jump` works unchanged.
+    pub(crate) fn emit_jump_if_arg_supplied(&mut self, param: u8, line: u32, column: u32) -> u32 {
+        self.write_op_code(OpCode::JumpIfArgSupplied, line, column);
+        self.write_u8(param);
+        self.write_u32(0xFFFF_FFFF);
+        self.instructions.len() as u32 - 4
+    }
+
     pub(crate) fn patch_jump(&mut self, offset: u32) {
         let jump = self.instructions.len() as u32 - offset - 4;
         let offset = offset as usize;
@@ -152,6 +161,95 @@ impl Chunk {
         self.write_u32(offset);
     }
 
+    /// Length in bytes of the instruction at `offset`, including its operands
+    pub(crate) fn instruction_len(&self, offset: usize) -> usize {
+        match OpCode::from_u8(self.read_u8(offset)) {
+            OpCode::Constant
+            | OpCode::String
+            | OpCode::GetLocal
+            | OpCode::SetLocal
+            | OpCode::GetBuiltin
+            | OpCode::GetGlobal
+            | OpCode::SetGlobal
+            | OpCode::GetField
+            | OpCode::SetField
+            | OpCode::Call
+            | OpCode::TailCall
+            | OpCode::CreateMap
+            | OpCode::CreateSet
+            | OpCode::CreateRange
+            | OpCode::GetUpvalue
+            | OpCode::SetUpvalue => 2,
+            OpCode::Constant2
+            | OpCode::String2
+            | OpCode::GetLocal2
+            | OpCode::SetLocal2
+            | OpCode::GetBuiltin2
+            | OpCode::GetGlobal2
+            | OpCode::SetGlobal2
+            | OpCode::GetField2
+            | OpCode::SetField2
+            | OpCode::CreateArray
+            | OpCode::ExportModule => 3,
+            OpCode::Constant4
+            | OpCode::String4
+            | OpCode::GetLocal4
+            | OpCode::SetLocal4
+            | OpCode::GetBuiltin4
+            | OpCode::GetGlobal4
+            | OpCode::SetGlobal4
+            | OpCode::GetField4
+            | OpCode::SetField4
+            | OpCode::JumpIfFalse
+            | OpCode::Jump
+            | OpCode::Loop
+            | OpCode::TryBegin
+            | OpCode::CloseUpvalues => 5,
+            OpCode::JumpIfArgSupplied => 6,
+            OpCode::CallNamed => 2 + self.read_u8(offset + 1) as usize * 4,
+            OpCode::Closure => 2 + self.read_u8(offset + 1) as usize * 5,
+            OpCode::Return
+            | OpCode::Negate
+            | OpCode::Add
+            | OpCode::Subtract
+            | OpCode::Multiply
+            | OpCode::Divide
+            | OpCode::FloorDivide
+            | OpCode::Modulo
+            | OpCode::Exponent
+            | OpCode::Nil
+            | OpCode::True
+            | OpCode::False
+            | OpCode::Equal
+            | OpCode::Same
+            | OpCode::UnpackElement
+            | OpCode::UnpackRest
+            | OpCode::Greater
+            | OpCode::Less
+            | OpCode::Not
+            | OpCode::Pop
+            | OpCode::GetIndex
+            | OpCode::SetIndex
+            | OpCode::GetIterator
+            | OpCode::IteratorNext
+            | OpCode::IteratorDone
+            | OpCode::PopIterator
+            | OpCode::ToString
+            | OpCode::BitwiseAnd
+            | OpCode::BitwiseOr
+            | OpCode::BitwiseXor
+            | OpCode::BitwiseNot
+            | OpCode::LeftShift
+            | OpCode::RightShift
+            | OpCode::Dup
+            | OpCode::Dup2
+            | OpCode::Tuck
+            | OpCode::Import
+            | OpCode::TryEnd
+            | OpCode::Throw => 1,
+        }
+    }
+
     pub(crate) fn instruction_count(&self) -> usize {
         self.instructions.len()
     }
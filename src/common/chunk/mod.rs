@@ -1,7 +1,9 @@
 mod constants;
 mod r#impl;
+mod stats;
 
-#[cfg(feature = "disassemble")]
 mod disassembler;
 #[cfg(test)]
 mod tests;
+
+pub use stats::ChunkStats;
@@ -0,0 +1,40 @@
+use crate::common::opcodes::OpCode;
+use crate::common::Chunk;
+use std::collections::BTreeMap;
+
+/// Size figures of a single chunk, as returned by [`Chunk::stats`].
+/// Functions defined in the chunk have their own chunks and are not included.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChunkStats {
+    /// Size of the bytecode in bytes, operands included
+    pub bytes: usize,
+    /// Number of instructions
+    pub instructions: usize,
+    pub constants: usize,
+    pub strings: usize,
+    pub locals: usize,
+    /// How often each opcode occurs, by opcode name
+    pub opcodes: BTreeMap<String, usize>,
+}
+
+impl Chunk {
+    /// Counts the instructions, constants, strings and locals of this chunk
+    pub fn stats(&self) -> ChunkStats {
+        let mut stats = ChunkStats {
+            bytes: self.instructions.len(),
+            constants: self.constants.values.len(),
+            strings: self.strings.values.len(),
+            locals: self.locals.len(),
+            ..ChunkStats::default()
+        };
+
+        let mut offset = 0;
+        while offset < self.instructions.len() {
+            let op_code = OpCode::from_u8(self.read_u8(offset));
+            *stats.opcodes.entry(format!("{:?}", op_code)).or_default() += 1;
+            stats.instructions += 1;
+            offset += self.instruction_len(offset);
+        }
+        stats
+    }
+}
@@ -0,0 +1,56 @@
+use crate::common::opcodes::OpCode;
+use crate::common::Chunk;
+use crate::number;
+use crate::vm::VirtualMachine;
+
+#[test]
+fn chunk_listing_names_each_instruction() {
+    let mut chunk = Chunk::new("sum");
+    chunk.write_constant(number!(1.0), 1, 0);
+    chunk.write_constant(number!(2.0), 1, 0);
+    chunk.write_op_code(OpCode::Add, 1, 0);
+    chunk.write_op_code(OpCode::Return, 2, 0);
+
+    let listing = chunk.disassemble();
+
+    assert_eq!(
+        "=== <sum>  ===\n\
+         0000      1 Constant 00 '1'\n\
+         0002      | Constant 01 '2'\n\
+         0004      | Add\n\
+         0005      2 Return\n\
+         === </sum> ===\n",
+        listing
+    );
+}
+
+#[test]
+fn compiled_program_can_be_disassembled() {
+    let mut vm = VirtualMachine::new();
+    let listing = vm
+        .disassemble("val a = 1\nval x = a + 2\nprint(x)\n")
+        .expect("program should compile");
+
+    assert!(listing.contains("Add"));
+    assert!(listing.contains("Call (args: 1)"));
+    assert!(listing.contains("Return"));
+}
+
+#[test]
+fn listing_includes_nested_functions() {
+    let mut vm = VirtualMachine::new();
+    let listing = vm
+        .disassemble("fn double(n) {\n    return n * 2\n}\nprint(double(21))\n")
+        .expect("program should compile");
+
+    assert!(listing.contains("=== <function_double>  ==="));
+    assert!(listing.contains("Multiply"));
+}
+
+#[test]
+fn disassemble_reports_compile_errors() {
+    let mut vm = VirtualMachine::new();
+
+    assert_eq!(None, vm.disassemble("val x = \n"));
+    assert!(!vm.get_formatted_errors("test.n").is_empty());
+}
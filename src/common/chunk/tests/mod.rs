@@ -1,2 +1,4 @@
 mod constants;
+mod disassembler;
 mod r#impl;
+mod stats;
@@ -0,0 +1,42 @@
+use crate::common::opcodes::OpCode;
+use crate::common::Chunk;
+use crate::number;
+
+#[test]
+fn stats_of_a_small_program() {
+    let chunk = crate::compile("val x = 1\nprint(x + 2)\n").unwrap();
+    let stats = chunk.stats();
+
+    assert_eq!(16, stats.bytes);
+    assert_eq!(10, stats.instructions);
+    assert_eq!(3, stats.constants);
+    assert_eq!(0, stats.strings);
+    assert_eq!(1, stats.locals);
+    assert_eq!(Some(&3), stats.opcodes.get("Constant"));
+    assert_eq!(Some(&1), stats.opcodes.get("Call"));
+    assert_eq!(Some(&1), stats.opcodes.get("Return"));
+    assert_eq!(stats.instructions, stats.opcodes.values().sum::<usize>());
+}
+
+#[test]
+fn stats_count_wide_operands_as_one_instruction() {
+    let mut chunk = Chunk::new("wide");
+    for i in 0..300 {
+        chunk.write_constant(number!(i as f64), 1, 1);
+    }
+    chunk.write_op_code(OpCode::Return, 1, 1);
+    let stats = chunk.stats();
+
+    assert_eq!(301, stats.instructions);
+    assert_eq!(256 * 2 + 44 * 3 + 1, stats.bytes);
+    assert_eq!(Some(&256), stats.opcodes.get("Constant"));
+    assert_eq!(Some(&44), stats.opcodes.get("Constant2"));
+}
+
+#[test]
+fn stats_of_an_empty_chunk() {
+    let stats = Chunk::new("empty").stats();
+
+    assert_eq!(0, stats.instructions);
+    assert!(stats.opcodes.is_empty());
+}
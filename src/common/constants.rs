@@ -1,6 +1,10 @@
 pub const MAX_FUNCTION_PARAMS: usize = 255;
 pub const MAX_CALL_ARGUMENTS: usize = 255;
 
+/// Most elements an array, or bytes a string, built in one go may have (about 67
+/// million). Larger sizes are a runtime error rather than a failed allocation.
+pub const MAX_SEQUENCE_LENGTH: usize = 1 << 26;
+
 /// Arity marker for variadic functions (functions that accept any number of arguments)
 /// Using u8::MAX (255) as a special marker to indicate variadic functions
 pub const VARIADIC_ARITY: u8 = u8::MAX;
@@ -1,4 +1,4 @@
-use crate::common::errors::CompilationError;
+use crate::common::errors::{CompilationError, Severity};
 use crate::common::SourceLocation;
 use colored::Colorize;
 
@@ -27,16 +27,30 @@ impl ErrorRenderer {
         }
 
         // Add summary
-        if !errors.is_empty() {
+        let error_count = errors
+            .iter()
+            .filter(|e| e.severity == Severity::Error)
+            .count();
+        let warning_count = errors.len() - error_count;
+        if error_count > 0 {
             output.push('\n');
-            let error_word = if errors.len() == 1 { "error" } else { "errors" };
+            let error_word = if error_count == 1 { "error" } else { "errors" };
             let summary = format!(
                 "\nerror: aborting due to {} previous {}",
-                errors.len(),
-                error_word
+                error_count, error_word
             );
             output.push_str(&self.colorize(&summary, "red", true));
             output.push('\n');
+        } else if warning_count > 0 {
+            output.push('\n');
+            let warning_word = if warning_count == 1 {
+                "warning"
+            } else {
+                "warnings"
+            };
+            let summary = format!("\nwarning: {} {} emitted", warning_count, warning_word);
+            output.push_str(&self.colorize(&summary, "yellow", true));
+            output.push('\n');
         }
 
         output
@@ -45,8 +59,12 @@ impl ErrorRenderer {
     fn render_error(&self, error: &CompilationError, source: &str, filename: &str) -> String {
         let mut output = String::new();
 
-        // Error header: error: <message>
-        let error_label = self.colorize("error", "red", true);
+        // Error header: error: <message> (or warning: <message>)
+        let (label, color) = match error.severity {
+            Severity::Error => ("error", "red"),
+            Severity::Warning => ("warning", "yellow"),
+        };
+        let error_label = self.colorize(label, color, true);
         let message = format!(": {}", self.lowercase_first(&error.message));
         output.push_str(&format!("{}{}\n", error_label, message));
 
@@ -65,7 +83,7 @@ impl ErrorRenderer {
         let snippet = self.extract_source_snippet(source, error.location);
 
         // Render source lines
-        output.push_str(&self.render_snippet(&snippet, error.location.column));
+        output.push_str(&self.render_snippet(&snippet, error.location.column, color));
 
         output
     }
@@ -94,7 +112,7 @@ impl ErrorRenderer {
         }
     }
 
-    fn render_snippet(&self, snippet: &SourceSnippet, error_column: u32) -> String {
+    fn render_snippet(&self, snippet: &SourceSnippet, error_column: u32, color: &str) -> String {
         let mut output = String::new();
 
         // Calculate max line number width for alignment
@@ -116,7 +134,7 @@ impl ErrorRenderer {
             if line.is_error_line {
                 // Calculate spaces before the caret (error_column is 1-based)
                 let spaces_before = " ".repeat((error_column as usize).saturating_sub(1));
-                let indicator = self.colorize("^", "red", true);
+                let indicator = self.colorize("^", color, true);
 
                 output.push_str(&format!(
                     " {} {} {}\n{}{}\n",
@@ -7,6 +7,15 @@ pub enum CompilationPhase {
     Parse,
     Semantic,
     Codegen,
+    Lint,
+}
+
+/// Whether a diagnostic stops compilation (`Error`) or is only reported (`Warning`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Severity {
+    #[default]
+    Error,
+    Warning,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -17,7 +26,9 @@ pub enum CompilationErrorKind {
     ImmutableAssignment,
     ArityExceeded,
     Internal,
-    #[allow(dead_code)]
+    AssignmentInCondition,
+    UnreachableCode,
+    UnusedVariable,
     Other,
 }
 
@@ -30,6 +41,9 @@ impl Display for CompilationErrorKind {
             CompilationErrorKind::ImmutableAssignment => write!(f, "Immutable Assignment"),
             CompilationErrorKind::ArityExceeded => write!(f, "Arity Exceeded"),
             CompilationErrorKind::Internal => write!(f, "Internal Error"),
+            CompilationErrorKind::AssignmentInCondition => write!(f, "Assignment In Condition"),
+            CompilationErrorKind::UnreachableCode => write!(f, "Unreachable Code"),
+            CompilationErrorKind::UnusedVariable => write!(f, "Unused Variable"),
             CompilationErrorKind::Other => write!(f, "Error"),
         }
     }
@@ -41,6 +55,7 @@ pub struct CompilationError {
     pub kind: CompilationErrorKind,
     pub message: String,
     pub location: SourceLocation,
+    pub severity: Severity,
 }
 
 impl CompilationError {
@@ -55,6 +70,20 @@ impl CompilationError {
             kind,
             message: message.into(),
             location,
+            severity: Severity::Error,
+        }
+    }
+
+    /// Creates a warning-severity diagnostic that does not stop compilation
+    pub fn warning(
+        phase: CompilationPhase,
+        kind: CompilationErrorKind,
+        message: impl Into<String>,
+        location: SourceLocation,
+    ) -> Self {
+        CompilationError {
+            severity: Severity::Warning,
+            ..CompilationError::new(phase, kind, message, location)
         }
     }
 }
@@ -1,7 +1,8 @@
 use crate::common::constants::VARIADIC_ARITY;
 use crate::common::stdlib;
 use crate::common::string_similarity::find_closest_match;
-use crate::common::NativeFn;
+use crate::common::{NativeFn, Value, VmNativeFn};
+use crate::vm::VirtualMachine;
 use std::collections::HashMap;
 use std::sync::OnceLock;
 
@@ -26,23 +27,48 @@ pub(crate) enum NativeCallable {
         #[allow(dead_code)]
         arity: u8,
     },
+    /// Static method with access to VM state: Regex.match(pattern, text)
+    VmStaticMethod {
+        function: VmNativeFn,
+        #[allow(dead_code)]
+        arity: u8,
+    },
+    /// Instance method with access to VM state, e.g. to call a callback: arr.find(fn)
+    VmInstanceMethod {
+        function: VmNativeFn,
+        #[allow(dead_code)]
+        arity: u8,
+    },
 }
 
 impl NativeCallable {
-    pub fn function(&self) -> NativeFn {
+    pub fn call(&self, vm: &mut VirtualMachine, args: &[Value]) -> Result<Value, String> {
         match self {
-            NativeCallable::StaticMethod { function, .. } => *function,
-            NativeCallable::InstanceMethod { function, .. } => *function,
-            NativeCallable::Constructor { function, .. } => *function,
+            NativeCallable::StaticMethod { function, .. } => function(args),
+            NativeCallable::InstanceMethod { function, .. } => function(args),
+            NativeCallable::Constructor { function, .. } => function(args),
+            NativeCallable::VmStaticMethod { function, .. } => function(vm, args),
+            NativeCallable::VmInstanceMethod { function, .. } => function(vm, args),
         }
     }
 
+    /// Whether the receiver is passed as the first argument. Methods that aren't called on a
+    /// value may share a name with an instance method of the same type, like `Array.fill`.
+    fn takes_receiver(&self) -> bool {
+        matches!(
+            self,
+            NativeCallable::InstanceMethod { .. } | NativeCallable::VmInstanceMethod { .. }
+        )
+    }
+
     #[allow(dead_code)]
     pub fn arity(&self) -> u8 {
         match self {
             NativeCallable::StaticMethod { arity, .. } => *arity,
             NativeCallable::InstanceMethod { arity, .. } => *arity,
             NativeCallable::Constructor { arity, .. } => *arity,
+            NativeCallable::VmStaticMethod { arity, .. } => *arity,
+            NativeCallable::VmInstanceMethod { arity, .. } => *arity,
         }
     }
 }
@@ -55,11 +81,107 @@ pub(crate) const NATIVE_METHODS: &[(&str, &str, NativeCallable)] = &[
     (
         "",
         "print",
-        NativeCallable::StaticMethod {
+        NativeCallable::VmStaticMethod {
             function: stdlib::system_functions::native_system_print,
             arity: VARIADIC_ARITY,
         },
     ),
+    (
+        "",
+        "str",
+        NativeCallable::StaticMethod {
+            function: stdlib::conversion_functions::native_str,
+            arity: 1,
+        },
+    ),
+    (
+        "",
+        "num",
+        NativeCallable::StaticMethod {
+            function: stdlib::conversion_functions::native_num,
+            arity: 1,
+        },
+    ),
+    (
+        "",
+        "type",
+        NativeCallable::StaticMethod {
+            function: stdlib::conversion_functions::native_type,
+            arity: 1,
+        },
+    ),
+    (
+        "",
+        "len",
+        NativeCallable::StaticMethod {
+            function: stdlib::conversion_functions::native_len,
+            arity: 1,
+        },
+    ),
+    (
+        "",
+        "tuple",
+        NativeCallable::StaticMethod {
+            function: stdlib::conversion_functions::native_tuple,
+            arity: VARIADIC_ARITY,
+        },
+    ),
+    (
+        "",
+        "freeze",
+        NativeCallable::StaticMethod {
+            function: stdlib::conversion_functions::native_freeze,
+            arity: 1,
+        },
+    ),
+    (
+        "",
+        "Ok",
+        NativeCallable::StaticMethod {
+            function: stdlib::result_functions::native_ok,
+            arity: 1,
+        },
+    ),
+    (
+        "",
+        "Err",
+        NativeCallable::StaticMethod {
+            function: stdlib::result_functions::native_err,
+            arity: 1,
+        },
+    ),
+    (
+        "",
+        "Some",
+        NativeCallable::StaticMethod {
+            function: stdlib::result_functions::native_some,
+            arity: 1,
+        },
+    ),
+    (
+        "",
+        "formatNumber",
+        NativeCallable::StaticMethod {
+            function: stdlib::number_functions::native_format_number,
+            arity: 2,
+        },
+    ),
+    (
+        "",
+        "assert",
+        NativeCallable::StaticMethod {
+            function: stdlib::system_functions::native_system_assert,
+            arity: 2,
+        },
+    ),
+    (
+        "",
+        "input",
+        NativeCallable::VmStaticMethod {
+            function: stdlib::system_functions::native_system_input,
+            arity: 1,
+        },
+    ),
     // Math static methods
     (
         "Math",
@@ -109,6 +231,89 @@ pub(crate) const NATIVE_METHODS: &[(&str, &str, NativeCallable)] = &[
             arity: VARIADIC_ARITY,
         },
     ),
+    // Regex static methods
+    (
+        "Regex",
+        "match",
+        NativeCallable::VmStaticMethod {
+            function: stdlib::regex_functions::native_regex_match,
+            arity: 2,
+        },
+    ),
+    (
+        "Regex",
+        "find",
+        NativeCallable::VmStaticMethod {
+            function: stdlib::regex_functions::native_regex_find,
+            arity: 2,
+        },
+    ),
+    (
+        "Regex",
+        "findAll",
+        NativeCallable::VmStaticMethod {
+            function: stdlib::regex_functions::native_regex_find_all,
+            arity: 2,
+        },
+    ),
+    // System static methods
+    (
+        "System",
+        "env",
+        NativeCallable::StaticMethod {
+            function: stdlib::system_functions::native_system_env,
+            arity: 1,
+        },
+    ),
+    (
+        "System",
+        "hasFlag",
+        NativeCallable::VmStaticMethod {
+            function: stdlib::system_functions::native_system_has_flag,
+            arity: 1,
+        },
+    ),
+    (
+        "System",
+        "argValue",
+        NativeCallable::VmStaticMethod {
+            function: stdlib::system_functions::native_system_arg_value,
+            arity: 1,
+        },
+    ),
+    (
+        "System",
+        "exit",
+        NativeCallable::VmStaticMethod {
+            function: stdlib::system_functions::native_system_exit,
+            arity: 1,
+        },
+    ),
+    (
+        "System",
+        "envOrDefault",
+        NativeCallable::StaticMethod {
+            function: stdlib::system_functions::native_system_env_or_default,
+            arity: 2,
+        },
+    ),
+    // Time static methods
+    (
+        "Time",
+        "now",
+        NativeCallable::StaticMethod {
+            function: stdlib::time_functions::native_time_now,
+            arity: 0,
+        },
+    ),
+    (
+        "Time",
+        "monotonic",
+        NativeCallable::StaticMethod {
+            function: stdlib::time_functions::native_time_monotonic,
+            arity: 0,
+        },
+    ),
     // Array instance methods
     (
         "Array",
@@ -126,6 +331,22 @@ pub(crate) const NATIVE_METHODS: &[(&str, &str, NativeCallable)] = &[
             arity: 0,
         },
     ),
+    (
+        "Array",
+        "shift",
+        NativeCallable::InstanceMethod {
+            function: stdlib::array_functions::native_array_shift,
+            arity: 0,
+        },
+    ),
+    (
+        "Array",
+        "unshift",
+        NativeCallable::InstanceMethod {
+            function: stdlib::array_functions::native_array_unshift,
+            arity: VARIADIC_ARITY,
+        },
+    ),
     (
         "Array",
         "length",
@@ -142,6 +363,70 @@ pub(crate) const NATIVE_METHODS: &[(&str, &str, NativeCallable)] = &[
             arity: 0,
         },
     ),
+    (
+        "Array",
+        "clone",
+        NativeCallable::InstanceMethod {
+            function: stdlib::array_functions::native_array_clone,
+            arity: 0,
+        },
+    ),
+    (
+        "Array",
+        "deepClone",
+        NativeCallable::InstanceMethod {
+            function: stdlib::array_functions::native_array_deep_clone,
+            arity: 0,
+        },
+    ),
+    (
+        "Array",
+        "enumerate",
+        NativeCallable::InstanceMethod {
+            function: stdlib::array_functions::native_array_enumerate,
+            arity: 0,
+        },
+    ),
+    (
+        "Array",
+        "find",
+        NativeCallable::VmInstanceMethod {
+            function: stdlib::array_functions::native_array_find,
+            arity: 1,
+        },
+    ),
+    (
+        "Array",
+        "findIndex",
+        NativeCallable::VmInstanceMethod {
+            function: stdlib::array_functions::native_array_find_index,
+            arity: 1,
+        },
+    ),
+    (
+        "Array",
+        "every",
+        NativeCallable::VmInstanceMethod {
+            function: stdlib::array_functions::native_array_every,
+            arity: 1,
+        },
+    ),
+    (
+        "Array",
+        "some",
+        NativeCallable::VmInstanceMethod {
+            function: stdlib::array_functions::native_array_some,
+            arity: 1,
+        },
+    ),
+    (
+        "Array",
+        "forEach",
+        NativeCallable::VmInstanceMethod {
+            function: stdlib::array_functions::native_array_for_each,
+            arity: 1,
+        },
+    ),
     (
         "Array",
         "contains",
@@ -166,6 +451,14 @@ pub(crate) const NATIVE_METHODS: &[(&str, &str, NativeCallable)] = &[
             arity: 0,
         },
     ),
+    (
+        "Array",
+        "fill",
+        NativeCallable::InstanceMethod {
+            function: stdlib::array_functions::native_array_fill,
+            arity: 1,
+        },
+    ),
     (
         "Array",
         "slice",
@@ -214,6 +507,31 @@ pub(crate) const NATIVE_METHODS: &[(&str, &str, NativeCallable)] = &[
             arity: 0,
         },
     ),
+    // Array static methods
+    (
+        "Array",
+        "from",
+        NativeCallable::StaticMethod {
+            function: stdlib::array_functions::native_array_from,
+            arity: 1,
+        },
+    ),
+    (
+        "Array",
+        "fill",
+        NativeCallable::StaticMethod {
+            function: stdlib::array_functions::native_array_filled,
+            arity: 2,
+        },
+    ),
+    (
+        "Array",
+        "range",
+        NativeCallable::StaticMethod {
+            function: stdlib::array_functions::native_array_range,
+            arity: 2,
+        },
+    ),
     // String instance methods
     (
         "String",
@@ -327,6 +645,23 @@ pub(crate) const NATIVE_METHODS: &[(&str, &str, NativeCallable)] = &[
             arity: 0,
         },
     ),
+    (
+        "String",
+        "bytes",
+        NativeCallable::InstanceMethod {
+            function: stdlib::string_functions::native_string_bytes,
+            arity: 0,
+        },
+    ),
+    // String static methods
+    (
+        "String",
+        "fromBytes",
+        NativeCallable::StaticMethod {
+            function: stdlib::string_functions::native_string_from_bytes,
+            arity: 1,
+        },
+    ),
     // Number instance methods
     (
         "Number",
@@ -336,6 +671,14 @@ pub(crate) const NATIVE_METHODS: &[(&str, &str, NativeCallable)] = &[
             arity: 0,
         },
     ),
+    (
+        "Number",
+        "toInt",
+        NativeCallable::InstanceMethod {
+            function: stdlib::number_functions::native_number_to_int,
+            arity: 0,
+        },
+    ),
     // Boolean instance methods
     (
         "Boolean",
@@ -345,6 +688,72 @@ pub(crate) const NATIVE_METHODS: &[(&str, &str, NativeCallable)] = &[
             arity: 0,
         },
     ),
+    // Result instance methods
+    (
+        "Result",
+        "isOk",
+        NativeCallable::InstanceMethod {
+            function: stdlib::result_functions::native_result_is_ok,
+            arity: 0,
+        },
+    ),
+    (
+        "Result",
+        "isErr",
+        NativeCallable::InstanceMethod {
+            function: stdlib::result_functions::native_result_is_err,
+            arity: 0,
+        },
+    ),
+    (
+        "Result",
+        "unwrap",
+        NativeCallable::InstanceMethod {
+            function: stdlib::result_functions::native_result_unwrap,
+            arity: 0,
+        },
+    ),
+    (
+        "Result",
+        "unwrapOr",
+        NativeCallable::InstanceMethod {
+            function: stdlib::result_functions::native_result_unwrap_or,
+            arity: 1,
+        },
+    ),
+    // Option instance methods
+    (
+        "Option",
+        "isSome",
+        NativeCallable::InstanceMethod {
+            function: stdlib::result_functions::native_option_is_some,
+            arity: 0,
+        },
+    ),
+    (
+        "Option",
+        "isNone",
+        NativeCallable::InstanceMethod {
+            function: stdlib::result_functions::native_option_is_none,
+            arity: 0,
+        },
+    ),
+    (
+        "Option",
+        "unwrap",
+        NativeCallable::InstanceMethod {
+            function: stdlib::result_functions::native_option_unwrap,
+            arity: 0,
+        },
+    ),
+    (
+        "Option",
+        "unwrapOr",
+        NativeCallable::InstanceMethod {
+            function: stdlib::result_functions::native_option_unwrap_or,
+            arity: 1,
+        },
+    ),
     // Map instance methods
     (
         "Map",
@@ -402,6 +811,39 @@ pub(crate) const NATIVE_METHODS: &[(&str, &str, NativeCallable)] = &[
             arity: 0,
         },
     ),
+    (
+        "Map",
+        "forEach",
+        NativeCallable::VmInstanceMethod {
+            function: stdlib::map_functions::native_map_for_each,
+            arity: 1,
+        },
+    ),
+    (
+        "Map",
+        "clone",
+        NativeCallable::InstanceMethod {
+            function: stdlib::map_functions::native_map_clone,
+            arity: 0,
+        },
+    ),
+    (
+        "Map",
+        "deepClone",
+        NativeCallable::InstanceMethod {
+            function: stdlib::map_functions::native_map_deep_clone,
+            arity: 0,
+        },
+    ),
+    // Map static methods
+    (
+        "Map",
+        "from",
+        NativeCallable::StaticMethod {
+            function: stdlib::map_functions::native_map_from,
+            arity: 1,
+        },
+    ),
     // Set instance methods
     (
         "Set",
@@ -483,6 +925,22 @@ pub(crate) const NATIVE_METHODS: &[(&str, &str, NativeCallable)] = &[
             arity: 0,
         },
     ),
+    (
+        "Set",
+        "clone",
+        NativeCallable::InstanceMethod {
+            function: stdlib::set_functions::native_set_clone,
+            arity: 0,
+        },
+    ),
+    (
+        "Set",
+        "deepClone",
+        NativeCallable::InstanceMethod {
+            function: stdlib::set_functions::native_set_clone,
+            arity: 0,
+        },
+    ),
     // File constructor
     (
         "File",
@@ -501,6 +959,14 @@ pub(crate) const NATIVE_METHODS: &[(&str, &str, NativeCallable)] = &[
             arity: 0,
         },
     ),
+    (
+        "File",
+        "readString",
+        NativeCallable::InstanceMethod {
+            function: stdlib::file_functions::native_file_read,
+            arity: 0,
+        },
+    ),
     (
         "File",
         "readLines",
@@ -517,16 +983,67 @@ pub(crate) const NATIVE_METHODS: &[(&str, &str, NativeCallable)] = &[
             arity: 1,
         },
     ),
+    (
+        "File",
+        "writeString",
+        NativeCallable::InstanceMethod {
+            function: stdlib::file_functions::native_file_write_string,
+            arity: 1,
+        },
+    ),
+    (
+        "File",
+        "appendString",
+        NativeCallable::InstanceMethod {
+            function: stdlib::file_functions::native_file_append_string,
+            arity: 1,
+        },
+    ),
+    (
+        "File",
+        "exists",
+        NativeCallable::InstanceMethod {
+            function: stdlib::file_functions::native_file_exists,
+            arity: 0,
+        },
+    ),
+    (
+        "File",
+        "delete",
+        NativeCallable::InstanceMethod {
+            function: stdlib::file_functions::native_file_delete,
+            arity: 0,
+        },
+    ),
+    (
+        "File",
+        "listDir",
+        NativeCallable::StaticMethod {
+            function: stdlib::file_functions::native_file_list_dir,
+            arity: 1,
+        },
+    ),
+    (
+        "File",
+        "close",
+        NativeCallable::InstanceMethod {
+            function: stdlib::file_functions::native_file_close,
+            arity: 0,
+        },
+    ),
 ];
 
 /// HashMap for O(1) method lookups at runtime
 static METHOD_MAP: OnceLock<HashMap<(&'static str, &'static str), &'static NativeCallable>> =
     OnceLock::new();
 
-/// Initialize the method lookup HashMap (called lazily on first access)
+/// Initialize the method lookup HashMap (called lazily on first access).
+/// Methods are looked up by name when called on a value, so only methods that take a receiver
+/// are kept. Static methods like `Array.fill` are called by index.
 fn init_method_map() -> HashMap<(&'static str, &'static str), &'static NativeCallable> {
     NATIVE_METHODS
         .iter()
+        .filter(|(_, _, callable)| callable.takes_receiver())
         .map(|(type_name, method_name, callable)| ((*type_name, *method_name), callable))
         .collect()
 }
@@ -543,11 +1060,12 @@ pub(crate) fn get_native_method_by_name(
 }
 
 /// Get the registry index for a native method (O(n) - but called at compile time)
-/// Returns None if the method doesn't exist
+/// Only global functions, static methods and constructors are called by index; returns None
+/// if there is no such method
 pub fn get_native_method_index(type_name: &str, method_name: &str) -> Option<usize> {
-    NATIVE_METHODS
-        .iter()
-        .position(|(t, m, _)| *t == type_name && *m == method_name)
+    NATIVE_METHODS.iter().position(|(t, m, callable)| {
+        *t == type_name && *m == method_name && !callable.takes_receiver()
+    })
 }
 
 /// Get a native method by registry index (O(1) - use at runtime)
@@ -555,10 +1073,11 @@ pub(crate) fn get_native_method_by_index(index: usize) -> Option<&'static Native
     NATIVE_METHODS.get(index).map(|(_, _, callable)| callable)
 }
 
+/// Methods that can be called on a value of the type, without its static methods
 pub fn get_methods_for_type(type_name: &str) -> Vec<&'static str> {
     NATIVE_METHODS
         .iter()
-        .filter(|(t, _, _)| *t == type_name)
+        .filter(|(t, _, callable)| *t == type_name && callable.takes_receiver())
         .map(|(_, m, _)| *m)
         .collect()
 }
@@ -569,7 +1088,9 @@ pub fn get_static_methods_for_type(type_name: &str) -> Vec<&'static str> {
         .filter_map(|(t, m, callable)| {
             if *t == type_name {
                 match callable {
-                    NativeCallable::StaticMethod { .. } => Some(*m),
+                    NativeCallable::StaticMethod { .. } | NativeCallable::VmStaticMethod { .. } => {
+                        Some(*m)
+                    }
                     _ => None,
                 }
             } else {
@@ -584,10 +1105,12 @@ pub fn is_static_namespace(name: &str) -> bool {
 }
 
 pub fn is_static_method(type_name: &str, method_name: &str) -> bool {
-    matches!(
-        get_native_method_by_name(type_name, method_name),
-        Some(NativeCallable::StaticMethod { .. })
-    )
+    get_native_method_index(type_name, method_name).is_some_and(|index| {
+        matches!(
+            NATIVE_METHODS[index].2,
+            NativeCallable::StaticMethod { .. } | NativeCallable::VmStaticMethod { .. }
+        )
+    })
 }
 
 pub fn suggest_method(type_name: &str, method_name: &str) -> Option<&'static str> {
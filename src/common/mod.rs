@@ -1,3 +1,4 @@
+use indexmap::IndexMap;
 use ordered_float::OrderedFloat;
 use std::cell::RefCell;
 use std::collections::{BTreeSet, HashMap};
@@ -11,6 +12,7 @@ pub mod errors;
 pub mod method_registry;
 pub(crate) mod opcodes;
 pub mod stdlib;
+pub mod string_interner;
 pub mod string_similarity;
 #[cfg(test)]
 mod tests;
@@ -20,6 +22,9 @@ mod tests;
 // The actual implementation will be in vm/mod.rs
 pub(crate) type NativeFn = fn(&[Value]) -> Result<Value, String>;
 
+/// Native function that needs access to VM state (e.g. caches kept across calls)
+pub(crate) type VmNativeFn = fn(&mut crate::vm::VirtualMachine, &[Value]) -> Result<Value, String>;
+
 #[derive(Debug, PartialEq)]
 pub struct Chunk {
     #[allow(dead_code)]
@@ -94,6 +99,35 @@ pub enum Object {
     Map(Rc<RefCell<HashMap<MapKey, Value>>>),
     Set(Rc<RefCell<BTreeSet<SetKey>>>),
     File(Rc<str>),
+    /// Immutable sequence created by `tuple(...)`. Its elements can all be map keys, so the
+    /// tuple can be one as well.
+    Tuple(Rc<[Value]>),
+    /// Read-only view of an array, map or set, created by `freeze(...)`. Reads see changes
+    /// made through the original collection; mutating through the view is a runtime error.
+    Frozen(Rc<Object>),
+    /// An `enum` declaration, whose variants are read like fields: `Color.Red`
+    Enum(Rc<ObjEnum>),
+    /// The variant of an enum at the given index
+    EnumVariant(Rc<ObjEnum>, usize),
+    /// A module bound by `import * as`, whose exports are read like fields
+    Module(Rc<ObjModule>),
+    /// A runtime error caught by `try`/`catch`
+    Error(Rc<ObjError>),
+    /// `Ok(value)` or `Err(error)`, returned by code that can fail without raising an error
+    Result(std::result::Result<Value, Value>),
+    /// `Some(value)` or `None`, for a value that may be absent
+    Option(Option<Value>),
+}
+
+impl Object {
+    /// The collection behind a frozen view, or this object itself. Code that only reads
+    /// matches on this so frozen collections behave like the collection they wrap.
+    pub(crate) fn unfrozen(&self) -> &Object {
+        match self {
+            Object::Frozen(inner) => inner,
+            other => other,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -101,23 +135,64 @@ pub enum MapKey {
     String(Rc<str>),
     Number(OrderedFloat<f64>),
     Boolean(bool),
+    Tuple(Rc<[MapKey]>),
 }
 
 pub type SetKey = MapKey;
 
+impl MapKey {
+    /// Key for a value that can be used as a map key or set element: a string, number,
+    /// boolean or tuple. Arrays, maps and sets can change after insertion, so they can't.
+    pub(crate) fn from_value(value: &Value) -> Option<MapKey> {
+        match value {
+            Value::Object(obj) => match obj.as_ref() {
+                Object::String(s) => Some(MapKey::String(Rc::clone(&s.value))),
+                Object::Tuple(elements) => elements
+                    .iter()
+                    .map(MapKey::from_value)
+                    .collect::<Option<_>>()
+                    .map(MapKey::Tuple),
+                _ => None,
+            },
+            Value::Number(n) => Some(MapKey::Number(OrderedFloat(*n))),
+            // Ints share the numeric key space so `m[2]` and `m[4 // 2]` are the same entry
+            Value::Int(i) => Some(MapKey::Number(OrderedFloat(*i as f64))),
+            Value::Boolean(b) => Some(MapKey::Boolean(*b)),
+            Value::Nil => None,
+        }
+    }
+
+    pub(crate) fn to_value(&self) -> Value {
+        match self {
+            MapKey::String(s) => Value::Object(Rc::new(Object::String(ObjString {
+                value: Rc::clone(s),
+            }))),
+            MapKey::Number(n) => Value::Number(n.into_inner()),
+            MapKey::Boolean(b) => Value::Boolean(*b),
+            MapKey::Tuple(elements) => {
+                Value::new_tuple(elements.iter().map(MapKey::to_value).collect())
+            }
+        }
+    }
+}
+
 impl Display for MapKey {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             MapKey::String(s) => write!(f, "{}", s),
-            MapKey::Number(n) => write!(f, "{}", n),
+            MapKey::Number(n) => write!(f, "{}", format_number(n.into_inner())),
             MapKey::Boolean(b) => write!(f, "{}", b),
+            MapKey::Tuple(elements) => write_tuple(f, elements.iter()),
         }
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub enum Value {
     Number(f64),
+    /// Whole number produced by floor division, bitwise operators and `toInt()`.
+    /// Arithmetic between two ints stays an int; mixing with a `Number` gives a `Number`.
+    Int(i64),
     Object(Rc<Object>),
     Boolean(bool),
     Nil,
@@ -132,7 +207,41 @@ pub struct ObjString {
 pub struct ObjFunction {
     pub name: String,
     pub arity: u8,
+    /// Arguments a caller must pass; the parameters after these have defaults
+    pub min_arity: u8,
+    /// Whether the last parameter collects the remaining arguments into an array
+    pub variadic: bool,
     pub chunk: Rc<Chunk>,
+    /// Variables captured from enclosing functions, indexed by GetUpvalue/SetUpvalue
+    pub upvalues: Vec<Rc<RefCell<Upvalue>>>,
+}
+
+/// A variable captured by a closure.
+/// While the enclosing function is running the value lives on the stack at the given
+/// absolute slot; when that function returns it is moved into the upvalue itself.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Upvalue {
+    Open(usize),
+    Closed(Value),
+}
+
+impl ObjFunction {
+    pub(crate) fn new(
+        name: String,
+        arity: u8,
+        min_arity: u8,
+        variadic: bool,
+        chunk: Chunk,
+    ) -> Self {
+        ObjFunction {
+            name,
+            arity,
+            min_arity,
+            variadic,
+            chunk: Rc::new(chunk),
+            upvalues: Vec::new(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -153,25 +262,95 @@ pub struct ObjInstance {
 pub struct ObjStruct {
     pub name: String,
     pub fields: Vec<String>,
+    /// Default values of the trailing fields, which may be omitted when constructing
+    pub defaults: Vec<Value>,
+    /// Methods declared in the struct body; each takes the instance as its first parameter
+    pub methods: HashMap<String, Rc<ObjFunction>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjEnum {
+    pub name: String,
+    pub variants: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjModule {
+    /// Path of the module's file
+    pub name: String,
+    pub exports: IndexMap<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjError {
+    /// What went wrong, as the VM would have reported it without the source location
+    pub message: String,
 }
 
 impl Value {
-    pub(crate) fn new_object(instance: ObjInstance) -> Value {
-        Value::Object(Rc::new(Object::Instance(Rc::new(RefCell::new(instance)))))
+    /// The numeric value of a `Number` or `Int`, or None for anything else
+    pub(crate) fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(n) => Some(*n),
+            Value::Int(i) => Some(*i as f64),
+            _ => None,
+        }
+    }
+
+    /// Name of the value's type, as returned by `type()`
+    pub(crate) fn type_name(&self) -> &'static str {
+        match self {
+            Value::Number(_) => "number",
+            Value::Int(_) => "int",
+            Value::Boolean(_) => "boolean",
+            Value::Nil => "nil",
+            Value::Object(obj) => match obj.unfrozen() {
+                Object::String(_) => "string",
+                Object::Array(_) => "array",
+                Object::Map(_) => "map",
+                Object::Set(_) => "set",
+                Object::Function(_) | Object::NativeFunction(_) => "function",
+                Object::Struct(_) => "struct",
+                Object::Instance(_) => "instance",
+                Object::File(_) => "file",
+                Object::Tuple(_) => "tuple",
+                Object::Enum(_) => "enum",
+                Object::EnumVariant(..) => "variant",
+                Object::Module(_) => "module",
+                Object::Error(_) => "error",
+                Object::Result(_) => "result",
+                Object::Option(_) => "option",
+                Object::Frozen(_) => unreachable!("unfrozen() never returns a frozen view"),
+            },
+        }
     }
 
-    pub(crate) fn new_struct(name: String, fields: Vec<String>) -> Self {
-        Value::Object(Rc::new(Object::Struct(Rc::new(ObjStruct { name, fields }))))
+    pub(crate) fn new_object(instance: ObjInstance) -> Value {
+        Value::Object(Rc::new(Object::Instance(Rc::new(RefCell::new(instance)))))
     }
 
-    pub(crate) fn new_function(name: String, arity: u8, chunk: Chunk) -> Self {
-        Value::Object(Rc::new(Object::Function(Rc::new(ObjFunction {
+    pub(crate) fn new_struct(
+        name: String,
+        fields: Vec<String>,
+        defaults: Vec<Value>,
+        methods: HashMap<String, Rc<ObjFunction>>,
+    ) -> Self {
+        Value::Object(Rc::new(Object::Struct(Rc::new(ObjStruct {
             name,
-            arity,
-            chunk: Rc::new(chunk),
+            fields,
+            defaults,
+            methods,
         }))))
     }
 
+    pub(crate) fn new_enum(name: String, variants: Vec<String>) -> Self {
+        Value::Object(Rc::new(Object::Enum(Rc::new(ObjEnum { name, variants }))))
+    }
+
+    pub(crate) fn new_function(function: ObjFunction) -> Self {
+        Value::Object(Rc::new(Object::Function(Rc::new(function))))
+    }
+
     pub(crate) fn new_native_function(
         name: String,
         arity: u8,
@@ -200,15 +379,105 @@ impl Value {
         Value::Object(Rc::new(Object::Set(Rc::new(RefCell::new(elements)))))
     }
 
+    pub(crate) fn new_tuple(elements: Vec<Value>) -> Self {
+        Value::Object(Rc::new(Object::Tuple(Rc::from(elements))))
+    }
+
+    pub(crate) fn new_result(result: std::result::Result<Value, Value>) -> Self {
+        Value::Object(Rc::new(Object::Result(result)))
+    }
+
+    pub(crate) fn new_option(option: Option<Value>) -> Self {
+        Value::Object(Rc::new(Object::Option(option)))
+    }
+
     pub(crate) fn new_file(path: String) -> Self {
         Value::Object(Rc::new(Object::File(Rc::from(path))))
     }
+
+    /// Whether both values are the same object, as tested by `is`. Collections, instances and
+    /// functions are compared by reference; strings and other values, which can't be changed
+    /// in place, by value.
+    pub(crate) fn is_same(&self, other: &Value) -> bool {
+        let (Value::Object(a), Value::Object(b)) = (self, other) else {
+            return self == other;
+        };
+        match (a.as_ref(), b.as_ref()) {
+            (Object::String(a), Object::String(b)) => a == b,
+            (Object::File(a), Object::File(b)) => a == b,
+            (Object::Tuple(a), Object::Tuple(b)) => a == b,
+            (Object::Result(a), Object::Result(b)) => a == b,
+            (Object::Option(a), Object::Option(b)) => a == b,
+            (Object::Frozen(a), Object::Frozen(b)) => Rc::ptr_eq(a, b),
+            (Object::Function(a), Object::Function(b)) => Rc::ptr_eq(a, b),
+            (Object::NativeFunction(a), Object::NativeFunction(b)) => Rc::ptr_eq(a, b),
+            (Object::Struct(a), Object::Struct(b)) => Rc::ptr_eq(a, b),
+            (Object::Enum(a), Object::Enum(b)) => Rc::ptr_eq(a, b),
+            (Object::EnumVariant(..), Object::EnumVariant(..)) => a == b,
+            (Object::Module(a), Object::Module(b)) => Rc::ptr_eq(a, b),
+            (Object::Error(a), Object::Error(b)) => Rc::ptr_eq(a, b),
+            (Object::Instance(a), Object::Instance(b)) => Rc::ptr_eq(a, b),
+            (Object::Array(a), Object::Array(b)) => Rc::ptr_eq(a, b),
+            (Object::Map(a), Object::Map(b)) => Rc::ptr_eq(a, b),
+            (Object::Set(a), Object::Set(b)) => Rc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+
+    /// Copies this value and every array, map and set nested in it. Other objects, such as
+    /// struct instances, stay shared. A collection reachable along several paths, including
+    /// one that contains itself, is copied once and the copies are linked the same way.
+    pub(crate) fn deep_clone(&self) -> Value {
+        self.deep_clone_with(&mut HashMap::new())
+    }
+
+    fn deep_clone_with(&self, copies: &mut HashMap<*const Object, Value>) -> Value {
+        let Value::Object(object) = self else {
+            return self.clone();
+        };
+        if let Some(copy) = copies.get(&Rc::as_ptr(object)) {
+            return copy.clone();
+        }
+        match object.as_ref() {
+            Object::Array(array) => {
+                let elements = Rc::new(RefCell::new(Vec::new()));
+                let copy = Value::Object(Rc::new(Object::Array(Rc::clone(&elements))));
+                copies.insert(Rc::as_ptr(object), copy.clone());
+                let copied = array
+                    .borrow()
+                    .iter()
+                    .map(|element| element.deep_clone_with(copies))
+                    .collect();
+                *elements.borrow_mut() = copied;
+                copy
+            }
+            Object::Map(map) => {
+                let entries = Rc::new(RefCell::new(HashMap::new()));
+                let copy = Value::Object(Rc::new(Object::Map(Rc::clone(&entries))));
+                copies.insert(Rc::as_ptr(object), copy.clone());
+                let copied = map
+                    .borrow()
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.deep_clone_with(copies)))
+                    .collect();
+                *entries.borrow_mut() = copied;
+                copy
+            }
+            // Set elements are strings, numbers and booleans, which are never shared mutably
+            Object::Set(set) => Value::new_set(set.borrow().clone()),
+            // The copy of a frozen collection is a regular, mutable one
+            Object::Frozen(inner) => Value::Object(Rc::clone(inner)).deep_clone_with(copies),
+            _ => self.clone(),
+        }
+    }
 }
 
 pub struct CallFrame {
     pub function: Rc<ObjFunction>,
     pub ip: usize,
     pub slot_start: isize, // Can be -1 for script frame
+    /// Number of arguments the caller actually passed
+    pub arg_count: u8,
 }
 
 impl Display for Object {
@@ -262,10 +531,36 @@ impl Display for Object {
                 write!(f, "}}")
             }
             Object::File(path) => write!(f, "<file: {}>", path),
+            Object::Tuple(elements) => write_tuple(f, elements.iter()),
+            Object::Frozen(inner) => write!(f, "{}", inner),
+            Object::Enum(r#enum) => write!(f, "<enum {}>", r#enum.name),
+            Object::EnumVariant(r#enum, index) => {
+                write!(f, "{}.{}", r#enum.name, r#enum.variants[*index])
+            }
+            Object::Module(module) => write!(f, "<module {}>", module.name),
+            Object::Error(error) => write!(f, "Error: {}", error.message),
+            Object::Result(Ok(value)) => write!(f, "Ok({})", value),
+            Object::Result(Err(error)) => write!(f, "Err({})", error),
+            Object::Option(Some(value)) => write!(f, "Some({})", value),
+            Object::Option(None) => write!(f, "None"),
         }
     }
 }
 
+fn write_tuple<T: Display>(
+    f: &mut Formatter<'_>,
+    elements: impl Iterator<Item = T>,
+) -> std::fmt::Result {
+    write!(f, "(")?;
+    for (i, element) in elements.enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}", element)?;
+    }
+    write!(f, ")")
+}
+
 impl PartialEq<Rc<str>> for ObjString {
     fn eq(&self, other: &Rc<str>) -> bool {
         self.value == *other
@@ -274,7 +569,8 @@ impl PartialEq<Rc<str>> for ObjString {
 
 impl PartialEq for ObjString {
     fn eq(&self, other: &Self) -> bool {
-        self.value == other.value
+        // Strings are interned, so equal strings nearly always share their allocation
+        Rc::ptr_eq(&self.value, &other.value) || self.value == other.value
     }
 }
 
@@ -304,13 +600,45 @@ impl PartialEq for ObjInstance {
     }
 }
 
+/// Ints and numbers compare by numeric value, so `4 // 2 == 2` holds. A frozen collection
+/// equals the collection it wraps.
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Value::Int(a), Value::Int(b)) => a == b,
+            (Value::Int(a), Value::Number(b)) | (Value::Number(b), Value::Int(a)) => {
+                *a as f64 == *b
+            }
+            (Value::Number(a), Value::Number(b)) => a == b,
+            (Value::Object(a), Value::Object(b)) => a.unfrozen() == b.unfrozen(),
+            (Value::Boolean(a), Value::Boolean(b)) => a == b,
+            (Value::Nil, Value::Nil) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Formats a number for display. Integral values print without a decimal point and other
+/// values with the fewest digits that read back as the same number. Negative zero prints
+/// as `0`, and the non-finite values as `nan`, `inf` and `-inf`.
+pub(crate) fn format_number(value: f64) -> String {
+    if value.is_nan() {
+        "nan".to_string()
+    } else if value == 0.0 {
+        "0".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
 impl Display for Value {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
             "{}",
             match self {
-                Value::Number(val) => val.to_string(),
+                Value::Number(val) => format_number(*val),
+                Value::Int(val) => val.to_string(),
                 Value::Boolean(val) => val.to_string(),
                 Value::Nil => "nil".to_string(),
                 Value::Object(val) => format!("{}", val),
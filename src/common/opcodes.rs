@@ -1,8 +1,11 @@
 use std::mem::transmute;
 
 impl OpCode {
+    /// Number of opcodes; bytes from this value on don't encode an instruction
+    pub(crate) const COUNT: usize = OpCode::CloseUpvalues as usize + 1;
+
     #[inline(always)]
-    pub(crate) fn from_u8(value: u8) -> OpCode {
+    pub(crate) const fn from_u8(value: u8) -> OpCode {
         unsafe { transmute(value) }
     }
 }
@@ -79,4 +82,55 @@ pub(crate) enum OpCode {
     BitwiseNot,
     LeftShift,
     RightShift,
+
+    // Stack manipulation
+    Dup,
+    Dup2,
+
+    // Closures
+    Closure,
+    GetUpvalue,
+    SetUpvalue,
+
+    // Default parameters
+    JumpIfArgSupplied,
+
+    // Named struct construction
+    CallNamed,
+
+    /// `Call` whose result is returned right away; reuses the frame of the returning function.
+    TailCall,
+
+    /// Pops two operands and pushes whether they are the same object (`is`).
+    Same,
+
+    // Destructuring, both pop an index and the array or tuple being destructured
+    /// Pushes the element at the index, or nil past the end
+    UnpackElement,
+    /// Pushes a new array of the elements from the index on.
+    UnpackRest,
+
+    /// Copies the top value below the one under it: `a b` becomes `b a b`.
+    Tuck,
+
+    // Modules
+    /// Pops a module's function and pushes the module. The module runs the first time it
+    /// is imported; later imports get the module from the VM's cache.
+    Import,
+    /// Operand: number of exports (u16). Pops that many name and value pairs, caches the
+    /// module they form under the running module's name and pushes it.
+    ExportModule,
+
+    // Error handling
+    /// Operand: jump offset (u32) to the `catch` block. Runtime errors until the matching
+    /// `TryEnd` unwind to that block, with the error pushed.
+    TryBegin,
+    /// Ends the innermost `try` block of the current frame.
+    TryEnd,
+    /// Pops a value and raises it as an error, which a `catch` block gets as is.
+    Throw,
+    /// Operand: local index (u32). Moves the captured locals from that index on off the
+    /// stack, so closures made in a loop iteration keep the values of that iteration.
+    /// Update `OpCode::COUNT` when adding an opcode after this one.
+    CloseUpvalues,
 }
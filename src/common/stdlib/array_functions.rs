@@ -1,8 +1,10 @@
-use crate::common::{Object, Value};
-use crate::{extract_arg, extract_receiver, extract_string_value};
+use crate::common::constants::MAX_SEQUENCE_LENGTH;
+use crate::common::{Object, SetKey, Value};
+use crate::vm::VirtualMachine;
+use crate::{extract_arg, extract_receiver, extract_string_value, is_false_like, string};
 
 /// Native implementation of Array.push(value)
-/// Adds an element to the end of the array and returns nil
+/// Adds an element to the end of the array and returns the array for chaining
 /// New calling convention: [receiver, args...]
 pub fn native_array_push(args: &[Value]) -> Result<Value, String> {
     if args.len() != 2 {
@@ -13,16 +15,15 @@ pub fn native_array_push(args: &[Value]) -> Result<Value, String> {
     }
 
     // Extract the array
-    let array_ref = extract_receiver!(args, Array, "push")?;
+    let array_ref = extract_receiver!(args, mut Array, "push")?;
 
     // Extract the value to push (args[1] in new convention)
     let value = &args[1];
 
     // Push the value onto the array
-    let mut array = array_ref.borrow_mut();
-    array.push(value.clone());
+    array_ref.borrow_mut().push(value.clone());
 
-    Ok(Value::Nil)
+    Ok(args[0].clone())
 }
 
 /// Native implementation of Array.pop()
@@ -36,13 +37,46 @@ pub fn native_array_pop(args: &[Value]) -> Result<Value, String> {
     }
 
     // Extract the array
-    let array_ref = extract_receiver!(args, Array, "pop")?;
+    let array_ref = extract_receiver!(args, mut Array, "pop")?;
 
     // Pop the last element
     let mut array = array_ref.borrow_mut();
     Ok(array.pop().unwrap_or(Value::Nil))
 }
 
+/// Native implementation of Array.shift()
+/// Removes and returns the first element of the array, or nil if the array is empty
+pub fn native_array_shift(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err(format!(
+            "shift() expects no arguments, got {}",
+            args.len() - 1
+        ));
+    }
+
+    let array_ref = extract_receiver!(args, mut Array, "shift")?;
+    let mut array = array_ref.borrow_mut();
+    if array.is_empty() {
+        return Ok(Value::Nil);
+    }
+    Ok(array.remove(0))
+}
+
+/// Native implementation of Array.unshift(value, ...)
+/// Inserts the values at the start of the array, in argument order, and returns the array
+pub fn native_array_unshift(args: &[Value]) -> Result<Value, String> {
+    if args.len() < 2 {
+        return Err("unshift() expects at least 1 argument (value), got 0".to_string());
+    }
+
+    let array_ref = extract_receiver!(args, mut Array, "unshift")?;
+    array_ref
+        .borrow_mut()
+        .splice(0..0, args[1..].iter().cloned());
+
+    Ok(args[0].clone())
+}
+
 /// Native implementation of Array.length()
 /// Returns the number of elements in the array
 pub fn native_array_length(args: &[Value]) -> Result<Value, String> {
@@ -97,7 +131,7 @@ pub fn native_array_contains(args: &[Value]) -> Result<Value, String> {
 }
 
 /// Native implementation of Array.sort()
-/// Sorts array in place (numbers ascending, strings alphabetically)
+/// Sorts array in place (numbers ascending, strings alphabetically) and returns it
 pub fn native_array_sort(args: &[Value]) -> Result<Value, String> {
     if args.len() != 1 {
         return Err(format!(
@@ -107,32 +141,32 @@ pub fn native_array_sort(args: &[Value]) -> Result<Value, String> {
     }
 
     // Extract the array
-    let array_ref = extract_receiver!(args, Array, "sort")?;
+    let array_ref = extract_receiver!(args, mut Array, "sort")?;
 
     // Sort the array
     let mut array = array_ref.borrow_mut();
 
     // Sort with custom comparison that handles mixed types
-    array.sort_by(|a, b| match (a, b) {
-        (Value::Number(n1), Value::Number(n2)) => {
-            n1.partial_cmp(n2).unwrap_or(std::cmp::Ordering::Equal)
-        }
-        (Value::Object(o1), Value::Object(o2)) => match (o1.as_ref(), o2.as_ref()) {
-            (Object::String(s1), Object::String(s2)) => s1.value.cmp(&s2.value),
+    array.sort_by(|a, b| match (a.as_f64(), b.as_f64()) {
+        (Some(n1), Some(n2)) => n1.partial_cmp(&n2).unwrap_or(std::cmp::Ordering::Equal),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => match (a, b) {
+            (Value::Object(o1), Value::Object(o2)) => match (o1.as_ref(), o2.as_ref()) {
+                (Object::String(s1), Object::String(s2)) => s1.value.cmp(&s2.value),
+                _ => std::cmp::Ordering::Equal,
+            },
+            (Value::Object(_), _) => std::cmp::Ordering::Less,
+            (_, Value::Object(_)) => std::cmp::Ordering::Greater,
             _ => std::cmp::Ordering::Equal,
         },
-        (Value::Number(_), _) => std::cmp::Ordering::Less,
-        (_, Value::Number(_)) => std::cmp::Ordering::Greater,
-        (Value::Object(_), _) => std::cmp::Ordering::Less,
-        (_, Value::Object(_)) => std::cmp::Ordering::Greater,
-        _ => std::cmp::Ordering::Equal,
     });
 
-    Ok(Value::Nil)
+    Ok(args[0].clone())
 }
 
 /// Native implementation of Array.reverse()
-/// Reverses array in place
+/// Reverses array in place and returns it
 pub fn native_array_reverse(args: &[Value]) -> Result<Value, String> {
     if args.len() != 1 {
         return Err(format!(
@@ -142,15 +176,197 @@ pub fn native_array_reverse(args: &[Value]) -> Result<Value, String> {
     }
 
     // Extract the array
-    let array_ref = extract_receiver!(args, Array, "reverse")?;
+    let array_ref = extract_receiver!(args, mut Array, "reverse")?;
 
     // Reverse the array
-    let mut array = array_ref.borrow_mut();
-    array.reverse();
+    array_ref.borrow_mut().reverse();
+
+    Ok(args[0].clone())
+}
+
+/// Native implementation of Array.fill(value)
+/// Replaces every element with value in place and returns the array
+pub fn native_array_fill(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 2 {
+        return Err(format!(
+            "fill() expects 1 argument (value), got {}",
+            args.len() - 1
+        ));
+    }
+
+    // Extract the array
+    let array_ref = extract_receiver!(args, mut Array, "fill")?;
+
+    array_ref.borrow_mut().fill(args[1].clone());
+
+    Ok(args[0].clone())
+}
+
+/// Native implementation of Array.clone()
+/// Returns a new array holding the same elements; nested collections stay shared
+pub fn native_array_clone(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err(format!(
+            "clone() expects no arguments, got {}",
+            args.len() - 1
+        ));
+    }
+
+    let array_ref = extract_receiver!(args, Array, "clone")?;
+    let elements = array_ref.borrow().clone();
+    Ok(Value::new_array(elements))
+}
+
+/// Native implementation of Array.deepClone()
+/// Returns a copy in which nested arrays, maps and sets are copied as well
+pub fn native_array_deep_clone(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err(format!(
+            "deepClone() expects no arguments, got {}",
+            args.len() - 1
+        ));
+    }
+
+    extract_receiver!(args, Array, "deepClone")?;
+    Ok(args[0].deep_clone())
+}
+
+/// Native implementation of Array.enumerate()
+/// Returns a new array of `[index, element]` pairs
+pub fn native_array_enumerate(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err(format!(
+            "enumerate() expects no arguments, got {}",
+            args.len() - 1
+        ));
+    }
+
+    let array_ref = extract_receiver!(args, Array, "enumerate")?;
+    let pairs = array_ref
+        .borrow()
+        .iter()
+        .enumerate()
+        .map(|(index, element)| {
+            Value::new_array(vec![Value::Number(index as f64), element.clone()])
+        })
+        .collect();
+    Ok(Value::new_array(pairs))
+}
+
+/// Native implementation of Array.find(predicate)
+/// Returns the first element the predicate holds for, or nil
+pub fn native_array_find(vm: &mut VirtualMachine, args: &[Value]) -> Result<Value, String> {
+    let found = find_first(vm, args, "find")?;
+    Ok(found.map_or(Value::Nil, |(_, element)| element))
+}
+
+/// Native implementation of Array.findIndex(predicate)
+/// Returns the index of the first element the predicate holds for, or -1
+pub fn native_array_find_index(vm: &mut VirtualMachine, args: &[Value]) -> Result<Value, String> {
+    let found = find_first(vm, args, "findIndex")?;
+    Ok(Value::Number(found.map_or(-1.0, |(index, _)| index as f64)))
+}
+
+/// Native implementation of Array.every(predicate)
+/// Returns whether the predicate is true for every element; true for an empty array
+pub fn native_array_every(vm: &mut VirtualMachine, args: &[Value]) -> Result<Value, String> {
+    let failed = first_with_result(vm, args, "every", false)?;
+    Ok(Value::Boolean(failed.is_none()))
+}
+
+/// Native implementation of Array.some(predicate)
+/// Returns whether the predicate is true for any element; false for an empty array
+pub fn native_array_some(vm: &mut VirtualMachine, args: &[Value]) -> Result<Value, String> {
+    let found = first_with_result(vm, args, "some", true)?;
+    Ok(Value::Boolean(found.is_some()))
+}
+
+/// Native implementation of Array.forEach(callback)
+/// Calls the callback with each element in turn and returns nil
+pub fn native_array_for_each(vm: &mut VirtualMachine, args: &[Value]) -> Result<Value, String> {
+    if args.len() != 2 {
+        return Err(format!(
+            "forEach() expects 1 argument (callback), got {}",
+            args.len() - 1
+        ));
+    }
 
+    let array_ref = extract_receiver!(args, Array, "forEach")?;
+    // Only the indices present at the start are visited, so appending in the callback
+    // doesn't loop forever; indices removed by the callback are skipped
+    let length = array_ref.borrow().len();
+    for index in 0..length {
+        let Some(element) = array_ref.borrow().get(index).cloned() else {
+            break;
+        };
+        vm.call_callback(&args[1], std::slice::from_ref(&element))?;
+    }
     Ok(Value::Nil)
 }
 
+/// Index of the first element the predicate returns `wanted` for. The predicate has to
+/// return a boolean.
+fn first_with_result(
+    vm: &mut VirtualMachine,
+    args: &[Value],
+    method: &str,
+    wanted: bool,
+) -> Result<Option<usize>, String> {
+    if args.len() != 2 {
+        return Err(format!(
+            "{}() expects 1 argument (predicate), got {}",
+            method,
+            args.len() - 1
+        ));
+    }
+
+    let array_ref = extract_receiver!(args, Array, method)?;
+    for index in 0.. {
+        let Some(element) = array_ref.borrow().get(index).cloned() else {
+            break;
+        };
+        match vm.call_callback(&args[1], std::slice::from_ref(&element))? {
+            Value::Boolean(result) if result == wanted => return Ok(Some(index)),
+            Value::Boolean(_) => {}
+            other => {
+                return Err(format!(
+                    "{}() predicate must return a boolean, got {}",
+                    method, other
+                ))
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Calls the predicate in `args[1]` on each element in turn until its result is truthy
+fn find_first(
+    vm: &mut VirtualMachine,
+    args: &[Value],
+    method: &str,
+) -> Result<Option<(usize, Value)>, String> {
+    if args.len() != 2 {
+        return Err(format!(
+            "{}() expects 1 argument (predicate), got {}",
+            method,
+            args.len() - 1
+        ));
+    }
+
+    let array_ref = extract_receiver!(args, Array, method)?;
+    // The predicate may change the array, so it isn't borrowed across calls
+    for index in 0.. {
+        let Some(element) = array_ref.borrow().get(index).cloned() else {
+            break;
+        };
+        let result = vm.call_callback(&args[1], std::slice::from_ref(&element))?;
+        if !is_false_like!(result) {
+            return Ok(Some((index, element)));
+        }
+    }
+    Ok(None)
+}
+
 /// Native implementation of Array.slice(start, end)
 /// Extracts a subarray (supports negative indices)
 pub fn native_array_slice(args: &[Value]) -> Result<Value, String> {
@@ -216,11 +432,7 @@ pub fn native_array_join(args: &[Value]) -> Result<Value, String> {
     let parts: Vec<String> = array.iter().map(|v| format!("{}", v)).collect();
     let result = parts.join(delimiter);
 
-    Ok(Value::Object(std::rc::Rc::new(Object::String(
-        crate::common::ObjString {
-            value: std::rc::Rc::from(result),
-        },
-    ))))
+    Ok(string!(result))
 }
 
 /// Native implementation of Array.indexOf(element)
@@ -265,9 +477,9 @@ pub fn native_array_sum(args: &[Value]) -> Result<Value, String> {
     let mut sum = 0.0;
 
     for (i, value) in array.iter().enumerate() {
-        match value {
-            Value::Number(n) => sum += n,
-            _ => {
+        match value.as_f64() {
+            Some(n) => sum += n,
+            None => {
                 return Err(format!(
                     "sum() requires all elements to be numbers, but element at index {} is not",
                     i
@@ -303,7 +515,7 @@ pub fn native_array_min(args: &[Value]) -> Result<Value, String> {
 
     for value in array.iter().skip(1) {
         let is_less = match (value, min) {
-            (Value::Number(n1), Value::Number(n2)) => n1 < n2,
+            (a, b) if a.as_f64().is_some() && b.as_f64().is_some() => a.as_f64() < b.as_f64(),
             (Value::Object(o1), Value::Object(o2)) => match (o1.as_ref(), o2.as_ref()) {
                 (Object::String(s1), Object::String(s2)) => s1.value < s2.value,
                 _ => return Err("min() can only compare numbers or strings".to_string()),
@@ -343,7 +555,7 @@ pub fn native_array_max(args: &[Value]) -> Result<Value, String> {
 
     for value in array.iter().skip(1) {
         let is_greater = match (value, max) {
-            (Value::Number(n1), Value::Number(n2)) => n1 > n2,
+            (a, b) if a.as_f64().is_some() && b.as_f64().is_some() => a.as_f64() > b.as_f64(),
             (Value::Object(o1), Value::Object(o2)) => match (o1.as_ref(), o2.as_ref()) {
                 (Object::String(s1), Object::String(s2)) => s1.value > s2.value,
                 _ => return Err("max() can only compare numbers or strings".to_string()),
@@ -358,3 +570,97 @@ pub fn native_array_max(args: &[Value]) -> Result<Value, String> {
 
     Ok(max.clone())
 }
+
+/// Native implementation of Array.fill(value, count)
+/// Builds an array holding `value` `count` times. Objects aren't copied: `Array.fill([], 3)`
+/// holds the same array three times.
+pub fn native_array_filled(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 2 {
+        return Err(format!(
+            "Array.fill() expects 2 arguments (value, count), got {}",
+            args.len()
+        ));
+    }
+
+    let count = extract_arg!(args, 1, Number, "count", "Array.fill")?;
+    if count < 0.0 || count.fract() != 0.0 {
+        return Err(format!(
+            "Array.fill() count must be a non-negative whole number, got {}",
+            args[1]
+        ));
+    }
+    if count > MAX_SEQUENCE_LENGTH as f64 {
+        return Err(format!(
+            "Array.fill() count exceeds the maximum array length of {}",
+            MAX_SEQUENCE_LENGTH
+        ));
+    }
+    Ok(Value::new_array(vec![args[0].clone(); count as usize]))
+}
+
+/// Native implementation of Array.range(start, end)
+/// Builds the array of whole numbers from `start` up to but not including `end`, like `start..end`
+pub fn native_array_range(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 2 {
+        return Err(format!(
+            "Array.range() expects 2 arguments (start, end), got {}",
+            args.len()
+        ));
+    }
+
+    let start = extract_arg!(args, 0, Number, "start", "Array.range")?;
+    let end = extract_arg!(args, 1, Number, "end", "Array.range")?;
+    if start.fract() != 0.0 || end.fract() != 0.0 {
+        return Err(format!(
+            "Array.range() bounds must be whole numbers, got {} and {}",
+            args[0], args[1]
+        ));
+    }
+    if end - start > MAX_SEQUENCE_LENGTH as f64 {
+        return Err(format!(
+            "Array.range() exceeds the maximum array length of {}",
+            MAX_SEQUENCE_LENGTH
+        ));
+    }
+    let elements = (start as i64..end as i64)
+        .map(|i| Value::Number(i as f64))
+        .collect();
+    Ok(Value::new_array(elements))
+}
+
+/// Native implementation of Array.from(iterable)
+/// Builds a new array from an array (copied), a set (in set order) or a string (one element per char)
+pub fn native_array_from(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err(format!(
+            "Array.from() expects 1 argument, got {}",
+            args.len()
+        ));
+    }
+
+    let elements = match &args[0] {
+        Value::Object(obj) => match obj.unfrozen() {
+            Object::Array(array_ref) => array_ref.borrow().clone(),
+            Object::Set(set_ref) => set_ref.borrow().iter().map(SetKey::to_value).collect(),
+            Object::String(s) => s
+                .value
+                .chars()
+                .map(|c| string!(c.to_string().as_str()))
+                .collect(),
+            _ => {
+                return Err(format!(
+                    "Array.from() expects an array, set or string, got {}",
+                    args[0]
+                ))
+            }
+        },
+        _ => {
+            return Err(format!(
+                "Array.from() expects an array, set or string, got {}",
+                args[0]
+            ))
+        }
+    };
+
+    Ok(Value::new_array(elements))
+}
@@ -0,0 +1,110 @@
+use crate::common::{MapKey, Object, Value};
+use crate::string;
+use std::rc::Rc;
+
+/// Native implementation of str(x)
+/// Converts any value to its display string
+pub fn native_str(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err(format!("str() expects 1 argument, got {}", args.len()));
+    }
+
+    Ok(string!(args[0].to_string()))
+}
+
+/// Native implementation of num(x)
+/// Converts a string or boolean to a number
+/// Numbers are returned unchanged; any other value is an error
+pub fn native_num(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err(format!("num() expects 1 argument, got {}", args.len()));
+    }
+
+    match &args[0] {
+        Value::Number(n) => Ok(Value::Number(*n)),
+        Value::Int(i) => Ok(Value::Int(*i)),
+        Value::Boolean(b) => Ok(Value::Number(if *b { 1.0 } else { 0.0 })),
+        Value::Object(obj) => match obj.as_ref() {
+            Object::String(s) => match s.value.trim().parse::<f64>() {
+                Ok(n) => Ok(Value::Number(n)),
+                Err(_) => Err(format!("num() failed: '{}' is not a valid number", s.value)),
+            },
+            _ => Err(format!("num() cannot convert {} to a number", args[0])),
+        },
+        Value::Nil => Err("num() cannot convert nil to a number".to_string()),
+    }
+}
+
+/// Native implementation of type(x)
+/// Returns the lowercase name of the value's kind, e.g. "number", "int" or "instance"
+pub fn native_type(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err(format!("type() expects 1 argument, got {}", args.len()));
+    }
+
+    Ok(string!(args[0].type_name()))
+}
+
+/// Native implementation of len(x)
+/// Counts the characters of a string or the elements of an array, map, set or tuple
+pub fn native_len(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err(format!("len() expects 1 argument, got {}", args.len()));
+    }
+
+    let len = match &args[0] {
+        Value::Object(obj) => match obj.unfrozen() {
+            Object::String(s) => s.value.chars().count(),
+            Object::Array(array) => array.borrow().len(),
+            Object::Map(map) => map.borrow().len(),
+            Object::Set(set) => set.borrow().len(),
+            Object::Tuple(elements) => elements.len(),
+            _ => return Err(format!("len() cannot measure {}", args[0])),
+        },
+        other => return Err(format!("len() cannot measure {}", other)),
+    };
+    Ok(Value::Number(len as f64))
+}
+
+/// Native implementation of tuple(a, b, ...)
+/// Creates an immutable tuple of the arguments. Only values that can be map keys are allowed,
+/// so every tuple can be a map key or set element itself.
+pub fn native_tuple(args: &[Value]) -> Result<Value, String> {
+    if let Some((index, element)) = args
+        .iter()
+        .enumerate()
+        .find(|(_, element)| MapKey::from_value(element).is_none())
+    {
+        return Err(format!(
+            "tuple() elements must be strings, numbers, booleans or tuples, got {} at index {}",
+            element, index
+        ));
+    }
+    Ok(Value::new_tuple(args.to_vec()))
+}
+
+/// Native implementation of freeze(collection)
+/// Returns a read-only view of an array, map or set. Freezing a frozen collection returns it
+/// unchanged.
+pub fn native_freeze(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err(format!("freeze() expects 1 argument, got {}", args.len()));
+    }
+
+    match &args[0] {
+        Value::Object(obj) => match obj.as_ref() {
+            Object::Array(_) | Object::Map(_) | Object::Set(_) => {
+                Ok(Value::Object(Rc::new(Object::Frozen(Rc::clone(obj)))))
+            }
+            Object::Frozen(_) => Ok(args[0].clone()),
+            _ => Err(format!(
+                "freeze() expects an array, map or set, got {}",
+                args[0]
+            )),
+        },
+        other => Err(format!(
+            "freeze() expects an array, map or set, got {}",
+            other
+        )),
+    }
+}
@@ -9,8 +9,8 @@
 //! // Extract a string receiver (args[0])
 //! let obj_string = extract_receiver!(args, String, "len")?;
 //!
-//! // Extract an array receiver
-//! let array_ref = extract_receiver!(args, Array, "push")?;
+//! // Extract an array receiver that is about to be changed
+//! let array_ref = extract_receiver!(args, mut Array, "push")?;
 //!
 //! // Extract a number argument at index 1
 //! let index = extract_arg!(args, 1, Number, "index", "substring")?;
@@ -29,6 +29,7 @@
 /// - `Array` → `&Rc<RefCell<Vec<Value>>>`
 /// - `Map` → `&Rc<RefCell<HashMap<MapKey, Value>>>`
 /// - `Set` → `&Rc<RefCell<BTreeSet<SetKey>>>`
+/// - `mut Array`, `mut Map`, `mut Set` → as above, but a frozen collection is an error
 /// - `File` → `&Rc<str>`
 /// - `Number` → `f64`
 /// - `Boolean` → `bool`
@@ -37,7 +38,7 @@
 ///
 /// ```ignore
 /// let obj_string = extract_receiver!(args, String, "len")?;
-/// let array_ref = extract_receiver!(args, Array, "push")?;
+/// let array_ref = extract_receiver!(args, mut Array, "push")?;
 /// let num = extract_receiver!(args, Number, "abs")?;
 /// ```
 #[macro_export]
@@ -57,7 +58,7 @@ macro_rules! extract_receiver {
     // Array extraction
     ($args:expr, Array, $method:expr) => {
         match $args.get(0) {
-            Some(Value::Object(obj)) => match obj.as_ref() {
+            Some(Value::Object(obj)) => match obj.unfrozen() {
                 Object::Array(arr) => Ok(arr),
                 _ => Err(format!("{}() can only be called on arrays", $method)),
             },
@@ -69,7 +70,7 @@ macro_rules! extract_receiver {
     // Map extraction
     ($args:expr, Map, $method:expr) => {
         match $args.get(0) {
-            Some(Value::Object(obj)) => match obj.as_ref() {
+            Some(Value::Object(obj)) => match obj.unfrozen() {
                 Object::Map(m) => Ok(m),
                 _ => Err(format!("{}() can only be called on maps", $method)),
             },
@@ -81,7 +82,7 @@ macro_rules! extract_receiver {
     // Set extraction
     ($args:expr, Set, $method:expr) => {
         match $args.get(0) {
-            Some(Value::Object(obj)) => match obj.as_ref() {
+            Some(Value::Object(obj)) => match obj.unfrozen() {
                 Object::Set(s) => Ok(s),
                 _ => Err(format!("{}() can only be called on sets", $method)),
             },
@@ -90,6 +91,45 @@ macro_rules! extract_receiver {
         }
     };
 
+    // Mutable array extraction, rejecting frozen views
+    ($args:expr, mut Array, $method:expr) => {
+        match $args.get(0) {
+            Some(Value::Object(obj)) => match obj.as_ref() {
+                Object::Array(value) => Ok(value),
+                Object::Frozen(_) => Err(format!("{}() cannot mutate frozen collection", $method)),
+                _ => Err(format!("{}() can only be called on arrays", $method)),
+            },
+            Some(_) => Err(format!("{}() can only be called on arrays", $method)),
+            None => Err(format!("{}() can only be called on arrays", $method)),
+        }
+    };
+
+    // Mutable map extraction, rejecting frozen views
+    ($args:expr, mut Map, $method:expr) => {
+        match $args.get(0) {
+            Some(Value::Object(obj)) => match obj.as_ref() {
+                Object::Map(value) => Ok(value),
+                Object::Frozen(_) => Err(format!("{}() cannot mutate frozen collection", $method)),
+                _ => Err(format!("{}() can only be called on maps", $method)),
+            },
+            Some(_) => Err(format!("{}() can only be called on maps", $method)),
+            None => Err(format!("{}() can only be called on maps", $method)),
+        }
+    };
+
+    // Mutable set extraction, rejecting frozen views
+    ($args:expr, mut Set, $method:expr) => {
+        match $args.get(0) {
+            Some(Value::Object(obj)) => match obj.as_ref() {
+                Object::Set(value) => Ok(value),
+                Object::Frozen(_) => Err(format!("{}() cannot mutate frozen collection", $method)),
+                _ => Err(format!("{}() can only be called on sets", $method)),
+            },
+            Some(_) => Err(format!("{}() can only be called on sets", $method)),
+            None => Err(format!("{}() can only be called on sets", $method)),
+        }
+    };
+
     // File extraction
     ($args:expr, File, $method:expr) => {
         match $args.get(0) {
@@ -106,6 +146,7 @@ macro_rules! extract_receiver {
     ($args:expr, Number, $method:expr) => {
         match $args.get(0) {
             Some(Value::Number(n)) => Ok(*n),
+            Some(Value::Int(i)) => Ok(*i as f64),
             Some(_) => Err(format!("{}() can only be called on numbers", $method)),
             None => Err(format!("{}() can only be called on numbers", $method)),
         }
@@ -163,6 +204,7 @@ macro_rules! extract_arg {
     ($args:expr, $idx:expr, Number, $arg_name:expr, $method:expr) => {
         match $args.get($idx) {
             Some(Value::Number(n)) => Ok(*n),
+            Some(Value::Int(i)) => Ok(*i as f64),
             Some(_) => Err(format!("{}() {} must be a number", $method, $arg_name)),
             None => Err(format!(
                 "{}() missing required argument: {}",
@@ -174,7 +216,7 @@ macro_rules! extract_arg {
     // Set argument
     ($args:expr, $idx:expr, Set, $arg_name:expr, $method:expr) => {
         match $args.get($idx) {
-            Some(Value::Object(obj)) => match obj.as_ref() {
+            Some(Value::Object(obj)) => match obj.unfrozen() {
                 Object::Set(s) => Ok(s),
                 _ => Err(format!("{}() {} must be a set", $method, $arg_name)),
             },
@@ -189,7 +231,7 @@ macro_rules! extract_arg {
     // Array argument
     ($args:expr, $idx:expr, Array, $arg_name:expr, $method:expr) => {
         match $args.get($idx) {
-            Some(Value::Object(obj)) => match obj.as_ref() {
+            Some(Value::Object(obj)) => match obj.unfrozen() {
                 Object::Array(arr) => Ok(arr),
                 _ => Err(format!("{}() {} must be an array", $method, $arg_name)),
             },
@@ -1,6 +1,6 @@
-use crate::common::{ObjString, Object, Value};
-use crate::{extract_arg, extract_receiver, extract_string_value};
-use std::rc::Rc;
+use crate::common::{Object, Value};
+use crate::{extract_arg, extract_receiver, extract_string_value, string};
+use std::io::Write;
 
 /// Native implementation of File(path) constructor
 /// Creates a new File object with the given path
@@ -13,7 +13,7 @@ pub fn native_file_constructor(args: &[Value]) -> Result<Value, String> {
     Ok(Value::new_file(s.value.to_string()))
 }
 
-/// Native implementation of File.read()
+/// Native implementation of File.read() and its alias File.readString()
 /// Reads the entire contents of the file and returns it as a string
 pub fn native_file_read(args: &[Value]) -> Result<Value, String> {
     if args.len() != 1 {
@@ -29,25 +29,18 @@ pub fn native_file_read(args: &[Value]) -> Result<Value, String> {
     match std::fs::read_to_string(file_path.as_ref()) {
         Ok(contents) => {
             // Return the contents as a String value
-            Ok(Value::Object(Rc::new(Object::String(ObjString {
-                value: Rc::from(contents),
-            }))))
-        }
-        Err(e) => {
-            // Return descriptive error message based on error kind
-            let error_msg = match e.kind() {
-                std::io::ErrorKind::NotFound => {
-                    format!("File not found: {}", file_path.as_ref())
-                }
-                std::io::ErrorKind::PermissionDenied => {
-                    format!("Permission denied: {}", file_path.as_ref())
-                }
-                _ => {
-                    format!("Failed to read file '{}': {}", file_path.as_ref(), e)
-                }
-            };
-            Err(error_msg)
+            Ok(string!(contents))
         }
+        Err(e) => Err(read_error(file_path.as_ref(), &e)),
+    }
+}
+
+/// Builds a descriptive read error that includes the OS error message
+fn read_error(path: &str, e: &std::io::Error) -> String {
+    match e.kind() {
+        std::io::ErrorKind::NotFound => format!("File not found: {} ({})", path, e),
+        std::io::ErrorKind::PermissionDenied => format!("Permission denied: {} ({})", path, e),
+        _ => format!("Failed to read file '{}': {}", path, e),
     }
 }
 
@@ -68,33 +61,12 @@ pub fn native_file_read_lines(args: &[Value]) -> Result<Value, String> {
     match std::fs::read_to_string(file_path.as_ref()) {
         Ok(contents) => {
             // Split by lines - this automatically strips \n and \r\n line endings
-            let lines: Vec<Value> = contents
-                .lines()
-                .map(|line| {
-                    Value::Object(Rc::new(Object::String(ObjString {
-                        value: Rc::from(line),
-                    })))
-                })
-                .collect();
+            let lines: Vec<Value> = contents.lines().map(|line| string!(line)).collect();
 
             // Return an array of string values
             Ok(Value::new_array(lines))
         }
-        Err(e) => {
-            // Return descriptive error message based on error kind
-            let error_msg = match e.kind() {
-                std::io::ErrorKind::NotFound => {
-                    format!("File not found: {}", file_path.as_ref())
-                }
-                std::io::ErrorKind::PermissionDenied => {
-                    format!("Permission denied: {}", file_path.as_ref())
-                }
-                _ => {
-                    format!("Failed to read file '{}': {}", file_path.as_ref(), e)
-                }
-            };
-            Err(error_msg)
-        }
+        Err(e) => Err(read_error(file_path.as_ref(), &e)),
     }
 }
 
@@ -119,20 +91,147 @@ pub fn native_file_write(args: &[Value]) -> Result<Value, String> {
     // Write the content to the file
     match std::fs::write(file_path.as_ref(), content) {
         Ok(()) => Ok(Value::Nil),
-        Err(e) => {
-            // Return descriptive error message based on error kind
-            let error_msg = match e.kind() {
-                std::io::ErrorKind::PermissionDenied => {
-                    format!("Permission denied: {}", file_path.as_ref())
-                }
-                std::io::ErrorKind::NotFound => {
-                    format!("Directory not found for file: {}", file_path.as_ref())
-                }
-                _ => {
-                    format!("Failed to write file '{}': {}", file_path.as_ref(), e)
-                }
-            };
-            Err(error_msg)
+        Err(e) => Err(write_error(file_path.as_ref(), &e)),
+    }
+}
+
+/// Native implementation of File.writeString(content)
+/// Truncates the file (creating it if needed), writes content and returns the number of bytes written
+pub fn native_file_write_string(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 2 {
+        return Err(format!(
+            "writeString() expects 1 argument, got {}",
+            args.len() - 1
+        ));
+    }
+
+    let file_path = extract_receiver!(args, File, "writeString")?;
+    let content = extract_string_value!(args, 1, "content", "writeString");
+
+    match std::fs::write(file_path.as_ref(), content) {
+        Ok(()) => Ok(Value::Number(content.len() as f64)),
+        Err(e) => Err(write_error(file_path.as_ref(), &e)),
+    }
+}
+
+/// Native implementation of File.appendString(content)
+/// Appends content to the file (creating it if needed) and returns the number of bytes written
+pub fn native_file_append_string(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 2 {
+        return Err(format!(
+            "appendString() expects 1 argument, got {}",
+            args.len() - 1
+        ));
+    }
+
+    let file_path = extract_receiver!(args, File, "appendString")?;
+    let content = extract_string_value!(args, 1, "content", "appendString");
+
+    let result = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(file_path.as_ref())
+        .and_then(|mut file| file.write_all(content.as_bytes()));
+
+    match result {
+        Ok(()) => Ok(Value::Number(content.len() as f64)),
+        Err(e) => Err(write_error(file_path.as_ref(), &e)),
+    }
+}
+
+/// Builds a descriptive write error that includes the OS error message
+fn write_error(path: &str, e: &std::io::Error) -> String {
+    match e.kind() {
+        std::io::ErrorKind::PermissionDenied => format!("Permission denied: {} ({})", path, e),
+        std::io::ErrorKind::NotFound => {
+            format!("Directory not found for file: {} ({})", path, e)
         }
+        _ => format!("Failed to write file '{}': {}", path, e),
+    }
+}
+
+/// Native implementation of File.exists()
+/// Returns true if something exists at the file's path
+pub fn native_file_exists(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err(format!(
+            "exists() expects 0 arguments (only receiver), got {}",
+            args.len() - 1
+        ));
     }
+
+    let file_path = extract_receiver!(args, File, "exists")?;
+    Ok(Value::Boolean(
+        std::path::Path::new(file_path.as_ref()).exists(),
+    ))
+}
+
+/// Native implementation of File.delete()
+/// Removes the file and returns true, or false if it did not exist
+pub fn native_file_delete(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err(format!(
+            "delete() expects 0 arguments (only receiver), got {}",
+            args.len() - 1
+        ));
+    }
+
+    let file_path = extract_receiver!(args, File, "delete")?;
+    match std::fs::remove_file(file_path.as_ref()) {
+        Ok(()) => Ok(Value::Boolean(true)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Value::Boolean(false)),
+        Err(e) => match e.kind() {
+            std::io::ErrorKind::PermissionDenied => {
+                Err(format!("Permission denied: {} ({})", file_path.as_ref(), e))
+            }
+            _ => Err(format!(
+                "Failed to delete file '{}': {}",
+                file_path.as_ref(),
+                e
+            )),
+        },
+    }
+}
+
+/// Native implementation of File.listDir(path)
+/// Returns the names of the directory's entries as an array of strings, sorted by name
+pub fn native_file_list_dir(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err(format!(
+            "File.listDir() expects 1 argument, got {}",
+            args.len()
+        ));
+    }
+
+    let path = extract_string_value!(args, 0, "path", "listDir");
+    if !std::path::Path::new(path).is_dir() {
+        return Err(format!("File.listDir() path is not a directory: {}", path));
+    }
+
+    let entries = std::fs::read_dir(path).map_err(|e| read_error(path, &e))?;
+    let mut names = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(|e| read_error(path, &e))?;
+        names.push(entry.file_name().to_string_lossy().to_string());
+    }
+    names.sort();
+
+    Ok(Value::new_array(
+        names.into_iter().map(|name| string!(name)).collect(),
+    ))
+}
+
+/// Native implementation of File.close()
+/// Files hold no OS handle between calls, so there is nothing to release; this exists
+/// so File values can be used as `with` block resources
+pub fn native_file_close(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err(format!(
+            "close() expects 0 arguments (only receiver), got {}",
+            args.len() - 1
+        ));
+    }
+
+    extract_receiver!(args, File, "close")?;
+    Ok(Value::Nil)
 }
@@ -1,7 +1,7 @@
 use crate::common::{MapKey, Object, Value};
-use crate::extract_receiver;
-use ordered_float::OrderedFloat;
-use std::rc::Rc;
+use crate::vm::VirtualMachine;
+use crate::{extract_arg, extract_receiver};
+use std::collections::HashMap;
 
 pub fn native_map_get(args: &[Value]) -> Result<Value, String> {
     if args.len() != 2 {
@@ -15,11 +15,11 @@ pub fn native_map_get(args: &[Value]) -> Result<Value, String> {
     let map_ref = extract_receiver!(args, Map, "get")?;
 
     // Convert key to MapKey
-    let key = match value_to_map_key(&args[1]) {
+    let key = match MapKey::from_value(&args[1]) {
         Some(k) => k,
         None => {
             return Err(format!(
-                "Invalid map key type: {}. Only strings, numbers, and booleans can be used as map keys.",
+                "Invalid map key type: {}. Only strings, numbers, booleans and tuples can be used as map keys.",
                 args[1]
             ));
         }
@@ -54,11 +54,11 @@ pub fn native_map_has(args: &[Value]) -> Result<Value, String> {
     let map_ref = extract_receiver!(args, Map, "has")?;
 
     // Convert key to MapKey
-    let key = match value_to_map_key(&args[1]) {
+    let key = match MapKey::from_value(&args[1]) {
         Some(k) => k,
         None => {
             return Err(format!(
-                "Invalid map key type: {}. Only strings, numbers, and booleans can be used as map keys.",
+                "Invalid map key type: {}. Only strings, numbers, booleans and tuples can be used as map keys.",
                 args[1]
             ));
         }
@@ -78,14 +78,14 @@ pub fn native_map_remove(args: &[Value]) -> Result<Value, String> {
     }
 
     // Extract the map
-    let map_ref = extract_receiver!(args, Map, "remove")?;
+    let map_ref = extract_receiver!(args, mut Map, "remove")?;
 
     // Convert key to MapKey
-    let key = match value_to_map_key(&args[1]) {
+    let key = match MapKey::from_value(&args[1]) {
         Some(k) => k,
         None => {
             return Err(format!(
-                "Invalid map key type: {}. Only strings, numbers, and booleans can be used as map keys.",
+                "Invalid map key type: {}. Only strings, numbers, booleans and tuples can be used as map keys.",
                 args[1]
             ));
         }
@@ -106,10 +106,56 @@ pub fn native_map_keys(args: &[Value]) -> Result<Value, String> {
 
     // Collect keys into an array
     let map = map_ref.borrow();
-    let keys: Vec<Value> = map.keys().map(map_key_to_value).collect();
+    let keys: Vec<Value> = map.keys().map(MapKey::to_value).collect();
     Ok(Value::new_array(keys))
 }
 
+/// Native implementation of Map.forEach(callback)
+/// Calls the callback with the key and value of each entry and returns nil
+pub fn native_map_for_each(vm: &mut VirtualMachine, args: &[Value]) -> Result<Value, String> {
+    if args.len() != 2 {
+        return Err(format!(
+            "forEach() expects 1 argument (callback), got {}",
+            args.len() - 1
+        ));
+    }
+
+    let map_ref = extract_receiver!(args, Map, "forEach")?;
+    // The callback may change the map, so only the keys are taken up front and each value
+    // is looked up right before its call. Entries removed in the meantime are skipped.
+    let keys: Vec<MapKey> = map_ref.borrow().keys().cloned().collect();
+    for key in keys {
+        let Some(value) = map_ref.borrow().get(&key).cloned() else {
+            continue;
+        };
+        vm.call_callback(&args[1], &[key.to_value(), value])?;
+    }
+    Ok(Value::Nil)
+}
+
+/// Native implementation of Map.clone()
+/// Returns a new map with the same entries; nested collections stay shared
+pub fn native_map_clone(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err("clone() expects no arguments".to_string());
+    }
+
+    let map_ref = extract_receiver!(args, Map, "clone")?;
+    let entries = map_ref.borrow().clone();
+    Ok(Value::new_map(entries))
+}
+
+/// Native implementation of Map.deepClone()
+/// Returns a copy in which nested arrays, maps and sets are copied as well
+pub fn native_map_deep_clone(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err("deepClone() expects no arguments".to_string());
+    }
+
+    extract_receiver!(args, Map, "deepClone")?;
+    Ok(args[0].deep_clone())
+}
+
 pub fn native_map_values(args: &[Value]) -> Result<Value, String> {
     if args.len() != 1 {
         return Err("values() expects no arguments".to_string());
@@ -136,32 +182,47 @@ pub fn native_map_entries(args: &[Value]) -> Result<Value, String> {
     let map = map_ref.borrow();
     let entries: Vec<Value> = map
         .iter()
-        .map(|(key, value)| Value::new_array(vec![map_key_to_value(key), value.clone()]))
+        .map(|(key, value)| Value::new_array(vec![MapKey::to_value(key), value.clone()]))
         .collect();
     Ok(Value::new_array(entries))
 }
 
-fn value_to_map_key(value: &Value) -> Option<MapKey> {
-    match value {
-        Value::Object(obj) => match obj.as_ref() {
-            Object::String(s) => Some(MapKey::String(Rc::clone(&s.value))),
-            _ => None,
-        },
-        Value::Number(n) => Some(MapKey::Number(OrderedFloat(*n))),
-        Value::Boolean(b) => Some(MapKey::Boolean(*b)),
-        Value::Nil => None,
+/// Native implementation of Map.from(pairs)
+/// Builds a new map from an array of [key, value] pairs; later pairs overwrite earlier keys
+pub fn native_map_from(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err(format!("Map.from() expects 1 argument, got {}", args.len()));
     }
-}
 
-fn map_key_to_value(key: &MapKey) -> Value {
-    match key {
-        MapKey::String(s) => {
-            use crate::common::ObjString;
-            Value::Object(Rc::new(Object::String(ObjString {
-                value: Rc::clone(s),
-            })))
-        }
-        MapKey::Number(n) => Value::Number(n.into_inner()),
-        MapKey::Boolean(b) => Value::Boolean(*b),
+    let pairs_ref = extract_arg!(args, 0, Array, "pairs", "Map.from")?;
+    let mut entries = HashMap::new();
+    for (index, pair) in pairs_ref.borrow().iter().enumerate() {
+        let (key, value) = match pair {
+            Value::Object(obj) => match obj.unfrozen() {
+                Object::Array(pair_ref) if pair_ref.borrow().len() == 2 => {
+                    let pair = pair_ref.borrow();
+                    (pair[0].clone(), pair[1].clone())
+                }
+                _ => return Err(invalid_pair_error(index, pair)),
+            },
+            _ => return Err(invalid_pair_error(index, pair)),
+        };
+
+        let key = MapKey::from_value(&key).ok_or_else(|| {
+            format!(
+                "Map.from() invalid key at index {}: {}. Only strings, numbers, booleans and tuples can be used as map keys.",
+                index, key
+            )
+        })?;
+        entries.insert(key, value);
     }
+
+    Ok(Value::new_map(entries))
+}
+
+fn invalid_pair_error(index: usize, pair: &Value) -> String {
+    format!(
+        "Map.from() expects [key, value] pairs, got {} at index {}",
+        pair, index
+    )
 }
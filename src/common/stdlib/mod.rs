@@ -1,16 +1,21 @@
-use crate::common::{ObjString, Object, Value};
+use crate::common::Value;
+use crate::string;
 use indexmap::IndexMap;
-use std::rc::Rc;
 
 pub(crate) mod array_functions;
 pub(crate) mod boolean_functions;
+pub(crate) mod conversion_functions;
 pub(crate) mod file_functions;
 pub(crate) mod map_functions;
 pub(crate) mod math_functions;
+pub(crate) mod modules;
 pub(crate) mod number_functions;
+pub(crate) mod regex_functions;
+pub(crate) mod result_functions;
 pub(crate) mod set_functions;
 pub(crate) mod string_functions;
 pub(crate) mod system_functions;
+pub(crate) mod time_functions;
 
 #[macro_use]
 pub(crate) mod extraction_macros;
@@ -19,25 +24,19 @@ pub(crate) mod extraction_macros;
 mod tests;
 
 /// Create stdlib objects for the VM.
-/// Math and File are now handled through the unified registry system.
-/// Only runtime values like args remain as builtins.
+/// Math, File, Regex, System and Time are now handled through the unified registry system.
+/// Only runtime values like args and None remain as builtins.
 pub fn create_builtin_objects(args: Vec<String>) -> IndexMap<String, Value> {
     let mut builtin = IndexMap::new();
 
     // args is a runtime value, so it remains a stdlib
     builtin.insert("args".to_string(), create_args_array(args));
+    builtin.insert("None".to_string(), Value::new_option(None));
 
     builtin
 }
 
 fn create_args_array(args: Vec<String>) -> Value {
-    let elements: Vec<Value> = args
-        .into_iter()
-        .map(|arg| {
-            Value::Object(Rc::new(Object::String(ObjString {
-                value: Rc::from(arg.as_str()),
-            })))
-        })
-        .collect();
+    let elements: Vec<Value> = args.into_iter().map(|arg| string!(arg)).collect();
     Value::new_array(elements)
 }
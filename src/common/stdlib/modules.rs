@@ -0,0 +1,13 @@
+//! Modules of the standard library written in Neon. Scripts import them by their path, like
+//! `import { clamp } from "std/math"`, and they are compiled like any other imported file.
+
+/// Source of each module, by import path
+const MODULES: &[(&str, &str)] = &[("std/math", include_str!("modules/math.n"))];
+
+/// Source of the standard library module imported as `path`
+pub(crate) fn source(path: &str) -> Option<&'static str> {
+    MODULES
+        .iter()
+        .find(|(name, _)| *name == path)
+        .map(|(_, source)| *source)
+}
@@ -1,17 +1,101 @@
-use crate::common::Value;
-use crate::{extract_receiver, string};
+use crate::common::{format_number, Value};
+use crate::{extract_arg, extract_receiver, string};
 
 /// Native implementation of Number.toString()
-/// Converts a number to its string representation
-/// Handles edge case: removes trailing ".0" for integer values
+/// Converts a number to its string representation, formatted the same way print() shows it
 pub fn native_number_to_string(args: &[Value]) -> Result<Value, String> {
+    if let Some(Value::Int(i)) = args.first() {
+        return Ok(string!(i.to_string()));
+    }
     let num = extract_receiver!(args, Number, "toString")?;
-    let num_str = if num.fract() == 0.0 && num.is_finite() {
-        // Integer value: format without decimal point
-        format!("{:.0}", num)
-    } else {
-        // Decimal value: use standard formatting
-        num.to_string()
+    Ok(string!(format_number(num)))
+}
+
+/// Native implementation of Number.toInt()
+/// Truncates towards zero; nan, inf and numbers outside the int range are errors
+pub fn native_number_to_int(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err(format!(
+            "toInt() expects no arguments, got {}",
+            args.len() - 1
+        ));
+    }
+    if let Value::Int(i) = args[0] {
+        return Ok(Value::Int(i));
+    }
+
+    let num = extract_receiver!(args, Number, "toInt")?;
+    if !num.is_finite() || num < i64::MIN as f64 || num >= i64::MAX as f64 {
+        return Err(format!(
+            "toInt() cannot convert {} to an int",
+            format_number(num)
+        ));
+    }
+    Ok(Value::Int(num.trunc() as i64))
+}
+
+/// Most decimals formatNumber() accepts; f64 has no meaningful digits beyond this
+const MAX_FORMAT_DECIMALS: f64 = 20.0;
+
+/// Native implementation of formatNumber(number, decimals)
+/// Rounds to a fixed number of decimals and separates thousands with commas: `"1,234,567.50"`
+pub fn native_format_number(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 2 {
+        return Err(format!(
+            "formatNumber() expects 2 arguments, got {}",
+            args.len()
+        ));
+    }
+
+    let decimals = extract_arg!(args, 1, Number, "decimals", "formatNumber")?;
+    if decimals.fract() != 0.0 || !(0.0..=MAX_FORMAT_DECIMALS).contains(&decimals) {
+        return Err(format!(
+            "formatNumber() decimals must be a whole number from 0 to {}, got {}",
+            MAX_FORMAT_DECIMALS, args[1]
+        ));
+    }
+    let decimals = decimals as usize;
+
+    // Ints are formatted from their digits, so values beyond f64 precision stay exact
+    let (negative, fixed) = match args[0] {
+        Value::Int(i) => {
+            let digits = i.unsigned_abs().to_string();
+            let fixed = if decimals == 0 {
+                digits
+            } else {
+                format!("{}.{}", digits, "0".repeat(decimals))
+            };
+            (i < 0, fixed)
+        }
+        _ => {
+            let number = extract_arg!(args, 0, Number, "number", "formatNumber")?;
+            if !number.is_finite() {
+                return Ok(string!(format_number(number)));
+            }
+            (number < 0.0, format!("{:.*}", decimals, number.abs()))
+        }
     };
-    Ok(string!(num_str))
+
+    let (integer, fraction) = match fixed.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (fixed.as_str(), None),
+    };
+    // A negative number that rounds to zero prints without its sign, like -0 does
+    let rounds_to_zero = fixed.bytes().all(|b| b == b'0' || b == b'.');
+
+    let mut formatted = String::with_capacity(fixed.len() + fixed.len() / 3 + 1);
+    if negative && !rounds_to_zero {
+        formatted.push('-');
+    }
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            formatted.push(',');
+        }
+        formatted.push(digit);
+    }
+    if let Some(fraction) = fraction {
+        formatted.push('.');
+        formatted.push_str(fraction);
+    }
+    Ok(string!(formatted))
 }
@@ -0,0 +1,51 @@
+use crate::common::{Object, Value};
+use crate::vm::VirtualMachine;
+use crate::{extract_arg, extract_string_value, string};
+use regex::Regex;
+
+/// Extracts (pattern, text) and returns the cached compiled pattern with the text
+fn regex_and_text<'a>(
+    vm: &'a mut VirtualMachine,
+    args: &'a [Value],
+    method: &str,
+) -> Result<(&'a Regex, &'a str), String> {
+    if args.len() != 2 {
+        return Err(format!(
+            "Regex.{}() expects 2 arguments (pattern, text), got {}",
+            method,
+            args.len()
+        ));
+    }
+
+    let pattern = extract_string_value!(args, 0, "pattern", method);
+    let text = extract_string_value!(args, 1, "text", method);
+    let regex = vm
+        .compiled_regex(pattern)
+        .map_err(|e| format!("Regex.{}() invalid pattern: {}", method, e))?;
+    Ok((regex, text))
+}
+
+/// Native implementation of Regex.match(pattern, text)
+/// Returns true if the pattern matches anywhere in the text
+pub fn native_regex_match(vm: &mut VirtualMachine, args: &[Value]) -> Result<Value, String> {
+    let (regex, text) = regex_and_text(vm, args, "match")?;
+    Ok(Value::Boolean(regex.is_match(text)))
+}
+
+/// Native implementation of Regex.find(pattern, text)
+/// Returns the first match as a string, or nil if there is none
+pub fn native_regex_find(vm: &mut VirtualMachine, args: &[Value]) -> Result<Value, String> {
+    let (regex, text) = regex_and_text(vm, args, "find")?;
+    Ok(regex
+        .find(text)
+        .map(|m| string!(m.as_str()))
+        .unwrap_or(Value::Nil))
+}
+
+/// Native implementation of Regex.findAll(pattern, text)
+/// Returns an array of all non-overlapping matches
+pub fn native_regex_find_all(vm: &mut VirtualMachine, args: &[Value]) -> Result<Value, String> {
+    let (regex, text) = regex_and_text(vm, args, "findAll")?;
+    let matches = regex.find_iter(text).map(|m| string!(m.as_str())).collect();
+    Ok(Value::new_array(matches))
+}
@@ -0,0 +1,113 @@
+use crate::common::{Object, Value};
+
+/// Native implementation of Ok(value)
+/// Wraps the value of an operation that succeeded
+pub fn native_ok(args: &[Value]) -> Result<Value, String> {
+    let value = single_arg(args, "Ok")?;
+    Ok(Value::new_result(Ok(value)))
+}
+
+/// Native implementation of Err(error)
+/// Wraps the error of an operation that failed, usually a message
+pub fn native_err(args: &[Value]) -> Result<Value, String> {
+    let error = single_arg(args, "Err")?;
+    Ok(Value::new_result(Err(error)))
+}
+
+/// Native implementation of Some(value)
+/// Wraps a value that is present; `None` stands for an absent one
+pub fn native_some(args: &[Value]) -> Result<Value, String> {
+    let value = single_arg(args, "Some")?;
+    Ok(Value::new_option(Some(value)))
+}
+
+/// Native implementation of Result.isOk()
+pub fn native_result_is_ok(args: &[Value]) -> Result<Value, String> {
+    let result = result_receiver(args, "isOk")?;
+    Ok(Value::Boolean(result.is_ok()))
+}
+
+/// Native implementation of Result.isErr()
+pub fn native_result_is_err(args: &[Value]) -> Result<Value, String> {
+    let result = result_receiver(args, "isErr")?;
+    Ok(Value::Boolean(result.is_err()))
+}
+
+/// Native implementation of Result.unwrap()
+/// Returns the value of an `Ok`; unwrapping an `Err` is an error carrying its message
+pub fn native_result_unwrap(args: &[Value]) -> Result<Value, String> {
+    match result_receiver(args, "unwrap")? {
+        Ok(value) => Ok(value.clone()),
+        Err(error) => Err(format!("unwrap() called on Err({})", error)),
+    }
+}
+
+/// Native implementation of Result.unwrapOr(default)
+/// Returns the value of an `Ok`, or the default for an `Err`
+pub fn native_result_unwrap_or(args: &[Value]) -> Result<Value, String> {
+    let result = result_receiver(args, "unwrapOr")?;
+    let default = single_arg(&args[1..], "unwrapOr")?;
+    Ok(result.as_ref().map_or(default, Value::clone))
+}
+
+/// Native implementation of Option.isSome()
+pub fn native_option_is_some(args: &[Value]) -> Result<Value, String> {
+    let option = option_receiver(args, "isSome")?;
+    Ok(Value::Boolean(option.is_some()))
+}
+
+/// Native implementation of Option.isNone()
+pub fn native_option_is_none(args: &[Value]) -> Result<Value, String> {
+    let option = option_receiver(args, "isNone")?;
+    Ok(Value::Boolean(option.is_none()))
+}
+
+/// Native implementation of Option.unwrap()
+/// Returns the value of a `Some`; unwrapping `None` is an error
+pub fn native_option_unwrap(args: &[Value]) -> Result<Value, String> {
+    option_receiver(args, "unwrap")?
+        .clone()
+        .ok_or_else(|| "unwrap() called on None".to_string())
+}
+
+/// Native implementation of Option.unwrapOr(default)
+/// Returns the value of a `Some`, or the default for `None`
+pub fn native_option_unwrap_or(args: &[Value]) -> Result<Value, String> {
+    let option = option_receiver(args, "unwrapOr")?;
+    let default = single_arg(&args[1..], "unwrapOr")?;
+    Ok(option.clone().unwrap_or(default))
+}
+
+fn single_arg(args: &[Value], function: &str) -> Result<Value, String> {
+    match args {
+        [value] => Ok(value.clone()),
+        _ => Err(format!(
+            "{}() expects 1 argument, got {}",
+            function,
+            args.len()
+        )),
+    }
+}
+
+fn result_receiver<'a>(
+    args: &'a [Value],
+    method: &str,
+) -> Result<&'a Result<Value, Value>, String> {
+    match args.first() {
+        Some(Value::Object(obj)) => match obj.as_ref() {
+            Object::Result(result) => Ok(result),
+            _ => Err(format!("{}() can only be called on results", method)),
+        },
+        _ => Err(format!("{}() can only be called on results", method)),
+    }
+}
+
+fn option_receiver<'a>(args: &'a [Value], method: &str) -> Result<&'a Option<Value>, String> {
+    match args.first() {
+        Some(Value::Object(obj)) => match obj.as_ref() {
+            Object::Option(option) => Ok(option),
+            _ => Err(format!("{}() can only be called on options", method)),
+        },
+        _ => Err(format!("{}() can only be called on options", method)),
+    }
+}
@@ -1,8 +1,6 @@
 use crate::common::{Object, SetKey, Value};
 use crate::{extract_arg, extract_receiver};
-use ordered_float::OrderedFloat;
 use std::collections::BTreeSet;
-use std::rc::Rc;
 
 /// Native implementation of Set.add(element)
 /// Adds an element to the set, returns true if added (was not present), false otherwise
@@ -14,14 +12,14 @@ pub fn native_set_add(args: &[Value]) -> Result<Value, String> {
         ));
     }
 
-    let set_ref = extract_receiver!(args, Set, "add")?;
+    let set_ref = extract_receiver!(args, mut Set, "add")?;
 
     // Convert element to SetKey
-    let key = match value_to_set_key(&args[1]) {
+    let key = match SetKey::from_value(&args[1]) {
         Some(k) => k,
         None => {
             return Err(format!(
-                "Invalid set element type: {}. Only strings, numbers, and booleans can be used as set elements.",
+                "Invalid set element type: {}. Only strings, numbers, booleans and tuples can be used as set elements.",
                 args[1]
             ));
         }
@@ -43,14 +41,14 @@ pub fn native_set_remove(args: &[Value]) -> Result<Value, String> {
         ));
     }
 
-    let set_ref = extract_receiver!(args, Set, "remove")?;
+    let set_ref = extract_receiver!(args, mut Set, "remove")?;
 
     // Convert element to SetKey
-    let key = match value_to_set_key(&args[1]) {
+    let key = match SetKey::from_value(&args[1]) {
         Some(k) => k,
         None => {
             return Err(format!(
-                "Invalid set element type: {}. Only strings, numbers, and booleans can be used as set elements.",
+                "Invalid set element type: {}. Only strings, numbers, booleans and tuples can be used as set elements.",
                 args[1]
             ));
         }
@@ -75,11 +73,11 @@ pub fn native_set_has(args: &[Value]) -> Result<Value, String> {
     let set_ref = extract_receiver!(args, Set, "has")?;
 
     // Convert element to SetKey
-    let key = match value_to_set_key(&args[1]) {
+    let key = match SetKey::from_value(&args[1]) {
         Some(k) => k,
         None => {
             return Err(format!(
-                "Invalid set element type: {}. Only strings, numbers, and booleans can be used as set elements.",
+                "Invalid set element type: {}. Only strings, numbers, booleans and tuples can be used as set elements.",
                 args[1]
             ));
         }
@@ -102,6 +100,19 @@ pub fn native_set_size(args: &[Value]) -> Result<Value, String> {
     Ok(Value::Number(set.len() as f64))
 }
 
+/// Native implementation of Set.clone() and Set.deepClone()
+/// Returns a new set with the same elements. Elements are never collections, so both
+/// methods make a fully independent copy.
+pub fn native_set_clone(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err("clone() expects no arguments".to_string());
+    }
+
+    let set_ref = extract_receiver!(args, Set, "clone")?;
+    let elements = set_ref.borrow().clone();
+    Ok(Value::new_set(elements))
+}
+
 /// Native implementation of Set.clear()
 /// Removes all elements from the set, returns nil
 pub fn native_set_clear(args: &[Value]) -> Result<Value, String> {
@@ -109,7 +120,7 @@ pub fn native_set_clear(args: &[Value]) -> Result<Value, String> {
         return Err("clear() expects no arguments".to_string());
     }
 
-    let set_ref = extract_receiver!(args, Set, "clear")?;
+    let set_ref = extract_receiver!(args, mut Set, "clear")?;
     let mut set = set_ref.borrow_mut();
     set.clear();
     Ok(Value::Nil)
@@ -195,30 +206,6 @@ pub fn native_set_is_subset(args: &[Value]) -> Result<Value, String> {
     Ok(Value::Boolean(is_subset))
 }
 
-/// Helper function to convert a Value to a SetKey
-fn value_to_set_key(value: &Value) -> Option<SetKey> {
-    match value {
-        Value::Object(obj) => match obj.as_ref() {
-            Object::String(s) => Some(SetKey::String(Rc::clone(&s.value))),
-            _ => None,
-        },
-        Value::Number(n) => Some(SetKey::Number(OrderedFloat(*n))),
-        Value::Boolean(b) => Some(SetKey::Boolean(*b)),
-        Value::Nil => None,
-    }
-}
-
-/// Helper function to convert a SetKey back to a Value
-fn set_key_to_value(key: &SetKey) -> Value {
-    match key {
-        SetKey::String(s) => Value::Object(Rc::new(Object::String(crate::common::ObjString {
-            value: Rc::clone(s),
-        }))),
-        SetKey::Number(n) => Value::Number(n.0),
-        SetKey::Boolean(b) => Value::Boolean(*b),
-    }
-}
-
 /// Native implementation of Set.toArray()
 /// Returns a new array containing all elements from the set
 pub fn native_set_to_array(args: &[Value]) -> Result<Value, String> {
@@ -228,7 +215,7 @@ pub fn native_set_to_array(args: &[Value]) -> Result<Value, String> {
 
     let set_ref = extract_receiver!(args, Set, "toArray")?;
     let set = set_ref.borrow();
-    let array_elements: Vec<Value> = set.iter().map(set_key_to_value).collect();
+    let array_elements: Vec<Value> = set.iter().map(SetKey::to_value).collect();
 
     Ok(Value::new_array(array_elements))
 }
@@ -87,7 +87,7 @@ pub fn native_string_replace(args: &[Value]) -> Result<Value, String> {
 }
 
 /// Native implementation of String.toInt()
-/// Parses the string as an integer and returns it as a Number
+/// Parses the string as an integer and returns it as an Int
 /// Returns an error if the string cannot be parsed as an integer
 pub fn native_string_to_int(args: &[Value]) -> Result<Value, String> {
     if args.len() != 1 {
@@ -103,7 +103,7 @@ pub fn native_string_to_int(args: &[Value]) -> Result<Value, String> {
     // Trim whitespace and parse as i64
     let trimmed = obj_string.value.trim();
     match trimmed.parse::<i64>() {
-        Ok(num) => Ok(Value::Number(num as f64)),
+        Ok(num) => Ok(Value::Int(num)),
         Err(_) => Err(format!(
             "toInt() failed: '{}' is not a valid integer",
             obj_string.value
@@ -360,3 +360,54 @@ pub fn native_string_to_lower_case(args: &[Value]) -> Result<Value, String> {
     let lowercase = obj_string.value.to_lowercase();
     Ok(string!(lowercase))
 }
+
+/// Native implementation of String.bytes()
+/// Returns the UTF-8 encoding of the string as an array of byte values
+pub fn native_string_bytes(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err(format!(
+            "bytes() expects no arguments, got {}",
+            args.len() - 1
+        ));
+    }
+
+    // Extract the string
+    let obj_string = extract_receiver!(args, String, "bytes")?;
+
+    let bytes = obj_string
+        .value
+        .bytes()
+        .map(|b| Value::Number(b as f64))
+        .collect();
+    Ok(Value::new_array(bytes))
+}
+
+/// Native implementation of String.fromBytes(bytes)
+/// Decodes an array of byte values (integers in 0..255) as a UTF-8 string
+pub fn native_string_from_bytes(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err(format!(
+            "String.fromBytes() expects 1 argument, got {}",
+            args.len()
+        ));
+    }
+
+    let array_ref = extract_arg!(args, 0, Array, "bytes", "String.fromBytes")?;
+    let bytes = array_ref
+        .borrow()
+        .iter()
+        .enumerate()
+        .map(|(index, value)| match value.as_f64() {
+            Some(n) if n.fract() == 0.0 && (0.0..=255.0).contains(&n) => Ok(n as u8),
+            _ => Err(format!(
+                "String.fromBytes() expects integers in 0..255, got {} at index {}",
+                value, index
+            )),
+        })
+        .collect::<Result<Vec<u8>, String>>()?;
+
+    match String::from_utf8(bytes) {
+        Ok(decoded) => Ok(string!(decoded)),
+        Err(e) => Err(format!("String.fromBytes() invalid UTF-8: {}", e)),
+    }
+}
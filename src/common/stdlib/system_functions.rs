@@ -1,11 +1,10 @@
-use crate::common::Value;
-
-/// Native print(function - this is a placeholder that should never be called)
-/// The VM handles print(function calls directly in handle_print_function())
-/// This function exists only to satisfy the method registry requirements
-pub fn native_system_print(args: &[Value]) -> Result<Value, String> {
-    // This should never be called - the VM intercepts print(calls)
-    // But if it is called somehow, provide basic functionality
+use crate::common::{Object, Value};
+use crate::vm::VirtualMachine;
+use crate::{extract_arg, extract_string_value, is_false_like, string};
+
+/// Native implementation of print(args...)
+/// Joins the arguments with spaces and writes them as one line to the VM's output sink
+pub fn native_system_print(vm: &mut VirtualMachine, args: &[Value]) -> Result<Value, String> {
     if args.is_empty() {
         return Err("print() expects at least 1 argument".to_string());
     }
@@ -17,13 +16,151 @@ pub fn native_system_print(args: &[Value]) -> Result<Value, String> {
         .collect::<Vec<_>>()
         .join(" ");
 
-    // Print to stdout
-    #[cfg(not(target_arch = "wasm32"))]
-    println!("{}", output);
+    vm.write_output_line(&output)?;
+
+    Ok(Value::Nil)
+}
+
+/// Native implementation of input(prompt?)
+/// Writes the optional prompt, then returns the next trimmed line of input, or nil at its end
+pub fn native_system_input(vm: &mut VirtualMachine, args: &[Value]) -> Result<Value, String> {
+    if args.len() > 1 {
+        return Err(format!(
+            "input() expects at most 1 argument, got {}",
+            args.len()
+        ));
+    }
+
+    if !args.is_empty() {
+        let prompt = extract_string_value!(args, 0, "prompt", "input");
+        vm.write_prompt(prompt)?;
+    }
+    Ok(vm
+        .read_input_line()?
+        .map(|line| string!(line))
+        .unwrap_or(Value::Nil))
+}
+
+/// Native implementation of assert(condition, message?)
+/// Fails with a runtime error when the condition is nil or false
+pub fn native_system_assert(args: &[Value]) -> Result<Value, String> {
+    if args.is_empty() || args.len() > 2 {
+        return Err(format!(
+            "assert() expects 1 or 2 arguments, got {}",
+            args.len()
+        ));
+    }
+
+    if !is_false_like!(&args[0]) {
+        return Ok(Value::Nil);
+    }
+    match args.get(1) {
+        Some(message) => Err(format!("Assertion failed: {}", message)),
+        None => Err("Assertion failed".to_string()),
+    }
+}
+
+/// Native implementation of System.env(name)
+/// Returns the value of an environment variable, or nil if it is unset
+pub fn native_system_env(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err(format!(
+            "System.env() expects 1 argument, got {}",
+            args.len()
+        ));
+    }
+
+    let name = extract_string_value!(args, 0, "name", "env");
+    Ok(read_env(name)
+        .map(|value| string!(value))
+        .unwrap_or(Value::Nil))
+}
+
+/// Native implementation of System.envOrDefault(name, default)
+/// Returns the value of an environment variable, or the default if it is unset
+pub fn native_system_env_or_default(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 2 {
+        return Err(format!(
+            "System.envOrDefault() expects 2 arguments, got {}",
+            args.len()
+        ));
+    }
+
+    let name = extract_string_value!(args, 0, "name", "envOrDefault");
+    Ok(read_env(name)
+        .map(|value| string!(value))
+        .unwrap_or_else(|| args[1].clone()))
+}
+
+/// Native implementation of System.exit(code)
+/// Stops the script; the VM returns `Result::Exit(code)` instead of exiting the process
+pub fn native_system_exit(vm: &mut VirtualMachine, args: &[Value]) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err(format!(
+            "System.exit() expects 1 argument, got {}",
+            args.len()
+        ));
+    }
 
+    let code = extract_arg!(args, 0, Number, "code", "exit")?;
+    if code.fract() != 0.0 || code < i32::MIN as f64 || code > i32::MAX as f64 {
+        return Err(format!(
+            "System.exit() expects an integer status code, got {}",
+            args[0]
+        ));
+    }
+
+    vm.request_exit(code as i32);
     Ok(Value::Nil)
 }
 
+/// Native implementation of System.hasFlag(flag)
+/// Returns true if the flag appears among the script's command-line arguments
+pub fn native_system_has_flag(vm: &mut VirtualMachine, args: &[Value]) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err(format!(
+            "System.hasFlag() expects 1 argument, got {}",
+            args.len()
+        ));
+    }
+
+    let flag = extract_string_value!(args, 0, "flag", "hasFlag");
+    Ok(Value::Boolean(
+        vm.script_args().iter().any(|arg| arg == flag),
+    ))
+}
+
+/// Native implementation of System.argValue(name)
+/// Returns the argument following the first `name` token, or nil if absent
+pub fn native_system_arg_value(vm: &mut VirtualMachine, args: &[Value]) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err(format!(
+            "System.argValue() expects 1 argument, got {}",
+            args.len()
+        ));
+    }
+
+    let name = extract_string_value!(args, 0, "name", "argValue");
+    let script_args = vm.script_args();
+    Ok(script_args
+        .iter()
+        .position(|arg| arg == name)
+        .and_then(|index| script_args.get(index + 1))
+        .map(|value| string!(value.as_str()))
+        .unwrap_or(Value::Nil))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read_env(name: &str) -> Option<String> {
+    std::env::var(name).ok()
+}
+
+/// Environment variables don't exist in the browser
+#[cfg(target_arch = "wasm32")]
+fn read_env(_name: &str) -> Option<String> {
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -31,8 +168,9 @@ mod tests {
 
     #[test]
     fn test_print_single_argument() {
+        let mut vm = VirtualMachine::new();
         let args = vec![number!(42.0)];
-        let result = native_system_print(&args);
+        let result = native_system_print(&mut vm, &args);
 
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), Value::Nil);
@@ -40,8 +178,9 @@ mod tests {
 
     #[test]
     fn test_print_multiple_arguments() {
+        let mut vm = VirtualMachine::new();
         let args = vec![number!(1.0), number!(2.0), number!(3.0)];
-        let result = native_system_print(&args);
+        let result = native_system_print(&mut vm, &args);
 
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), Value::Nil);
@@ -49,8 +188,9 @@ mod tests {
 
     #[test]
     fn test_print_mixed_types() {
+        let mut vm = VirtualMachine::new();
         let args = vec![string!("Hello"), number!(42.0), boolean!(true)];
-        let result = native_system_print(&args);
+        let result = native_system_print(&mut vm, &args);
 
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), Value::Nil);
@@ -58,8 +198,9 @@ mod tests {
 
     #[test]
     fn test_print_no_arguments() {
+        let mut vm = VirtualMachine::new();
         let args = vec![];
-        let result = native_system_print(&args);
+        let result = native_system_print(&mut vm, &args);
 
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), "print() expects at least 1 argument");
@@ -67,8 +208,9 @@ mod tests {
 
     #[test]
     fn test_print_string_argument() {
+        let mut vm = VirtualMachine::new();
         let args = vec![string!("Hello World")];
-        let result = native_system_print(&args);
+        let result = native_system_print(&mut vm, &args);
 
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), Value::Nil);
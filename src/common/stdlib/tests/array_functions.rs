@@ -66,6 +66,52 @@ fn test_array_pop() {
     assert_eq!("3\n[1, 2]\n2\n[1]", vm.get_output());
 }
 
+#[test]
+fn test_array_shift() {
+    let program = r#"
+        val arr = [1, 2, 3]
+        print(arr.shift())
+        print(arr)
+        print(arr.shift())
+        print(arr.shift())
+        print(arr.shift())
+        print(arr)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("1\n[2, 3]\n2\n3\nnil\n[]", vm.get_output());
+}
+
+#[test]
+fn test_array_unshift() {
+    let program = r#"
+        val arr = [3]
+        print(arr.unshift(2))
+        print(arr.unshift(0, 1))
+        print([].unshift("a"))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("[2, 3]\n[0, 1, 2, 3]\n[a]", vm.get_output());
+}
+
+#[test]
+fn test_array_shift_and_unshift_as_queue() {
+    let program = r#"
+        val queue = []
+        queue.push(1).push(2)
+        queue.unshift(0)
+        print(queue.shift() + queue.shift() + queue.pop())
+        print(queue.length())
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("3\n0", vm.get_output());
+}
+
 // ============================================================================
 // Array.length() and Array.size() - Success Cases
 // ============================================================================
@@ -166,6 +212,60 @@ fn test_array_reverse() {
     assert_eq!("[5, 4, 3, 2, 1]\n[42]\n[]", vm.get_output());
 }
 
+// ============================================================================
+// Array.fill() - Success Cases
+// ============================================================================
+
+#[test]
+fn test_array_fill() {
+    let program = r#"
+        val arr = [1, 2, 3]
+        arr.fill(0)
+        print(arr)
+
+        val empty = []
+        empty.fill(7)
+        print(empty)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("[0, 0, 0]\n[]", vm.get_output());
+}
+
+// ============================================================================
+// Mutating Methods - Chaining
+// ============================================================================
+
+#[test]
+fn test_array_mutating_methods_chain() {
+    let program = r#"
+        val arr = [3]
+        arr.push(1).push(2).sort()
+        print(arr)
+        print(arr.push(5).reverse())
+        print(arr.fill("x").size())
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("[1, 2, 3]\n[5, 3, 2, 1]\n4", vm.get_output());
+}
+
+#[test]
+fn test_array_push_returns_same_array() {
+    let program = r#"
+        val arr = [1]
+        val same = arr.push(2)
+        same.push(3)
+        print(arr)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("[1, 2, 3]", vm.get_output());
+}
+
 // ============================================================================
 // Array.slice() - Success Cases
 // ============================================================================
@@ -465,3 +565,529 @@ fn test_array_max_empty() {
     let mut vm = VirtualMachine::new();
     assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
 }
+
+// ============================================================================
+// Array.from()
+// ============================================================================
+
+#[test]
+fn test_array_from_set() {
+    let program = r#"
+        val arr = Array.from({3, 1, 2})
+        print(arr)
+        print(arr.push(4))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("[1, 2, 3]\n[1, 2, 3, 4]", vm.get_output());
+}
+
+#[test]
+fn test_array_from_string() {
+    let program = r#"
+        print(Array.from("héllo"))
+        print(Array.from("").size())
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("[h, é, l, l, o]\n0", vm.get_output());
+}
+
+#[test]
+fn test_array_from_array_copies() {
+    let program = r#"
+        val original = [1, 2]
+        val copy = Array.from(original)
+        copy.push(3)
+        print(original)
+        print(copy)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("[1, 2]\n[1, 2, 3]", vm.get_output());
+}
+
+#[test]
+fn test_array_from_invalid_argument() {
+    let program = r#"
+        Array.from(42)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm
+        .get_runtime_errors()
+        .contains("Array.from() expects an array, set or string, got 42"));
+}
+
+// ============================================================================
+// Array.clone() and Array.deepClone()
+// ============================================================================
+
+#[test]
+fn test_array_clone_is_shallow() {
+    let program = r#"
+        val original = [1, [2, 3]]
+        val copy = original.clone()
+        copy.push(4)
+        copy[1].push(5)
+        print(original)
+        print(copy)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("[1, [2, 3, 5]]\n[1, [2, 3, 5], 4]", vm.get_output());
+}
+
+#[test]
+fn test_array_deep_clone_is_independent() {
+    let program = r#"
+        val original = [1, [2, 3], {"k": [4]}]
+        val copy = original.deepClone()
+        copy.push(5)
+        copy[1].push(6)
+        copy[2]["k"].push(7)
+        print(original)
+        print(copy)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!(
+        "[1, [2, 3], {k: [4]}]\n[1, [2, 3, 6], {k: [4, 7]}, 5]",
+        vm.get_output()
+    );
+}
+
+#[test]
+fn test_array_deep_clone_keeps_shared_and_cyclic_structure() {
+    let program = r#"
+        val shared = [1]
+        val original = [shared, shared]
+        original.push(original)
+        val copy = original.deepClone()
+        copy[0].push(2)
+        print(copy[1])
+        print(shared)
+        print(copy[2].length())
+        copy.push(3)
+        print(copy[2].length())
+        print(original.length())
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("[1, 2]\n[1]\n3\n4\n3", vm.get_output());
+}
+
+#[test]
+fn test_array_clone_with_arguments() {
+    let program = r#"
+        [1].clone(2)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm
+        .get_runtime_errors()
+        .contains("clone() expects no arguments, got 1"));
+}
+
+// ============================================================================
+// Array.enumerate()
+// ============================================================================
+
+#[test]
+fn test_array_enumerate_pairs_indices_with_elements() {
+    let program = r#"
+        print([10, 20].enumerate())
+        print([].enumerate())
+        for (pair in ["a", "b"].enumerate()) {
+            print("${pair[0]}: ${pair[1]}")
+        }
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("[[0, 10], [1, 20]]\n[]\n0: a\n1: b", vm.get_output());
+}
+
+#[test]
+fn test_array_enumerate_returns_a_new_array() {
+    let program = r#"
+        val items = ["x"]
+        val pairs = items.enumerate()
+        pairs.push([1, "y"])
+        print(items)
+        print(pairs.length())
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("[x]\n2", vm.get_output());
+}
+
+// ============================================================================
+// Array.find() and Array.findIndex()
+// ============================================================================
+
+#[test]
+fn test_array_find_returns_first_match() {
+    let program = r#"
+        val numbers = [1, 4, 6, 9]
+        print(numbers.find(n => n > 3))
+        print(numbers.findIndex(n => n > 3))
+        print(numbers.find(n => n % 3 == 0))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("4\n1\n6", vm.get_output());
+}
+
+#[test]
+fn test_array_find_without_match() {
+    let program = r#"
+        print([1, 2].find(n => n > 5))
+        print([1, 2].findIndex(n => n > 5))
+        print([].find(n => true))
+        print([].findIndex(n => true))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("nil\n-1\nnil\n-1", vm.get_output());
+}
+
+#[test]
+fn test_array_find_stops_at_first_match() {
+    let program = r#"
+        var calls = 0
+        fn isEven(n) {
+            calls = calls + 1
+            return n % 2 == 0
+        }
+        print([1, 2, 3, 4].find(isEven))
+        print(calls)
+        calls = 0
+        print([1, 3, 4, 6].findIndex(isEven))
+        print(calls)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("2\n2\n2\n3", vm.get_output());
+}
+
+#[test]
+fn test_array_find_uses_truthiness() {
+    let program = r#"
+        print([false, nil, 0, 1].findIndex(x => x))
+        print(["a", "b"].find(s => nil))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("2\nnil", vm.get_output());
+}
+
+#[test]
+fn test_array_find_closure_captures() {
+    let program = r#"
+        fn firstAbove(items, limit) {
+            return items.find(n => n > limit)
+        }
+        print(firstAbove([5, 10, 15], 7))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("10", vm.get_output());
+}
+
+#[test]
+fn test_array_find_error_in_predicate() {
+    let program = r#"
+        print("before")
+        [1, 2].find(n => n + "x")
+        print("after")
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert_eq!("before", vm.get_output());
+    assert_eq!(1, vm.get_runtime_errors().matches("Operands").count());
+}
+
+#[test]
+fn test_array_find_requires_a_function() {
+    let program = r#"
+        [1, 2].find(3)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm.get_runtime_errors().contains("3 is not a function"));
+}
+
+// ============================================================================
+// Array.every() and Array.some()
+// ============================================================================
+
+#[test]
+fn test_array_every_and_some() {
+    let program = r#"
+        val positive = n => n > 0
+        print([1, 2, 3].every(positive))
+        print([1, 2, 3].some(positive))
+        print([-1, -2].every(positive))
+        print([-1, -2].some(positive))
+        print([-1, 2].every(positive))
+        print([-1, 2].some(positive))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("true\ntrue\nfalse\nfalse\nfalse\ntrue", vm.get_output());
+}
+
+#[test]
+fn test_array_every_and_some_on_empty_array() {
+    let program = r#"
+        print([].every(n => false))
+        print([].some(n => true))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("true\nfalse", vm.get_output());
+}
+
+#[test]
+fn test_array_every_and_some_short_circuit() {
+    let program = r#"
+        var calls = 0
+        fn isSmall(n) {
+            calls = calls + 1
+            return n < 3
+        }
+        print([1, 5, 2, 7].every(isSmall))
+        print(calls)
+        calls = 0
+        print([5, 1, 7, 2].some(isSmall))
+        print(calls)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("false\n2\ntrue\n2", vm.get_output());
+}
+
+#[test]
+fn test_array_every_requires_boolean_predicate() {
+    let program = r#"
+        [1, 2].every(n => n)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm
+        .get_runtime_errors()
+        .contains("every() predicate must return a boolean, got 1"));
+}
+
+// ============================================================================
+// Array.forEach()
+// ============================================================================
+
+#[test]
+fn test_array_for_each_visits_elements_in_order() {
+    let program = r#"
+        var calls = 0
+        val result = ["a", "b", "c"].forEach(fn(s) {
+            calls = calls + 1
+            print("${calls}: ${s}")
+        })
+        print(result)
+        print(calls)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("1: a\n2: b\n3: c\nnil\n3", vm.get_output());
+}
+
+#[test]
+fn test_array_for_each_on_empty_array() {
+    let program = r#"
+        var calls = 0
+        [].forEach(fn(x) {
+            calls = calls + 1
+        })
+        print(calls)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("0", vm.get_output());
+}
+
+#[test]
+fn test_array_for_each_while_mutating() {
+    let program = r#"
+        val grows = [1, 2]
+        grows.forEach(fn(x) {
+            grows.push(x)
+        })
+        print(grows)
+        val shrinks = [1, 2, 3, 4]
+        var seen = 0
+        shrinks.forEach(fn(x) {
+            seen = seen + 1
+            shrinks.pop()
+        })
+        print(seen)
+        print(shrinks)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("[1, 2, 1, 2]\n2\n[1, 2]", vm.get_output());
+}
+
+#[test]
+fn test_array_for_each_error_in_callback() {
+    let program = r#"
+        [1, 2].forEach(fn(x) {
+            print(x)
+            return x.nope()
+        })
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert_eq!("1", vm.get_output());
+}
+
+// ============================================================================
+// Array.fill() and Array.range()
+// ============================================================================
+
+#[test]
+fn test_array_fill_constructor() {
+    let program = r#"
+        print(Array.fill(0, 3))
+        print(Array.fill("x", 0))
+        val grid = Array.fill(nil, 2)
+        grid[0] = 1
+        print(grid)
+        print(Array.fill(7, 2).fill(1))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("[0, 0, 0]\n[]\n[1, nil]\n[1, 1]", vm.get_output());
+}
+
+#[test]
+fn test_array_fill_constructor_shares_objects() {
+    let program = r#"
+        val rows = Array.fill([], 2)
+        rows[0].push(1)
+        print(rows)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("[[1], [1]]", vm.get_output());
+}
+
+#[test]
+fn test_array_fill_constructor_rejects_negative_count() {
+    let program = r#"
+        Array.fill(0, -1)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm
+        .get_runtime_errors()
+        .contains("Array.fill() count must be a non-negative whole number, got -1"));
+}
+
+#[test]
+fn test_array_fill_constructor_rejects_huge_counts() {
+    for count in ["10 ** 300", "10 ** 12"] {
+        let mut vm = VirtualMachine::new();
+        let program = format!("Array.fill(0, {})", count);
+        assert_eq!(Result::RuntimeError, vm.interpret(program));
+        assert!(vm
+            .get_runtime_errors()
+            .contains("Array.fill() count exceeds the maximum array length of 67108864"));
+    }
+}
+
+#[test]
+fn test_array_range_constructor() {
+    let program = r#"
+        print(Array.range(0, 4))
+        print(Array.range(-2, 1))
+        print(Array.range(3, 3))
+        print(Array.range(5, 1))
+        print(Array.range(1, 4) == 1..4)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("[0, 1, 2, 3]\n[-2, -1, 0]\n[]\n[]\ntrue", vm.get_output());
+}
+
+#[test]
+fn test_array_range_rejects_huge_ranges() {
+    let program = r#"
+        try {
+            Array.range(0, 10 ** 300)
+        } catch (e) {
+            print(e.message)
+        }
+        print(Array.range(10 ** 12, 10 ** 12 + 2))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!(
+        "Array.range() exceeds the maximum array length of 67108864\n[1000000000000, 1000000000001]",
+        vm.get_output()
+    );
+}
+
+#[test]
+fn test_array_range_rejects_fractional_bounds() {
+    let program = r#"
+        Array.range(0, 2.5)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm
+        .get_runtime_errors()
+        .contains("Array.range() bounds must be whole numbers, got 0 and 2.5"));
+}
+
+#[test]
+fn test_array_unshift_without_values() {
+    let program = r#"
+        val arr = [1]
+        arr.unshift()
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm
+        .get_runtime_errors()
+        .contains("unshift() expects at least 1 argument (value), got 0"));
+}
@@ -0,0 +1,443 @@
+use crate::vm::{Result, VirtualMachine};
+
+// ============================================================================
+// str() - Success Cases
+// ============================================================================
+
+#[test]
+fn test_str_converts_every_value_kind() {
+    let program = r#"
+        struct Point {
+            x
+            y
+        }
+        fn add(a, b) {
+            return a + b
+        }
+        print(str(42))
+        print(str(3.5))
+        print(str(true))
+        print(str(nil))
+        print(str("already a string"))
+        print(str([1, 2, 3]))
+        print(str({"a": 1}))
+        print(str({1}))
+        print(str(add))
+        print(str(Point(1, 2)))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!(
+        "42\n3.5\ntrue\nnil\nalready a string\n[1, 2, 3]\n{a: 1}\n{1}\n<fn add>\n<Point instance>",
+        vm.get_output()
+    );
+}
+
+#[test]
+fn test_str_result_supports_string_methods() {
+    let program = r#"
+        print(str(12345).len())
+        print(str(7) + "!")
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("5\n7!", vm.get_output());
+}
+
+// ============================================================================
+// num() - Success Cases
+// ============================================================================
+
+#[test]
+fn test_num_converts_numeric_strings() {
+    let program = r#"
+        print(num("42"))
+        print(num("-2.5"))
+        print(num("  7  "))
+        print(num("1e3"))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("42\n-2.5\n7\n1000", vm.get_output());
+}
+
+#[test]
+fn test_num_converts_booleans() {
+    let program = r#"
+        print(num(true))
+        print(num(false))
+        print(num(true) + num(true))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("1\n0\n2", vm.get_output());
+}
+
+#[test]
+fn test_num_passes_numbers_through() {
+    let program = r#"
+        print(num(3.25))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("3.25", vm.get_output());
+}
+
+#[test]
+fn test_str_and_num_round_trip() {
+    let program = r#"
+        print(num(str(99)) + 1)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("100", vm.get_output());
+}
+
+// ============================================================================
+// num() - Error Cases
+// ============================================================================
+
+#[test]
+fn test_num_invalid_string() {
+    let program = r#"
+        print(num("abc"))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm
+        .get_runtime_errors()
+        .contains("num() failed: 'abc' is not a valid number"));
+}
+
+#[test]
+fn test_num_nil() {
+    let program = r#"
+        print(num(nil))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+}
+
+#[test]
+fn test_num_array() {
+    let program = r#"
+        print(num([1]))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+}
+
+// ============================================================================
+// type()
+// ============================================================================
+
+#[test]
+fn test_type_names_every_value_kind() {
+    let program = r#"
+        struct Point {
+            x
+            y
+        }
+        fn add(a, b) {
+            return a + b
+        }
+        print(type(42))
+        print(type("text"))
+        print(type(true))
+        print(type(nil))
+        print(type([1, 2]))
+        print(type({"a": 1}))
+        print(type({1}))
+        print(type(add))
+        print(type(x => x))
+        print(type(Point))
+        print(type(Point(1, 2)))
+        print(type(File("data.txt")))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!(
+        "number\nstring\nboolean\nnil\narray\nmap\nset\nfunction\nfunction\nstruct\ninstance\nfile",
+        vm.get_output()
+    );
+}
+
+#[test]
+fn test_type_result_supports_string_methods() {
+    let program = r#"
+        print(type(1) == "number")
+        print(type([]).toUpperCase())
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("true\nARRAY", vm.get_output());
+}
+
+#[test]
+fn test_type_requires_one_argument() {
+    let program = r#"
+        print(type(1, 2))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm
+        .get_runtime_errors()
+        .contains("type() expects 1 argument, got 2"));
+}
+
+// ============================================================================
+// len()
+// ============================================================================
+
+#[test]
+fn test_len_measures_every_collection() {
+    let program = r#"
+        print(len("héllo"))
+        print(len([1, 2, 3]))
+        print(len({"a": 1, "b": 2}))
+        print(len({1, 2, 3, 4}))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("5\n3\n2\n4", vm.get_output());
+}
+
+#[test]
+fn test_len_of_empty_collections() {
+    let program = r#"
+        val emptySet = {1}
+        emptySet.clear()
+        print(len(""))
+        print(len([]))
+        print(len({}))
+        print(len(emptySet))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("0\n0\n0\n0", vm.get_output());
+}
+
+#[test]
+fn test_len_matches_collection_methods() {
+    let program = r#"
+        val items = [1, 2]
+        items.push(3)
+        print(len(items) == items.length())
+        print(len("abc") == "abc".len())
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("true\ntrue", vm.get_output());
+}
+
+#[test]
+fn test_len_of_non_collections_is_error() {
+    for value in ["42", "true", "nil"] {
+        let program = format!("print(len({}))\n", value);
+        let mut vm = VirtualMachine::new();
+        assert_eq!(Result::RuntimeError, vm.interpret(program));
+        assert!(vm.get_runtime_errors().contains("len() cannot measure"));
+    }
+}
+
+// ============================================================================
+// tuple()
+// ============================================================================
+
+#[test]
+fn test_tuple_reads_like_an_array() {
+    let program = r#"
+        val t = tuple(1, "a", true)
+        print(t)
+        print(type(t))
+        print(len(t))
+        print(t[1])
+        print(t[-1])
+        for (x in t) {
+            print(x)
+        }
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!(
+        "(1, a, true)\ntuple\n3\na\ntrue\n1\na\ntrue",
+        vm.get_output()
+    );
+}
+
+#[test]
+fn test_tuple_equality_is_by_value() {
+    let program = r#"
+        print(tuple(1, 2) == tuple(1, 2))
+        print(tuple(1, 2) is tuple(1, 2))
+        print(tuple(1, 2) == tuple(2, 1))
+        print(tuple(tuple()) == tuple(tuple()))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("true\ntrue\nfalse\ntrue", vm.get_output());
+}
+
+#[test]
+fn test_tuple_rejects_mutable_elements() {
+    let program = r#"
+        tuple(1, [2])
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm.get_runtime_errors().contains(
+        "tuple() elements must be strings, numbers, booleans or tuples, got [2] at index 1"
+    ));
+}
+
+#[test]
+fn test_tuple_index_assignment_is_an_error() {
+    let program = r#"
+        val t = tuple(1, 2)
+        t[0] = 3
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    vm.interpret(program.to_string());
+    assert!(vm
+        .get_runtime_errors()
+        .contains("Tuples are immutable, cannot assign to an index of (1, 2)."));
+}
+
+// ============================================================================
+// freeze()
+// ============================================================================
+
+#[test]
+fn test_frozen_collections_can_be_read() {
+    let program = r#"
+        val numbers = freeze([3, 1, 2])
+        print(numbers)
+        print(numbers[0])
+        print(numbers.length())
+        print(numbers.contains(2))
+        print(len(numbers))
+        print(type(numbers))
+        print(numbers == [3, 1, 2])
+        val config = freeze({"debug": true})
+        print(config["debug"])
+        print(config.has("debug"))
+        val tags = freeze({"a", "b"})
+        print(tags.has("a"))
+        for (n in numbers) {
+            print(n)
+        }
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!(
+        "[3, 1, 2]\n3\n3\ntrue\n3\narray\ntrue\ntrue\ntrue\ntrue\n3\n1\n2",
+        vm.get_output()
+    );
+}
+
+#[test]
+fn test_frozen_view_sees_changes_to_the_original() {
+    let program = r#"
+        val items = [1]
+        val view = freeze(items)
+        items.push(2)
+        print(view)
+        val copy = view.clone()
+        copy.push(3)
+        print(copy)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("[1, 2]\n[1, 2, 3]", vm.get_output());
+}
+
+#[test]
+fn test_freeze_is_idempotent() {
+    let program = r#"
+        val once = freeze([1])
+        val twice = freeze(once)
+        print(once is twice)
+        print(twice)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("true\n[1]", vm.get_output());
+}
+
+#[test]
+fn test_frozen_array_push_is_an_error() {
+    let program = r#"
+        val numbers = freeze([1, 2])
+        numbers.push(3)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm
+        .get_runtime_errors()
+        .contains("push() cannot mutate frozen collection"));
+}
+
+#[test]
+fn test_frozen_map_and_set_mutations_are_errors() {
+    for program in [
+        r#"freeze({"a": 1}).remove("a")"#,
+        r#"freeze({1, 2}).add(3)"#,
+        r#"freeze({1, 2}).clear()"#,
+    ] {
+        let mut vm = VirtualMachine::new();
+        assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+        assert!(vm
+            .get_runtime_errors()
+            .contains("cannot mutate frozen collection"));
+    }
+}
+
+#[test]
+fn test_frozen_index_assignment_is_an_error() {
+    let program = r#"
+        val numbers = freeze([1, 2])
+        numbers[0] = 5
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    vm.interpret(program.to_string());
+    assert!(vm
+        .get_runtime_errors()
+        .contains("Cannot mutate frozen collection [1, 2]."));
+}
+
+#[test]
+fn test_freeze_rejects_other_values() {
+    let program = r#"
+        freeze("text")
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm
+        .get_runtime_errors()
+        .contains("freeze() expects an array, map or set, got text"));
+}
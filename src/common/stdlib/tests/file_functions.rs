@@ -117,6 +117,28 @@ fn test_file_read_multiline() {
     fs::remove_file(test_path).ok();
 }
 
+#[test]
+fn test_file_read_string() {
+    let test_path = "/tmp/claude/file_read_string_test.txt";
+    fs::create_dir_all("/tmp/claude").ok();
+    fs::write(test_path, "first line\nsecond line\n").unwrap();
+
+    let program = format!(
+        r#"
+        val content = File("{}").readString()
+        print(content.len())
+        print(content == File("{}").read())
+    "#,
+        test_path, test_path
+    );
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program));
+    assert_eq!("23\ntrue", vm.get_output());
+
+    fs::remove_file(test_path).ok();
+}
+
 // ============================================================================
 // File.readLines() - Success Cases
 // ============================================================================
@@ -317,9 +339,9 @@ fn test_file_write_multiline() {
     let mut vm = VirtualMachine::new();
     assert_eq!(Result::Ok, vm.interpret(program));
 
-    // Verify the file content - Neon escapes \n in string literals
+    // Verify the file content - \n in the string literal is a line break
     let content = fs::read_to_string(test_path).unwrap();
-    assert_eq!("Line 1\\nLine 2\\nLine 3", content);
+    assert_eq!("Line 1\nLine 2\nLine 3", content);
 
     // Cleanup
     fs::remove_file(test_path).ok();
@@ -430,6 +452,100 @@ fn test_file_read_lines_nonexistent() {
     assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
 }
 
+#[test]
+fn test_file_read_string_nonexistent_reports_os_error() {
+    let program = r#"
+        val f = File("/tmp/claude/nonexistent_file_24680.txt")
+        f.readString()
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    let errors = vm.get_runtime_errors();
+    assert!(errors.contains("File not found: /tmp/claude/nonexistent_file_24680.txt"));
+    assert!(errors.contains("No such file or directory"));
+}
+
+// ============================================================================
+// File.writeString() / File.appendString()
+// ============================================================================
+
+fn temp_path(name: &str) -> String {
+    std::env::temp_dir()
+        .join(format!("neon_{}_{}", std::process::id(), name))
+        .to_string_lossy()
+        .to_string()
+}
+
+#[test]
+fn test_file_write_string_truncates_and_reads_back() {
+    let test_path = temp_path("write_string.txt");
+    fs::write(&test_path, "previous content that is longer").unwrap();
+
+    let program = format!(
+        r#"
+        val f = File("{}")
+        print(f.writeString("hello"))
+        print(f.readString())
+    "#,
+        test_path
+    );
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program));
+    assert_eq!("5\nhello", vm.get_output());
+
+    fs::remove_file(&test_path).ok();
+}
+
+#[test]
+fn test_file_append_string() {
+    let test_path = temp_path("append_string.txt");
+    fs::remove_file(&test_path).ok();
+
+    let program = format!(
+        r#"
+        val f = File("{}")
+        f.appendString("one")
+        print(f.appendString("two"))
+        print(f.readString())
+    "#,
+        test_path
+    );
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program));
+    assert_eq!("3\nonetwo", vm.get_output());
+
+    fs::remove_file(&test_path).ok();
+}
+
+#[test]
+fn test_file_write_string_non_string_argument() {
+    let program = format!(
+        r#"
+        File("{}").writeString(42)
+    "#,
+        temp_path("write_string_non_string.txt")
+    );
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program));
+}
+
+#[test]
+fn test_file_write_string_missing_directory_reports_path() {
+    let program = r#"
+        File("/nonexistent_dir_neon/out.txt").writeString("x")
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    let errors = vm.get_runtime_errors();
+    assert!(errors.contains("/nonexistent_dir_neon/out.txt"));
+    assert!(errors.contains("No such file or directory"));
+}
+
 #[test]
 fn test_file_write_wrong_arg_count() {
     let program = r#"
@@ -484,3 +600,72 @@ fn test_file_read_lines_wrong_arg_count() {
     // Cleanup
     fs::remove_file(test_path).ok();
 }
+
+// ============================================================================
+// File.exists() / File.delete() / File.listDir()
+// ============================================================================
+
+#[test]
+fn test_file_exists_and_delete() {
+    let test_path = temp_path("exists_delete.txt");
+    fs::write(&test_path, "x").unwrap();
+
+    let program = format!(
+        r#"
+        val f = File("{}")
+        print(f.exists())
+        print(f.delete())
+        print(f.exists())
+        print(f.delete())
+    "#,
+        test_path
+    );
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program));
+    assert_eq!("true\ntrue\nfalse\nfalse", vm.get_output());
+    assert!(!std::path::Path::new(&test_path).exists());
+}
+
+#[test]
+fn test_file_list_dir() {
+    let dir = temp_path("list_dir");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(format!("{}/b.txt", dir), "").unwrap();
+    fs::write(format!("{}/a.txt", dir), "").unwrap();
+    fs::create_dir_all(format!("{}/sub", dir)).unwrap();
+
+    let program = format!(
+        r#"
+        print(File.listDir("{}"))
+    "#,
+        dir
+    );
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program));
+    assert_eq!("[a.txt, b.txt, sub]", vm.get_output());
+
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_file_list_dir_not_a_directory() {
+    let test_path = temp_path("list_dir_file.txt");
+    fs::write(&test_path, "x").unwrap();
+
+    let program = format!(
+        r#"
+        File.listDir("{}")
+    "#,
+        test_path
+    );
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program));
+    assert!(vm
+        .get_runtime_errors()
+        .contains("File.listDir() path is not a directory"));
+
+    fs::remove_file(&test_path).ok();
+}
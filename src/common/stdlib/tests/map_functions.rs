@@ -131,6 +131,50 @@ fn test_map_mixed_values() {
     assert_eq!("hello\n42\ntrue", vm.get_output());
 }
 
+#[test]
+fn test_map_for_each_visits_every_entry_once() {
+    let program = r#"
+        val m = {"a": 1, "b": 2, "c": 3}
+        var calls = 0
+        val keys = []
+        var total = 0
+        val result = m.forEach(fn(key, value) {
+            calls = calls + 1
+            keys.push(key)
+            total = total + value
+        })
+        print(result)
+        print(calls)
+        print(keys.sort())
+        print(total)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("nil\n3\n[a, b, c]\n6", vm.get_output());
+}
+
+#[test]
+fn test_map_for_each_while_mutating() {
+    let program = r#"
+        val m = {1: "x", 2: "y", 3: "z"}
+        var calls = 0
+        m.forEach(fn(key, value) {
+            calls = calls + 1
+            m.remove(1)
+            m.remove(2)
+            m.remove(3)
+            m[key + 10] = value
+        })
+        print(calls)
+        print(m.size())
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("1\n1", vm.get_output());
+}
+
 // ============================================================================
 // Map Functions - Error Cases
 // ============================================================================
@@ -167,3 +211,150 @@ fn test_map_remove_wrong_arg_count() {
     let mut vm = VirtualMachine::new();
     assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
 }
+
+// ============================================================================
+// Map.from()
+// ============================================================================
+
+#[test]
+fn test_map_from_pairs() {
+    let program = r#"
+        val m = Map.from([["a", 1], ["b", 2], ["a", 3]])
+        print(m.size())
+        print(m.get("a"))
+        print(m.get("b"))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("2\n3\n2", vm.get_output());
+}
+
+#[test]
+fn test_map_from_entries_round_trip() {
+    let program = r#"
+        val m = Map.from(({"x": 10}).entries())
+        print(m.get("x"))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("10", vm.get_output());
+}
+
+#[test]
+fn test_map_from_invalid_pair() {
+    let program = r#"
+        Map.from([["a", 1], ["b"]])
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm
+        .get_runtime_errors()
+        .contains("Map.from() expects [key, value] pairs, got [b] at index 1"));
+}
+
+#[test]
+fn test_map_from_non_array() {
+    let program = r#"
+        Map.from("ab")
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm
+        .get_runtime_errors()
+        .contains("Map.from() pairs must be an array"));
+}
+
+// ============================================================================
+// Map.clone() and Map.deepClone()
+// ============================================================================
+
+#[test]
+fn test_map_clone_is_shallow() {
+    let program = r#"
+        val original = {"a": [1]}
+        val copy = original.clone()
+        copy["b"] = 2
+        copy["a"].push(3)
+        print(original)
+        print(copy.size())
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("{a: [1, 3]}\n2", vm.get_output());
+}
+
+#[test]
+fn test_map_deep_clone_is_independent() {
+    let program = r#"
+        val original = {"a": [1], "b": {"c": {1, 2}}}
+        val copy = original.deepClone()
+        copy["a"].push(2)
+        copy["b"]["c"].add(3)
+        copy["d"] = 4
+        print(original["a"])
+        print(original["b"]["c"].size())
+        print(original.size())
+        print(copy["a"])
+        print(copy["b"]["c"].size())
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("[1]\n2\n2\n[1, 2]\n3", vm.get_output());
+}
+
+// ============================================================================
+// Tuple keys
+// ============================================================================
+
+#[test]
+fn test_map_with_tuple_keys() {
+    let program = r#"
+        val grid = {}
+        grid[tuple(0, 0)] = "origin"
+        grid[tuple(1, 2)] = "point"
+        print(grid[tuple(0, 0)])
+        print(grid[tuple(1, 2)])
+        print(grid[tuple(2, 1)])
+        print(grid.has(tuple(1, 2)))
+        print(grid.get(tuple(0, 0)))
+        val key = grid.keys()[0]
+        print(type(key))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("origin\npoint\nnil\ntrue\norigin\ntuple", vm.get_output());
+}
+
+#[test]
+fn test_map_literal_with_nested_tuple_key() {
+    let program = r#"
+        val m = {tuple("a", tuple(1, 2)): 1}
+        print(m[tuple("a", tuple(1, 2))])
+        print(m)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("1\n{(a, (1, 2)): 1}", vm.get_output());
+}
+
+#[test]
+fn test_map_rejects_array_key() {
+    let program = r#"
+        val m = {}
+        m.has([1, 2])
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm.get_runtime_errors().contains(
+        "Invalid map key type: [1, 2]. Only strings, numbers, booleans and tuples can be used as map keys."
+    ));
+}
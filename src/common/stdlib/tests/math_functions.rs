@@ -154,5 +154,5 @@ fn test_math_floor_ceil_edge_cases() {
 
     let mut vm = VirtualMachine::new();
     assert_eq!(Result::Ok, vm.interpret(program.to_string()));
-    assert_eq!("0\n1\n-1\n-0\n0\n1", vm.get_output());
+    assert_eq!("0\n1\n-1\n0\n0\n1", vm.get_output());
 }
@@ -1,10 +1,16 @@
 mod array_functions;
 mod boolean_functions;
+mod conversion_functions;
 mod file_functions;
 mod map_functions;
 mod math_errors;
 mod math_functions;
 mod math_variadic;
+mod modules;
 mod number_functions;
+mod regex_functions;
+mod result_functions;
 mod set_functions;
 mod string_functions;
+mod system_functions;
+mod time_functions;
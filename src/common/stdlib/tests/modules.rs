@@ -0,0 +1,39 @@
+use crate::vm::{Result, VirtualMachine};
+
+#[test]
+fn std_math_functions_can_be_imported() {
+    let program = r#"
+        import { clamp, lerp } from "std/math"
+        print(clamp(15, 0, 10))
+        print(clamp(-3, 0, 10))
+        print(clamp(4, 0, 10))
+        print(lerp(10, 20, 0.25))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("10\n0\n4\n12.5", vm.get_output());
+}
+
+#[test]
+fn std_math_can_be_imported_as_a_namespace() {
+    let program = r#"
+        import * as M from "std/math"
+        print(M.lerp(0, 8, 0.5))
+        print(M.clamp(0.5, 1, 2))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("4\n1", vm.get_output());
+}
+
+#[test]
+fn unknown_std_module_is_a_compile_error() {
+    let program = r#"
+        import { sum } from "std/nothing"
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::CompileError, vm.interpret(program.to_string()));
+}
@@ -38,7 +38,7 @@ fn test_number_to_string_special_values() {
 
     let mut vm = VirtualMachine::new();
     assert_eq!(Result::Ok, vm.interpret(program.to_string()));
-    assert_eq!("inf\n-inf\nNaN", vm.get_output());
+    assert_eq!("inf\n-inf\nnan", vm.get_output());
 }
 
 #[test]
@@ -54,3 +54,72 @@ fn test_number_to_string_very_small() {
     let output = vm.get_output();
     assert!(output.starts_with("0.000000000"));
 }
+
+// ============================================================================
+// formatNumber()
+// ============================================================================
+
+#[test]
+fn test_format_number_groups_thousands() {
+    let program = r#"
+        print(formatNumber(1234567.5, 2))
+        print(formatNumber(0, 2))
+        print(formatNumber(999, 0))
+        print(formatNumber(1000, 0))
+        print(formatNumber(999999.999, 2))
+        print(formatNumber(100000, 1))
+        print(formatNumber(12.345, 0))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!(
+        "1,234,567.50\n0.00\n999\n1,000\n1,000,000.00\n100,000.0\n12",
+        vm.get_output()
+    );
+}
+
+#[test]
+fn test_format_number_negatives() {
+    let program = r#"
+        print(formatNumber(-1234.5, 2))
+        print(formatNumber(-999, 0))
+        print(formatNumber(-1000, 0))
+        print(formatNumber(-0.001, 2))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("-1,234.50\n-999\n-1,000\n0.00", vm.get_output());
+}
+
+#[test]
+fn test_format_number_special_values() {
+    let program = r#"
+        print(formatNumber(0 / 0, 2))
+        print(formatNumber(1 / 0, 2))
+        print(formatNumber(-1 / 0, 2))
+        print(formatNumber(10 ** 21, 0))
+        print(formatNumber("9007199254740993".toInt(), 1))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!(
+        "nan\ninf\n-inf\n1,000,000,000,000,000,000,000\n9,007,199,254,740,993.0",
+        vm.get_output()
+    );
+}
+
+#[test]
+fn test_format_number_invalid_decimals() {
+    let program = r#"
+        print(formatNumber(1, -1))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm
+        .get_runtime_errors()
+        .contains("formatNumber() decimals must be a whole number from 0 to 20, got -1"));
+}
@@ -0,0 +1,126 @@
+use crate::vm::{Result, VirtualMachine, REGEX_CACHE_CAPACITY};
+
+// ============================================================================
+// Regex.match() - Success Cases
+// ============================================================================
+
+#[test]
+fn test_regex_match() {
+    let program = r#"
+        print(Regex.match("^[a-z]+$", "hello"))
+        print(Regex.match("^[a-z]+$", "Hello"))
+        print(Regex.match("""\d+""", "abc 123"))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("true\nfalse\ntrue", vm.get_output());
+}
+
+// ============================================================================
+// Regex.find() - Success Cases
+// ============================================================================
+
+#[test]
+fn test_regex_find_first_match() {
+    let program = r#"
+        print(Regex.find("""\d+""", "abc 123 def 456"))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("123", vm.get_output());
+}
+
+#[test]
+fn test_regex_find_no_match_returns_nil() {
+    let program = r#"
+        print(Regex.find("""\d+""", "no digits here"))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("nil", vm.get_output());
+}
+
+// ============================================================================
+// Regex.findAll() - Success Cases
+// ============================================================================
+
+#[test]
+fn test_regex_find_all() {
+    let program = r#"
+        val words = Regex.findAll("[a-z]+", "one, two; three")
+        print(words)
+        print(words.size())
+        print(Regex.findAll("""\d""", "none"))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("[one, two, three]\n3\n[]", vm.get_output());
+}
+
+#[test]
+fn test_regex_patterns_are_cached() {
+    let program = r#"
+        for (var i = 0; i < 10; i = i + 1) {
+            Regex.match("a+", "aaa")
+            Regex.find("b+", "bbb")
+        }
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!(2, vm.regex_cache_len());
+}
+
+#[test]
+fn test_regex_cache_is_bounded() {
+    let program = r#"
+        for (var i = 0; i < 200; i = i + 1) {
+            Regex.match("a{" + str(i) + "}", "aaa")
+        }
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!(REGEX_CACHE_CAPACITY, vm.regex_cache_len());
+}
+
+// ============================================================================
+// Error Cases
+// ============================================================================
+
+#[test]
+fn test_regex_invalid_pattern() {
+    let program = r#"
+        print(Regex.match("(unclosed", "text"))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    let errors = vm.get_runtime_errors();
+    assert!(errors.contains("Regex.match() invalid pattern"));
+    assert!(errors.contains("unclosed group"));
+}
+
+#[test]
+fn test_regex_non_string_argument() {
+    let program = r#"
+        print(Regex.find(42, "text"))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+}
+
+#[test]
+fn test_regex_unknown_method() {
+    let program = r#"
+        print(Regex.replace("a", "b"))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::CompileError, vm.interpret(program.to_string()));
+}
@@ -0,0 +1,143 @@
+use crate::vm::{Result, VirtualMachine};
+
+// ============================================================================
+// Result - Ok(value) / Err(error)
+// ============================================================================
+
+#[test]
+fn test_result_ok_and_err() {
+    let program = r#"
+        fn parse(text) {
+            try {
+                return Ok(num(text))
+            } catch (e) {
+                return Err("not a number: ${text}")
+            }
+        }
+        val good = parse("42")
+        val bad = parse("x")
+        print(good)
+        print(bad)
+        print(good.isOk())
+        print(good.isErr())
+        print(bad.isOk())
+        print(bad.isErr())
+        print(type(good))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!(
+        "Ok(42)\nErr(not a number: x)\ntrue\nfalse\nfalse\ntrue\nresult",
+        vm.get_output()
+    );
+}
+
+#[test]
+fn test_result_unwrap() {
+    let program = r#"
+        print(Ok(41).unwrap() + 1)
+        print(Ok("value").unwrapOr("default"))
+        print(Err("failed").unwrapOr("default"))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("42\nvalue\ndefault", vm.get_output());
+}
+
+#[test]
+fn test_result_unwrap_on_err_is_an_error() {
+    let program = r#"
+        val result = Err("disk full")
+        print(result.unwrap())
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm
+        .get_runtime_errors()
+        .contains("unwrap() called on Err(disk full)"));
+}
+
+#[test]
+fn test_result_unwrap_on_err_can_be_caught() {
+    let program = r#"
+        try {
+            Err("disk full").unwrap()
+        } catch (e) {
+            print(e.message)
+        }
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("unwrap() called on Err(disk full)", vm.get_output());
+}
+
+#[test]
+fn test_results_compare_by_content() {
+    let program = r#"
+        print(Ok(1) == Ok(1))
+        print(Ok(1) == Err(1))
+        print(Err("a") != Err("b"))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("true\nfalse\ntrue", vm.get_output());
+}
+
+// ============================================================================
+// Option - Some(value) / None
+// ============================================================================
+
+#[test]
+fn test_option_some_and_none() {
+    let program = r#"
+        fn first(items) {
+            if (items.size() == 0) {
+                return None
+            }
+            return Some(items[0])
+        }
+        val found = first([7, 8])
+        val missing = first([])
+        print(found)
+        print(missing)
+        print(found.isSome())
+        print(missing.isNone())
+        print(found.unwrap())
+        print(missing.unwrapOr(0))
+        print(found.unwrapOr(0))
+        print(type(missing))
+        print(missing == None)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!(
+        "Some(7)\nNone\ntrue\ntrue\n7\n0\n7\noption\ntrue",
+        vm.get_output()
+    );
+}
+
+#[test]
+fn test_option_unwrap_on_none_is_an_error() {
+    let program = r#"
+        val missing = None
+        print(missing.unwrap())
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm.get_runtime_errors().contains("unwrap() called on None"));
+}
+
+#[test]
+fn test_constructors_expect_one_argument() {
+    for program in ["Ok()", "Err(1, 2)", "Some()"] {
+        let mut vm = VirtualMachine::new();
+        assert_ne!(Result::Ok, vm.interpret(program.to_string()), "{}", program);
+    }
+}
@@ -216,3 +216,44 @@ fn test_set_remove_wrong_arg_count() {
     let mut vm = VirtualMachine::new();
     assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
 }
+
+// ============================================================================
+// Set.clone() and Set.deepClone()
+// ============================================================================
+
+#[test]
+fn test_set_clone_is_independent() {
+    let program = r#"
+        val original = {1, 2}
+        val copy = original.clone()
+        val deep = original.deepClone()
+        copy.add(3)
+        deep.remove(1)
+        print(original.size())
+        print(copy.size())
+        print(deep.size())
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("2\n3\n1", vm.get_output());
+}
+
+// ============================================================================
+// Tuple elements
+// ============================================================================
+
+#[test]
+fn test_set_with_tuple_elements() {
+    let program = r#"
+        val seen = {tuple(1, 2)}
+        seen.add(tuple(1, 2))
+        seen.add(tuple(2, 1))
+        print(seen.size())
+        print(seen.has(tuple(2, 1)))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("2\ntrue", vm.get_output());
+}
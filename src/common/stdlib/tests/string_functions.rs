@@ -355,3 +355,69 @@ fn test_string_char_at_wrong_arg_count() {
     let mut vm = VirtualMachine::new();
     assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
 }
+
+// ============================================================================
+// String.bytes() / String.fromBytes()
+// ============================================================================
+
+#[test]
+fn test_string_bytes() {
+    let program = r#"
+        print("héllo".bytes())
+        print("".bytes().size())
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("[104, 195, 169, 108, 108, 111]\n0", vm.get_output());
+}
+
+#[test]
+fn test_string_bytes_round_trip() {
+    let program = r#"
+        val original = "héllo"
+        val decoded = String.fromBytes(original.bytes())
+        print(decoded)
+        print(decoded == original)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("héllo\ntrue", vm.get_output());
+}
+
+#[test]
+fn test_string_from_bytes_out_of_range() {
+    let program = r#"
+        String.fromBytes([104, 256])
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm
+        .get_runtime_errors()
+        .contains("String.fromBytes() expects integers in 0..255, got 256 at index 1"));
+}
+
+#[test]
+fn test_string_from_bytes_non_integer() {
+    let program = r#"
+        String.fromBytes([104.5])
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+}
+
+#[test]
+fn test_string_from_bytes_invalid_utf8() {
+    let program = r#"
+        String.fromBytes([195, 40])
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm
+        .get_runtime_errors()
+        .contains("String.fromBytes() invalid UTF-8"));
+}
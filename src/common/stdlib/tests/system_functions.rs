@@ -0,0 +1,293 @@
+use crate::vm::{Result, VirtualMachine};
+
+// ============================================================================
+// System.env() / System.envOrDefault()
+// ============================================================================
+
+#[test]
+fn test_system_env_reads_variable() {
+    std::env::set_var("NEON_TEST_ENV_READ", "hello from env");
+    let program = r#"
+        print(System.env("NEON_TEST_ENV_READ"))
+        print(System.env("NEON_TEST_ENV_READ").len())
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("hello from env\n14", vm.get_output());
+}
+
+#[test]
+fn test_system_env_unset_returns_nil() {
+    std::env::remove_var("NEON_TEST_ENV_UNSET");
+    let program = r#"
+        print(System.env("NEON_TEST_ENV_UNSET"))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("nil", vm.get_output());
+}
+
+#[test]
+fn test_system_env_or_default() {
+    std::env::set_var("NEON_TEST_ENV_DEFAULT_SET", "configured");
+    std::env::remove_var("NEON_TEST_ENV_DEFAULT_UNSET");
+    let program = r#"
+        print(System.envOrDefault("NEON_TEST_ENV_DEFAULT_SET", "fallback"))
+        print(System.envOrDefault("NEON_TEST_ENV_DEFAULT_UNSET", "fallback"))
+        print(System.envOrDefault("NEON_TEST_ENV_DEFAULT_UNSET", 8080))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("configured\nfallback\n8080", vm.get_output());
+}
+
+#[test]
+fn test_system_env_requires_string_name() {
+    let program = r#"
+        print(System.env(42))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+}
+
+// ============================================================================
+// System.exit()
+// ============================================================================
+
+#[test]
+fn test_system_exit_stops_execution_with_code() {
+    let program = r#"
+        print("before")
+        System.exit(3)
+        print("after")
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Exit(3), vm.interpret(program.to_string()));
+    assert_eq!("before", vm.get_output());
+}
+
+#[test]
+fn test_system_exit_from_nested_function() {
+    let program = r#"
+        fn bail(code) {
+            System.exit(code)
+            return 1
+        }
+        bail(0)
+        print("unreachable")
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Exit(0), vm.interpret(program.to_string()));
+    assert_eq!("", vm.get_output());
+}
+
+#[test]
+fn test_system_exit_does_not_leak_into_next_run() {
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Exit(1), vm.interpret("System.exit(1)".to_string()));
+    assert_eq!(Result::Ok, vm.interpret("print(\"ok\")".to_string()));
+}
+
+#[test]
+fn test_system_exit_requires_integer() {
+    let program = r#"
+        System.exit(1.5)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm
+        .get_runtime_errors()
+        .contains("System.exit() expects an integer status code, got 1.5"));
+}
+
+#[test]
+fn test_system_exit_requires_number() {
+    let program = r#"
+        System.exit("0")
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+}
+
+// ============================================================================
+// System.hasFlag() / System.argValue()
+// ============================================================================
+
+fn vm_with_args(args: &[&str]) -> VirtualMachine {
+    VirtualMachine::with_args(args.iter().map(|arg| arg.to_string()).collect())
+}
+
+#[test]
+fn test_system_has_flag() {
+    let program = r#"
+        print(System.hasFlag("--verbose"))
+        print(System.hasFlag("--quiet"))
+    "#;
+
+    let mut vm = vm_with_args(&["input.txt", "--verbose"]);
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("true\nfalse", vm.get_output());
+}
+
+#[test]
+fn test_system_arg_value() {
+    let program = r#"
+        print(System.argValue("--name"))
+        print(System.argValue("--missing"))
+        print(System.argValue("--last"))
+    "#;
+
+    let mut vm = vm_with_args(&["--name", "neon", "--last"]);
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("neon\nnil\nnil", vm.get_output());
+}
+
+#[test]
+fn test_system_arg_helpers_without_args() {
+    let program = r#"
+        print(System.hasFlag("--verbose"))
+        print(System.argValue("--name"))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("false\nnil", vm.get_output());
+}
+
+#[test]
+fn test_system_has_flag_requires_string() {
+    let program = r#"
+        print(System.hasFlag(1))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+}
+
+// ============================================================================
+// assert()
+// ============================================================================
+
+#[test]
+fn test_assert_passes_for_truthy_values() {
+    let program = r#"
+        assert(true)
+        assert(0)
+        assert("", "empty strings are truthy")
+        print("done")
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("done", vm.get_output());
+}
+
+#[test]
+fn test_assert_fails_without_message() {
+    let program = r#"
+        assert(1 > 2)
+        print("unreachable")
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm.get_runtime_errors().contains("Assertion failed"));
+    assert_eq!("", vm.get_output());
+}
+
+#[test]
+fn test_assert_fails_with_message_for_nil() {
+    let program = r#"
+        val config = nil
+        assert(config, "config must be loaded")
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm
+        .get_runtime_errors()
+        .contains("Assertion failed: config must be loaded"));
+}
+
+#[test]
+fn test_assert_argument_count() {
+    let program = r#"
+        assert(true, "a", "b")
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm
+        .get_runtime_errors()
+        .contains("assert() expects 1 or 2 arguments, got 3"));
+}
+
+// ============================================================================
+// input()
+// ============================================================================
+
+fn vm_with_input(input: &str) -> VirtualMachine {
+    let mut vm = VirtualMachine::new();
+    vm.set_input_source(Box::new(std::io::Cursor::new(input.to_string())));
+    vm
+}
+
+#[test]
+fn test_input_reads_trimmed_lines() {
+    let program = r#"
+        val name = input()
+        val city = input()
+        print("${name} from ${city}")
+    "#;
+
+    let mut vm = vm_with_input("  Ada \nLondon\r\n");
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("Ada from London", vm.get_output());
+}
+
+#[test]
+fn test_input_writes_prompt_first() {
+    let program = r#"
+        val answer = input("Continue? ")
+        print(answer)
+    "#;
+
+    let mut vm = vm_with_input("yes\n");
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("Continue? yes", vm.get_output());
+}
+
+#[test]
+fn test_input_returns_nil_at_end_of_input() {
+    let program = r#"
+        var line = nil
+        var count = 0
+        while ((line = input()) != nil) {
+            count = count + 1
+        }
+        print(count)
+        print(input())
+    "#;
+
+    let mut vm = vm_with_input("a\nb\nc");
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("3\nnil", vm.get_output());
+}
+
+#[test]
+fn test_input_requires_string_prompt() {
+    let program = r#"
+        input(42)
+    "#;
+
+    let mut vm = vm_with_input("");
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+}
@@ -0,0 +1,67 @@
+use crate::vm::{Result, VirtualMachine};
+
+// ============================================================================
+// Time.now() - Success Cases
+// ============================================================================
+
+#[test]
+fn test_time_now_returns_epoch_millis() {
+    let program = r#"
+        val now = Time.now()
+        print(now > 1600000000000)
+        print(now < 4000000000000)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("true\ntrue", vm.get_output());
+}
+
+// ============================================================================
+// Time.monotonic() - Success Cases
+// ============================================================================
+
+#[test]
+fn test_time_monotonic_never_goes_backwards() {
+    let program = r#"
+        val start = Time.monotonic()
+        var sum = 0
+        for (var i = 0; i < 1000; i = i + 1) {
+            sum = sum + i
+        }
+        val elapsed = Time.monotonic() - start
+        print(elapsed >= 0)
+        print(sum)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("true\n499500", vm.get_output());
+}
+
+// ============================================================================
+// Error Cases
+// ============================================================================
+
+#[test]
+fn test_time_now_rejects_arguments() {
+    let program = r#"
+        print(Time.now(1))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm
+        .get_runtime_errors()
+        .contains("Time.now() expects 0 arguments, got 1"));
+}
+
+#[test]
+fn test_time_unknown_method() {
+    let program = r#"
+        print(Time.today())
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::CompileError, vm.interpret(program.to_string()));
+}
@@ -0,0 +1,68 @@
+use crate::common::Value;
+
+/// Native implementation of Time.now()
+/// Returns the current wall-clock time in milliseconds since the Unix epoch
+pub fn native_time_now(args: &[Value]) -> Result<Value, String> {
+    if !args.is_empty() {
+        return Err(format!(
+            "Time.now() expects 0 arguments, got {}",
+            args.len()
+        ));
+    }
+
+    Ok(Value::Number(clock::epoch_millis()))
+}
+
+/// Native implementation of Time.monotonic()
+/// Returns milliseconds from a monotonic clock, suitable for measuring elapsed time
+/// Only differences between two readings are meaningful
+pub fn native_time_monotonic(args: &[Value]) -> Result<Value, String> {
+    if !args.is_empty() {
+        return Err(format!(
+            "Time.monotonic() expects 0 arguments, got {}",
+            args.len()
+        ));
+    }
+
+    Ok(Value::Number(clock::monotonic_millis()))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod clock {
+    use std::sync::OnceLock;
+    use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+    pub fn epoch_millis() -> f64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs_f64() * 1000.0)
+            .unwrap_or(0.0)
+    }
+
+    pub fn monotonic_millis() -> f64 {
+        static START: OnceLock<Instant> = OnceLock::new();
+        START.get_or_init(Instant::now).elapsed().as_secs_f64() * 1000.0
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod clock {
+    use wasm_bindgen::prelude::*;
+
+    #[wasm_bindgen]
+    extern "C" {
+        #[wasm_bindgen(js_namespace = Date, js_name = now)]
+        fn date_now() -> f64;
+
+        #[wasm_bindgen(js_namespace = performance, js_name = now)]
+        fn performance_now() -> f64;
+    }
+
+    pub fn epoch_millis() -> f64 {
+        date_now()
+    }
+
+    pub fn monotonic_millis() -> f64 {
+        performance_now()
+    }
+}
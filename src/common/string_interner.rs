@@ -0,0 +1,58 @@
+//! Interning of string values.
+//!
+//! Every string value is created through [`intern`] (the `string!` macro calls it), so equal
+//! strings share one allocation no matter whether they come from a literal, concatenation or
+//! a native function. Comparing two equal strings then only compares pointers.
+//!
+//! Natives don't get access to the VM, so the interner is per thread rather than per VM.
+//! Strings only the interner still refers to are dropped whenever the table has doubled in
+//! size since it was last cleaned up.
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::rc::Rc;
+
+/// Size below which the table is never cleaned up
+const MIN_PRUNE_SIZE: usize = 1024;
+
+thread_local! {
+    static INTERNER: RefCell<Interner> = RefCell::new(Interner::new());
+}
+
+struct Interner {
+    strings: HashSet<Rc<str>>,
+    prune_at: usize,
+}
+
+impl Interner {
+    fn new() -> Self {
+        Interner {
+            strings: HashSet::new(),
+            prune_at: MIN_PRUNE_SIZE,
+        }
+    }
+
+    fn intern(&mut self, value: &str) -> Rc<str> {
+        if let Some(existing) = self.strings.get(value) {
+            return existing.clone();
+        }
+        if self.strings.len() >= self.prune_at {
+            self.strings.retain(|s| Rc::strong_count(s) > 1);
+            self.prune_at = (self.strings.len() * 2).max(MIN_PRUNE_SIZE);
+        }
+        let interned: Rc<str> = Rc::from(value);
+        self.strings.insert(interned.clone());
+        interned
+    }
+}
+
+/// Returns the shared allocation holding `value`, creating it on first use
+pub fn intern(value: &str) -> Rc<str> {
+    INTERNER.with(|interner| interner.borrow_mut().intern(value))
+}
+
+/// Number of strings currently held by this thread's interner
+#[cfg(test)]
+pub(crate) fn interned_count() -> usize {
+    INTERNER.with(|interner| interner.borrow().strings.len())
+}
@@ -283,3 +283,39 @@ fn test_set_uniqueness() {
         panic!("Expected Object value");
     }
 }
+
+#[test]
+fn test_number_display_rules() {
+    assert_eq!(Value::Number(5.0).to_string(), "5");
+    assert_eq!(Value::Number(-42.0).to_string(), "-42");
+    assert_eq!(Value::Number(2.5).to_string(), "2.5");
+    assert_eq!(Value::Number(0.1 + 0.2).to_string(), "0.30000000000000004");
+    assert_eq!(Value::Number(10.0 / 3.0).to_string(), "3.3333333333333335");
+    assert_eq!(Value::Number(1e21).to_string(), "1000000000000000000000");
+}
+
+#[test]
+fn test_number_display_special_values() {
+    assert_eq!(Value::Number(-0.0).to_string(), "0");
+    assert_eq!(Value::Number(f64::NAN).to_string(), "nan");
+    assert_eq!(Value::Number(f64::INFINITY).to_string(), "inf");
+    assert_eq!(Value::Number(f64::NEG_INFINITY).to_string(), "-inf");
+}
+
+#[test]
+fn test_number_map_key_display_matches_value_display() {
+    assert_eq!(MapKey::Number(OrderedFloat(-0.0)).to_string(), "0");
+    assert_eq!(MapKey::Number(OrderedFloat(7.0)).to_string(), "7");
+    assert_eq!(MapKey::Number(OrderedFloat(0.5)).to_string(), "0.5");
+}
+
+#[test]
+fn test_int_equals_number_with_same_value() {
+    assert_eq!(Value::Int(2), Value::Number(2.0));
+    assert_eq!(Value::Number(2.0), Value::Int(2));
+    assert_ne!(Value::Int(2), Value::Number(2.5));
+    assert_eq!(
+        Value::Int(9_007_199_254_740_993).to_string(),
+        "9007199254740993"
+    );
+}
@@ -1,2 +1,3 @@
 mod common_mod;
+mod string_interner;
 mod string_similarity;
@@ -0,0 +1,58 @@
+use crate::common::string_interner::{intern, interned_count};
+use crate::common::Value;
+use crate::vm::{Result, VirtualMachine};
+use crate::{as_string, string};
+use std::rc::Rc;
+
+#[test]
+fn equal_strings_share_storage() {
+    let literal = string!("neon");
+    let built = string!(format!("ne{}", "on"));
+
+    assert!(Rc::ptr_eq(
+        &as_string!(literal).value,
+        &as_string!(built).value
+    ));
+}
+
+#[test]
+fn different_strings_are_kept_apart() {
+    let a = intern("left");
+    let b = intern("right");
+
+    assert!(!Rc::ptr_eq(&a, &b));
+    assert_eq!("left", a.as_ref());
+    assert_eq!("right", b.as_ref());
+}
+
+#[test]
+fn unused_strings_are_dropped() {
+    let kept = intern("kept");
+    for i in 0..10_000 {
+        intern(&format!("temporary {}", i));
+    }
+
+    assert!(interned_count() < 2048);
+    assert!(Rc::ptr_eq(&kept, &intern("kept")));
+}
+
+#[test]
+fn runtime_strings_share_storage_with_literals() {
+    let mut vm = VirtualMachine::new();
+    let outcome = vm.eval(
+        "val a = \"ne\"\nval b = a + \"on\"\n[b, \"neon\", \"neon\".toUpperCase().toLowerCase()]",
+    );
+    assert_eq!(Result::Ok, outcome.result);
+
+    let Some(Value::Object(array)) = outcome.value else {
+        panic!("expected an array, got {:?}", outcome.value);
+    };
+    let crate::common::Object::Array(elements) = array.as_ref() else {
+        panic!("expected an array");
+    };
+    let elements = elements.borrow();
+    let first = &as_string!(&elements[0]).value;
+    for element in elements.iter().skip(1) {
+        assert!(Rc::ptr_eq(first, &as_string!(element).value));
+    }
+}
@@ -1,4 +1,6 @@
-use crate::common::SourceLocation;
+use crate::common::constants::VARIADIC_ARITY;
+use crate::common::{SourceLocation, Value};
+use crate::{boolean, number, string};
 
 /// Binary operators
 #[derive(Debug, Clone, PartialEq)]
@@ -14,6 +16,8 @@ pub enum BinaryOp {
     // Comparison
     Equal,
     NotEqual,
+    /// Reference identity, unlike the structural `Equal`
+    Is,
     Greater,
     GreaterEqual,
     Less,
@@ -21,6 +25,7 @@ pub enum BinaryOp {
     // Logical
     And,
     Or,
+    NilCoalesce,
     // Bitwise
     BitwiseAnd,
     BitwiseOr,
@@ -81,6 +86,12 @@ pub enum Expr {
         right: Box<Expr>,
         location: SourceLocation,
     },
+    /// `a < b <= c`: the comparisons joined by `&&`, each middle operand evaluated once
+    ChainedComparison {
+        operands: Vec<Expr>,
+        operators: Vec<BinaryOp>,
+        location: SourceLocation,
+    },
     Unary {
         operator: UnaryOp,
         operand: Box<Expr>,
@@ -91,14 +102,22 @@ pub enum Expr {
         arguments: Vec<Expr>,
         location: SourceLocation,
     },
+    /// Struct construction with labeled arguments: `Point(x: 3, y: 4)`
+    NamedCall {
+        callee: Box<Expr>,
+        arguments: Vec<(String, Expr)>,
+        location: SourceLocation,
+    },
     GetField {
         object: Box<Expr>,
         field: String,
         location: SourceLocation,
     },
+    /// `object.field = value`, or `object.field op= value` when `operator` is set
     SetField {
         object: Box<Expr>,
         field: String,
+        operator: Option<BinaryOp>,
         value: Box<Expr>,
         location: SourceLocation,
     },
@@ -123,9 +142,23 @@ pub enum Expr {
         index: Box<Expr>,
         location: SourceLocation,
     },
+    /// `object?.field`: nil if object is nil, otherwise the same as `object.field`
+    OptionalGetField {
+        object: Box<Expr>,
+        field: String,
+        location: SourceLocation,
+    },
+    /// `object?.[index]`: nil if object is nil, otherwise the same as `object[index]`
+    OptionalIndex {
+        object: Box<Expr>,
+        index: Box<Expr>,
+        location: SourceLocation,
+    },
+    /// `object[index] = value`, or `object[index] op= value` when `operator` is set
     IndexAssign {
         object: Box<Expr>,
         index: Box<Expr>,
+        operator: Option<BinaryOp>,
         value: Box<Expr>,
         location: SourceLocation,
     },
@@ -149,6 +182,99 @@ pub enum Expr {
         else_expr: Box<Expr>,
         location: SourceLocation,
     },
+    /// Anonymous function: `fn(x) { ... }`, or `x => expr` (body is a single return)
+    Lambda {
+        params: Vec<Parameter>,
+        body: Vec<Stmt>,
+        location: SourceLocation,
+    },
+}
+
+/// A function parameter; `default` is evaluated at call time when the argument is omitted.
+/// A `rest` parameter (`...name`) collects all remaining arguments into an array.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parameter {
+    pub name: String,
+    pub default: Option<Expr>,
+    pub rest: bool,
+}
+
+impl Parameter {
+    pub fn new(name: impl Into<String>) -> Self {
+        Parameter {
+            name: name.into(),
+            default: None,
+            rest: false,
+        }
+    }
+}
+
+/// Number of parameters a caller has to supply, i.e. those before the first default
+pub fn required_arity(params: &[Parameter]) -> u8 {
+    params
+        .iter()
+        .take_while(|p| p.default.is_none() && !p.rest)
+        .count() as u8
+}
+
+/// Most arguments a caller can pass; `VARIADIC_ARITY` if the last parameter is a rest parameter
+pub fn max_arity(params: &[Parameter]) -> u8 {
+    match params.last() {
+        Some(param) if param.rest => VARIADIC_ARITY,
+        _ => params.len() as u8,
+    }
+}
+
+/// A struct field; fields with a `default` may be omitted when constructing an instance
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructField {
+    pub name: String,
+    pub default: Option<Expr>,
+}
+
+/// A `fn` declared inside a struct body; `this` refers to the instance it is called on
+#[derive(Debug, Clone, PartialEq)]
+pub struct Method {
+    pub name: String,
+    pub params: Vec<Parameter>,
+    pub body: Vec<Stmt>,
+    pub doc: Option<String>,
+    pub location: SourceLocation,
+}
+
+/// One `pattern => body` arm of a match statement
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchArm {
+    pub pattern: Expr,
+    pub body: Stmt,
+}
+
+/// `catch (error) { body }` of a try statement
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatchClause {
+    pub error: String,
+    pub body: Vec<Stmt>,
+}
+
+/// Left-hand side of a destructuring declaration
+#[derive(Debug, Clone, PartialEq)]
+pub enum DestructurePattern {
+    /// `[a, b, ...rest]`: elements by position; `rest` gets the remaining ones as an array
+    Array {
+        names: Vec<String>,
+        rest: Option<String>,
+    },
+    /// `{x, y}`: fields of a struct instance, each bound to a name of the same name
+    Struct { fields: Vec<String> },
+}
+
+/// What an import statement binds from the module
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImportItems {
+    /// `{ add, sub }`: each exported symbol under its own name
+    Symbols(Vec<(String, SourceLocation)>),
+    /// `* as M`: the whole module, whose exports are read like fields
+    Namespace(String),
 }
 
 /// Statement nodes
@@ -164,15 +290,44 @@ pub enum Stmt {
         initializer: Option<Expr>,
         location: SourceLocation,
     },
+    /// `val [a, b] = value` or `var {x, y} = value`
+    Destructure {
+        pattern: DestructurePattern,
+        initializer: Expr,
+        mutable: bool,
+        location: SourceLocation,
+    },
+    /// `doc` is the text of the `///` comment lines right before the declaration
     Fn {
         name: String,
-        params: Vec<String>,
+        params: Vec<Parameter>,
         body: Vec<Stmt>,
+        doc: Option<String>,
         location: SourceLocation,
     },
     Struct {
         name: String,
-        fields: Vec<String>,
+        fields: Vec<StructField>,
+        methods: Vec<Method>,
+        doc: Option<String>,
+        location: SourceLocation,
+    },
+    /// `enum Color { Red, Green, Blue }`
+    Enum {
+        name: String,
+        variants: Vec<String>,
+        doc: Option<String>,
+        location: SourceLocation,
+    },
+    /// `export fn add(a, b) {...}`: a top-level declaration that importers can bind
+    Export {
+        declaration: Box<Stmt>,
+        location: SourceLocation,
+    },
+    /// `import { add, sub } from "math_utils"` or `import * as M from "math_utils"`
+    Import {
+        path: String,
+        items: ImportItems,
         location: SourceLocation,
     },
     Expression {
@@ -192,6 +347,21 @@ pub enum Stmt {
     While {
         condition: Expr,
         body: Box<Stmt>,
+        /// Name given with `label: while (...)`, for `break label` / `continue label`
+        label: Option<String>,
+        location: SourceLocation,
+    },
+    /// Loop that checks its condition after the body, so the body runs at least once
+    DoWhile {
+        body: Box<Stmt>,
+        condition: Expr,
+        label: Option<String>,
+        location: SourceLocation,
+    },
+    Match {
+        scrutinee: Expr,
+        arms: Vec<MatchArm>,
+        else_branch: Option<Box<Stmt>>,
         location: SourceLocation,
     },
     Return {
@@ -202,18 +372,35 @@ pub enum Stmt {
         variable: String,
         collection: Expr,
         body: Box<Stmt>,
+        label: Option<String>,
         location: SourceLocation,
     },
+    /// `try { body } catch (error) { ... } finally { ... }`: runs the `catch` block with the
+    /// error that stopped `body`, then the `finally` block however the statement is left.
+    /// At least one of the two is present.
+    Try {
+        body: Vec<Stmt>,
+        catch: Option<CatchClause>,
+        finally: Option<Vec<Stmt>>,
+        location: SourceLocation,
+    },
+    /// Raises `value` as an error, to be caught by the innermost `try`
+    Throw {
+        value: Expr,
+        location: SourceLocation,
+    },
+    /// Leaves the innermost loop, or the loop named by `label`
     Break {
+        label: Option<String>,
         location: SourceLocation,
     },
     Continue {
+        label: Option<String>,
         location: SourceLocation,
     },
 }
 
 impl Expr {
-    #[allow(dead_code)]
     pub fn location(&self) -> &SourceLocation {
         match self {
             Expr::Number { location, .. }
@@ -224,8 +411,10 @@ impl Expr {
             | Expr::Variable { location, .. }
             | Expr::Assign { location, .. }
             | Expr::Binary { location, .. }
+            | Expr::ChainedComparison { location, .. }
             | Expr::Unary { location, .. }
             | Expr::Call { location, .. }
+            | Expr::NamedCall { location, .. }
             | Expr::GetField { location, .. }
             | Expr::SetField { location, .. }
             | Expr::Grouping { location, .. }
@@ -233,30 +422,68 @@ impl Expr {
             | Expr::ArrayLiteral { location, .. }
             | Expr::SetLiteral { location, .. }
             | Expr::Index { location, .. }
+            | Expr::OptionalGetField { location, .. }
+            | Expr::OptionalIndex { location, .. }
             | Expr::IndexAssign { location, .. }
             | Expr::Range { location, .. }
             | Expr::PostfixIncrement { location, .. }
             | Expr::PostfixDecrement { location, .. }
-            | Expr::Conditional { location, .. } => location,
+            | Expr::Conditional { location, .. }
+            | Expr::Lambda { location, .. } => location,
+        }
+    }
+
+    /// The value of a literal (optionally negated number), or None for anything else
+    pub fn literal_value(&self) -> Option<Value> {
+        match self {
+            Expr::Number { value, .. } => Some(number!(*value)),
+            Expr::String { value, .. } => Some(string!(value.as_str())),
+            Expr::Boolean { value, .. } => Some(boolean!(*value)),
+            Expr::Nil { .. } => Some(Value::Nil),
+            Expr::Unary {
+                operator: UnaryOp::Negate,
+                operand,
+                ..
+            } => match operand.as_ref() {
+                Expr::Number { value, .. } => Some(number!(-*value)),
+                _ => None,
+            },
+            _ => None,
         }
     }
 }
 
 impl Stmt {
+    /// The declaration an `export` marks, or the statement itself
+    pub fn declaration(&self) -> &Stmt {
+        match self {
+            Stmt::Export { declaration, .. } => declaration,
+            stmt => stmt,
+        }
+    }
+
     pub fn location(&self) -> &SourceLocation {
         match self {
             Stmt::Val { location, .. }
             | Stmt::Var { location, .. }
+            | Stmt::Destructure { location, .. }
             | Stmt::Fn { location, .. }
             | Stmt::Struct { location, .. }
+            | Stmt::Enum { location, .. }
+            | Stmt::Export { location, .. }
+            | Stmt::Import { location, .. }
             | Stmt::Expression { location, .. }
             | Stmt::Block { location, .. }
             | Stmt::If { location, .. }
             | Stmt::While { location, .. }
+            | Stmt::DoWhile { location, .. }
+            | Stmt::Match { location, .. }
             | Stmt::Return { location, .. }
             | Stmt::ForIn { location, .. }
-            | Stmt::Break { location }
-            | Stmt::Continue { location } => location,
+            | Stmt::Try { location, .. }
+            | Stmt::Throw { location, .. }
+            | Stmt::Break { location, .. }
+            | Stmt::Continue { location, .. } => location,
         }
     }
 }
@@ -5,24 +5,86 @@ use crate::common::errors::{
 /// Code generator for the multi-pass compiler
 /// Generates bytecode from AST using symbol table information
 use crate::common::opcodes::OpCode;
-use crate::common::{Chunk, Local, SourceLocation, Value};
-use crate::compiler::ast::{BinaryOp, Expr, Stmt, UnaryOp};
+use crate::common::{Chunk, Local, ObjFunction, SourceLocation, Value};
+use crate::compiler::ast::{
+    required_arity, BinaryOp, CatchClause, DestructurePattern, Expr, ImportItems, MatchArm, Method,
+    Parameter, Stmt, StructField, UnaryOp,
+};
+use crate::compiler::module_resolver::Module;
+use crate::compiler::peephole;
 use crate::{number, string};
 use indexmap::IndexMap;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Name of the hidden local holding the value of a destructuring declaration
+const DESTRUCTURED: &str = "$destructured";
+
+/// Name of the local a `catch` block binds its error to while it isn't in scope
+const CAUGHT: &str = "$caught";
+
+/// Name of the hidden local holding the value of a `return` while `finally` blocks run
+const RETURNED: &str = "$returned";
 
 struct LoopContext {
     #[allow(dead_code)]
     loop_start: u32,
+    label: Option<String>,
+    /// For-in loops keep an iterator on the VM's iterator stack while they run
+    has_iterator: bool,
+    /// Number of `try` blocks around the loop in its function
+    try_depth: usize,
     break_jumps: Vec<u32>,
     continue_jumps: Vec<u32>,
+    /// Index of the first local declared by the loop
+    first_local: usize,
+    /// Whether a closure captures a local declared by the loop. Each iteration then closes
+    /// them, as the next one reuses their slots.
+    captures_locals: bool,
+}
+
+/// A `try` statement around the code being generated
+#[derive(Clone)]
+struct TryBlock {
+    /// Whether the VM's handler for the statement is registered, i.e. the code is in the
+    /// `try` body rather than in the `catch` block
+    catching: bool,
+    finally: Option<Vec<Stmt>>,
+}
+
+/// Where a name lives at the point it is used
+enum VariableSlot {
+    Builtin(u32),
+    /// Local of the script frame, reachable from every function
+    Global(u32),
+    Local(u32),
+    /// Variable captured from an enclosing function
+    Upvalue(u32),
+}
+
+/// A variable a function captures: a local of the directly enclosing function,
+/// or one of that function's own upvalues
+#[derive(PartialEq)]
+struct Capture {
+    is_local: bool,
+    index: u32,
 }
 
 pub struct CodeGenerator {
     chunks: Vec<Chunk>,
+    /// Captured variables of the function compiled into the chunk at the same position
+    captures: Vec<Vec<Capture>>,
     scope_depth: u32,
     errors: Vec<CompilationError>,
     loop_contexts: Vec<LoopContext>,
+    /// `try` statements around the code being generated in its function, innermost last
+    try_blocks: Vec<TryBlock>,
     builtin: indexmap::IndexMap<String, Value>,
+    /// Modules loaded for the file's imports, by the path written in the import
+    modules: HashMap<String, Rc<Module>>,
+    /// Whether a module is compiled. Its functions capture its top-level variables instead
+    /// of reading them as globals, as a module doesn't run in the script frame.
+    is_module: bool,
 }
 
 impl CodeGenerator {
@@ -31,18 +93,80 @@ impl CodeGenerator {
 
         CodeGenerator {
             chunks,
+            captures: vec![Vec::new()],
             scope_depth: 0,
             errors: Vec::new(),
             loop_contexts: Vec::new(),
+            try_blocks: Vec::new(),
             builtin,
+            modules: HashMap::new(),
+            is_module: false,
         }
     }
 
+    /// Makes the modules loaded for the file's imports available to its import statements
+    pub(crate) fn with_modules(mut self, modules: HashMap<String, Rc<Module>>) -> Self {
+        self.modules = modules;
+        self
+    }
+
     pub fn generate(&mut self, statements: &[Stmt]) -> CompilationResult<Chunk> {
+        self.generate_script(statements, false)
+            .map(|(chunk, _)| chunk)
+    }
+
+    /// Generates code for REPL evaluation.
+    /// If the last top-level statement is an expression, its value is returned
+    /// from the script instead of being discarded. The flag reports whether that happened.
+    pub fn generate_eval(&mut self, statements: &[Stmt]) -> CompilationResult<(Chunk, bool)> {
+        self.generate_script(statements, true)
+    }
+
+    /// Generates code for a module. The chunk ends by handing the module's exports, the
+    /// top-level variables named in `exports`, to `ExportModule`, which returns the module.
+    pub(crate) fn generate_module(
+        &mut self,
+        statements: &[Stmt],
+        exports: &[String],
+    ) -> CompilationResult<Chunk> {
+        self.is_module = true;
+        self.generate_script_with(statements, false, |codegen| {
+            let location = SourceLocation {
+                offset: 0,
+                line: 0,
+                column: 0,
+            };
+            for name in exports {
+                codegen.emit_string(string!(name.as_str()), location);
+                codegen.emit_variable_get(name, location);
+            }
+            codegen.emit_op_code(OpCode::ExportModule, location);
+            codegen.current_chunk().write_u16(exports.len() as u16);
+            codegen.emit_op_code(OpCode::Return, location);
+        })
+        .map(|(chunk, _)| chunk)
+    }
+
+    fn generate_script(
+        &mut self,
+        statements: &[Stmt],
+        return_last_expression: bool,
+    ) -> CompilationResult<(Chunk, bool)> {
+        self.generate_script_with(statements, return_last_expression, Self::emit_return)
+    }
+
+    /// Generates the script; `emit_end` emits its return unless it returns the trailing
+    /// expression's value
+    fn generate_script_with(
+        &mut self,
+        statements: &[Stmt],
+        return_last_expression: bool,
+        emit_end: impl FnOnce(&mut Self),
+    ) -> CompilationResult<(Chunk, bool)> {
         // First: Define all functions and structs with placeholders
         // This allows forward references to work
         for stmt in statements {
-            match stmt {
+            match stmt.declaration() {
                 Stmt::Fn { name, location, .. } => {
                     // Define function with nil placeholder
                     self.emit_op_code(OpCode::Nil, *location);
@@ -53,11 +177,29 @@ impl CodeGenerator {
                 Stmt::Struct {
                     name,
                     fields,
+                    methods,
                     location,
+                    ..
                 } => {
-                    // Create the struct value
-                    let struct_value = Value::new_struct(name.clone(), fields.clone());
-                    self.emit_constant(struct_value, *location);
+                    // Create the struct value; one with methods gets a nil placeholder like a
+                    // function, as its methods are compiled where the struct is declared
+                    if methods.is_empty() {
+                        let struct_value = Self::struct_value(name, fields, HashMap::new());
+                        self.emit_constant(struct_value, *location);
+                    } else {
+                        self.emit_op_code(OpCode::Nil, *location);
+                    }
+                    let local = Local::new(name.clone(), self.scope_depth, false);
+                    self.current_chunk()
+                        .define_local(local, location.line, location.column);
+                }
+                Stmt::Enum {
+                    name,
+                    variants,
+                    location,
+                    ..
+                } => {
+                    self.emit_constant(Value::new_enum(name.clone(), variants.clone()), *location);
                     let local = Local::new(name.clone(), self.scope_depth, false);
                     self.current_chunk()
                         .define_local(local, location.line, location.column);
@@ -67,15 +209,38 @@ impl CodeGenerator {
         }
 
         // Then: Generate code for all statements
-        for stmt in statements {
+        let trailing_expression = match statements.last() {
+            Some(Stmt::Expression { expr, location }) if return_last_expression => {
+                Some((expr, *location))
+            }
+            _ => None,
+        };
+        let body = if trailing_expression.is_some() {
+            &statements[..statements.len() - 1]
+        } else {
+            statements
+        };
+        for stmt in body {
             self.generate_stmt(stmt);
         }
 
-        // Emit final return
-        self.emit_return();
+        // Emit final return, handing back the trailing expression's value if requested
+        let returns_value = match trailing_expression {
+            Some((expr, location)) => {
+                self.generate_expr(expr);
+                self.emit_op_code(OpCode::Return, location);
+                true
+            }
+            None => {
+                emit_end(self);
+                false
+            }
+        };
 
         if self.errors.is_empty() {
-            Ok(self.chunks.pop().unwrap())
+            let mut chunk = self.chunks.pop().unwrap();
+            peephole::optimize(&mut chunk);
+            Ok((chunk, returns_value))
         } else {
             Err(self.errors.clone())
         }
@@ -98,43 +263,47 @@ impl CodeGenerator {
     }
 
     fn emit_variable_get(&mut self, name: &str, location: SourceLocation) -> Option<()> {
-        let (maybe_index, _is_mutable, is_global, _is_builtin) = self.get_variable_index(name);
-        if let Some(index) = maybe_index {
-            if is_global {
-                self.emit_op_code_variant(OpCode::GetGlobal, index, location);
-            } else {
-                self.emit_op_code_variant(OpCode::GetLocal, index, location);
+        match self.resolve_variable(name, location) {
+            Some(VariableSlot::Builtin(index)) => {
+                self.emit_op_code_variant(OpCode::GetBuiltin, index, location)
+            }
+            Some(VariableSlot::Global(index)) => {
+                self.emit_op_code_variant(OpCode::GetGlobal, index, location)
+            }
+            Some(VariableSlot::Local(index)) => {
+                self.emit_op_code_variant(OpCode::GetLocal, index, location)
+            }
+            Some(VariableSlot::Upvalue(index)) => {
+                self.emit_op_code(OpCode::GetUpvalue, location);
+                self.current_chunk().write_u8(index as u8);
+            }
+            None => {
+                self.undefined_variable(name, location);
+                return None;
             }
-            Some(())
-        } else {
-            self.errors.push(CompilationError::new(
-                CompilationPhase::Codegen,
-                CompilationErrorKind::UndefinedSymbol,
-                format!("Undefined variable '{}'", name),
-                location,
-            ));
-            None
         }
+        Some(())
     }
 
     fn emit_variable_set(&mut self, name: &str, location: SourceLocation) -> Option<()> {
-        let (maybe_index, _is_mutable, is_global, _is_builtin) = self.get_variable_index(name);
-        if let Some(index) = maybe_index {
-            if is_global {
-                self.emit_op_code_variant(OpCode::SetGlobal, index, location);
-            } else {
-                self.emit_op_code_variant(OpCode::SetLocal, index, location);
+        match self.resolve_variable(name, location) {
+            // Built-ins share the global index space; the analyzer rejects assigning to them
+            Some(VariableSlot::Builtin(index)) | Some(VariableSlot::Global(index)) => {
+                self.emit_op_code_variant(OpCode::SetGlobal, index, location)
+            }
+            Some(VariableSlot::Local(index)) => {
+                self.emit_op_code_variant(OpCode::SetLocal, index, location)
+            }
+            Some(VariableSlot::Upvalue(index)) => {
+                self.emit_op_code(OpCode::SetUpvalue, location);
+                self.current_chunk().write_u8(index as u8);
+            }
+            None => {
+                self.undefined_variable(name, location);
+                return None;
             }
-            Some(())
-        } else {
-            self.errors.push(CompilationError::new(
-                CompilationPhase::Codegen,
-                CompilationErrorKind::UndefinedSymbol,
-                format!("Undefined variable '{}'", name),
-                location,
-            ));
-            None
         }
+        Some(())
     }
 
     fn emit_constant(&mut self, value: Value, location: SourceLocation) {
@@ -171,38 +340,100 @@ impl CodeGenerator {
             .emit_loop(loop_start, location.line, location.column);
     }
 
-    fn get_variable_index(&self, name: &str) -> (Option<u32>, bool, bool, bool) {
-        // Returns: (index, is_mutable, is_global, is_builtin)
-
+    fn resolve_variable(&mut self, name: &str, location: SourceLocation) -> Option<VariableSlot> {
         if self.is_builtin(name) {
-            let index = self.get_builtin_index(name);
-            if let Some(index) = index {
-                return (Some(index as u32), false, true, true); // is_global = true, is_builtin = true
-            }
-            return (None, false, false, false);
+            return self
+                .get_builtin_index(name)
+                .map(|index| VariableSlot::Builtin(index as u32));
         }
 
-        // Search in chunk stack from innermost to outermost
+        // First try the current chunk (parameters and locals)
         let current_chunk_idx = self.chunks.len() - 1;
+        if let Some(index) = self.chunks[current_chunk_idx].get_local_index(name).0 {
+            return Some(VariableSlot::Local(index));
+        }
 
-        // First try to find in current chunk (parameters and locals)
-        let current_result = self.chunks[current_chunk_idx].get_local_index(name);
-        if current_result.0.is_some() {
-            let index = current_result.0.unwrap();
-            return (Some(index), current_result.1, false, false);
+        // Then the locals of enclosing functions, innermost first
+        if let Some(index) = self.resolve_upvalue(current_chunk_idx, name, location) {
+            return Some(VariableSlot::Upvalue(index));
         }
 
-        // Then try to find in parent chunks (global scope for nested functions)
-        if current_chunk_idx > 0 {
-            for chunk_idx in (0..current_chunk_idx).rev() {
-                let index = self.chunks[chunk_idx].get_local_index(name);
-                if index.0.is_some() {
-                    return (Some(index.0.unwrap()), index.1, true, false); // is_global = true
-                }
+        // Finally the script's top-level chunk (globals for functions)
+        if current_chunk_idx > 0 && !self.is_module {
+            if let Some(index) = self.chunks[0].get_local_index(name).0 {
+                return Some(VariableSlot::Global(index));
+            }
+        }
+
+        None
+    }
+
+    /// Resolves `name` as a variable captured by the function compiled into `chunk_idx`,
+    /// threading it through every function in between
+    fn resolve_upvalue(
+        &mut self,
+        chunk_idx: usize,
+        name: &str,
+        location: SourceLocation,
+    ) -> Option<u32> {
+        if chunk_idx == 0 {
+            return None;
+        }
+
+        let enclosing_idx = chunk_idx - 1;
+        if let Some(index) = self.chunks[enclosing_idx].get_local_index(name).0 {
+            // Functions directly inside the script read its top-level variables as globals;
+            // those declared in a block are captured, as their slots are reused
+            if enclosing_idx == 0
+                && !self.is_module
+                && self.chunks[0].locals[index as usize].depth == 0
+            {
+                return None;
             }
+            return Some(self.add_capture(chunk_idx, true, index, location));
+        }
+        if enclosing_idx == 0 {
+            return None;
+        }
+
+        let index = self.resolve_upvalue(enclosing_idx, name, location)?;
+        Some(self.add_capture(chunk_idx, false, index, location))
+    }
+
+    fn add_capture(
+        &mut self,
+        chunk_idx: usize,
+        is_local: bool,
+        index: u32,
+        location: SourceLocation,
+    ) -> u32 {
+        let capture = Capture { is_local, index };
+        let captures = &mut self.captures[chunk_idx];
+        if let Some(existing) = captures.iter().position(|c| *c == capture) {
+            return existing as u32;
+        }
+
+        if captures.len() >= u8::MAX as usize {
+            self.errors.push(CompilationError::new(
+                CompilationPhase::Codegen,
+                CompilationErrorKind::ArityExceeded,
+                format!("A function can capture at most {} variables", u8::MAX),
+                location,
+            ));
+            return 0;
         }
 
-        (None, false, false, false)
+        captures.push(capture);
+        (captures.len() - 1) as u32
+    }
+
+    fn undefined_variable(&mut self, name: &str, location: SourceLocation) {
+        self.errors.push(CompilationError::new(
+            CompilationPhase::Codegen,
+            CompilationErrorKind::UndefinedSymbol,
+            format!("Undefined variable '{}'", name),
+            location,
+        ));
     }
 
     // ===== Statement Generation =====
@@ -226,6 +457,43 @@ impl CodeGenerator {
             .define_local(local, location.line, location.column);
     }
 
+    /// Binds each imported name, or the module itself, as a local. Every binding imports the
+    /// module again, which after the first time only reads it from the VM's cache.
+    fn generate_import_stmt(&mut self, path: &str, items: &ImportItems, location: SourceLocation) {
+        let Some(module) = self.modules.get(path).cloned() else {
+            self.errors.push(CompilationError::new(
+                CompilationPhase::Codegen,
+                CompilationErrorKind::Internal,
+                format!("Module '{}' was not loaded", path),
+                location,
+            ));
+            return;
+        };
+
+        match items {
+            ImportItems::Symbols(symbols) => {
+                for (name, location) in symbols {
+                    self.emit_constant(module.function.clone(), *location);
+                    self.emit_op_code(OpCode::Import, *location);
+                    let field_index = self.current_chunk().add_string(string!(name.as_str()));
+                    self.emit_op_code_variant(OpCode::GetField, field_index, *location);
+                    self.define_import(name, *location);
+                }
+            }
+            ImportItems::Namespace(name) => {
+                self.emit_constant(module.function.clone(), location);
+                self.emit_op_code(OpCode::Import, location);
+                self.define_import(name, location);
+            }
+        }
+    }
+
+    fn define_import(&mut self, name: &str, location: SourceLocation) {
+        let local = Local::new(name.to_string(), self.scope_depth, false);
+        self.current_chunk()
+            .define_local(local, location.line, location.column);
+    }
+
     fn generate_var_stmt(
         &mut self,
         name: &str,
@@ -245,28 +513,138 @@ impl CodeGenerator {
             .define_local(local, location.line, location.column);
     }
 
+    fn generate_destructure_stmt(
+        &mut self,
+        pattern: &DestructurePattern,
+        initializer: &Expr,
+        mutable: bool,
+        location: SourceLocation,
+    ) {
+        // The destructured value is kept in a hidden local that each binding reads from
+        self.generate_expr(initializer);
+        let local = Local::new(DESTRUCTURED.to_string(), self.scope_depth, false);
+        self.current_chunk()
+            .define_local(local, location.line, location.column);
+
+        let source = Expr::Variable {
+            name: DESTRUCTURED.to_string(),
+            location,
+        };
+        match pattern {
+            DestructurePattern::Array { names, rest } => {
+                let bindings = names
+                    .iter()
+                    .map(|name| (name, OpCode::UnpackElement))
+                    .chain(rest.iter().map(|rest| (rest, OpCode::UnpackRest)));
+                for (index, (name, op_code)) in bindings.enumerate() {
+                    self.generate_expr(&source);
+                    self.emit_constant(number!(index as f64), location);
+                    self.emit_op_code(op_code, location);
+                    let local = Local::new(name.clone(), self.scope_depth, mutable);
+                    self.current_chunk()
+                        .define_local(local, location.line, location.column);
+                }
+            }
+            DestructurePattern::Struct { fields } => {
+                for field in fields {
+                    self.generate_expr(&Expr::GetField {
+                        object: Box::new(source.clone()),
+                        field: field.clone(),
+                        location,
+                    });
+                    let local = Local::new(field.clone(), self.scope_depth, mutable);
+                    self.current_chunk()
+                        .define_local(local, location.line, location.column);
+                }
+            }
+        }
+    }
+
     fn generate_fn_stmt(
         &mut self,
         name: &str,
-        params: &[String],
+        params: &[Parameter],
         body: &[Stmt],
         location: SourceLocation,
     ) {
         // Function was already defined with nil placeholder
         // Now compile the function body and replace the placeholder
+        self.generate_function(name, params, body, location);
+
+        // Update the placeholder and pop the function value from the stack
+        self.emit_variable_set(name, location);
+        self.emit_op_code(OpCode::Pop, location);
+    }
+
+    /// Compiles a function body and pushes the function,
+    /// wrapped in a closure when it captures variables of enclosing functions
+    fn generate_function(
+        &mut self,
+        name: &str,
+        params: &[Parameter],
+        body: &[Stmt],
+        location: SourceLocation,
+    ) {
+        let (function, captures) = self.compile_function(name, params, body, location);
+        self.emit_constant(Value::new_function(function), location);
 
+        for capture in captures.iter().filter(|capture| capture.is_local) {
+            for loop_context in &mut self.loop_contexts {
+                if capture.index as usize >= loop_context.first_local {
+                    loop_context.captures_locals = true;
+                }
+            }
+        }
+
+        if !captures.is_empty() {
+            self.emit_op_code(OpCode::Closure, location);
+            let chunk = self.current_chunk();
+            chunk.write_u8(captures.len() as u8);
+            for capture in captures {
+                chunk.write_u8(capture.is_local as u8);
+                chunk.write_u32(capture.index);
+            }
+        }
+    }
+
+    /// Compiles a function body into its own chunk
+    fn compile_function(
+        &mut self,
+        name: &str,
+        params: &[Parameter],
+        body: &[Stmt],
+        location: SourceLocation,
+    ) -> (ObjFunction, Vec<Capture>) {
         // Create a new chunk for the function
         self.chunks.push(Chunk::new(&format!("function_{}", name)));
+        self.captures.push(Vec::new());
 
-        // Enter function scope
+        // Enter function scope; loops around the definition can't be targeted from inside
         self.scope_depth += 1;
+        let enclosing_loops = std::mem::take(&mut self.loop_contexts);
+        let enclosing_try_blocks = std::mem::take(&mut self.try_blocks);
 
         // Define parameters as local variables in the function scope
         for param in params {
-            let param_local = Local::new(param.clone(), self.scope_depth, false);
+            let param_local = Local::new(param.name.clone(), self.scope_depth, false);
             self.current_chunk().add_parameter(param_local);
         }
 
+        // Fill in omitted arguments; the VM reserves their slots before entering the function
+        for (index, param) in params.iter().enumerate() {
+            if let Some(default) = &param.default {
+                let skip = self.current_chunk().emit_jump_if_arg_supplied(
+                    index as u8,
+                    location.line,
+                    location.column,
+                );
+                self.generate_expr(default);
+                self.emit_op_code_variant(OpCode::SetLocal, index as u32, location);
+                self.emit_op_code(OpCode::Pop, location);
+                self.patch_jump(skip);
+            }
+        }
+
         // Compile function body
         for stmt in body {
             self.generate_stmt(stmt);
@@ -276,37 +654,62 @@ impl CodeGenerator {
         self.emit_return();
 
         // Exit function scope
+        self.loop_contexts = enclosing_loops;
+        self.try_blocks = enclosing_try_blocks;
         self.scope_depth -= 1;
 
-        let function_chunk = self.chunks.pop().unwrap();
-        let function_value =
-            Value::new_function(name.to_string(), params.len() as u8, function_chunk);
+        let mut function_chunk = self.chunks.pop().unwrap();
+        peephole::optimize(&mut function_chunk);
+        let captures = self.captures.pop().unwrap();
+        let function = ObjFunction::new(
+            name.to_string(),
+            params.len() as u8,
+            required_arity(params),
+            params.last().is_some_and(|p| p.rest),
+            function_chunk,
+        );
+        (function, captures)
+    }
 
-        // Replace the nil placeholder with the actual function
-        self.emit_constant(function_value, location);
+    /// Builds a struct that has methods and stores it in the placeholder defined up front.
+    /// Structs are only declared at the top level, so their methods never capture anything.
+    fn generate_struct_stmt(
+        &mut self,
+        name: &str,
+        fields: &[StructField],
+        methods: &[Method],
+        location: SourceLocation,
+    ) {
+        let mut compiled_methods = HashMap::new();
+        for method in methods {
+            // The receiver is passed as a hidden first parameter
+            let params: Vec<Parameter> = std::iter::once(Parameter::new("this"))
+                .chain(method.params.iter().cloned())
+                .collect();
+            let (function, _) =
+                self.compile_function(&method.name, &params, &method.body, method.location);
+            compiled_methods.insert(method.name.clone(), Rc::new(function));
+        }
 
-        // Get the index of the function variable we defined earlier
-        let (index, _is_mutable, is_global, _is_builtin) = self.get_variable_index(name);
-        let index = match index {
-            Some(idx) => idx,
-            None => {
-                self.errors.push(CompilationError::new(
-                    CompilationPhase::Codegen,
-                    CompilationErrorKind::Internal,
-                    format!("Function '{}' was not found after definition", name),
-                    location,
-                ));
-                return;
-            }
-        };
+        let struct_value = Self::struct_value(name, fields, compiled_methods);
+        self.emit_constant(struct_value, location);
+        self.emit_variable_set(name, location);
+        self.emit_op_code(OpCode::Pop, location);
+    }
 
-        // Emit the appropriate Set opcode to update the placeholder
-        if is_global {
-            self.emit_op_code_variant(OpCode::SetGlobal, index, location);
-        } else {
-            self.emit_op_code_variant(OpCode::SetLocal, index, location);
-        }
-        self.emit_op_code(OpCode::Pop, location); // Pop the function value from the stack
+    /// Field defaults are literals (checked during semantic analysis), so they are stored
+    /// on the struct as plain values
+    fn struct_value(
+        name: &str,
+        fields: &[StructField],
+        methods: HashMap<String, Rc<ObjFunction>>,
+    ) -> Value {
+        let names = fields.iter().map(|f| f.name.clone()).collect();
+        let defaults = fields
+            .iter()
+            .filter_map(|f| f.default.as_ref().and_then(Expr::literal_value))
+            .collect();
+        Value::new_struct(name.to_string(), names, defaults, methods)
     }
 
     fn generate_expression_stmt(&mut self, expr: &Expr, location: SourceLocation) {
@@ -344,14 +747,64 @@ impl CodeGenerator {
         self.patch_jump(else_jump);
     }
 
-    fn generate_while_stmt(&mut self, condition: &Expr, body: &Stmt, location: SourceLocation) {
+    fn generate_match_stmt(
+        &mut self,
+        scrutinee: &Expr,
+        arms: &[MatchArm],
+        else_branch: &Option<Box<Stmt>>,
+        location: SourceLocation,
+    ) {
+        // The scrutinee stays on the stack while patterns are compared against it,
+        // and is popped before running the body of the arm that matched
+        self.generate_expr(scrutinee);
+
+        let mut end_jumps = Vec::new();
+        for arm in arms {
+            self.emit_op_code(OpCode::Dup, location);
+            self.generate_expr(&arm.pattern);
+            self.emit_op_code(OpCode::Equal, location);
+
+            let next_arm = self.emit_jump(OpCode::JumpIfFalse, location);
+            self.emit_op_code(OpCode::Pop, location); // Pop comparison result
+            self.emit_op_code(OpCode::Pop, location); // Pop scrutinee
+            self.generate_stmt(&arm.body);
+            end_jumps.push(self.emit_jump(OpCode::Jump, location));
+
+            self.patch_jump(next_arm);
+            self.emit_op_code(OpCode::Pop, location); // Pop comparison result
+        }
+
+        // No arm matched
+        self.emit_op_code(OpCode::Pop, location); // Pop scrutinee
+        if let Some(else_stmt) = else_branch {
+            self.generate_stmt(else_stmt);
+        }
+
+        for end_jump in end_jumps {
+            self.patch_jump(end_jump);
+        }
+    }
+
+    fn generate_while_stmt(
+        &mut self,
+        condition: &Expr,
+        body: &Stmt,
+        label: &Option<String>,
+        location: SourceLocation,
+    ) {
         let loop_start = self.current_chunk().instruction_count() as u32;
 
         // Push loop context for break/continue tracking
+        let first_local = self.current_chunk().locals.len();
         self.loop_contexts.push(LoopContext {
             loop_start,
+            label: label.clone(),
+            has_iterator: false,
+            try_depth: self.try_blocks.len(),
             break_jumps: Vec::new(),
             continue_jumps: Vec::new(),
+            first_local,
+            captures_locals: false,
         });
 
         self.generate_expr(condition);
@@ -390,64 +843,180 @@ impl CodeGenerator {
 
         // Patch any remaining continue jumps (for non-desugared while loops)
         // These should jump to just before the Loop instruction
-        for continue_jump in loop_context.continue_jumps {
-            self.patch_jump(continue_jump);
+        for continue_jump in &loop_context.continue_jumps {
+            self.patch_jump(*continue_jump);
         }
 
+        self.close_loop_upvalues(&loop_context, location);
         self.emit_loop(loop_start, location);
 
         self.patch_jump(exit_jump);
         self.emit_op_code(OpCode::Pop, location); // Pop the condition value for the false case (exiting loop)
 
         // Patch all break jumps
-        for break_jump in loop_context.break_jumps {
-            self.patch_jump(break_jump);
+        for break_jump in &loop_context.break_jumps {
+            self.patch_jump(*break_jump);
+        }
+        self.close_loop_upvalues(&loop_context, location);
+    }
+
+    fn generate_do_while_stmt(
+        &mut self,
+        body: &Stmt,
+        condition: &Expr,
+        label: &Option<String>,
+        location: SourceLocation,
+    ) {
+        let loop_start = self.current_chunk().instruction_count() as u32;
+
+        let first_local = self.current_chunk().locals.len();
+        self.loop_contexts.push(LoopContext {
+            loop_start,
+            label: label.clone(),
+            has_iterator: false,
+            try_depth: self.try_blocks.len(),
+            break_jumps: Vec::new(),
+            continue_jumps: Vec::new(),
+            first_local,
+            captures_locals: false,
+        });
+
+        self.generate_stmt(body);
+
+        // 'continue' skips the rest of the body but still checks the condition
+        let loop_context = self.loop_contexts.pop().unwrap();
+        for continue_jump in &loop_context.continue_jumps {
+            self.patch_jump(*continue_jump);
+        }
+
+        self.generate_expr(condition);
+
+        let exit_jump = self.emit_jump(OpCode::JumpIfFalse, location);
+        self.emit_op_code(OpCode::Pop, location); // Pop the condition value before looping back
+        self.close_loop_upvalues(&loop_context, location);
+        self.emit_loop(loop_start, location);
+
+        self.patch_jump(exit_jump);
+        self.emit_op_code(OpCode::Pop, location); // Pop the condition value when leaving the loop
+
+        for break_jump in &loop_context.break_jumps {
+            self.patch_jump(*break_jump);
+        }
+        self.close_loop_upvalues(&loop_context, location);
+    }
+
+    /// Closes the locals of a loop that closures captured, before their slots are reused
+    fn close_loop_upvalues(&mut self, loop_context: &LoopContext, location: SourceLocation) {
+        if loop_context.captures_locals {
+            self.emit_op_code(OpCode::CloseUpvalues, location);
+            self.current_chunk()
+                .write_u32(loop_context.first_local as u32);
         }
     }
 
     fn generate_return_stmt(&mut self, value: &Expr, location: SourceLocation) {
-        self.generate_expr(value);
+        if self.try_blocks.iter().any(|block| block.finally.is_some()) {
+            // The value is computed before the `finally` blocks run
+            self.generate_expr(value);
+            let local = Local::new(RETURNED.to_string(), self.scope_depth, false);
+            self.current_chunk()
+                .define_local(local, location.line, location.column);
+            let slot = self.current_chunk().locals.len() - 1;
+            self.leave_try_blocks(0, location);
+            self.emit_op_code_variant(OpCode::GetLocal, slot as u32, location);
+            self.emit_op_code(OpCode::Return, location);
+            return;
+        }
+
+        match value {
+            // A tail call would drop the frame of a `try` block before its callee ran
+            Expr::Call {
+                callee,
+                arguments,
+                location: call_location,
+            } if !matches!(callee.as_ref(), Expr::GetField { .. })
+                && !Self::is_native_call(callee)
+                && self.try_blocks.is_empty() =>
+            {
+                self.generate_call(callee, arguments, OpCode::TailCall, *call_location)
+            }
+            _ => self.generate_expr(value),
+        }
         self.emit_op_code(OpCode::Return, location);
     }
 
-    fn generate_break_stmt(&mut self, location: SourceLocation) {
-        // Emit a Jump opcode and record its location for later patching
-        if self.loop_contexts.is_empty() {
-            self.errors.push(CompilationError::new(
-                CompilationPhase::Codegen,
-                CompilationErrorKind::Other,
-                "Cannot use 'break' outside of a loop".to_string(),
-                location,
-            ));
-            return;
+    fn generate_break_stmt(&mut self, label: &Option<String>, location: SourceLocation) {
+        if let Some(target) = self.exited_loop("break", label, location) {
+            let jump_index = self.emit_jump(OpCode::Jump, location);
+            self.loop_contexts[target].break_jumps.push(jump_index);
+        }
+    }
+
+    fn generate_continue_stmt(&mut self, label: &Option<String>, location: SourceLocation) {
+        // The jump is patched once the loop body is generated, so it lands before the
+        // Loop instruction, which is crucial for C-style for loops where increment comes at the end
+        if let Some(target) = self.exited_loop("continue", label, location) {
+            let jump_index = self.emit_jump(OpCode::Jump, location);
+            self.loop_contexts[target].continue_jumps.push(jump_index);
         }
-        let jump_index = self.emit_jump(OpCode::Jump, location);
-        self.loop_contexts
-            .last_mut()
-            .unwrap()
-            .break_jumps
-            .push(jump_index);
     }
 
-    fn generate_continue_stmt(&mut self, location: SourceLocation) {
-        // Emit a Jump opcode and record it for later patching
-        // This allows continue to jump to the right place (before the Loop instruction)
-        // which is crucial for C-style for loops where increment comes at the end
-        if self.loop_contexts.is_empty() {
+    /// Index of the loop context a `break`/`continue` targets. Leaves the `try` blocks inside
+    /// the target and pops the iterators of the for-in loops nested inside it, since the
+    /// jump leaves them.
+    fn exited_loop(
+        &mut self,
+        keyword: &str,
+        label: &Option<String>,
+        location: SourceLocation,
+    ) -> Option<usize> {
+        let target = match label {
+            Some(_) => self
+                .loop_contexts
+                .iter()
+                .rposition(|context| context.label == *label),
+            None => self.loop_contexts.len().checked_sub(1),
+        };
+        let Some(target) = target else {
+            let message = match label {
+                Some(name) => format!("Undefined loop label '{}' in '{}'", name, keyword),
+                None => format!("Cannot use '{}' outside of a loop", keyword),
+            };
             self.errors.push(CompilationError::new(
                 CompilationPhase::Codegen,
                 CompilationErrorKind::Other,
-                "Cannot use 'continue' outside of a loop".to_string(),
+                message,
                 location,
             ));
-            return;
+            return None;
+        };
+
+        self.leave_try_blocks(self.loop_contexts[target].try_depth, location);
+        let inner_iterators = self.loop_contexts[target + 1..]
+            .iter()
+            .filter(|context| context.has_iterator)
+            .count();
+        for _ in 0..inner_iterators {
+            self.emit_op_code(OpCode::PopIterator, location);
         }
-        let jump_index = self.emit_jump(OpCode::Jump, location);
-        self.loop_contexts
-            .last_mut()
-            .unwrap()
-            .continue_jumps
-            .push(jump_index);
+        Some(target)
+    }
+
+    /// Emits what a jump out of the `try` blocks from `depth` on needs, innermost first: the
+    /// end of the blocks whose handler is registered and a copy of their `finally` blocks.
+    /// Each `finally` block is generated without the `try` blocks it is no longer inside.
+    fn leave_try_blocks(&mut self, depth: usize, location: SourceLocation) {
+        let try_blocks = self.try_blocks.clone();
+        for (index, block) in try_blocks.iter().enumerate().skip(depth).rev() {
+            self.try_blocks.truncate(index);
+            if block.catching {
+                self.emit_op_code(OpCode::TryEnd, location);
+            }
+            if let Some(finally) = &block.finally {
+                self.generate_block_stmt(finally);
+            }
+        }
+        self.try_blocks = try_blocks;
     }
 
     fn generate_for_in_stmt(
@@ -455,6 +1024,7 @@ impl CodeGenerator {
         variable: &str,
         collection: &Expr,
         body: &Stmt,
+        label: &Option<String>,
         location: SourceLocation,
     ) {
         // For-in loop code generation strategy:
@@ -487,10 +1057,16 @@ impl CodeGenerator {
         let loop_start = self.current_chunk().instruction_count() as u32;
 
         // Push loop context for break/continue tracking
+        let first_local = self.current_chunk().locals.len();
         self.loop_contexts.push(LoopContext {
             loop_start,
+            label: label.clone(),
+            has_iterator: true,
+            try_depth: self.try_blocks.len(),
             break_jumps: Vec::new(),
             continue_jumps: Vec::new(),
+            first_local,
+            captures_locals: false,
         });
 
         // Check if iterator has more elements (pushes true if more, false if done)
@@ -513,14 +1089,19 @@ impl CodeGenerator {
         // Generate the loop body
         self.generate_stmt(body);
 
-        // Pop the old loop variable value before getting the next one
+        // Pop the old loop variable value before getting the next one, closing it first if
+        // a closure captured it
+        let loop_context = self.loop_contexts.pop().unwrap();
+        self.close_loop_upvalues(&loop_context, location);
         self.emit_op_code(OpCode::Pop, location);
 
         // Patch all continue jumps to point here (just before the Loop)
         // This allows continue to properly skip to the next iteration
-        let loop_context = self.loop_contexts.pop().unwrap();
-        for continue_jump in loop_context.continue_jumps {
-            self.patch_jump(continue_jump);
+        if !loop_context.continue_jumps.is_empty() {
+            for continue_jump in &loop_context.continue_jumps {
+                self.patch_jump(*continue_jump);
+            }
+            self.close_loop_upvalues(&loop_context, location);
         }
 
         // Jump back to loop start (will push next value)
@@ -535,18 +1116,151 @@ impl CodeGenerator {
         // Note: The loop variable has already been popped in the last iteration
         // before jumping back. So we don't need to pop it here.
 
+        // Patch all break jumps; a break leaves the iterator behind as well
+        for break_jump in &loop_context.break_jumps {
+            self.patch_jump(*break_jump);
+        }
+        self.close_loop_upvalues(&loop_context, location);
+
         // Pop the iterator from the VM's iterator stack
         self.emit_op_code(OpCode::PopIterator, location);
 
-        // Patch all break jumps
-        for break_jump in loop_context.break_jumps {
-            self.patch_jump(break_jump);
-        }
-
         // Exit the loop scope
         self.scope_depth -= 1;
     }
 
+    /// Bytecode structure:
+    ///   Nil, SetLocal error     ; reserve the error's slot
+    ///   TryBegin catch
+    ///   <body>
+    ///   TryEnd
+    ///   <finally>
+    ///   Jump end
+    ///   catch:                  ; the VM unwinds to the slot and pushes the error
+    ///   SetLocal error, Pop
+    ///   Nil, ...                ; one per local of the body, which the unwind dropped
+    ///   <catch block>           ; with a `finally` block it is guarded like the body:
+    ///                           ; TryBegin rethrow, <catch block>, TryEnd, <finally>,
+    ///                           ; Jump end, rethrow: SetLocal error, Pop, Nil, ...
+    ///   <finally>               ; only with a `finally` block, which then rethrows:
+    ///   GetLocal error, Throw
+    ///   Nil, ...                ; for the jumps to the end, one per local declared after them
+    ///   end:
+    ///
+    /// The slot is reserved before the body, so the VM finds it however many locals the body
+    /// declared. It only goes by the error's name while the `catch` block is generated.
+    /// `return`, `break` and `continue` run the `finally` block on their way out, see
+    /// `leave_try_blocks`.
+    fn generate_try_stmt(
+        &mut self,
+        body: &[Stmt],
+        catch: &Option<CatchClause>,
+        finally: &Option<Vec<Stmt>>,
+        location: SourceLocation,
+    ) {
+        self.emit_op_code(OpCode::Nil, location);
+        let local = Local::new(CAUGHT.to_string(), self.scope_depth + 1, false);
+        self.current_chunk()
+            .define_local(local, location.line, location.column);
+        let slot = self.current_chunk().locals.len() - 1;
+
+        let mut end_jumps = Vec::new();
+        let catch_jump = self.generate_guarded(body, finally, location, &mut end_jumps);
+        self.patch_jump(catch_jump);
+        self.emit_op_code_variant(OpCode::SetLocal, slot as u32, location);
+        self.emit_op_code(OpCode::Pop, location);
+        self.restore_unwound_locals(slot + 1, location);
+
+        if let Some(catch) = catch {
+            self.current_chunk().locals[slot].name = catch.error.clone();
+            if finally.is_some() {
+                let catch_locals = self.current_chunk().locals.len();
+                let rethrow_jump =
+                    self.generate_guarded(&catch.body, finally, location, &mut end_jumps);
+                self.patch_jump(rethrow_jump);
+                self.emit_op_code_variant(OpCode::SetLocal, slot as u32, location);
+                self.emit_op_code(OpCode::Pop, location);
+                self.restore_unwound_locals(catch_locals, location);
+            } else {
+                self.generate_block_stmt(&catch.body);
+            }
+            self.current_chunk().locals[slot].name = CAUGHT.to_string();
+        }
+
+        if let Some(finally) = finally {
+            self.generate_block_stmt(finally);
+            self.emit_op_code_variant(OpCode::GetLocal, slot as u32, location);
+            self.emit_op_code(OpCode::Throw, location);
+        }
+
+        self.join_end_jumps(end_jumps, finally.is_none(), location);
+    }
+
+    /// Patches the jumps to the end of a try statement. A jump taken before the later parts
+    /// of the statement declared their locals continues through a `nil` for each of them, so
+    /// every path leaves the statement with the stack height its locals take up.
+    /// `falls_through` tells whether the code before the end reaches it without a jump.
+    fn join_end_jumps(
+        &mut self,
+        mut end_jumps: Vec<(u32, usize)>,
+        falls_through: bool,
+        location: SourceLocation,
+    ) {
+        let locals = self.current_chunk().locals.len();
+        end_jumps.sort_by_key(|(_, declared)| *declared);
+        let padded = end_jumps
+            .first()
+            .is_some_and(|(_, declared)| *declared < locals);
+        let skip_padding =
+            (falls_through && padded).then(|| self.emit_jump(OpCode::Jump, location));
+
+        for (index, (end_jump, declared)) in end_jumps.iter().enumerate() {
+            self.patch_jump(*end_jump);
+            let next = end_jumps.get(index + 1).map_or(locals, |(_, next)| *next);
+            for _ in *declared..next {
+                self.emit_op_code(OpCode::Nil, location);
+            }
+        }
+        if let Some(skip_padding) = skip_padding {
+            self.patch_jump(skip_padding);
+        }
+    }
+
+    /// Pushes `nil` for the locals from `first` on, which a guarded block declared but the VM
+    /// dropped when it unwound to the handler. Later locals then find their slots at the
+    /// stack height they would have after the block ran to its end.
+    fn restore_unwound_locals(&mut self, first: usize, location: SourceLocation) {
+        for _ in first..self.current_chunk().locals.len() {
+            self.emit_op_code(OpCode::Nil, location);
+        }
+    }
+
+    /// Generates `statements` with a handler registered, followed by the `finally` block
+    /// and a jump to the end of the try statement, which is added to `end_jumps`.
+    /// Returns the `TryBegin` to patch with the start of the code handling an error.
+    fn generate_guarded(
+        &mut self,
+        statements: &[Stmt],
+        finally: &Option<Vec<Stmt>>,
+        location: SourceLocation,
+        end_jumps: &mut Vec<(u32, usize)>,
+    ) -> u32 {
+        let catch_jump = self.emit_jump(OpCode::TryBegin, location);
+        self.try_blocks.push(TryBlock {
+            catching: true,
+            finally: finally.clone(),
+        });
+        self.generate_block_stmt(statements);
+        self.try_blocks.pop();
+        self.emit_op_code(OpCode::TryEnd, location);
+        if let Some(finally) = finally {
+            self.generate_block_stmt(finally);
+        }
+        let end_jump = self.emit_jump(OpCode::Jump, location);
+        end_jumps.push((end_jump, self.current_chunk().locals.len()));
+        catch_jump
+    }
+
     fn generate_stmt(&mut self, stmt: &Stmt) {
         match stmt {
             Stmt::Val {
@@ -563,16 +1277,44 @@ impl CodeGenerator {
             } => {
                 self.generate_var_stmt(name, initializer, *location);
             }
+            Stmt::Destructure {
+                pattern,
+                initializer,
+                mutable,
+                location,
+            } => {
+                self.generate_destructure_stmt(pattern, initializer, *mutable, *location);
+            }
             Stmt::Fn {
                 name,
                 params,
                 body,
                 location,
+                ..
             } => {
                 self.generate_fn_stmt(name, params, body, *location);
             }
-            Stmt::Struct { .. } => {
-                // Struct was already defined, nothing to do here
+            Stmt::Struct {
+                name,
+                fields,
+                methods,
+                location,
+                ..
+            } => {
+                // A struct without methods was already fully defined up front
+                if !methods.is_empty() {
+                    self.generate_struct_stmt(name, fields, methods, *location);
+                }
+            }
+            // Enums are fully defined up front
+            Stmt::Enum { .. } => {}
+            Stmt::Export { declaration, .. } => self.generate_stmt(declaration),
+            Stmt::Import {
+                path,
+                items,
+                location,
+            } => {
+                self.generate_import_stmt(path, items, *location);
             }
             Stmt::Expression { expr, location } => {
                 self.generate_expression_stmt(expr, *location);
@@ -591,26 +1333,56 @@ impl CodeGenerator {
             Stmt::While {
                 condition,
                 body,
+                label,
+                location,
+            } => {
+                self.generate_while_stmt(condition, body, label, *location);
+            }
+            Stmt::DoWhile {
+                body,
+                condition,
+                label,
+                location,
+            } => {
+                self.generate_do_while_stmt(body, condition, label, *location);
+            }
+            Stmt::Match {
+                scrutinee,
+                arms,
+                else_branch,
                 location,
             } => {
-                self.generate_while_stmt(condition, body, *location);
+                self.generate_match_stmt(scrutinee, arms, else_branch, *location);
             }
             Stmt::Return { value, location } => {
                 self.generate_return_stmt(value, *location);
             }
-            Stmt::Break { location } => {
-                self.generate_break_stmt(*location);
+            Stmt::Break { label, location } => {
+                self.generate_break_stmt(label, *location);
             }
-            Stmt::Continue { location } => {
-                self.generate_continue_stmt(*location);
+            Stmt::Continue { label, location } => {
+                self.generate_continue_stmt(label, *location);
             }
             Stmt::ForIn {
                 variable,
                 collection,
                 body,
+                label,
                 location,
             } => {
-                self.generate_for_in_stmt(variable, collection, body, *location);
+                self.generate_for_in_stmt(variable, collection, body, label, *location);
+            }
+            Stmt::Try {
+                body,
+                catch,
+                finally,
+                location,
+            } => {
+                self.generate_try_stmt(body, catch, finally, *location);
+            }
+            Stmt::Throw { value, location } => {
+                self.generate_expr(value);
+                self.emit_op_code(OpCode::Throw, *location);
             }
         }
     }
@@ -653,45 +1425,13 @@ impl CodeGenerator {
     }
 
     fn generate_variable_expr(&mut self, name: &str, location: SourceLocation) {
-        let (maybe_index, _is_mutable, is_global, is_builtin) = self.get_variable_index(name);
-        if let Some(index) = maybe_index {
-            if is_builtin {
-                self.emit_op_code_variant(OpCode::GetBuiltin, index, location);
-            } else if is_global {
-                self.emit_op_code_variant(OpCode::GetGlobal, index, location);
-            } else {
-                self.emit_op_code_variant(OpCode::GetLocal, index, location);
-            }
-        } else {
-            self.errors.push(CompilationError::new(
-                CompilationPhase::Codegen,
-                CompilationErrorKind::UndefinedSymbol,
-                format!("Undefined variable '{}'", name),
-                location,
-            ));
-        }
+        self.emit_variable_get(name, location);
     }
 
     fn generate_assign_expr(&mut self, name: &str, value: &Expr, location: SourceLocation) {
         // Generate the value being assigned
         self.generate_expr(value);
-
-        // Get the variable index
-        let (maybe_index, _is_mutable, is_global, _is_builtin) = self.get_variable_index(name);
-        if let Some(index) = maybe_index {
-            if is_global {
-                self.emit_op_code_variant(OpCode::SetGlobal, index, location);
-            } else {
-                self.emit_op_code_variant(OpCode::SetLocal, index, location);
-            }
-        } else {
-            self.errors.push(CompilationError::new(
-                CompilationPhase::Codegen,
-                CompilationErrorKind::UndefinedSymbol,
-                format!("Undefined variable '{}'", name),
-                location,
-            ));
-        }
+        self.emit_variable_set(name, location);
     }
 
     fn generate_binary_expr(
@@ -731,42 +1471,187 @@ impl CodeGenerator {
                 // 6. Patch end jump (left was true, skip right evaluation)
                 self.patch_jump(end_jump);
             }
+            BinaryOp::NilCoalesce => {
+                // For `a ?? b`:
+                // 1. Evaluate left operand and test a copy of it against nil
+                self.generate_expr(left);
+                self.emit_op_code(OpCode::Dup, location);
+                self.emit_op_code(OpCode::Nil, location);
+                self.emit_op_code(OpCode::Equal, location);
+                // 2. If not nil, jump to end keeping the left value
+                let keep_jump = self.emit_jump(OpCode::JumpIfFalse, location);
+                // 3. Left was nil: pop the test result and the nil, evaluate right
+                self.emit_op_code(OpCode::Pop, location);
+                self.emit_op_code(OpCode::Pop, location);
+                self.generate_expr(right);
+                let end_jump = self.emit_jump(OpCode::Jump, location);
+                // 4. Left was not nil: pop the test result
+                self.patch_jump(keep_jump);
+                self.emit_op_code(OpCode::Pop, location);
+                self.patch_jump(end_jump);
+            }
             _ => {
                 // Regular binary operators: evaluate both operands first
                 self.generate_expr(left);
                 self.generate_expr(right);
+                self.emit_binary_op(operator, location);
+            }
+        }
+    }
 
-                match operator {
-                    BinaryOp::Add => self.emit_op_code(OpCode::Add, location),
-                    BinaryOp::Subtract => self.emit_op_code(OpCode::Subtract, location),
-                    BinaryOp::Multiply => self.emit_op_code(OpCode::Multiply, location),
-                    BinaryOp::Divide => self.emit_op_code(OpCode::Divide, location),
-                    BinaryOp::FloorDivide => self.emit_op_code(OpCode::FloorDivide, location),
-                    BinaryOp::Modulo => self.emit_op_code(OpCode::Modulo, location),
-                    BinaryOp::Exponent => self.emit_op_code(OpCode::Exponent, location),
-                    BinaryOp::Equal => self.emit_op_code(OpCode::Equal, location),
-                    BinaryOp::NotEqual => {
-                        self.emit_op_code(OpCode::Equal, location);
-                        self.emit_op_code(OpCode::Not, location);
-                    }
-                    BinaryOp::Greater => self.emit_op_code(OpCode::Greater, location),
-                    BinaryOp::GreaterEqual => {
-                        self.emit_op_code(OpCode::Less, location);
-                        self.emit_op_code(OpCode::Not, location);
-                    }
-                    BinaryOp::Less => self.emit_op_code(OpCode::Less, location),
-                    BinaryOp::LessEqual => {
-                        self.emit_op_code(OpCode::Greater, location);
-                        self.emit_op_code(OpCode::Not, location);
-                    }
-                    BinaryOp::BitwiseAnd => self.emit_op_code(OpCode::BitwiseAnd, location),
-                    BinaryOp::BitwiseOr => self.emit_op_code(OpCode::BitwiseOr, location),
-                    BinaryOp::BitwiseXor => self.emit_op_code(OpCode::BitwiseXor, location),
-                    BinaryOp::LeftShift => self.emit_op_code(OpCode::LeftShift, location),
-                    BinaryOp::RightShift => self.emit_op_code(OpCode::RightShift, location),
-                    BinaryOp::And | BinaryOp::Or => unreachable!(),
+    /// `a < b < c` as `a < b && b < c`, where a copy of `b` stays on the stack for the second
+    /// comparison so it is evaluated only once
+    fn generate_chained_comparison(
+        &mut self,
+        operands: &[Expr],
+        operators: &[BinaryOp],
+        location: SourceLocation,
+    ) {
+        self.generate_expr(&operands[0]);
+        let mut false_jumps = Vec::new();
+        for (operand, operator) in operands[1..operands.len() - 1].iter().zip(operators) {
+            // [left] -> [middle, left, middle] -> [middle, result]
+            self.generate_expr(operand);
+            self.emit_op_code(OpCode::Tuck, location);
+            self.emit_binary_op(operator, location);
+            false_jumps.push(self.emit_jump(OpCode::JumpIfFalse, location));
+            self.emit_op_code(OpCode::Pop, location);
+        }
+        self.generate_expr(&operands[operands.len() - 1]);
+        self.emit_binary_op(&operators[operators.len() - 1], location);
+        let end_jump = self.emit_jump(OpCode::Jump, location);
+
+        // A comparison failed: replace it and the kept middle operand by the result
+        for jump in false_jumps {
+            self.patch_jump(jump);
+        }
+        self.emit_op_code(OpCode::Pop, location);
+        self.emit_op_code(OpCode::Pop, location);
+        self.emit_op_code(OpCode::False, location);
+        self.patch_jump(end_jump);
+    }
+
+    /// Emits the opcode(s) for a non-short-circuit binary operator whose operands are on the stack
+    fn emit_binary_op(&mut self, operator: &BinaryOp, location: SourceLocation) {
+        match operator {
+            BinaryOp::Add => self.emit_op_code(OpCode::Add, location),
+            BinaryOp::Subtract => self.emit_op_code(OpCode::Subtract, location),
+            BinaryOp::Multiply => self.emit_op_code(OpCode::Multiply, location),
+            BinaryOp::Divide => self.emit_op_code(OpCode::Divide, location),
+            BinaryOp::FloorDivide => self.emit_op_code(OpCode::FloorDivide, location),
+            BinaryOp::Modulo => self.emit_op_code(OpCode::Modulo, location),
+            BinaryOp::Exponent => self.emit_op_code(OpCode::Exponent, location),
+            BinaryOp::Equal => self.emit_op_code(OpCode::Equal, location),
+            BinaryOp::NotEqual => {
+                self.emit_op_code(OpCode::Equal, location);
+                self.emit_op_code(OpCode::Not, location);
+            }
+            BinaryOp::Is => self.emit_op_code(OpCode::Same, location),
+            BinaryOp::Greater => self.emit_op_code(OpCode::Greater, location),
+            BinaryOp::GreaterEqual => {
+                self.emit_op_code(OpCode::Less, location);
+                self.emit_op_code(OpCode::Not, location);
+            }
+            BinaryOp::Less => self.emit_op_code(OpCode::Less, location),
+            BinaryOp::LessEqual => {
+                self.emit_op_code(OpCode::Greater, location);
+                self.emit_op_code(OpCode::Not, location);
+            }
+            BinaryOp::BitwiseAnd => self.emit_op_code(OpCode::BitwiseAnd, location),
+            BinaryOp::BitwiseOr => self.emit_op_code(OpCode::BitwiseOr, location),
+            BinaryOp::BitwiseXor => self.emit_op_code(OpCode::BitwiseXor, location),
+            BinaryOp::LeftShift => self.emit_op_code(OpCode::LeftShift, location),
+            BinaryOp::RightShift => self.emit_op_code(OpCode::RightShift, location),
+            BinaryOp::And | BinaryOp::Or | BinaryOp::NilCoalesce => unreachable!(),
+        }
+    }
+
+    /// Emits a nil check for the value on top of the stack, used by optional chaining.
+    /// If the value is nil it is left on the stack as the result and execution jumps to
+    /// the returned (unpatched) jump; otherwise it stays on the stack for the access that follows.
+    /// Whether `expr` is `?.` or a field or index access on one
+    fn in_optional_chain(expr: &Expr) -> bool {
+        match expr {
+            Expr::OptionalGetField { .. } | Expr::OptionalIndex { .. } => true,
+            Expr::GetField { object, .. } | Expr::Index { object, .. } => {
+                Self::in_optional_chain(object)
+            }
+            _ => false,
+        }
+    }
+
+    /// Generates a chain of field and index accesses containing `?.`. A nil object in front
+    /// of a `?.` makes the whole chain nil, skipping the accesses after it as well.
+    fn generate_optional_chain(&mut self, expr: &Expr) {
+        let mut skip_jumps = Vec::new();
+        self.generate_chain_link(expr, &mut skip_jumps);
+        for skip_jump in skip_jumps {
+            self.patch_jump(skip_jump);
+        }
+    }
+
+    fn generate_chain_link(&mut self, expr: &Expr, skip_jumps: &mut Vec<u32>) {
+        match expr {
+            Expr::GetField {
+                object,
+                field,
+                location,
+            }
+            | Expr::OptionalGetField {
+                object,
+                field,
+                location,
+            } => {
+                self.generate_chain_link(object, skip_jumps);
+                if matches!(expr, Expr::OptionalGetField { .. }) {
+                    skip_jumps.push(self.emit_nil_guard(*location));
+                }
+                let field_index = self.current_chunk().add_string(string!(field.as_str()));
+                self.emit_op_code_variant(OpCode::GetField, field_index, *location);
+            }
+            Expr::Index {
+                object,
+                index,
+                location,
+            }
+            | Expr::OptionalIndex {
+                object,
+                index,
+                location,
+            } => {
+                self.generate_chain_link(object, skip_jumps);
+                if matches!(expr, Expr::OptionalIndex { .. }) {
+                    skip_jumps.push(self.emit_nil_guard(*location));
                 }
+                self.generate_expr(index);
+                self.emit_op_code(OpCode::GetIndex, *location);
+            }
+            _ => self.generate_expr(expr),
+        }
+    }
+
+    fn emit_nil_guard(&mut self, location: SourceLocation) -> u32 {
+        self.emit_op_code(OpCode::Dup, location);
+        self.emit_op_code(OpCode::Nil, location);
+        self.emit_op_code(OpCode::Equal, location);
+        let access_jump = self.emit_jump(OpCode::JumpIfFalse, location);
+        self.emit_op_code(OpCode::Pop, location); // Pop comparison result, nil stays as result
+        let skip_jump = self.emit_jump(OpCode::Jump, location);
+        self.patch_jump(access_jump);
+        self.emit_op_code(OpCode::Pop, location); // Pop comparison result
+        skip_jump
+    }
+
+    /// Whether the callee is a global native function or a native constructor, which are
+    /// called by dedicated instruction sequences
+    fn is_native_call(callee: &Expr) -> bool {
+        match callee {
+            Expr::Variable { name, .. } => {
+                crate::common::method_registry::get_native_method_index("", name).is_some()
+                    || crate::common::method_registry::get_native_method_index(name, "new")
+                        .is_some()
             }
+            _ => false,
         }
     }
 
@@ -791,14 +1676,16 @@ impl CodeGenerator {
         } else if is_constructor_call {
             self.generate_constructor_call_expr(callee, arguments, location);
         } else {
-            self.generate_regular_call_expr(callee, arguments, location);
+            self.generate_call(callee, arguments, OpCode::Call, location);
         }
     }
 
-    fn generate_regular_call_expr(
+    /// Emits `Call` or `TailCall` for a callee that isn't native
+    fn generate_call(
         &mut self,
         callee: &Expr,
         arguments: &[Expr],
+        call: OpCode,
         location: SourceLocation,
     ) {
         // Regular function call - could be user-defined function
@@ -813,10 +1700,34 @@ impl CodeGenerator {
         self.generate_expr(callee);
 
         // Emit unified CALL instruction
-        self.emit_op_code(OpCode::Call, location);
+        self.emit_op_code(call, location);
         self.current_chunk().write_u8(arguments.len() as u8);
     }
 
+    /// Named struct construction: [values..., struct], followed by the labels in argument order
+    fn generate_named_call_expr(
+        &mut self,
+        callee: &Expr,
+        arguments: &[(String, Expr)],
+        location: SourceLocation,
+    ) {
+        for (_, value) in arguments {
+            self.generate_expr(value);
+        }
+        self.generate_expr(callee);
+
+        let labels: Vec<u32> = arguments
+            .iter()
+            .map(|(label, _)| self.current_chunk().add_string(string!(label.as_str())))
+            .collect();
+        self.emit_op_code(OpCode::CallNamed, location);
+        let chunk = self.current_chunk();
+        chunk.write_u8(labels.len() as u8);
+        for label in labels {
+            chunk.write_u32(label);
+        }
+    }
+
     fn generate_constructor_call_expr(
         &mut self,
         callee: &Expr,
@@ -1066,6 +1977,13 @@ impl CodeGenerator {
             } => {
                 self.generate_binary_expr(left, operator, right, *location);
             }
+            Expr::ChainedComparison {
+                operands,
+                operators,
+                location,
+            } => {
+                self.generate_chained_comparison(operands, operators, *location);
+            }
             Expr::Unary {
                 operator,
                 operand,
@@ -1092,6 +2010,18 @@ impl CodeGenerator {
                     self.generate_call_expr(callee, arguments, *location);
                 }
             }
+            Expr::NamedCall {
+                callee,
+                arguments,
+                location,
+            } => {
+                self.generate_named_call_expr(callee, arguments, *location);
+            }
+            Expr::GetField { object, .. } | Expr::Index { object, .. }
+                if Self::in_optional_chain(object) =>
+            {
+                self.generate_optional_chain(expr)
+            }
             Expr::GetField {
                 object,
                 field,
@@ -1105,11 +2035,21 @@ impl CodeGenerator {
             Expr::SetField {
                 object,
                 field,
+                operator,
                 value,
                 location,
             } => {
                 self.generate_expr(object);
-                self.generate_expr(value);
+                if let Some(operator) = operator {
+                    // obj.f op= v: reuse the evaluated object for both the read and the write
+                    self.emit_op_code(OpCode::Dup, *location);
+                    let field_index = self.current_chunk().add_string(string!(field.as_str()));
+                    self.emit_op_code_variant(OpCode::GetField, field_index, *location);
+                    self.generate_expr(value);
+                    self.emit_binary_op(operator, *location);
+                } else {
+                    self.generate_expr(value);
+                }
                 let field_string = string!(field.as_str());
                 let field_index = self.current_chunk().add_string(field_string);
                 self.emit_op_code_variant(OpCode::SetField, field_index, *location);
@@ -1146,15 +2086,34 @@ impl CodeGenerator {
                 self.generate_expr(index);
                 self.emit_op_code(OpCode::GetIndex, *location);
             }
+            Expr::Lambda {
+                params,
+                body,
+                location,
+            } => {
+                self.generate_function("<lambda>", params, body, *location);
+            }
+            Expr::OptionalGetField { .. } | Expr::OptionalIndex { .. } => {
+                self.generate_optional_chain(expr)
+            }
             Expr::IndexAssign {
                 object,
                 index,
+                operator,
                 value,
                 location,
             } => {
                 self.generate_expr(object);
                 self.generate_expr(index);
-                self.generate_expr(value);
+                if let Some(operator) = operator {
+                    // a[i] op= v: reuse the evaluated object and index for both the read and the write
+                    self.emit_op_code(OpCode::Dup2, *location);
+                    self.emit_op_code(OpCode::GetIndex, *location);
+                    self.generate_expr(value);
+                    self.emit_binary_op(operator, *location);
+                } else {
+                    self.generate_expr(value);
+                }
                 self.emit_op_code(OpCode::SetIndex, *location);
             }
             Expr::Range {
@@ -1,74 +1,238 @@
-use crate::common::{Chunk, Value};
+use crate::common::errors::{CompilationError, CompilationErrorKind, CompilationPhase};
+use crate::common::{Chunk, ObjFunction, SourceLocation, Value};
+use crate::compiler::ast::Stmt;
 use crate::compiler::codegen::CodeGenerator;
+use crate::compiler::constant_folding::fold_constants;
+use crate::compiler::lint::Linter;
+use crate::compiler::module_resolver::{exported_names, Module, ModuleResolver};
 use crate::compiler::parser::Parser;
 use crate::compiler::semantic::SemanticAnalyzer;
 use crate::compiler::Compiler;
 use indexmap::IndexMap;
+use std::collections::HashMap;
+use std::path::Path;
+use std::rc::Rc;
 
 impl Compiler {
     pub fn new(builtin: IndexMap<String, Value>) -> Compiler {
         Compiler {
             compilation_errors: String::new(),
             structured_errors: Vec::new(),
+            warnings: Vec::new(),
             builtin,
+            script_path: None,
+            resolver: ModuleResolver::new(),
         }
     }
 
+    /// Sets the file the compiled source comes from, so its imports are found next to it
+    pub fn set_script_path(&mut self, path: &Path) {
+        self.script_path = Some(path.to_path_buf());
+    }
+
     pub fn compile(&mut self, source: &str) -> Option<Chunk> {
+        self.compile_script(source, false).map(|(chunk, _)| chunk)
+    }
+
+    /// Compiles REPL input so that a trailing expression's value is returned
+    /// from the script. The flag reports whether the chunk returns such a value.
+    pub fn compile_for_eval(&mut self, source: &str) -> Option<(Chunk, bool)> {
+        self.compile_script(source, true)
+    }
+
+    fn compile_script(&mut self, source: &str, eval: bool) -> Option<(Chunk, bool)> {
         // Multi-pass compilation:
-        // Pass 1: Parse source into AST
-        // Pass 2: Semantic analysis
-        // Pass 3: Code generation
+        // Pass 1: Parse source into AST, then compile the modules it imports
+        // Pass 2: Semantic analysis (followed by the lint pass)
+        // Pass 3: Constant folding
+        // Pass 4: Code generation
 
         // Phase 1: Parse
         let mut parser = Parser::new(source);
-        let ast = match parser.parse() {
+        let mut ast = match parser.parse() {
             Ok(ast) => ast,
-            Err(errors) => {
-                // Store structured errors
-                self.structured_errors = errors.clone();
-                // Collect all parse errors
-                self.compilation_errors = errors
-                    .iter()
-                    .map(|e| e.to_string())
-                    .collect::<Vec<_>>()
-                    .join("\n");
-                return None;
-            }
+            Err(errors) => return self.fail(errors),
+        };
+        let directory = self
+            .script_path
+            .as_deref()
+            .and_then(Path::parent)
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        let modules = match self.load_imports(&ast, &directory) {
+            Ok(modules) => modules,
+            Err(errors) => return self.fail(errors),
         };
 
         // Phase 2: Semantic analysis
-        let mut analyzer = SemanticAnalyzer::new();
-        let _ = match analyzer.analyze(&ast) {
-            Ok(table) => table,
-            Err(errors) => {
-                // Store structured errors
-                self.structured_errors = errors.clone();
-                // Collect all semantic errors
-                self.compilation_errors = errors
-                    .iter()
-                    .map(|e| e.to_string())
-                    .collect::<Vec<_>>()
-                    .join("\n");
-                return None;
-            }
+        let mut analyzer = SemanticAnalyzer::new().with_modules(modules.clone());
+        if let Err(errors) = analyzer.analyze(&ast) {
+            return self.fail(errors);
+        }
+
+        // Lint: report likely mistakes as warnings without failing compilation
+        self.warnings = analyzer.warnings().to_vec();
+        self.warnings.extend(Linter::new().lint(&ast));
+
+        // Phase 3: Fold operators on literals into their result
+        fold_constants(&mut ast);
+
+        // Phase 4: Code generation
+        let mut codegen = CodeGenerator::new(self.builtin.clone()).with_modules(modules);
+        let generated = if eval {
+            codegen.generate_eval(&ast)
+        } else {
+            codegen.generate(&ast).map(|chunk| (chunk, false))
         };
+        match generated {
+            Ok(result) => Some(result),
+            Err(errors) => self.fail(errors),
+        }
+    }
 
-        // Phase 3: Code generation
-        let mut codegen = CodeGenerator::new(self.builtin.clone());
-        match codegen.generate(&ast) {
-            Ok(chunk) => Some(chunk),
-            Err(errors) => {
-                // Store structured errors
-                self.structured_errors = errors.clone();
-                // Collect all codegen errors
-                self.compilation_errors = errors
-                    .iter()
-                    .map(|e| e.to_string())
-                    .collect::<Vec<_>>()
-                    .join("\n");
-                None
+    /// Stores the errors that stopped compilation
+    fn fail<T>(&mut self, errors: Vec<CompilationError>) -> Option<T> {
+        self.compilation_errors = errors
+            .iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        self.structured_errors = errors;
+        None
+    }
+
+    /// Compiles the modules imported by a file in `directory`, by the path written in the import
+    fn load_imports(
+        &mut self,
+        statements: &[Stmt],
+        directory: &Path,
+    ) -> Result<HashMap<String, Rc<Module>>, Vec<CompilationError>> {
+        let mut modules = HashMap::new();
+        let mut errors = Vec::new();
+        for stmt in statements {
+            let Stmt::Import { path, location, .. } = stmt else {
+                continue;
+            };
+            if modules.contains_key(path) {
+                continue;
             }
+            match self.load_module(path, directory, *location) {
+                Ok(module) => {
+                    modules.insert(path.clone(), module);
+                }
+                Err(module_errors) => errors.extend(module_errors),
+            }
+        }
+        if errors.is_empty() {
+            Ok(modules)
+        } else {
+            Err(errors)
         }
     }
+
+    /// Compiles the module imported as `path`, or returns it if it was compiled before.
+    /// Errors in the module are reported at the import.
+    fn load_module(
+        &mut self,
+        path: &str,
+        directory: &Path,
+        location: SourceLocation,
+    ) -> Result<Rc<Module>, Vec<CompilationError>> {
+        let file = self.resolver.resolve(path, directory).map_err(|tried| {
+            let tried: Vec<String> = tried
+                .iter()
+                .map(|file| file.display().to_string())
+                .collect();
+            vec![CompilationError::new(
+                CompilationPhase::Semantic,
+                CompilationErrorKind::UndefinedSymbol,
+                format!("Cannot find module '{}', tried {}", path, tried.join(", ")),
+                location,
+            )]
+        })?;
+        if let Some(module) = self.resolver.get(&file) {
+            return Ok(module);
+        }
+
+        self.resolver.start_loading(&file).map_err(|cycle| {
+            vec![CompilationError::new(
+                CompilationPhase::Semantic,
+                CompilationErrorKind::Other,
+                cycle,
+                location,
+            )]
+        })?;
+        let module = self.read_module(path, &file, location);
+        self.resolver.finish_loading(module.as_ref().ok().cloned());
+        module
+    }
+
+    fn read_module(
+        &mut self,
+        path: &str,
+        file: &Path,
+        location: SourceLocation,
+    ) -> Result<Rc<Module>, Vec<CompilationError>> {
+        let source = self.resolver.read(file).map_err(|error| {
+            vec![CompilationError::new(
+                CompilationPhase::Semantic,
+                CompilationErrorKind::UndefinedSymbol,
+                format!(
+                    "Cannot read module '{}' from {}: {}",
+                    path,
+                    file.display(),
+                    error
+                ),
+                location,
+            )]
+        })?;
+        self.compile_module(&source, file).map_err(|errors| {
+            errors
+                .into_iter()
+                .map(|error| CompilationError {
+                    message: format!(
+                        "{} (in module '{}' at {}:{})",
+                        error.message, path, error.location.line, error.location.column
+                    ),
+                    location,
+                    ..error
+                })
+                .collect()
+        })
+    }
+
+    /// Runs a module's source through the same passes as a script; importers can bind the
+    /// declarations it exports
+    fn compile_module(
+        &mut self,
+        source: &str,
+        file: &Path,
+    ) -> Result<Rc<Module>, Vec<CompilationError>> {
+        let mut ast = Parser::new(source).parse()?;
+        let directory = file.parent().map(Path::to_path_buf).unwrap_or_default();
+        let modules = self.load_imports(&ast, &directory)?;
+
+        let symbols = SemanticAnalyzer::new()
+            .with_modules(modules.clone())
+            .analyze(&ast)?;
+        let exports: IndexMap<_, _> = exported_names(&ast)
+            .into_iter()
+            .filter_map(|name| {
+                let kind = symbols.resolve(&name)?.kind.clone();
+                Some((name, kind))
+            })
+            .collect();
+
+        fold_constants(&mut ast);
+        let export_names: Vec<String> = exports.keys().cloned().collect();
+        let chunk = CodeGenerator::new(self.builtin.clone())
+            .with_modules(modules)
+            .generate_module(&ast, &export_names)?;
+
+        let function = ObjFunction::new(file.display().to_string(), 0, 0, false, chunk);
+        Ok(Rc::new(Module {
+            function: Value::new_function(function),
+            exports,
+        }))
+    }
 }
@@ -0,0 +1,53 @@
+use crate::compiler::token::TokenType;
+use crate::compiler::Scanner;
+
+/// How far a piece of interactive input is from something worth compiling
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputStatus {
+    /// All brackets are closed and no string or comment is left open
+    Complete,
+    /// More lines could still complete it: a bracket, string or block comment is open
+    Incomplete,
+    /// No further input can fix it, such as a closing bracket that doesn't match; it is
+    /// compiled right away so the error gets reported
+    Invalid,
+}
+
+/// Tells whether `source` is a complete piece of input, as the REPL needs to know before
+/// reading a continuation line.
+///
+/// Only the tokens are looked at, not the grammar. A statement can't continue on the next line
+/// unless a bracket is open, so a trailing operator doesn't ask for more input.
+pub fn input_status(source: &str) -> InputStatus {
+    let mut scanner = Scanner::new(source);
+    let mut open_brackets = Vec::new();
+    loop {
+        let token = scanner.scan_token();
+        let closing = match token.token_type {
+            TokenType::LeftParen | TokenType::LeftBrace | TokenType::LeftBracket => {
+                open_brackets.push(token.token_type);
+                continue;
+            }
+            TokenType::RightParen => TokenType::LeftParen,
+            TokenType::RightBrace => TokenType::LeftBrace,
+            TokenType::RightBracket => TokenType::LeftBracket,
+            TokenType::Error => return error_status(&token.token),
+            TokenType::Eof if open_brackets.is_empty() => return InputStatus::Complete,
+            TokenType::Eof => return InputStatus::Incomplete,
+            _ => continue,
+        };
+        if open_brackets.pop() != Some(closing) {
+            return InputStatus::Invalid;
+        }
+    }
+}
+
+/// Errors the scanner reports for a string or comment that runs until the end of the input
+fn error_status(message: &str) -> InputStatus {
+    match message {
+        "Unterminated string" | "Unterminated raw string" | "Unterminated block comment" => {
+            InputStatus::Incomplete
+        }
+        _ => InputStatus::Invalid,
+    }
+}
@@ -0,0 +1,319 @@
+use crate::common::{SourceLocation, Value};
+use crate::compiler::ast::{BinaryOp, Expr, InterpolationPart, Stmt, UnaryOp};
+use std::cmp::Ordering;
+
+/// Optimization pass that replaces operators on literals by their result, so
+/// `2 * 3 + 1` is compiled as the constant `7`.
+///
+/// Only operations that can't fail are folded, and each one is computed the way the VM would
+/// compute it. Results that aren't finite numbers (`1 / 0`) are left to the VM.
+pub(crate) fn fold_constants(statements: &mut [Stmt]) {
+    for stmt in statements {
+        fold_stmt(stmt);
+    }
+}
+
+fn fold_stmt(stmt: &mut Stmt) {
+    match stmt {
+        Stmt::Val { initializer, .. } | Stmt::Var { initializer, .. } => {
+            if let Some(initializer) = initializer {
+                fold_expr(initializer);
+            }
+        }
+        Stmt::Destructure { initializer, .. } => fold_expr(initializer),
+        Stmt::Fn { params, body, .. } => {
+            for default in params.iter_mut().filter_map(|param| param.default.as_mut()) {
+                fold_expr(default);
+            }
+            fold_constants(body);
+        }
+        Stmt::Struct { methods, .. } => {
+            for method in methods {
+                for default in method.params.iter_mut().filter_map(|p| p.default.as_mut()) {
+                    fold_expr(default);
+                }
+                fold_constants(&mut method.body);
+            }
+        }
+        Stmt::Export { declaration, .. } => fold_stmt(declaration),
+        Stmt::Expression { expr, .. }
+        | Stmt::Return { value: expr, .. }
+        | Stmt::Throw { value: expr, .. } => fold_expr(expr),
+        Stmt::Block { statements, .. } => fold_constants(statements),
+        Stmt::Try {
+            body,
+            catch,
+            finally,
+            ..
+        } => {
+            fold_constants(body);
+            if let Some(catch) = catch {
+                fold_constants(&mut catch.body);
+            }
+            if let Some(finally) = finally {
+                fold_constants(finally);
+            }
+        }
+        Stmt::If {
+            condition,
+            then_branch,
+            else_branch,
+            ..
+        } => {
+            fold_expr(condition);
+            fold_stmt(then_branch);
+            if let Some(else_branch) = else_branch {
+                fold_stmt(else_branch);
+            }
+        }
+        Stmt::While {
+            condition, body, ..
+        } => {
+            fold_expr(condition);
+            fold_stmt(body);
+        }
+        Stmt::DoWhile {
+            body, condition, ..
+        } => {
+            fold_stmt(body);
+            fold_expr(condition);
+        }
+        Stmt::Match {
+            scrutinee,
+            arms,
+            else_branch,
+            ..
+        } => {
+            fold_expr(scrutinee);
+            for arm in arms {
+                fold_expr(&mut arm.pattern);
+                fold_stmt(&mut arm.body);
+            }
+            if let Some(else_branch) = else_branch {
+                fold_stmt(else_branch);
+            }
+        }
+        Stmt::ForIn {
+            collection, body, ..
+        } => {
+            fold_expr(collection);
+            fold_stmt(body);
+        }
+        Stmt::Enum { .. } | Stmt::Import { .. } | Stmt::Break { .. } | Stmt::Continue { .. } => {}
+    }
+}
+
+fn fold_expr(expr: &mut Expr) {
+    match expr {
+        Expr::Number { .. }
+        | Expr::String { .. }
+        | Expr::Boolean { .. }
+        | Expr::Nil { .. }
+        | Expr::Variable { .. } => {}
+        Expr::StringInterpolation { parts, .. } => {
+            for part in parts {
+                if let InterpolationPart::Expression(expr) = part {
+                    fold_expr(expr);
+                }
+            }
+        }
+        Expr::Assign { value, .. } => fold_expr(value),
+        Expr::Binary { left, right, .. } => {
+            fold_expr(left);
+            fold_expr(right);
+        }
+        Expr::ChainedComparison { operands, .. } => {
+            for operand in operands {
+                fold_expr(operand);
+            }
+        }
+        Expr::Unary { operand, .. }
+        | Expr::PostfixIncrement { operand, .. }
+        | Expr::PostfixDecrement { operand, .. } => fold_expr(operand),
+        Expr::Call {
+            callee, arguments, ..
+        } => {
+            fold_expr(callee);
+            for argument in arguments {
+                fold_expr(argument);
+            }
+        }
+        Expr::NamedCall {
+            callee, arguments, ..
+        } => {
+            fold_expr(callee);
+            for (_, argument) in arguments {
+                fold_expr(argument);
+            }
+        }
+        Expr::GetField { object, .. } | Expr::OptionalGetField { object, .. } => fold_expr(object),
+        Expr::SetField { object, value, .. } => {
+            fold_expr(object);
+            fold_expr(value);
+        }
+        Expr::Grouping { expr, .. } => fold_expr(expr),
+        Expr::MapLiteral { entries, .. } => {
+            for (key, value) in entries {
+                fold_expr(key);
+                fold_expr(value);
+            }
+        }
+        Expr::ArrayLiteral { elements, .. } | Expr::SetLiteral { elements, .. } => {
+            for element in elements {
+                fold_expr(element);
+            }
+        }
+        Expr::Index { object, index, .. } | Expr::OptionalIndex { object, index, .. } => {
+            fold_expr(object);
+            fold_expr(index);
+        }
+        Expr::IndexAssign {
+            object,
+            index,
+            value,
+            ..
+        } => {
+            fold_expr(object);
+            fold_expr(index);
+            fold_expr(value);
+        }
+        Expr::Range { start, end, .. } => {
+            fold_expr(start);
+            fold_expr(end);
+        }
+        Expr::Conditional {
+            condition,
+            then_expr,
+            else_expr,
+            ..
+        } => {
+            fold_expr(condition);
+            fold_expr(then_expr);
+            fold_expr(else_expr);
+        }
+        Expr::Lambda { params, body, .. } => {
+            for default in params.iter_mut().filter_map(|param| param.default.as_mut()) {
+                fold_expr(default);
+            }
+            fold_constants(body);
+        }
+    }
+
+    if let Some(folded) = folded(expr) {
+        *expr = folded;
+    }
+}
+
+/// The literal an expression with already folded operands evaluates to, if it is known
+fn folded(expr: &Expr) -> Option<Expr> {
+    let location = *expr.location();
+    match expr {
+        Expr::Grouping { expr, .. } => match expr.as_ref() {
+            Expr::Number { .. } | Expr::String { .. } | Expr::Boolean { .. } => {
+                Some(expr.as_ref().clone())
+            }
+            _ => None,
+        },
+        Expr::Unary {
+            operator: UnaryOp::Negate,
+            operand,
+            ..
+        } => match operand.as_ref() {
+            Expr::Number { value, .. } => number(-value, location),
+            _ => None,
+        },
+        Expr::Unary {
+            operator: UnaryOp::Not,
+            operand,
+            ..
+        } => match operand.as_ref() {
+            Expr::Boolean { value, .. } => Some(Expr::Boolean {
+                value: !value,
+                location,
+            }),
+            _ => None,
+        },
+        Expr::Binary {
+            left,
+            operator,
+            right,
+            ..
+        } => fold_binary(left, operator, right, location),
+        _ => None,
+    }
+}
+
+fn fold_binary(
+    left: &Expr,
+    operator: &BinaryOp,
+    right: &Expr,
+    location: SourceLocation,
+) -> Option<Expr> {
+    let boolean = |value| Some(Expr::Boolean { value, location });
+    match (left, right) {
+        (Expr::Number { value: a, .. }, Expr::Number { value: b, .. }) => {
+            let (a, b) = (*a, *b);
+            match operator {
+                BinaryOp::Add => number(a + b, location),
+                BinaryOp::Subtract => number(a - b, location),
+                BinaryOp::Multiply => number(a * b, location),
+                BinaryOp::Divide => number(a / b, location),
+                BinaryOp::Modulo => number(a % b, location),
+                BinaryOp::Exponent => number(a.powf(b), location),
+                BinaryOp::Equal => boolean(a == b),
+                BinaryOp::NotEqual => boolean(a != b),
+                BinaryOp::Less => boolean(a < b),
+                BinaryOp::Greater => boolean(a > b),
+                // Compiled as the negation of the opposite comparison, which differs for nan
+                BinaryOp::LessEqual => boolean(a.partial_cmp(&b) != Some(Ordering::Greater)),
+                BinaryOp::GreaterEqual => boolean(a.partial_cmp(&b) != Some(Ordering::Less)),
+                _ => None,
+            }
+        }
+        (Expr::String { value: a, .. }, Expr::String { value: b, .. }) => match operator {
+            BinaryOp::Add => Some(Expr::String {
+                value: format!("{}{}", a, b),
+                location,
+            }),
+            BinaryOp::Equal => boolean(a == b),
+            BinaryOp::NotEqual => boolean(a != b),
+            BinaryOp::Less => boolean(a < b),
+            BinaryOp::Greater => boolean(a > b),
+            BinaryOp::LessEqual => boolean(a <= b),
+            BinaryOp::GreaterEqual => boolean(a >= b),
+            _ => None,
+        },
+        (Expr::Boolean { value: a, .. }, Expr::Boolean { value: b, .. }) => match operator {
+            BinaryOp::And => boolean(*a && *b),
+            BinaryOp::Or => boolean(*a || *b),
+            BinaryOp::Equal => boolean(a == b),
+            BinaryOp::NotEqual => boolean(a != b),
+            _ => None,
+        },
+        _ => {
+            // Literals of different kinds are never equal, as in `Value`'s equality
+            let (left, right) = (literal(left)?, literal(right)?);
+            match operator {
+                BinaryOp::Equal => boolean(left == right),
+                BinaryOp::NotEqual => boolean(left != right),
+                _ => None,
+            }
+        }
+    }
+}
+
+/// Value of a literal whose equality with other literals is known at compile time
+fn literal(expr: &Expr) -> Option<Value> {
+    match expr {
+        Expr::Number { .. } | Expr::String { .. } | Expr::Boolean { .. } | Expr::Nil { .. } => {
+            expr.literal_value()
+        }
+        _ => None,
+    }
+}
+
+fn number(value: f64, location: SourceLocation) -> Option<Expr> {
+    value
+        .is_finite()
+        .then_some(Expr::Number { value, location })
+}
@@ -0,0 +1,314 @@
+use crate::common::errors::{CompilationError, CompilationErrorKind, CompilationPhase};
+use crate::compiler::ast::{Expr, InterpolationPart, Parameter, Stmt};
+
+/// Lint pass that flags likely mistakes in programs that otherwise compile.
+/// Findings are warning-severity diagnostics and never stop compilation.
+pub(crate) struct Linter {
+    warnings: Vec<CompilationError>,
+}
+
+impl Linter {
+    pub fn new() -> Self {
+        Linter {
+            warnings: Vec::new(),
+        }
+    }
+
+    pub fn lint(mut self, statements: &[Stmt]) -> Vec<CompilationError> {
+        self.lint_statements(statements);
+        self.warnings
+    }
+
+    fn lint_statements(&mut self, statements: &[Stmt]) {
+        for stmt in statements {
+            self.lint_stmt(stmt);
+        }
+        self.check_unreachable(statements);
+    }
+
+    fn lint_stmt(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::If {
+                condition,
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                self.check_condition(condition, "if");
+                self.lint_expr(condition);
+                self.lint_stmt(then_branch);
+                if let Some(else_branch) = else_branch {
+                    self.lint_stmt(else_branch);
+                }
+            }
+            Stmt::While {
+                condition, body, ..
+            } => {
+                self.check_condition(condition, "while");
+                self.lint_expr(condition);
+                self.lint_stmt(body);
+            }
+            Stmt::DoWhile {
+                body, condition, ..
+            } => {
+                self.lint_stmt(body);
+                self.check_condition(condition, "do-while");
+                self.lint_expr(condition);
+            }
+            Stmt::Block { statements, .. } => self.lint_statements(statements),
+            Stmt::Fn { params, body, .. } => {
+                self.lint_params(params);
+                self.lint_statements(body);
+            }
+            Stmt::Try {
+                body,
+                catch,
+                finally,
+                ..
+            } => {
+                self.lint_statements(body);
+                if let Some(catch) = catch {
+                    self.lint_statements(&catch.body);
+                }
+                if let Some(finally) = finally {
+                    self.lint_statements(finally);
+                }
+            }
+            Stmt::ForIn {
+                collection, body, ..
+            } => {
+                self.lint_expr(collection);
+                self.lint_stmt(body);
+            }
+            Stmt::Export { declaration, .. } => self.lint_stmt(declaration),
+            Stmt::Struct {
+                fields, methods, ..
+            } => {
+                for default in fields.iter().filter_map(|field| field.default.as_ref()) {
+                    self.lint_expr(default);
+                }
+                for method in methods {
+                    self.lint_params(&method.params);
+                    self.lint_statements(&method.body);
+                }
+            }
+            Stmt::Match {
+                scrutinee,
+                arms,
+                else_branch,
+                ..
+            } => {
+                self.lint_expr(scrutinee);
+                for arm in arms {
+                    self.lint_stmt(&arm.body);
+                }
+                if let Some(else_branch) = else_branch {
+                    self.lint_stmt(else_branch);
+                }
+            }
+            Stmt::Val {
+                initializer: Some(expr),
+                ..
+            }
+            | Stmt::Var {
+                initializer: Some(expr),
+                ..
+            }
+            | Stmt::Destructure {
+                initializer: expr, ..
+            }
+            | Stmt::Expression { expr, .. }
+            | Stmt::Return { value: expr, .. }
+            | Stmt::Throw { value: expr, .. } => self.lint_expr(expr),
+            _ => {}
+        }
+    }
+
+    /// Looks through an expression for lambdas, whose bodies are linted like a function's
+    fn lint_expr(&mut self, expr: &Expr) {
+        match expr {
+            Expr::Lambda { params, body, .. } => {
+                self.lint_params(params);
+                self.lint_statements(body);
+            }
+            Expr::StringInterpolation { parts, .. } => {
+                for part in parts {
+                    if let InterpolationPart::Expression(expr) = part {
+                        self.lint_expr(expr);
+                    }
+                }
+            }
+            Expr::Binary { left, right, .. } => {
+                self.lint_expr(left);
+                self.lint_expr(right);
+            }
+            Expr::ChainedComparison { operands, .. }
+            | Expr::ArrayLiteral {
+                elements: operands, ..
+            }
+            | Expr::SetLiteral {
+                elements: operands, ..
+            } => {
+                for operand in operands {
+                    self.lint_expr(operand);
+                }
+            }
+            Expr::Call {
+                callee, arguments, ..
+            } => {
+                self.lint_expr(callee);
+                for argument in arguments {
+                    self.lint_expr(argument);
+                }
+            }
+            Expr::NamedCall {
+                callee, arguments, ..
+            } => {
+                self.lint_expr(callee);
+                for (_, argument) in arguments {
+                    self.lint_expr(argument);
+                }
+            }
+            Expr::MapLiteral { entries, .. } => {
+                for (key, value) in entries {
+                    self.lint_expr(key);
+                    self.lint_expr(value);
+                }
+            }
+            Expr::SetField { object, value, .. } => {
+                self.lint_expr(object);
+                self.lint_expr(value);
+            }
+            Expr::Index { object, index, .. } | Expr::OptionalIndex { object, index, .. } => {
+                self.lint_expr(object);
+                self.lint_expr(index);
+            }
+            Expr::IndexAssign {
+                object,
+                index,
+                value,
+                ..
+            } => {
+                self.lint_expr(object);
+                self.lint_expr(index);
+                self.lint_expr(value);
+            }
+            Expr::Range { start, end, .. } => {
+                self.lint_expr(start);
+                self.lint_expr(end);
+            }
+            Expr::Conditional {
+                condition,
+                then_expr,
+                else_expr,
+                ..
+            } => {
+                self.lint_expr(condition);
+                self.lint_expr(then_expr);
+                self.lint_expr(else_expr);
+            }
+            Expr::Assign { value: inner, .. }
+            | Expr::Unary { operand: inner, .. }
+            | Expr::GetField { object: inner, .. }
+            | Expr::OptionalGetField { object: inner, .. }
+            | Expr::Grouping { expr: inner, .. }
+            | Expr::PostfixIncrement { operand: inner, .. }
+            | Expr::PostfixDecrement { operand: inner, .. } => self.lint_expr(inner),
+            Expr::Number { .. }
+            | Expr::String { .. }
+            | Expr::Boolean { .. }
+            | Expr::Nil { .. }
+            | Expr::Variable { .. } => {}
+        }
+    }
+
+    fn lint_params(&mut self, params: &[Parameter]) {
+        for default in params.iter().filter_map(|param| param.default.as_ref()) {
+            self.lint_expr(default);
+        }
+    }
+
+    /// An assignment used directly as a condition is usually a typo for `==`.
+    /// Wrapping it in an extra pair of parentheses marks it as intentional.
+    fn check_condition(&mut self, condition: &Expr, statement: &str) {
+        let is_assignment = matches!(
+            condition,
+            Expr::Assign { .. } | Expr::SetField { .. } | Expr::IndexAssign { .. }
+        );
+        if is_assignment {
+            self.warnings.push(CompilationError::warning(
+                CompilationPhase::Lint,
+                CompilationErrorKind::AssignmentInCondition,
+                format!(
+                    "Assignment used as '{}' condition. Did you mean '=='? Wrap it in extra parentheses if the assignment is intended.",
+                    statement
+                ),
+                *condition.location(),
+            ));
+        }
+    }
+
+    /// Statements after one that always leaves the block can never run.
+    /// Only the first of them is reported, the rest of the block is dead along with it.
+    fn check_unreachable(&mut self, statements: &[Stmt]) {
+        let Some(exit) = statements
+            .iter()
+            .position(|stmt| exits_block(stmt).is_some())
+        else {
+            return;
+        };
+        let Some(dead) = statements.get(exit + 1) else {
+            return;
+        };
+        // An expression statement's own location is where the previous line ended
+        let location = match dead {
+            Stmt::Expression { expr, .. } => *expr.location(),
+            _ => *dead.location(),
+        };
+        // Statements the parser adds, like the cleanup of a `with` block, carry the location
+        // of the construct they belong to, which is never past the exit
+        if location.offset <= statements[exit].location().offset {
+            return;
+        }
+        let message = match exits_block(&statements[exit]) {
+            Some(Some(keyword)) => format!("Unreachable code after '{}'.", keyword),
+            _ => "Unreachable code: every branch before it leaves the block.".to_string(),
+        };
+        self.warnings.push(CompilationError::warning(
+            CompilationPhase::Lint,
+            CompilationErrorKind::UnreachableCode,
+            message,
+            location,
+        ));
+    }
+}
+
+/// Whether control flow never continues past the statement: `Some(Some(keyword))` when the
+/// statement itself is a `return`, `throw`, `break` or `continue`, `Some(None)` when all of its
+/// branches end in one
+fn exits_block(stmt: &Stmt) -> Option<Option<&'static str>> {
+    match stmt {
+        Stmt::Return { .. } => Some(Some("return")),
+        Stmt::Throw { .. } => Some(Some("throw")),
+        Stmt::Break { .. } => Some(Some("break")),
+        Stmt::Continue { .. } => Some(Some("continue")),
+        Stmt::Block { statements, .. } => statements
+            .iter()
+            .any(|stmt| exits_block(stmt).is_some())
+            .then_some(None),
+        Stmt::If {
+            then_branch,
+            else_branch: Some(else_branch),
+            ..
+        } => (exits_block(then_branch).is_some() && exits_block(else_branch).is_some())
+            .then_some(None),
+        Stmt::Match {
+            arms,
+            else_branch: Some(else_branch),
+            ..
+        } => (arms.iter().all(|arm| exits_block(&arm.body).is_some())
+            && exits_block(else_branch).is_some())
+        .then_some(None),
+        _ => None,
+    }
+}
@@ -3,12 +3,20 @@ use crate::compiler::token::TokenType;
 pub(crate) mod ast;
 pub(crate) mod codegen;
 pub(crate) mod compiler_impl;
+mod completeness;
+pub(crate) mod constant_folding;
+pub(crate) mod lint;
+pub(crate) mod module_resolver;
 pub(crate) mod parser;
+pub(crate) mod peephole;
+pub(crate) mod purity;
 mod scanner;
 pub(crate) mod semantic;
 pub(crate) mod symbol_table;
 mod token;
 
+pub use completeness::{input_status, InputStatus};
+
 #[cfg(test)]
 mod tests;
 
@@ -37,7 +45,11 @@ pub(crate) struct Scanner {
 pub struct Compiler {
     compilation_errors: String,
     structured_errors: Vec<crate::common::errors::CompilationError>,
+    warnings: Vec<crate::common::errors::CompilationError>,
     builtin: indexmap::IndexMap<String, crate::common::Value>,
+    /// File the compiled source comes from; imports are resolved relative to its directory
+    script_path: Option<std::path::PathBuf>,
+    resolver: module_resolver::ModuleResolver,
 }
 
 impl Compiler {
@@ -48,4 +60,8 @@ impl Compiler {
     pub fn get_structured_errors(&self) -> Vec<crate::common::errors::CompilationError> {
         self.structured_errors.clone()
     }
+
+    pub fn get_warnings(&self) -> Vec<crate::common::errors::CompilationError> {
+        self.warnings.clone()
+    }
 }
@@ -0,0 +1,140 @@
+use crate::common::stdlib::modules;
+use crate::common::Value;
+use crate::compiler::ast::{DestructurePattern, Stmt};
+use crate::compiler::symbol_table::SymbolKind;
+use indexmap::IndexMap;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// Extension of Neon source files, appended to import paths
+const EXTENSION: &str = "n";
+
+/// Environment variable listing the directories searched for imports that aren't found next
+/// to the importing file, separated like `PATH`
+const SEARCH_PATH_VARIABLE: &str = "NEON_PATH";
+
+/// A source file compiled for an `import`
+#[derive(Debug)]
+pub(crate) struct Module {
+    /// The module's top-level code, named after its file. `Import` runs it the first time,
+    /// which returns the module, and caches the module under that name.
+    pub function: Value,
+    /// Names an importer can bind, with the kind of symbol each one is
+    pub exports: IndexMap<String, SymbolKind>,
+}
+
+/// Finds the files import paths refer to and keeps every module compiled so far,
+/// so a module imported from several files is only compiled once
+#[derive(Debug)]
+pub(crate) struct ModuleResolver {
+    search_path: Vec<PathBuf>,
+    /// Compiled modules by canonical file path
+    modules: HashMap<PathBuf, Rc<Module>>,
+    /// Files being compiled, each one imported by the one before it
+    loading: Vec<PathBuf>,
+}
+
+impl ModuleResolver {
+    /// Resolver searching the directories listed in `NEON_PATH`
+    pub fn new() -> Self {
+        let search_path = std::env::var_os(SEARCH_PATH_VARIABLE)
+            .map(|paths| std::env::split_paths(&paths).collect())
+            .unwrap_or_default();
+        ModuleResolver {
+            search_path,
+            modules: HashMap::new(),
+            loading: Vec::new(),
+        }
+    }
+
+    /// The file `path` refers to when imported by a file in `directory`: the first one that
+    /// exists in that directory or the search path. It is canonicalized, so `./a` and `a`
+    /// are the same module. Fails with the files tried when none of them exists.
+    ///
+    /// A module of the standard library resolves to its import path. Found files are absolute,
+    /// so they never take its place.
+    pub fn resolve(&self, path: &str, directory: &Path) -> Result<PathBuf, Vec<PathBuf>> {
+        if modules::source(path).is_some() {
+            return Ok(PathBuf::from(path));
+        }
+        let file_name = format!("{}.{}", path, EXTENSION);
+        let tried: Vec<PathBuf> = std::iter::once(directory)
+            .chain(self.search_path.iter().map(PathBuf::as_path))
+            .map(|directory| directory.join(&file_name))
+            .collect();
+        match tried.iter().find(|file| file.is_file()) {
+            Some(file) => Ok(file.canonicalize().unwrap_or_else(|_| file.clone())),
+            None => Err(tried),
+        }
+    }
+
+    /// Source of a file returned by `resolve`
+    pub fn read(&self, file: &Path) -> std::io::Result<Cow<'static, str>> {
+        let embedded = file
+            .to_str()
+            .filter(|_| file.is_relative())
+            .and_then(modules::source);
+        match embedded {
+            Some(source) => Ok(Cow::Borrowed(source)),
+            None => std::fs::read_to_string(file).map(Cow::Owned),
+        }
+    }
+
+    pub fn get(&self, file: &Path) -> Option<Rc<Module>> {
+        self.modules.get(file).cloned()
+    }
+
+    /// Marks `file` as being compiled until [`ModuleResolver::finish_loading`]. A file that
+    /// is already being compiled imports itself through the others; the error names the
+    /// files of that cycle, starting and ending with `file`.
+    pub fn start_loading(&mut self, file: &Path) -> Result<(), String> {
+        if let Some(start) = self.loading.iter().position(|loading| loading == file) {
+            let cycle: Vec<String> = self.loading[start..]
+                .iter()
+                .chain([&file.to_path_buf()])
+                .map(|file| file.display().to_string())
+                .collect();
+            return Err(format!("Circular import: {}", cycle.join(" -> ")));
+        }
+        self.loading.push(file.to_path_buf());
+        Ok(())
+    }
+
+    /// Ends the compilation of the file last passed to `start_loading`, keeping the module
+    /// if it compiled
+    pub fn finish_loading(&mut self, module: Option<Rc<Module>>) {
+        let file = self.loading.pop().expect("a module is being loaded");
+        if let Some(module) = module {
+            self.modules.insert(file, module);
+        }
+    }
+}
+
+/// Names a module exports: those of its top-level declarations marked with `export`,
+/// in declaration order
+pub(crate) fn exported_names(statements: &[Stmt]) -> Vec<String> {
+    let mut names = Vec::new();
+    for stmt in statements {
+        let Stmt::Export { declaration, .. } = stmt else {
+            continue;
+        };
+        match declaration.as_ref() {
+            Stmt::Val { name, .. }
+            | Stmt::Var { name, .. }
+            | Stmt::Fn { name, .. }
+            | Stmt::Struct { name, .. }
+            | Stmt::Enum { name, .. } => names.push(name.clone()),
+            Stmt::Destructure { pattern, .. } => match pattern {
+                DestructurePattern::Array {
+                    names: elements,
+                    rest,
+                } => names.extend(elements.iter().chain(rest).cloned()),
+                DestructurePattern::Struct { fields } => names.extend(fields.iter().cloned()),
+            },
+            _ => {}
+        }
+    }
+    names
+}
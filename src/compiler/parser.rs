@@ -4,9 +4,14 @@ use crate::common::errors::{
 use crate::common::SourceLocation;
 /// AST-building parser for the multi-pass compiler
 /// This parser builds an Abstract Syntax Tree instead of emitting bytecode directly
-use crate::compiler::ast::{BinaryOp, Expr, Stmt, UnaryOp};
+use crate::compiler::ast::{
+    required_arity, BinaryOp, CatchClause, DestructurePattern, Expr, ImportItems, MatchArm, Method,
+    Parameter, Stmt, StructField, UnaryOp,
+};
 use crate::compiler::token::TokenType;
 use crate::compiler::{Scanner, Token};
+use std::iter::Peekable;
+use std::str::Chars;
 
 /// AST Parser that builds an Abstract Syntax Tree
 pub struct Parser {
@@ -15,6 +20,15 @@ pub struct Parser {
     current_token: Token,
     errors: Vec<CompilationError>,
     panic_mode: bool,
+    /// Counter for compiler-generated variable names (never valid user identifiers)
+    synthetic_count: usize,
+    /// Set while parsing a match pattern, where `x =>` starts the arm rather than a lambda
+    parsing_match_pattern: bool,
+    /// Lines of the `///` comments read since the last token other than a line break
+    doc_lines: Vec<String>,
+    /// Doc comments in front of the previous and the current token
+    previous_doc: Option<String>,
+    current_doc: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
@@ -23,6 +37,7 @@ enum Precedence {
     Assignment,
     Ternary,
     Or,
+    Coalesce,   // ??
     BitwiseOr,  // |
     BitwiseXor, // ^
     BitwiseAnd, // &
@@ -45,7 +60,8 @@ impl Precedence {
             Precedence::None => Precedence::Assignment,
             Precedence::Assignment => Precedence::Ternary,
             Precedence::Ternary => Precedence::Or,
-            Precedence::Or => Precedence::BitwiseOr,
+            Precedence::Or => Precedence::Coalesce,
+            Precedence::Coalesce => Precedence::BitwiseOr,
             Precedence::BitwiseOr => Precedence::BitwiseXor,
             Precedence::BitwiseXor => Precedence::BitwiseAnd,
             Precedence::BitwiseAnd => Precedence::And,
@@ -72,6 +88,11 @@ impl Parser {
             current_token: Token::default(),
             errors: Vec::new(),
             panic_mode: false,
+            synthetic_count: 0,
+            parsing_match_pattern: false,
+            doc_lines: Vec::new(),
+            previous_doc: None,
+            current_doc: None,
         }
     }
 
@@ -100,12 +121,26 @@ impl Parser {
 
     fn advance(&mut self) {
         std::mem::swap(&mut self.previous_token, &mut self.current_token);
+        self.previous_doc = self.current_doc.take();
         loop {
             self.current_token = self.scanner.scan_token();
-            if self.current_token.token_type != TokenType::Error {
-                break;
+            match self.current_token.token_type {
+                TokenType::Error => {
+                    self.report_error_at_current(self.current_token.token.clone());
+                }
+                TokenType::DocComment => {
+                    let line = &self.current_token.token[3..];
+                    let line = line.strip_prefix(' ').unwrap_or(line).trim_end();
+                    self.doc_lines.push(line.to_string());
+                }
+                TokenType::NewLine => break,
+                _ => {
+                    if !self.doc_lines.is_empty() {
+                        self.current_doc = Some(std::mem::take(&mut self.doc_lines).join("\n"));
+                    }
+                    break;
+                }
             }
-            self.report_error_at_current(self.current_token.token.clone());
         }
     }
 
@@ -197,18 +232,72 @@ impl Parser {
         })
     }
 
-    fn parse_parameter_list(&mut self) -> Option<Vec<String>> {
+    fn parse_parameter_list(&mut self) -> Option<Vec<Parameter>> {
         self.parse_comma_separated_list(
             TokenType::RightParen,
             Some(crate::common::constants::MAX_FUNCTION_PARAMS),
             "Can't have more than 255 parameters.",
             |parser| {
+                let rest = parser.match_token(TokenType::DotDotDot);
                 if !parser.consume(TokenType::Identifier, "Expect parameter name.") {
                     return None;
                 }
-                Some(parser.previous_token.token.clone())
+                let name = parser.previous_token.token.clone();
+                let default = if parser.match_token(TokenType::Equal) {
+                    Some(parser.expression(false)?)
+                } else {
+                    None
+                };
+                Some(Parameter {
+                    name,
+                    default,
+                    rest,
+                })
             },
         )
+        .inspect(|params| self.check_parameter_order(params))
+    }
+
+    /// Omitted arguments are always the trailing ones, so defaults can't be followed
+    /// by a parameter without one, and a rest parameter takes whatever is left at the end
+    fn check_parameter_order(&mut self, params: &[Parameter]) {
+        let last = params.len().saturating_sub(1);
+        let required = required_arity(params) as usize;
+        for (index, param) in params.iter().enumerate() {
+            let message = if param.rest && index != last {
+                format!(
+                    "Rest parameter '{}' must be the last parameter.",
+                    param.name
+                )
+            } else if param.rest && param.default.is_some() {
+                format!(
+                    "Rest parameter '{}' can't have a default value.",
+                    param.name
+                )
+            } else if !param.rest && index > required && param.default.is_none() {
+                format!(
+                    "Parameter '{}' needs a default value because an earlier parameter has one.",
+                    param.name
+                )
+            } else {
+                continue;
+            };
+            self.report_error_at_current(message);
+            return;
+        }
+    }
+
+    /// Like parameters, only trailing fields can be left out when constructing an instance
+    fn check_field_order(&mut self, fields: &[StructField]) {
+        let first_default = fields.iter().position(|f| f.default.is_some());
+        if let Some(first_default) = first_default {
+            if let Some(field) = fields[first_default..].iter().find(|f| f.default.is_none()) {
+                self.report_error_at_current(format!(
+                    "Field '{}' needs a default value because an earlier field has one.",
+                    field.name
+                ));
+            }
+        }
     }
 
     fn parse_map_entry_list(&mut self) -> Option<Vec<(Expr, Expr)>> {
@@ -230,6 +319,21 @@ impl Parser {
         )
     }
 
+    /// Like `parse_arguments`, but an argument may be labeled (`x: 3`) for named struct construction
+    fn parse_call_arguments(&mut self) -> Option<Vec<(Option<String>, Expr)>> {
+        self.parse_comma_separated_list(
+            TokenType::RightParen,
+            Some(crate::common::constants::MAX_CALL_ARGUMENTS),
+            "Can't have more than 255 arguments.",
+            |parser| match parser.expression(false)? {
+                Expr::Variable { name, .. } if parser.match_token(TokenType::Colon) => {
+                    Some((Some(name), parser.expression(false)?))
+                }
+                value => Some((None, value)),
+            },
+        )
+    }
+
     fn current_location(&self) -> SourceLocation {
         SourceLocation {
             offset: self.previous_token.offset,
@@ -271,11 +375,18 @@ impl Parser {
             match self.current_token.token_type {
                 TokenType::Fn
                 | TokenType::Struct
+                | TokenType::Enum
+                | TokenType::Import
+                | TokenType::Export
                 | TokenType::Val
                 | TokenType::Var
                 | TokenType::For
                 | TokenType::If
                 | TokenType::While
+                | TokenType::With
+                | TokenType::Match
+                | TokenType::Try
+                | TokenType::Throw
                 | TokenType::Return => return,
                 _ => {}
             }
@@ -294,6 +405,12 @@ impl Parser {
             self.fn_declaration()
         } else if self.match_token(TokenType::Struct) {
             self.struct_declaration()
+        } else if self.match_token(TokenType::Enum) {
+            self.enum_declaration()
+        } else if self.match_token(TokenType::Import) {
+            self.import_declaration()
+        } else if self.match_token(TokenType::Export) {
+            self.export_declaration()
         } else {
             self.statement()
         }
@@ -304,6 +421,9 @@ impl Parser {
         is_mutable: bool,
         require_terminator: bool,
     ) -> Option<Stmt> {
+        if self.check(TokenType::LeftBracket) || self.check(TokenType::LeftBrace) {
+            return self.destructuring_declaration(is_mutable, require_terminator);
+        }
         if !self.consume(TokenType::Identifier, "Expecting variable name.") {
             return None;
         }
@@ -317,12 +437,7 @@ impl Parser {
         };
 
         if require_terminator {
-            let decl_type = if is_mutable { "variable" } else { "value" };
-            self.consume_either(
-                TokenType::NewLine,
-                TokenType::Eof,
-                &format!("Expecting '\\n' or '\\0' after {} declaration.", decl_type),
-            );
+            self.declaration_terminator(is_mutable);
         }
 
         Some(if is_mutable {
@@ -340,6 +455,91 @@ impl Parser {
         })
     }
 
+    /// `val [a, b, ...rest] = value` or `val {x, y} = value`
+    fn destructuring_declaration(
+        &mut self,
+        is_mutable: bool,
+        require_terminator: bool,
+    ) -> Option<Stmt> {
+        let location = self.current_location();
+
+        let pattern = if self.match_token(TokenType::LeftBracket) {
+            let mut elements =
+                self.parse_comma_separated_list(TokenType::RightBracket, None, "", |parser| {
+                    let rest = parser.match_token(TokenType::DotDotDot);
+                    if !parser.consume(TokenType::Identifier, "Expecting name in array pattern.") {
+                        return None;
+                    }
+                    Some((parser.previous_token.token.clone(), rest))
+                })?;
+            if !self.consume(
+                TokenType::RightBracket,
+                "Expecting ']' after array pattern.",
+            ) {
+                return None;
+            }
+            let rest = match elements.last() {
+                Some((_, true)) => elements.pop().map(|(name, _)| name),
+                _ => None,
+            };
+            if let Some((name, _)) = elements.iter().find(|(_, rest)| *rest) {
+                self.report_error_at_current(format!(
+                    "Rest element '{}' must be the last element of the pattern.",
+                    name
+                ));
+                return None;
+            }
+            DestructurePattern::Array {
+                names: elements.into_iter().map(|(name, _)| name).collect(),
+                rest,
+            }
+        } else {
+            self.advance(); // '{'
+            let fields =
+                self.parse_comma_separated_list(TokenType::RightBrace, None, "", |parser| {
+                    if !parser.consume(
+                        TokenType::Identifier,
+                        "Expecting field name in struct pattern.",
+                    ) {
+                        return None;
+                    }
+                    Some(parser.previous_token.token.clone())
+                })?;
+            if !self.consume(TokenType::RightBrace, "Expecting '}' after struct pattern.") {
+                return None;
+            }
+            DestructurePattern::Struct { fields }
+        };
+
+        if !self.consume(
+            TokenType::Equal,
+            "Expecting '=' after destructuring pattern.",
+        ) {
+            return None;
+        }
+        let initializer = self.expression(false)?;
+
+        if require_terminator {
+            self.declaration_terminator(is_mutable);
+        }
+
+        Some(Stmt::Destructure {
+            pattern,
+            initializer,
+            mutable: is_mutable,
+            location,
+        })
+    }
+
+    fn declaration_terminator(&mut self, is_mutable: bool) {
+        let decl_type = if is_mutable { "variable" } else { "value" };
+        self.consume_either(
+            TokenType::NewLine,
+            TokenType::Eof,
+            &format!("Expecting '\\n' or '\\0' after {} declaration.", decl_type),
+        );
+    }
+
     fn val_declaration(&mut self) -> Option<Stmt> {
         self.parse_variable_declaration(false, true)
     }
@@ -348,7 +548,9 @@ impl Parser {
         self.parse_variable_declaration(true, true)
     }
 
+    /// Parses a function after its `fn`, which the doc comment is attached to
     fn fn_declaration(&mut self) -> Option<Stmt> {
+        let doc = self.previous_doc.take();
         if !self.consume(TokenType::Identifier, "Expect function name.") {
             return None;
         }
@@ -373,11 +575,13 @@ impl Parser {
             name,
             params,
             body,
+            doc,
             location,
         })
     }
 
     fn struct_declaration(&mut self) -> Option<Stmt> {
+        let doc = self.previous_doc.take();
         if !self.consume(TokenType::Identifier, "Expect struct name.") {
             return None;
         }
@@ -389,14 +593,39 @@ impl Parser {
         }
 
         let mut fields = Vec::new();
+        let mut methods = Vec::new();
         self.skip_new_lines();
 
         if !self.check(TokenType::RightBrace) {
             loop {
-                if !self.consume(TokenType::Identifier, "Expect field name.") {
+                if self.match_token(TokenType::Fn) {
+                    match self.fn_declaration() {
+                        Some(Stmt::Fn {
+                            name,
+                            params,
+                            body,
+                            doc,
+                            location,
+                        }) => methods.push(Method {
+                            name,
+                            params,
+                            body,
+                            doc,
+                            location,
+                        }),
+                        _ => break,
+                    }
+                } else if self.consume(TokenType::Identifier, "Expect field name.") {
+                    let name = self.previous_token.token.clone();
+                    let default = if self.match_token(TokenType::Equal) {
+                        Some(self.expression(false)?)
+                    } else {
+                        None
+                    };
+                    fields.push(StructField { name, default });
+                } else {
                     break;
                 }
-                fields.push(self.previous_token.token.clone());
                 self.skip_new_lines();
                 if self.check(TokenType::RightBrace) {
                     break;
@@ -407,6 +636,7 @@ impl Parser {
         if !self.consume(TokenType::RightBrace, "Expect '}' after struct fields.") {
             return None;
         }
+        self.check_field_order(&fields);
         self.consume_either(
             TokenType::NewLine,
             TokenType::Eof,
@@ -416,10 +646,150 @@ impl Parser {
         Some(Stmt::Struct {
             name,
             fields,
+            methods,
+            doc,
+            location,
+        })
+    }
+
+    /// `enum Name { A, B, C }`; the variants are separated by commas, line breaks or both
+    fn enum_declaration(&mut self) -> Option<Stmt> {
+        let doc = self.previous_doc.take();
+        if !self.consume(TokenType::Identifier, "Expect enum name.") {
+            return None;
+        }
+        let name = self.previous_token.token.clone();
+        let location = self.current_location();
+
+        if !self.consume(TokenType::LeftBrace, "Expect '{' after enum name.") {
+            return None;
+        }
+        self.skip_new_lines();
+
+        let mut variants = Vec::new();
+        while !self.check(TokenType::RightBrace) {
+            if !self.consume(TokenType::Identifier, "Expect variant name.") {
+                return None;
+            }
+            variants.push(self.previous_token.token.clone());
+            self.match_token(TokenType::Comma);
+            self.skip_new_lines();
+        }
+        self.advance();
+
+        self.consume_either(
+            TokenType::NewLine,
+            TokenType::Eof,
+            "Expecting '\\n' or '\\0' after enum declaration.",
+        );
+
+        Some(Stmt::Enum {
+            name,
+            variants,
+            doc,
+            location,
+        })
+    }
+
+    /// `import { add, sub } from "path"` or `import * as M from "path"`
+    fn import_declaration(&mut self) -> Option<Stmt> {
+        let location = self.current_location();
+        let items = if self.match_token(TokenType::Star) {
+            if !self.match_contextual_keyword("as") {
+                self.report_error_at_current("Expecting 'as' after '*'.".to_string());
+                return None;
+            }
+            if !self.consume(TokenType::Identifier, "Expecting module name after 'as'.") {
+                return None;
+            }
+            ImportItems::Namespace(self.previous_token.token.clone())
+        } else {
+            if !self.consume(TokenType::LeftBrace, "Expecting '{' or '*' after 'import'.") {
+                return None;
+            }
+            self.skip_new_lines();
+            let mut symbols = Vec::new();
+            while !self.check(TokenType::RightBrace) {
+                if !self.consume(TokenType::Identifier, "Expecting name to import.") {
+                    return None;
+                }
+                symbols.push((self.previous_token.token.clone(), self.current_location()));
+                self.match_token(TokenType::Comma);
+                self.skip_new_lines();
+            }
+            self.advance();
+            if symbols.is_empty() {
+                self.report_error_at_current("Expecting at least one name to import.".to_string());
+                return None;
+            }
+            ImportItems::Symbols(symbols)
+        };
+
+        if !self.match_contextual_keyword("from") {
+            self.report_error_at_current("Expecting 'from' after imported names.".to_string());
+            return None;
+        }
+        if !self.consume(
+            TokenType::String,
+            "Expecting module path string after 'from'.",
+        ) {
+            return None;
+        }
+        let Some(Expr::String { value: path, .. }) = self.string() else {
+            return None;
+        };
+        self.consume_either(
+            TokenType::NewLine,
+            TokenType::Eof,
+            "Expecting '\\n' or '\\0' after import.",
+        );
+
+        Some(Stmt::Import {
+            path,
+            items,
             location,
         })
     }
 
+    /// `export` in front of a val, var, fn, struct or enum declaration
+    fn export_declaration(&mut self) -> Option<Stmt> {
+        let location = self.current_location();
+        // A doc comment above `export` documents the declaration
+        let doc = self.previous_doc.take();
+        let declaration = if self.match_token(TokenType::Val) {
+            self.val_declaration()
+        } else if self.match_token(TokenType::Var) {
+            self.var_declaration()
+        } else if self.match_token(TokenType::Fn) {
+            self.previous_doc = doc;
+            self.fn_declaration()
+        } else if self.match_token(TokenType::Struct) {
+            self.previous_doc = doc;
+            self.struct_declaration()
+        } else if self.match_token(TokenType::Enum) {
+            self.previous_doc = doc;
+            self.enum_declaration()
+        } else {
+            self.report_error_at_current(
+                "Expecting val, var, fn, struct or enum declaration after 'export'.".to_string(),
+            );
+            return None;
+        };
+        Some(Stmt::Export {
+            declaration: Box::new(declaration?),
+            location,
+        })
+    }
+
+    /// Consumes an identifier that acts as a keyword only in this position, like `from`
+    fn match_contextual_keyword(&mut self, keyword: &str) -> bool {
+        if self.check(TokenType::Identifier) && self.current_token.token == keyword {
+            self.advance();
+            return true;
+        }
+        false
+    }
+
     // ===== Statements =====
 
     fn statement(&mut self) -> Option<Stmt> {
@@ -433,9 +803,19 @@ impl Parser {
         } else if self.match_token(TokenType::If) {
             self.if_statement()
         } else if self.match_token(TokenType::While) {
-            self.while_statement()
+            self.while_statement(None)
+        } else if self.match_token(TokenType::Do) {
+            self.do_while_statement(None)
         } else if self.match_token(TokenType::For) {
-            self.for_statement()
+            self.for_statement(None)
+        } else if self.match_token(TokenType::With) {
+            self.with_statement()
+        } else if self.match_token(TokenType::Match) {
+            self.match_statement()
+        } else if self.match_token(TokenType::Try) {
+            self.try_statement()
+        } else if self.match_token(TokenType::Throw) {
+            self.throw_statement()
         } else if self.match_token(TokenType::Return) {
             self.return_statement()
         } else if self.match_token(TokenType::Break) {
@@ -450,6 +830,14 @@ impl Parser {
     fn expression_statement(&mut self) -> Option<Stmt> {
         let location = self.current_location();
         let expr = self.expression(false)?;
+        if let Expr::Variable { name, location } = &expr {
+            if self.match_token(TokenType::Colon) {
+                return self.labeled_loop(name.clone());
+            }
+            if self.check(TokenType::Comma) {
+                return self.multiple_assignment(name.clone(), *location);
+            }
+        }
         self.consume_either(
             TokenType::NewLine,
             TokenType::Eof,
@@ -458,6 +846,91 @@ impl Parser {
         Some(Stmt::Expression { expr, location })
     }
 
+    /// Parses the rest of `a, b = b, a` after its first target
+    fn multiple_assignment(&mut self, first: String, location: SourceLocation) -> Option<Stmt> {
+        let mut targets = vec![(first, location)];
+        while self.match_token(TokenType::Comma) {
+            if !self.consume(
+                TokenType::Identifier,
+                "Expecting variable name in assignment.",
+            ) {
+                return None;
+            }
+            targets.push((self.previous_token.token.clone(), self.current_location()));
+        }
+        if !self.consume(TokenType::Equal, "Expecting '=' after assignment targets.") {
+            return None;
+        }
+
+        let mut values = vec![self.expression(false)?];
+        while self.match_token(TokenType::Comma) {
+            values.push(self.expression(false)?);
+        }
+        if values.len() != targets.len() {
+            self.report_error_at_current(format!(
+                "Expecting {} values to assign, got {}.",
+                targets.len(),
+                values.len()
+            ));
+            return None;
+        }
+        self.consume_either(
+            TokenType::NewLine,
+            TokenType::Eof,
+            "Expecting '\\n' or '\\0' at end of expression.",
+        );
+
+        // Desugar to: Block { val $assign0 = value0, ..., target0 = $assign0, ... }
+        // so every value is evaluated before the first target changes
+        let temporaries: Vec<String> = (0..values.len())
+            .map(|_| {
+                self.synthetic_count += 1;
+                format!("$assign{}", self.synthetic_count)
+            })
+            .collect();
+        let mut statements: Vec<Stmt> = temporaries
+            .iter()
+            .zip(values)
+            .map(|(temporary, value)| Stmt::Val {
+                name: temporary.clone(),
+                initializer: Some(value),
+                location,
+            })
+            .collect();
+        for ((name, target_location), temporary) in targets.into_iter().zip(temporaries) {
+            statements.push(Stmt::Expression {
+                expr: Expr::Assign {
+                    name,
+                    value: Box::new(Expr::Variable {
+                        name: temporary,
+                        location: target_location,
+                    }),
+                    location: target_location,
+                },
+                location: target_location,
+            });
+        }
+
+        Some(Stmt::Block {
+            statements,
+            location,
+        })
+    }
+
+    /// Parses the loop following `label:`
+    fn labeled_loop(&mut self, label: String) -> Option<Stmt> {
+        if self.match_token(TokenType::While) {
+            self.while_statement(Some(label))
+        } else if self.match_token(TokenType::Do) {
+            self.do_while_statement(Some(label))
+        } else if self.match_token(TokenType::For) {
+            self.for_statement(Some(label))
+        } else {
+            self.report_error_at_current(format!("Expecting a loop after label '{}'.", label));
+            None
+        }
+    }
+
     fn block_statements(&mut self) -> Option<Vec<Stmt>> {
         let mut statements = Vec::new();
         self.skip_new_lines();
@@ -472,7 +945,13 @@ impl Parser {
             return None;
         }
 
-        if !self.check(TokenType::Else) {
+        // `} else`, `} catch`, `} finally` and the `} while (...)` closing a do-while continue
+        // the statement
+        if !self.check(TokenType::Else)
+            && !self.check(TokenType::Catch)
+            && !self.check(TokenType::Finally)
+            && !self.check(TokenType::While)
+        {
             self.consume_either(
                 TokenType::NewLine,
                 TokenType::Eof,
@@ -517,7 +996,7 @@ impl Parser {
         })
     }
 
-    fn while_statement(&mut self) -> Option<Stmt> {
+    fn while_statement(&mut self, label: Option<String>) -> Option<Stmt> {
         let location = self.current_location();
 
         if !self.consume(TokenType::LeftParen, "Expecting '(' after 'while'.") {
@@ -535,11 +1014,196 @@ impl Parser {
         Some(Stmt::While {
             condition,
             body,
+            label,
             location,
         })
     }
 
-    fn for_statement(&mut self) -> Option<Stmt> {
+    fn do_while_statement(&mut self, label: Option<String>) -> Option<Stmt> {
+        let location = self.current_location();
+
+        let body = Box::new(self.statement()?);
+
+        self.skip_new_lines();
+        if !self.consume(TokenType::While, "Expecting 'while' after 'do' body.") {
+            return None;
+        }
+        if !self.consume(TokenType::LeftParen, "Expecting '(' after 'while'.") {
+            return None;
+        }
+
+        let condition = self.expression(false)?;
+
+        if !self.consume(TokenType::RightParen, "Expecting ')' after condition.") {
+            return None;
+        }
+        self.consume_either(
+            TokenType::NewLine,
+            TokenType::Eof,
+            "Expecting '\\n' or '\\0' after do-while condition.",
+        );
+
+        Some(Stmt::DoWhile {
+            body,
+            condition,
+            label,
+            location,
+        })
+    }
+
+    fn match_statement(&mut self) -> Option<Stmt> {
+        let location = self.current_location();
+
+        let scrutinee = self.expression(false)?;
+
+        if !self.consume(TokenType::LeftBrace, "Expecting '{' after match value.") {
+            return None;
+        }
+
+        let mut arms = Vec::new();
+        let mut else_branch = None;
+        self.skip_new_lines();
+        while !self.check(TokenType::RightBrace) && !self.check(TokenType::Eof) {
+            if else_branch.is_some() {
+                self.report_error_at_current("'else' must be the last match arm.".to_string());
+                return None;
+            }
+
+            if self.match_token(TokenType::Else) {
+                if !self.consume(TokenType::FatArrow, "Expecting '=>' after 'else'.") {
+                    return None;
+                }
+                else_branch = Some(Box::new(self.match_arm_body()?));
+            } else {
+                self.parsing_match_pattern = true;
+                let pattern = self.expression(false);
+                self.parsing_match_pattern = false;
+                let pattern = pattern?;
+                if !self.consume(TokenType::FatArrow, "Expecting '=>' after match pattern.") {
+                    return None;
+                }
+                let body = self.match_arm_body()?;
+                arms.push(MatchArm { pattern, body });
+            }
+
+            // Arms are separated by a comma and/or newlines
+            self.match_token(TokenType::Comma);
+            self.skip_new_lines();
+        }
+
+        if !self.consume(TokenType::RightBrace, "Expect '}' after match arms.") {
+            return None;
+        }
+        self.consume_either(
+            TokenType::NewLine,
+            TokenType::Eof,
+            "Expecting '\\n' or '\\0' at end of match.",
+        );
+
+        Some(Stmt::Match {
+            scrutinee,
+            arms,
+            else_branch,
+            location,
+        })
+    }
+
+    /// An arm body is either a `{ ... }` block or a single expression
+    fn match_arm_body(&mut self) -> Option<Stmt> {
+        let location = self.current_location();
+
+        if self.match_token(TokenType::LeftBrace) {
+            let statements = self.inline_block_statements("Expect '}' after match arm.")?;
+            Some(Stmt::Block {
+                statements,
+                location,
+            })
+        } else {
+            let expr = self.expression(false)?;
+            Some(Stmt::Expression { expr, location })
+        }
+    }
+
+    /// Parses statements up to and including the closing '}' of a block that sits inside
+    /// an expression or arm, so unlike `block_statements` no newline is required after it
+    fn inline_block_statements(&mut self, message: &str) -> Option<Vec<Stmt>> {
+        let mut statements = Vec::new();
+        self.skip_new_lines();
+        while !self.check(TokenType::RightBrace) && !self.check(TokenType::Eof) {
+            if let Some(stmt) = self.declaration() {
+                statements.push(stmt);
+            }
+        }
+        if !self.consume(TokenType::RightBrace, message) {
+            return None;
+        }
+        Some(statements)
+    }
+
+    fn with_statement(&mut self) -> Option<Stmt> {
+        let location = self.current_location();
+
+        if !self.consume(TokenType::LeftParen, "Expecting '(' after 'with'.") {
+            return None;
+        }
+
+        if !self.consume(TokenType::Identifier, "Expecting resource name after '('.") {
+            return None;
+        }
+        let name = self.previous_token.token.clone();
+
+        if !self.consume(TokenType::Equal, "Expecting '=' after resource name.") {
+            return None;
+        }
+
+        let resource = self.expression(false)?;
+
+        if !self.consume(TokenType::RightParen, "Expecting ')' after resource.") {
+            return None;
+        }
+
+        if !self.consume(TokenType::LeftBrace, "Expecting '{' before 'with' body.") {
+            return None;
+        }
+        let body = self.block_statements()?;
+
+        // Desugar to: Block { val name = resource, try { body } finally { name.close() } }
+        // so the resource is closed however the body is left
+        let cleanup = Stmt::Expression {
+            expr: Expr::Call {
+                callee: Box::new(Expr::GetField {
+                    object: Box::new(Expr::Variable {
+                        name: name.clone(),
+                        location,
+                    }),
+                    field: "close".to_string(),
+                    location,
+                }),
+                arguments: Vec::new(),
+                location,
+            },
+            location,
+        };
+
+        Some(Stmt::Block {
+            statements: vec![
+                Stmt::Val {
+                    name,
+                    initializer: Some(resource),
+                    location,
+                },
+                Stmt::Try {
+                    body,
+                    catch: None,
+                    finally: Some(vec![cleanup]),
+                    location,
+                },
+            ],
+            location,
+        })
+    }
+
+    fn for_statement(&mut self, label: Option<String>) -> Option<Stmt> {
         let location = self.current_location();
 
         if !self.consume(TokenType::LeftParen, "Expecting '(' after 'for'.") {
@@ -559,7 +1223,7 @@ impl Parser {
             // Check for 'in' keyword
             if self.match_token(TokenType::In) {
                 // This is a for-in loop
-                return self.for_in_loop(identifier, location);
+                return self.for_in_loop(identifier, label, location);
             } else {
                 // This is not a for-in loop, report error
                 // User wrote: for (identifier ...
@@ -623,6 +1287,7 @@ impl Parser {
         let while_loop = Stmt::While {
             condition,
             body: Box::new(while_body),
+            label,
             location: body_location,
         };
 
@@ -632,7 +1297,12 @@ impl Parser {
         })
     }
 
-    fn for_in_loop(&mut self, variable: String, location: SourceLocation) -> Option<Stmt> {
+    fn for_in_loop(
+        &mut self,
+        variable: String,
+        label: Option<String>,
+        location: SourceLocation,
+    ) -> Option<Stmt> {
         // Parse collection expression
         let collection = self.expression(false)?;
 
@@ -647,10 +1317,74 @@ impl Parser {
             variable,
             collection,
             body,
+            label,
             location,
         })
     }
 
+    fn try_statement(&mut self) -> Option<Stmt> {
+        let location = self.current_location();
+
+        if !self.consume(TokenType::LeftBrace, "Expecting '{' after 'try'.") {
+            return None;
+        }
+        let body = self.block_statements()?;
+
+        let catch = if self.match_token(TokenType::Catch) {
+            if !self.consume(TokenType::LeftParen, "Expecting '(' after 'catch'.") {
+                return None;
+            }
+            if !self.consume(TokenType::Identifier, "Expecting error name after '('.") {
+                return None;
+            }
+            let error = self.previous_token.token.clone();
+            if !self.consume(TokenType::RightParen, "Expecting ')' after error name.") {
+                return None;
+            }
+            if !self.consume(TokenType::LeftBrace, "Expecting '{' before 'catch' body.") {
+                return None;
+            }
+            let body = self.block_statements()?;
+            Some(CatchClause { error, body })
+        } else {
+            None
+        };
+
+        let finally = if self.match_token(TokenType::Finally) {
+            if !self.consume(TokenType::LeftBrace, "Expecting '{' after 'finally'.") {
+                return None;
+            }
+            Some(self.block_statements()?)
+        } else {
+            None
+        };
+
+        if catch.is_none() && finally.is_none() {
+            self.report_error_at_current(
+                "Expecting 'catch' or 'finally' after 'try' block.".to_string(),
+            );
+            return None;
+        }
+
+        Some(Stmt::Try {
+            body,
+            catch,
+            finally,
+            location,
+        })
+    }
+
+    fn throw_statement(&mut self) -> Option<Stmt> {
+        let location = self.current_location();
+        let value = self.expression(false)?;
+        self.consume_either(
+            TokenType::NewLine,
+            TokenType::Eof,
+            "Expecting '\\n' or '\\0' at end of statement.",
+        );
+        Some(Stmt::Throw { value, location })
+    }
+
     fn return_statement(&mut self) -> Option<Stmt> {
         let location = self.current_location();
         let value = self.expression(false)?;
@@ -664,22 +1398,30 @@ impl Parser {
 
     fn break_statement(&mut self) -> Option<Stmt> {
         let location = self.current_location();
+        let label = self.loop_label();
         self.consume_either(
             TokenType::NewLine,
             TokenType::Eof,
             "Expecting '\\n' or '\\0' after 'break'.",
         );
-        Some(Stmt::Break { location })
+        Some(Stmt::Break { label, location })
     }
 
     fn continue_statement(&mut self) -> Option<Stmt> {
         let location = self.current_location();
+        let label = self.loop_label();
         self.consume_either(
             TokenType::NewLine,
             TokenType::Eof,
             "Expecting '\\n' or '\\0' after 'continue'.",
         );
-        Some(Stmt::Continue { location })
+        Some(Stmt::Continue { label, location })
+    }
+
+    /// The label after `break` or `continue`, if there is one
+    fn loop_label(&mut self) -> Option<String> {
+        self.match_token(TokenType::Identifier)
+            .then(|| self.previous_token.token.clone())
     }
 
     // ===== Expressions =====
@@ -699,12 +1441,18 @@ impl Parser {
             TokenType::Number => self.number(),
             TokenType::String => self.string(),
             TokenType::InterpolatedString => self.interpolated_string(),
+            TokenType::RawString => self.raw_string(),
             TokenType::True | TokenType::False | TokenType::Nil => self.literal(),
             TokenType::LeftParen => self.grouping(),
             TokenType::Minus | TokenType::Bang | TokenType::Tilde => self.unary(),
             TokenType::Identifier => self.variable(),
             TokenType::LeftBrace => self.brace_literal(),
             TokenType::LeftBracket => self.array_literal(),
+            TokenType::Fn => self.lambda(),
+            TokenType::This => Some(Expr::Variable {
+                name: "this".to_string(),
+                location: self.current_location(),
+            }),
             _ => {
                 self.report_error_at_current("Expect expression".to_string());
                 return None;
@@ -723,12 +1471,14 @@ impl Parser {
                 | TokenType::Percent
                 | TokenType::EqualEqual
                 | TokenType::BangEqual
+                | TokenType::Is
                 | TokenType::Greater
                 | TokenType::GreaterEqual
                 | TokenType::Less
                 | TokenType::LessEqual
                 | TokenType::AndAnd
                 | TokenType::OrOr
+                | TokenType::QuestionQuestion
                 | TokenType::Ampersand
                 | TokenType::Pipe
                 | TokenType::Caret
@@ -738,6 +1488,7 @@ impl Parser {
                 TokenType::LeftParen => self.call(expr),
                 TokenType::Dot => self.dot(expr),
                 TokenType::LeftBracket => self.index(expr),
+                TokenType::QuestionDot => self.optional_dot(expr),
                 TokenType::PlusPlus | TokenType::MinusMinus => self.postfix(expr),
                 TokenType::Question => self.ternary(expr),
                 _ => {
@@ -758,6 +1509,7 @@ impl Parser {
             TokenType::LeftParen
             | TokenType::Dot
             | TokenType::LeftBracket
+            | TokenType::QuestionDot
             | TokenType::PlusPlus
             | TokenType::MinusMinus => Precedence::Call,
             TokenType::StarStar => Precedence::Exponent,
@@ -771,12 +1523,13 @@ impl Parser {
             | TokenType::GreaterEqual
             | TokenType::Less
             | TokenType::LessEqual => Precedence::Comparison,
-            TokenType::EqualEqual | TokenType::BangEqual => Precedence::Equality,
+            TokenType::EqualEqual | TokenType::BangEqual | TokenType::Is => Precedence::Equality,
             TokenType::Ampersand => Precedence::BitwiseAnd,
             TokenType::Caret => Precedence::BitwiseXor,
             TokenType::Pipe => Precedence::BitwiseOr,
             TokenType::AndAnd => Precedence::And,
             TokenType::OrOr => Precedence::Or,
+            TokenType::QuestionQuestion => Precedence::Coalesce,
             TokenType::Question => Precedence::Ternary,
             _ => Precedence::None,
         }
@@ -796,23 +1549,18 @@ impl Parser {
         let clean: String = s.chars().filter(|c| *c != '_').collect();
 
         if clean.len() >= 2 {
-            match &clean[..2] {
-                "0x" | "0X" => {
-                    let hex_part = &clean[2..];
-                    let int_value = u64::from_str_radix(hex_part, 16).ok()?;
-                    return Some(int_value as f64);
-                }
-                "0b" | "0B" => {
-                    let bin_part = &clean[2..];
-                    let int_value = u64::from_str_radix(bin_part, 2).ok()?;
-                    return Some(int_value as f64);
-                }
-                "0o" | "0O" => {
-                    let oct_part = &clean[2..];
-                    let int_value = u64::from_str_radix(oct_part, 8).ok()?;
-                    return Some(int_value as f64);
-                }
-                _ => {}
+            let radix = match &clean[..2] {
+                "0x" | "0X" => Some(16),
+                "0b" | "0B" => Some(2),
+                "0o" | "0O" => Some(8),
+                _ => None,
+            };
+            if let Some(radix) = radix {
+                // Accumulated as a float, so a literal too large for an integer loses precision
+                // like a large decimal literal does instead of failing
+                return clean[2..].chars().try_fold(0.0, |value: f64, c| {
+                    Some(value * radix as f64 + c.to_digit(radix)? as f64)
+                });
             }
         }
 
@@ -822,11 +1570,64 @@ impl Parser {
 
     fn string(&self) -> Option<Expr> {
         let token_value = &self.previous_token.token;
-        let value = token_value[1..token_value.len() - 1].to_string();
+        let mut value = String::new();
+        let mut chars = token_value[1..token_value.len() - 1].chars().peekable();
+        while let Some(ch) = chars.next() {
+            match ch {
+                '\\' => value.push(Parser::unescape(&mut chars)),
+                _ => value.push(ch),
+            }
+        }
         let location = self.current_location();
         Some(Expr::String { value, location })
     }
 
+    /// Decodes the escape sequence after a backslash; the scanner has already checked it
+    fn unescape(chars: &mut Peekable<Chars>) -> char {
+        match chars.next() {
+            Some('n') => '\n',
+            Some('t') => '\t',
+            Some('r') => '\r',
+            Some('0') => '\0',
+            Some('u') => {
+                let digits: String = chars.by_ref().skip(1).take_while(|c| *c != '}').collect();
+                u32::from_str_radix(&digits, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .unwrap_or(char::REPLACEMENT_CHARACTER)
+            }
+            Some(other) => other,
+            None => '\\',
+        }
+    }
+
+    /// A `"""` literal is taken as written, without escapes or interpolation. When the text
+    /// starts on the line after the opening `"""` and the closing `"""` is on a line of its
+    /// own, that first line break, the last one and the indentation of the closing `"""` are
+    /// left out, so the literal can be indented along with the code around it.
+    fn raw_string(&self) -> Option<Expr> {
+        let token_value = &self.previous_token.token;
+        let content = &token_value[3..token_value.len() - 3];
+        let location = self.current_location();
+
+        let indented = content.strip_prefix('\n').and_then(|text| {
+            let (body, indent) = text.rsplit_once('\n')?;
+            indent
+                .chars()
+                .all(|c| c == ' ' || c == '\t')
+                .then_some((body, indent))
+        });
+        let value = match indented {
+            Some((body, indent)) => body
+                .split('\n')
+                .map(|line| line.strip_prefix(indent).unwrap_or(line))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            None => content.to_string(),
+        };
+        Some(Expr::String { value, location })
+    }
+
     fn interpolated_string(&mut self) -> Option<Expr> {
         use crate::compiler::ast::InterpolationPart;
 
@@ -840,7 +1641,9 @@ impl Parser {
         let mut chars = content.chars().peekable();
 
         while let Some(ch) = chars.next() {
-            if ch == '$' && chars.peek() == Some(&'{') {
+            if ch == '\\' {
+                current_literal.push(Parser::unescape(&mut chars));
+            } else if ch == '$' && chars.peek() == Some(&'{') {
                 chars.next();
 
                 if !current_literal.is_empty() {
@@ -921,11 +1724,99 @@ impl Parser {
                 value,
                 location,
             })
+        } else if !self.parsing_match_pattern && self.match_token(TokenType::FatArrow) {
+            self.arrow_lambda(name, location)
+        } else if let Some(operator) = self.match_compound_assignment() {
+            // x op= v  =>  x = x op v
+            let current = Expr::Variable {
+                name: name.clone(),
+                location,
+            };
+            let value = Box::new(self.compound_value(current, operator, location)?);
+            Some(Expr::Assign {
+                name,
+                value,
+                location,
+            })
         } else {
             Some(Expr::Variable { name, location })
         }
     }
 
+    /// Consumes a compound assignment token (`+=`, `-=`, ...) and returns its arithmetic operator
+    fn match_compound_assignment(&mut self) -> Option<BinaryOp> {
+        let operator = match self.current_token.token_type {
+            TokenType::PlusEqual => BinaryOp::Add,
+            TokenType::MinusEqual => BinaryOp::Subtract,
+            TokenType::StarEqual => BinaryOp::Multiply,
+            TokenType::SlashEqual => BinaryOp::Divide,
+            TokenType::PercentEqual => BinaryOp::Modulo,
+            _ => return None,
+        };
+        self.advance();
+        Some(operator)
+    }
+
+    /// Parses the right-hand side of a compound assignment to a variable and combines it
+    /// with the variable's current value
+    fn compound_value(
+        &mut self,
+        current: Expr,
+        operator: BinaryOp,
+        location: SourceLocation,
+    ) -> Option<Expr> {
+        let right = self.expression(false)?;
+        Some(Expr::Binary {
+            left: Box::new(current),
+            operator,
+            right: Box::new(right),
+            location,
+        })
+    }
+
+    /// `fn(params) { body }` in expression position
+    fn lambda(&mut self) -> Option<Expr> {
+        let location = self.current_location();
+
+        if !self.consume(TokenType::LeftParen, "Expect '(' after 'fn'.") {
+            return None;
+        }
+        let params = self.parse_parameter_list()?;
+        if !self.consume(TokenType::RightParen, "Expect ')' after parameters.") {
+            return None;
+        }
+        if !self.consume(TokenType::LeftBrace, "Expect '{' before function body.") {
+            return None;
+        }
+        let body = self.inline_block_statements("Expect '}' after function body.")?;
+
+        Some(Expr::Lambda {
+            params,
+            body,
+            location,
+        })
+    }
+
+    /// `param => expr` or `param => { body }`; the '=>' has already been consumed
+    fn arrow_lambda(&mut self, param: String, location: SourceLocation) -> Option<Expr> {
+        let body = if self.match_token(TokenType::LeftBrace) {
+            self.inline_block_statements("Expect '}' after function body.")?
+        } else {
+            let value_location = self.current_location();
+            let value = self.expression(false)?;
+            vec![Stmt::Return {
+                value,
+                location: value_location,
+            }]
+        };
+
+        Some(Expr::Lambda {
+            params: vec![Parameter::new(param)],
+            body,
+            location,
+        })
+    }
+
     // ===== Binary & Unary =====
 
     fn binary(&mut self, left: Expr) -> Option<Expr> {
@@ -951,12 +1842,14 @@ impl Parser {
             TokenType::Percent => BinaryOp::Modulo,
             TokenType::EqualEqual => BinaryOp::Equal,
             TokenType::BangEqual => BinaryOp::NotEqual,
+            TokenType::Is => BinaryOp::Is,
             TokenType::Greater => BinaryOp::Greater,
             TokenType::GreaterEqual => BinaryOp::GreaterEqual,
             TokenType::Less => BinaryOp::Less,
             TokenType::LessEqual => BinaryOp::LessEqual,
             TokenType::AndAnd => BinaryOp::And,
             TokenType::OrOr => BinaryOp::Or,
+            TokenType::QuestionQuestion => BinaryOp::NilCoalesce,
             TokenType::Ampersand => BinaryOp::BitwiseAnd,
             TokenType::Pipe => BinaryOp::BitwiseOr,
             TokenType::Caret => BinaryOp::BitwiseXor,
@@ -965,6 +1858,42 @@ impl Parser {
             _ => return None,
         };
 
+        // `a < b < c` continues the comparison on its left instead of comparing its result
+        let ordering = |operator: &BinaryOp| {
+            matches!(
+                operator,
+                BinaryOp::Less | BinaryOp::LessEqual | BinaryOp::Greater | BinaryOp::GreaterEqual
+            )
+        };
+        let left = match left {
+            Expr::Binary {
+                left: first,
+                operator: first_operator,
+                right: middle,
+                location,
+            } if ordering(&operator) && ordering(&first_operator) => {
+                return Some(Expr::ChainedComparison {
+                    operands: vec![*first, *middle, *right],
+                    operators: vec![first_operator, operator],
+                    location,
+                });
+            }
+            Expr::ChainedComparison {
+                mut operands,
+                mut operators,
+                location,
+            } if ordering(&operator) => {
+                operands.push(*right);
+                operators.push(operator);
+                return Some(Expr::ChainedComparison {
+                    operands,
+                    operators,
+                    location,
+                });
+            }
+            left => left,
+        };
+
         Some(Expr::Binary {
             left: Box::new(left),
             operator,
@@ -977,7 +1906,8 @@ impl Parser {
         let operator_type = self.previous_token.token_type.clone();
         let location = self.current_location();
 
-        let operand = Box::new(self.parse_precedence(Precedence::Unary, false)?);
+        // The operand may contain `**`, so `-2 ** 2` is `-(2 ** 2)` as in math notation
+        let operand = Box::new(self.parse_precedence(Precedence::Exponent, false)?);
 
         let operator = match operator_type {
             TokenType::Minus => UnaryOp::Negate,
@@ -1035,23 +1965,48 @@ impl Parser {
 
     fn call(&mut self, callee: Expr) -> Option<Expr> {
         let location = self.current_location();
-        let arguments = self.parse_arguments()?;
+        let arguments = self.parse_call_arguments()?;
 
         if !self.consume(TokenType::RightParen, "Expect ')' after arguments.") {
             return None;
         }
 
-        Some(Expr::Call {
-            callee: Box::new(callee),
-            arguments,
-            location,
-        })
+        let named = arguments
+            .iter()
+            .filter(|(label, _)| label.is_some())
+            .count();
+        if named > 0 && named < arguments.len() {
+            self.report_error_at_current(
+                "Can't mix positional and named arguments in one call.".to_string(),
+            );
+        }
+
+        let callee = Box::new(callee);
+        if named == arguments.len() && named > 0 {
+            Some(Expr::NamedCall {
+                callee,
+                arguments: arguments
+                    .into_iter()
+                    .map(|(label, value)| (label.unwrap_or_default(), value))
+                    .collect(),
+                location,
+            })
+        } else {
+            Some(Expr::Call {
+                callee,
+                arguments: arguments.into_iter().map(|(_, value)| value).collect(),
+                location,
+            })
+        }
     }
 
     fn dot(&mut self, object: Expr) -> Option<Expr> {
         let location = self.current_location();
 
-        if !self.consume(TokenType::Identifier, "Expect field name after '.'.") {
+        // `match` is a keyword but remains usable as a method name (Regex.match)
+        if !self.match_token(TokenType::Match)
+            && !self.consume(TokenType::Identifier, "Expect field name after '.'.")
+        {
             return None;
         }
         let field = self.previous_token.token.clone();
@@ -1083,6 +2038,16 @@ impl Parser {
             Some(Expr::SetField {
                 object: Box::new(object),
                 field,
+                operator: None,
+                value,
+                location,
+            })
+        } else if let Some(operator) = self.match_compound_assignment() {
+            let value = Box::new(self.expression(false)?);
+            Some(Expr::SetField {
+                object: Box::new(object),
+                field,
+                operator: Some(operator),
                 value,
                 location,
             })
@@ -1190,6 +2155,16 @@ impl Parser {
             Some(Expr::IndexAssign {
                 object: Box::new(object),
                 index,
+                operator: None,
+                value,
+                location,
+            })
+        } else if let Some(operator) = self.match_compound_assignment() {
+            let value = Box::new(self.expression(false)?);
+            Some(Expr::IndexAssign {
+                object: Box::new(object),
+                index,
+                operator: Some(operator),
                 value,
                 location,
             })
@@ -1202,6 +2177,58 @@ impl Parser {
         }
     }
 
+    fn optional_dot(&mut self, object: Expr) -> Option<Expr> {
+        if self.match_token(TokenType::LeftBracket) {
+            return self.optional_index(object);
+        }
+        let location = self.current_location();
+
+        if !self.match_token(TokenType::Match)
+            && !self.consume(TokenType::Identifier, "Expect field name after '?.'.")
+        {
+            return None;
+        }
+        let field = self.previous_token.token.clone();
+
+        if self.check(TokenType::LeftParen) {
+            self.report_error_at_current(
+                "Optional chaining is only supported for field access, not method calls."
+                    .to_string(),
+            );
+            return None;
+        }
+        if self.check(TokenType::Equal) || self.match_compound_assignment().is_some() {
+            self.report_error_at_current("Cannot assign through an optional chain.".to_string());
+            return None;
+        }
+
+        Some(Expr::OptionalGetField {
+            object: Box::new(object),
+            field,
+            location,
+        })
+    }
+
+    fn optional_index(&mut self, object: Expr) -> Option<Expr> {
+        let location = self.current_location();
+
+        let index = Box::new(self.expression(false)?);
+
+        if !self.consume(TokenType::RightBracket, "Expect ']' after index.") {
+            return None;
+        }
+        if self.check(TokenType::Equal) || self.match_compound_assignment().is_some() {
+            self.report_error_at_current("Cannot assign through an optional chain.".to_string());
+            return None;
+        }
+
+        Some(Expr::OptionalIndex {
+            object: Box::new(object),
+            index,
+            location,
+        })
+    }
+
     fn postfix(&self, operand: Expr) -> Option<Expr> {
         let operator_type = self.previous_token.token_type.clone();
         let location = self.current_location();
@@ -0,0 +1,223 @@
+use crate::common::opcodes::OpCode;
+use crate::common::{Chunk, SourceLocation};
+use std::collections::HashSet;
+
+/// Peephole pass over a finished chunk that removes instructions with no observable effect:
+///
+/// - values computed without jumps, effects or a chance of failing and popped right away, as
+///   emitted for expression statements like `x`, `"unused"`, `a == b` or `[x, !y]`
+/// - a double `Not` in front of an `if`-style conditional jump, where the tested value is
+///   popped on both paths so only its truthiness matters
+///
+/// Jump operands and source locations are rewritten to match the shorter code. Nothing is
+/// removed when a jump lands between the instructions of a removed sequence.
+pub(crate) fn optimize(chunk: &mut Chunk) {
+    loop {
+        let instructions = decode(chunk);
+        let removed = dead_instructions(chunk, &instructions);
+        if !removed.contains(&true) {
+            return;
+        }
+        rewrite(chunk, &instructions, &removed);
+    }
+}
+
+/// Decoded instruction: its offset, opcode and length in bytes including operands
+struct Instruction {
+    offset: usize,
+    op_code: OpCode,
+    len: usize,
+}
+
+fn decode(chunk: &Chunk) -> Vec<Instruction> {
+    let mut instructions = Vec::new();
+    let mut offset = 0;
+    while offset < chunk.instructions.len() {
+        let len = chunk.instruction_len(offset);
+        instructions.push(Instruction {
+            offset,
+            op_code: OpCode::from_u8(chunk.read_u8(offset)),
+            len,
+        });
+        offset += len;
+    }
+    instructions
+}
+
+/// Offset a jump instruction continues at when taken
+fn jump_target(chunk: &Chunk, instruction: &Instruction) -> Option<usize> {
+    let offset = instruction.offset;
+    match instruction.op_code {
+        OpCode::Jump | OpCode::JumpIfFalse | OpCode::TryBegin => {
+            Some(offset + 5 + chunk.read_u32(offset + 1) as usize)
+        }
+        OpCode::JumpIfArgSupplied => Some(offset + 6 + chunk.read_u32(offset + 2) as usize),
+        OpCode::Loop => Some(offset + 5 - chunk.read_u32(offset + 1) as usize),
+        _ => None,
+    }
+}
+
+/// Pushes a value without any other effect
+fn is_pure_push(op_code: &OpCode) -> bool {
+    matches!(
+        op_code,
+        OpCode::Constant
+            | OpCode::Constant2
+            | OpCode::Constant4
+            | OpCode::String
+            | OpCode::String2
+            | OpCode::String4
+            | OpCode::Nil
+            | OpCode::True
+            | OpCode::False
+            | OpCode::GetLocal
+            | OpCode::GetLocal2
+            | OpCode::GetLocal4
+            | OpCode::GetGlobal
+            | OpCode::GetGlobal2
+            | OpCode::GetGlobal4
+            | OpCode::GetBuiltin
+            | OpCode::GetBuiltin2
+            | OpCode::GetBuiltin4
+            | OpCode::GetUpvalue
+            | OpCode::Dup
+    )
+}
+
+/// Number of values a straight-line instruction without effects takes from the stack to push
+/// its result. None for anything else, including operations that can fail.
+fn pure_operands(chunk: &Chunk, instruction: &Instruction) -> Option<usize> {
+    match instruction.op_code {
+        OpCode::Not => Some(1),
+        OpCode::Equal | OpCode::Same => Some(2),
+        OpCode::CreateArray => Some(chunk.read_u16(instruction.offset + 1) as usize),
+        ref op_code if is_pure_push(op_code) => Some(0),
+        _ => None,
+    }
+}
+
+/// Index of the first instruction of the pure sequence that computes the value popped by the
+/// `Pop` at `pop`, if there is one and no jump lands inside it
+fn discarded_value_start(
+    chunk: &Chunk,
+    instructions: &[Instruction],
+    pop: usize,
+    is_target: impl Fn(&Instruction) -> bool,
+) -> Option<usize> {
+    let mut needed = 1;
+    let mut start = pop;
+    while needed > 0 {
+        if is_target(&instructions[start]) {
+            return None;
+        }
+        start = start.checked_sub(1)?;
+        needed = needed - 1 + pure_operands(chunk, &instructions[start])?;
+    }
+    Some(start)
+}
+
+fn dead_instructions(chunk: &Chunk, instructions: &[Instruction]) -> Vec<bool> {
+    let targets: HashSet<usize> = instructions
+        .iter()
+        .filter_map(|instruction| jump_target(chunk, instruction))
+        .collect();
+    let is_target = |instruction: &Instruction| targets.contains(&instruction.offset);
+    let op_at = |offset: usize| {
+        instructions
+            .binary_search_by_key(&offset, |instruction| instruction.offset)
+            .ok()
+            .map(|index| &instructions[index].op_code)
+    };
+
+    let mut removed = vec![false; instructions.len()];
+    for pop in 0..instructions.len() {
+        if instructions[pop].op_code != OpCode::Pop {
+            continue;
+        }
+        if let Some(start) = discarded_value_start(chunk, instructions, pop, is_target) {
+            removed[start..=pop].fill(true);
+        }
+    }
+
+    let mut i = 0;
+    while i + 1 < instructions.len() {
+        let (first, second) = (&instructions[i], &instructions[i + 1]);
+        if removed[i] {
+            i += 1;
+            continue;
+        }
+        if first.op_code == OpCode::Not
+            && second.op_code == OpCode::Not
+            && !is_target(second)
+            && instructions.get(i + 2).is_some_and(|jump| {
+                jump.op_code == OpCode::JumpIfFalse
+                    && !is_target(jump)
+                    && instructions.get(i + 3).is_some_and(|next| {
+                        next.op_code == OpCode::Pop
+                            && !is_target(next)
+                            && jump_target(chunk, jump).and_then(op_at) == Some(&OpCode::Pop)
+                    })
+            })
+        {
+            removed[i] = true;
+            removed[i + 1] = true;
+            i += 2;
+            continue;
+        }
+        i += 1;
+    }
+    removed
+}
+
+fn rewrite(chunk: &mut Chunk, instructions: &[Instruction], removed: &[bool]) {
+    // New offset of every old instruction; a removed one maps to the next instruction kept
+    let mut new_offsets = vec![0; instructions.len() + 1];
+    let mut offset = 0;
+    for (index, instruction) in instructions.iter().enumerate() {
+        new_offsets[index] = offset;
+        if !removed[index] {
+            offset += instruction.len;
+        }
+    }
+    new_offsets[instructions.len()] = offset;
+    let new_offset = |old: usize| {
+        let index = instructions.partition_point(|instruction| instruction.offset < old);
+        new_offsets[index]
+    };
+
+    let mut code = Vec::with_capacity(offset);
+    for (index, instruction) in instructions.iter().enumerate() {
+        if removed[index] {
+            continue;
+        }
+        let start = code.len();
+        code.extend_from_slice(
+            &chunk.instructions[instruction.offset..instruction.offset + instruction.len],
+        );
+        if let Some(target) = jump_target(chunk, instruction) {
+            let target = new_offset(target);
+            let (operand, jump) = match instruction.op_code {
+                OpCode::Loop => (start + 1, start + 5 - target),
+                OpCode::JumpIfArgSupplied => (start + 2, target - (start + 6)),
+                _ => (start + 1, target - (start + 5)),
+            };
+            code[operand..operand + 4].copy_from_slice(&(jump as u32).to_le_bytes());
+        }
+    }
+
+    let locations = chunk
+        .source_locations
+        .iter()
+        .filter(|location| {
+            let index = instructions.partition_point(|i| i.offset < location.offset);
+            !removed.get(index).copied().unwrap_or(false)
+        })
+        .map(|location| SourceLocation {
+            offset: new_offset(location.offset),
+            ..*location
+        })
+        .collect();
+
+    chunk.instructions = code;
+    chunk.source_locations = locations;
+}
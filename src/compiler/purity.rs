@@ -0,0 +1,90 @@
+use crate::compiler::ast::{BinaryOp, Expr, InterpolationPart, UnaryOp};
+
+impl Expr {
+    /// Conservatively determines whether evaluating this expression can be skipped without
+    /// any observable difference.
+    ///
+    /// That takes more than having no side effects: a runtime error can be caught, so an
+    /// operation that may fail is impure as well. Pure are literals, variable reads, lambdas,
+    /// `!`, equality, identity and the logical operators, conditionals, string interpolation
+    /// and array literals built from pure parts, and map and set literals whose keys are
+    /// literals. Arithmetic, ordering comparisons, field and index reads, ranges, assignments
+    /// and calls may fail or have effects, so they are impure.
+    #[allow(dead_code)]
+    pub fn is_pure(&self) -> bool {
+        match self {
+            Expr::Number { .. }
+            | Expr::String { .. }
+            | Expr::Boolean { .. }
+            | Expr::Nil { .. }
+            | Expr::Variable { .. }
+            | Expr::Lambda { .. } => true,
+
+            Expr::StringInterpolation { parts, .. } => parts.iter().all(|part| match part {
+                InterpolationPart::Literal(_) => true,
+                InterpolationPart::Expression(expr) => expr.is_pure(),
+            }),
+
+            Expr::Grouping { expr, .. } => expr.is_pure(),
+            Expr::Unary {
+                operator: UnaryOp::Not,
+                operand,
+                ..
+            } => operand.is_pure(),
+            Expr::Binary {
+                left,
+                operator,
+                right,
+                ..
+            } => is_infallible(operator) && left.is_pure() && right.is_pure(),
+            Expr::Conditional {
+                condition,
+                then_expr,
+                else_expr,
+                ..
+            } => condition.is_pure() && then_expr.is_pure() && else_expr.is_pure(),
+
+            Expr::ArrayLiteral { elements, .. } => elements.iter().all(Expr::is_pure),
+            // Arrays, maps and sets can't be keys, which only literal keys rule out
+            Expr::SetLiteral { elements, .. } => elements.iter().all(is_key_literal),
+            Expr::MapLiteral { entries, .. } => entries
+                .iter()
+                .all(|(key, value)| is_key_literal(key) && value.is_pure()),
+
+            Expr::Unary { .. }
+            | Expr::ChainedComparison { .. }
+            | Expr::Range { .. }
+            | Expr::GetField { .. }
+            | Expr::OptionalGetField { .. }
+            | Expr::Index { .. }
+            | Expr::OptionalIndex { .. }
+            | Expr::Call { .. }
+            | Expr::NamedCall { .. }
+            | Expr::Assign { .. }
+            | Expr::SetField { .. }
+            | Expr::IndexAssign { .. }
+            | Expr::PostfixIncrement { .. }
+            | Expr::PostfixDecrement { .. } => false,
+        }
+    }
+}
+
+/// Binary operators that give a result for operands of any type
+fn is_infallible(operator: &BinaryOp) -> bool {
+    matches!(
+        operator,
+        BinaryOp::Equal
+            | BinaryOp::NotEqual
+            | BinaryOp::Is
+            | BinaryOp::And
+            | BinaryOp::Or
+            | BinaryOp::NilCoalesce
+    )
+}
+
+fn is_key_literal(expr: &Expr) -> bool {
+    matches!(
+        expr,
+        Expr::Number { .. } | Expr::String { .. } | Expr::Boolean { .. }
+    )
+}
@@ -29,10 +29,9 @@ impl Scanner {
                 break;
             }
 
-            // increment line number if this is an empty line
-            if self.previous() == '\n' && c == '\n' {
-                self.line += 1;
-            }
+            // An empty line: only one NewLine token is produced for a run of them
+            self.line += 1;
+            self.column = 1;
         }
 
         if Scanner::is_alpha(c) {
@@ -54,6 +53,8 @@ impl Scanner {
                 if self.matches('.') {
                     if self.matches('=') {
                         self.make_token(TokenType::DotDotEqual)
+                    } else if self.matches('.') {
+                        self.make_token(TokenType::DotDotDot)
                     } else {
                         self.make_token(TokenType::DotDot)
                     }
@@ -64,6 +65,8 @@ impl Scanner {
             '-' => {
                 if self.matches('-') {
                     self.make_token(TokenType::MinusMinus)
+                } else if self.matches('=') {
+                    self.make_token(TokenType::MinusEqual)
                 } else {
                     self.make_token(TokenType::Minus)
                 }
@@ -71,17 +74,35 @@ impl Scanner {
             '+' => {
                 if self.matches('+') {
                     self.make_token(TokenType::PlusPlus)
+                } else if self.matches('=') {
+                    self.make_token(TokenType::PlusEqual)
                 } else {
                     self.make_token(TokenType::Plus)
                 }
             }
-            '%' => self.make_token(TokenType::Percent),
+            '%' => {
+                if self.matches('=') {
+                    self.make_token(TokenType::PercentEqual)
+                } else {
+                    self.make_token(TokenType::Percent)
+                }
+            }
             ';' => self.make_token(TokenType::Semicolon),
             ':' => self.make_token(TokenType::Colon),
-            '?' => self.make_token(TokenType::Question),
+            '?' => {
+                if self.matches('?') {
+                    self.make_token(TokenType::QuestionQuestion)
+                } else if self.matches('.') {
+                    self.make_token(TokenType::QuestionDot)
+                } else {
+                    self.make_token(TokenType::Question)
+                }
+            }
             '*' => {
                 if self.matches('*') {
                     self.make_token(TokenType::StarStar)
+                } else if self.matches('=') {
+                    self.make_token(TokenType::StarEqual)
                 } else {
                     self.make_token(TokenType::Star)
                 }
@@ -96,6 +117,8 @@ impl Scanner {
             '=' => {
                 if self.matches('=') {
                     self.make_token(TokenType::EqualEqual)
+                } else if self.matches('>') {
+                    self.make_token(TokenType::FatArrow)
                 } else {
                     self.make_token(TokenType::Equal)
                 }
@@ -135,31 +158,27 @@ impl Scanner {
             '^' => self.make_token(TokenType::Caret),
             '~' => self.make_token(TokenType::Tilde),
             '/' => {
-                if self.matches('/') {
+                if self.matches('*') {
+                    self.skip_block_comment()
+                } else if self.matches('/') {
                     // Check if this is a comment or integer division operator
                     // Comments have whitespace or newline after //
                     // Integer division has a non-whitespace character
-                    let next_char = self.peek();
-                    if next_char == ' '
-                        || next_char == '\t'
-                        || next_char == '\n'
-                        || next_char == '\r'
-                        || self.is_at_end()
-                    {
-                        // This is a comment
-                        while self.peek_next() != '\n' && !self.is_at_end() {
+                    let is_comment_end = |c: char| matches!(c, ' ' | '\t' | '\n' | '\r' | '\0');
+                    if self.peek() == '/' && is_comment_end(self.peek_next()) {
+                        self.make_doc_comment()
+                    } else if is_comment_end(self.peek()) {
+                        // The line break ending the comment is scanned as usual
+                        while self.peek() != '\n' && !self.is_at_end() {
                             self.advance();
                         }
-                        if !self.is_at_end() {
-                            self.advance();
-                        }
-                        self.line += 1;
-                        self.column = 1;
                         self.scan_token()
                     } else {
                         // This is the integer division operator
                         self.make_token(TokenType::SlashSlash)
                     }
+                } else if self.matches('=') {
+                    self.make_token(TokenType::SlashEqual)
                 } else {
                     self.make_token(TokenType::Slash)
                 }
@@ -170,6 +189,7 @@ impl Scanner {
                 self.column = 1;
                 new_line
             }
+            '"' if self.peek() == '"' && self.peek_next() == '"' => self.make_raw_string(),
             '"' => self.make_string(),
             _ => self.make_error_token("Unexpected character"),
         }
@@ -178,6 +198,8 @@ impl Scanner {
     fn make_string(&mut self) -> Token {
         let mut placeholders: Vec<(usize, usize)> = Vec::new();
         let mut placeholder_start = None;
+        // The first invalid escape and where it is; reported once the whole string is read
+        let mut invalid_escape = None;
         loop {
             if self.is_at_end() {
                 return self.make_error_token("Unterminated string");
@@ -185,6 +207,15 @@ impl Scanner {
             if self.peek() == '"' {
                 break;
             }
+            if self.peek() == '\\' {
+                let escape_start = self.current;
+                self.advance();
+                if let Err(message) = self.scan_escape() {
+                    let column = self.column_at(escape_start);
+                    invalid_escape.get_or_insert((message, self.line, column));
+                }
+                continue;
+            }
             if self.peek() == '$' && self.peek_next() == '{' {
                 placeholder_start = Some(self.current);
             }
@@ -199,12 +230,123 @@ impl Scanner {
             self.advance();
         }
         self.advance();
+        if let Some((message, line, column)) = invalid_escape {
+            return self.make_error_token_at(&message, line, column);
+        }
         if !placeholders.is_empty() {
             return self.make_token(TokenType::InterpolatedString);
         }
         self.make_token(TokenType::String)
     }
 
+    /// Checks the escape sequence following a backslash in a string and consumes it:
+    /// `\n`, `\t`, `\r`, `\\`, `\"`, `\0`, `\$` or `\u{...}` with 1 to 6 hex digits
+    fn scan_escape(&mut self) -> Result<(), String> {
+        match self.peek() {
+            'n' | 't' | 'r' | '\\' | '"' | '0' | '$' => {
+                self.advance();
+                Ok(())
+            }
+            'u' => {
+                self.advance();
+                let malformed =
+                    || "Invalid unicode escape, expecting '\\u{...}' with 1 to 6 hex digits";
+                if self.peek() != '{' {
+                    return Err(malformed().to_string());
+                }
+                self.advance();
+                let digits_start = self.current;
+                while Scanner::is_hex_digit(self.peek()) {
+                    self.advance();
+                }
+                let digits = String::from_iter(&self.source[digits_start..self.current]);
+                if self.peek() != '}' || digits.is_empty() || digits.len() > 6 {
+                    return Err(malformed().to_string());
+                }
+                self.advance();
+                u32::from_str_radix(&digits, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .map(|_| ())
+                    .ok_or_else(|| format!("Invalid unicode code point '{}' in escape", digits))
+            }
+            '\n' | '\0' => Err("Invalid escape sequence at end of line".to_string()),
+            c => Err(format!("Invalid escape sequence '\\{}'", c)),
+        }
+    }
+
+    /// Column of a position in the current token, which may span lines
+    fn column_at(&self, position: usize) -> u32 {
+        match self.source[self.start..position]
+            .iter()
+            .rposition(|c| *c == '\n')
+        {
+            Some(newline) => (position - self.start - newline) as u32,
+            None => self.column + (position - self.start) as u32,
+        }
+    }
+
+    /// Skips a `/* ... */` comment, which may be nested and span lines
+    fn skip_block_comment(&mut self) -> Token {
+        let (line, column) = (self.line, self.column);
+        self.column += 2;
+        let mut depth = 1;
+        while depth > 0 {
+            if self.is_at_end() {
+                return self.make_error_token_at("Unterminated block comment", line, column);
+            }
+            let c = self.advance();
+            self.column += 1;
+            if c == '\n' {
+                self.line += 1;
+                self.column = 1;
+            } else if c == '/' && self.peek() == '*' {
+                self.advance();
+                self.column += 1;
+                depth += 1;
+            } else if c == '*' && self.peek() == '/' {
+                self.advance();
+                self.column += 1;
+                depth -= 1;
+            }
+        }
+        self.scan_token()
+    }
+
+    /// A `///` comment up to the end of the line. Doesn't count as the token before the line
+    /// break, so a doc comment on a line of its own doesn't add an empty line.
+    fn make_doc_comment(&mut self) -> Token {
+        while self.peek() != '\n' && !self.is_at_end() {
+            self.advance();
+        }
+        let previous_token_type = self.previous_token_type.clone();
+        let token = self.make_token(TokenType::DocComment);
+        self.previous_token_type = previous_token_type;
+        token
+    }
+
+    /// `"""..."""`, taken verbatim up to the next `"""`
+    fn make_raw_string(&mut self) -> Token {
+        self.advance();
+        self.advance();
+        loop {
+            if self.is_at_end() {
+                return self.make_error_token("Unterminated raw string");
+            }
+            if self.peek() == '"' && self.peek_next() == '"' && self.peek_after_next() == '"' {
+                break;
+            }
+            if self.peek() == '\n' {
+                self.line += 1;
+            }
+            self.advance();
+        }
+        self.advance();
+        self.advance();
+        self.advance();
+        self.make_token(TokenType::RawString)
+    }
+
     fn make_identifier(&mut self) -> Token {
         loop {
             if !Scanner::is_alpha(self.peek()) && !Scanner::is_digit(self.peek()) {
@@ -250,6 +392,8 @@ impl Scanner {
                         .make_error_token("Invalid underscore placement in hexadecimal literal");
                 }
                 self.advance();
+            } else if Scanner::is_alpha(c) {
+                return self.make_error_token("Invalid digit in hexadecimal literal");
             } else {
                 break;
             }
@@ -265,6 +409,10 @@ impl Scanner {
     fn make_binary_number(&mut self) -> Token {
         self.advance(); // consume 'b' or 'B'
 
+        if ('2'..='9').contains(&self.peek()) {
+            return self.make_error_token("Invalid digit in binary literal (only 0 and 1 allowed)");
+        }
+
         if !Scanner::is_binary_digit(self.peek()) {
             return self.make_error_token("Binary literal requires at least one digit");
         }
@@ -280,7 +428,7 @@ impl Scanner {
                     return self.make_error_token("Invalid underscore placement in binary literal");
                 }
                 self.advance();
-            } else if ('2'..='9').contains(&c) {
+            } else if ('2'..='9').contains(&c) || Scanner::is_alpha(c) {
                 return self
                     .make_error_token("Invalid digit in binary literal (only 0 and 1 allowed)");
             } else {
@@ -319,7 +467,7 @@ impl Scanner {
                     return self.make_error_token("Invalid underscore placement in octal literal");
                 }
                 self.advance();
-            } else if c == '8' || c == '9' {
+            } else if c == '8' || c == '9' || Scanner::is_alpha(c) {
                 return self.make_error_token("Invalid digit in octal literal (only 0-7 allowed)");
             } else {
                 break;
@@ -423,6 +571,13 @@ impl Scanner {
         self.source[self.current + 1]
     }
 
+    fn peek_after_next(&self) -> char {
+        if self.current + 2 >= self.source.len() {
+            return '\0';
+        }
+        self.source[self.current + 2]
+    }
+
     fn skip_whitespace(&mut self) {
         loop {
             let c = self.peek();
@@ -465,18 +620,41 @@ impl Scanner {
         match chr {
             'a' => self.check_keyword(1, 2, "nd", TokenType::And),
             'b' => self.check_keyword(1, 4, "reak", TokenType::Break),
-            'c' => self.check_keyword(1, 7, "ontinue", TokenType::Continue),
-            'e' => self.check_keyword(1, 3, "lse", TokenType::Else),
+            'c' => {
+                if self.current - self.start > 1 {
+                    return match self.source[self.start + 1] {
+                        'a' => self.check_keyword(2, 3, "tch", TokenType::Catch),
+                        'o' => self.check_keyword(2, 6, "ntinue", TokenType::Continue),
+                        _ => TokenType::Identifier,
+                    };
+                }
+                TokenType::Identifier
+            }
+            'd' => self.check_keyword(1, 1, "o", TokenType::Do),
+            'e' => {
+                if self.current - self.start > 1 {
+                    return match self.source[self.start + 1] {
+                        'l' => self.check_keyword(2, 2, "se", TokenType::Else),
+                        'n' => self.check_keyword(2, 2, "um", TokenType::Enum),
+                        'x' => self.check_keyword(2, 4, "port", TokenType::Export),
+                        _ => TokenType::Identifier,
+                    };
+                }
+                TokenType::Identifier
+            }
             'i' => {
                 if self.current - self.start > 1 {
                     return match self.source[self.start + 1] {
                         'f' => self.check_keyword(2, 0, "", TokenType::If),
+                        'm' => self.check_keyword(2, 4, "port", TokenType::Import),
                         'n' => self.check_keyword(2, 0, "", TokenType::In),
+                        's' => self.check_keyword(2, 0, "", TokenType::Is),
                         _ => TokenType::Identifier,
                     };
                 }
                 TokenType::Identifier
             }
+            'm' => self.check_keyword(1, 4, "atch", TokenType::Match),
             'n' => self.check_keyword(1, 2, "il", TokenType::Nil),
             'o' => self.check_keyword(1, 1, "r", TokenType::Or),
             'r' => self.check_keyword(1, 5, "eturn", TokenType::Return),
@@ -500,11 +678,21 @@ impl Scanner {
                 }
                 TokenType::Identifier
             }
-            'w' => self.check_keyword(1, 4, "hile", TokenType::While),
+            'w' => {
+                if self.current - self.start > 1 {
+                    return match self.source[self.start + 1] {
+                        'h' => self.check_keyword(2, 3, "ile", TokenType::While),
+                        'i' => self.check_keyword(2, 2, "th", TokenType::With),
+                        _ => TokenType::Identifier,
+                    };
+                }
+                TokenType::Identifier
+            }
             'f' => {
                 if self.current - self.start > 1 {
                     return match self.source[self.start + 1] {
                         'a' => self.check_keyword(2, 3, "lse", TokenType::False),
+                        'i' => self.check_keyword(2, 5, "nally", TokenType::Finally),
                         'o' => self.check_keyword(2, 1, "r", TokenType::For),
                         'n' => TokenType::Fn,
                         _ => TokenType::Identifier,
@@ -515,8 +703,16 @@ impl Scanner {
             't' => {
                 if self.current - self.start > 1 {
                     return match self.source[self.start + 1] {
-                        'h' => self.check_keyword(2, 2, "is", TokenType::This),
-                        'r' => self.check_keyword(2, 2, "ue", TokenType::True),
+                        'h' => match self.check_keyword(2, 2, "is", TokenType::This) {
+                            TokenType::Identifier => {
+                                self.check_keyword(2, 3, "row", TokenType::Throw)
+                            }
+                            keyword => keyword,
+                        },
+                        'r' => match self.check_keyword(2, 2, "ue", TokenType::True) {
+                            TokenType::Identifier => self.check_keyword(2, 1, "y", TokenType::Try),
+                            keyword => keyword,
+                        },
                         _ => TokenType::Identifier,
                     };
                 }
@@ -537,6 +733,17 @@ impl Scanner {
         )
     }
 
+    fn make_error_token_at(&mut self, message: &str, line: u32, column: u32) -> Token {
+        self.previous_token_type = TokenType::Error;
+        Token::new(
+            TokenType::Error,
+            String::from(message),
+            line,
+            column,
+            self.offset,
+        )
+    }
+
     fn make_token(&mut self, token_type: TokenType) -> Token {
         self.previous_token_type = token_type.clone();
         let token_str = String::from_iter(&self.source[self.start..self.current]);
@@ -555,11 +762,4 @@ impl Scanner {
             self.offset,
         )
     }
-
-    fn previous(&self) -> char {
-        if self.current < 2 {
-            return '\n';
-        }
-        self.source[self.current - 2]
-    }
 }
@@ -2,19 +2,29 @@ use crate::common::errors::{
     CompilationError, CompilationErrorKind, CompilationPhase, CompilationResult,
 };
 
+use crate::common::constants::VARIADIC_ARITY;
 use crate::common::SourceLocation;
 /// Semantic analyzer for the multi-pass compiler
 /// Performs semantic analysis on the AST, building symbol tables and validating program semantics
-use crate::compiler::ast::{Expr, Stmt};
+use crate::compiler::ast::{
+    max_arity, required_arity, CatchClause, DestructurePattern, Expr, ImportItems, Method,
+    Parameter, Stmt, StructField,
+};
+use crate::compiler::module_resolver::Module;
 use crate::compiler::symbol_table::{Symbol, SymbolKind, SymbolTable};
 use std::collections::HashMap;
+use std::rc::Rc;
 
 /// Semantic analyzer that validates the AST and builds symbol tables
 pub struct SemanticAnalyzer {
     symbol_table: SymbolTable,
     errors: Vec<CompilationError>,
+    warnings: Vec<CompilationError>,
     type_env: HashMap<String, String>,
-    loop_depth: u32,
+    /// Labels of the loops around the current statement, innermost last
+    loop_labels: Vec<Option<String>>,
+    /// Modules loaded for the file's imports, by the path written in the import
+    modules: HashMap<String, Rc<Module>>,
 }
 
 impl SemanticAnalyzer {
@@ -32,14 +42,80 @@ impl SemanticAnalyzer {
                 line: 0,
                 column: 0,
             },
+            is_read: false,
         };
         let _ = symbol_table.define(math_symbol); // Ignore error since this is initial setup
 
+        // Pre-define Regex as a built-in global constant for the pattern functions
+        let regex_symbol = Symbol {
+            name: "Regex".to_string(),
+            kind: SymbolKind::Value,
+            is_mutable: false,
+            scope_depth: 0,
+            location: SourceLocation {
+                offset: 0,
+                line: 0,
+                column: 0,
+            },
+            is_read: false,
+        };
+        let _ = symbol_table.define(regex_symbol); // Ignore error since this is initial setup
+
+        // Pre-define System as a built-in global constant for process/environment access
+        let system_symbol = Symbol {
+            name: "System".to_string(),
+            kind: SymbolKind::Value,
+            is_mutable: false,
+            scope_depth: 0,
+            location: SourceLocation {
+                offset: 0,
+                line: 0,
+                column: 0,
+            },
+            is_read: false,
+        };
+        let _ = symbol_table.define(system_symbol); // Ignore error since this is initial setup
+
+        // Pre-define Time as a built-in global constant for the clock functions
+        let time_symbol = Symbol {
+            name: "Time".to_string(),
+            kind: SymbolKind::Value,
+            is_mutable: false,
+            scope_depth: 0,
+            location: SourceLocation {
+                offset: 0,
+                line: 0,
+                column: 0,
+            },
+            is_read: false,
+        };
+        let _ = symbol_table.define(time_symbol); // Ignore error since this is initial setup
+
+        // Pre-define Array, Map and String as built-in global constants for their static constructors
+        for namespace in ["Array", "Map", "String"] {
+            let namespace_symbol = Symbol {
+                name: namespace.to_string(),
+                kind: SymbolKind::Value,
+                is_mutable: false,
+                scope_depth: 0,
+                location: SourceLocation {
+                    offset: 0,
+                    line: 0,
+                    column: 0,
+                },
+                is_read: false,
+            };
+            let _ = symbol_table.define(namespace_symbol); // Ignore error since this is initial setup
+        }
+
         // Pre-define File as a built-in global function
         // This corresponds to the File constructor that will be available at runtime
         let file_symbol = Symbol {
             name: "File".to_string(),
-            kind: SymbolKind::Function { arity: 1 },
+            kind: SymbolKind::Function {
+                arity: 1,
+                min_arity: 1,
+            },
             is_mutable: false,
             scope_depth: 0,
             location: SourceLocation {
@@ -47,6 +123,7 @@ impl SemanticAnalyzer {
                 line: 0,
                 column: 0,
             },
+            is_read: false,
         };
         let _ = symbol_table.define(file_symbol); // Ignore error since this is initial setup
 
@@ -62,21 +139,46 @@ impl SemanticAnalyzer {
                 line: 0,
                 column: 0,
             },
+            is_read: false,
         };
         let _ = symbol_table.define(args_symbol); // Ignore error since this is initial setup
 
+        // None is the absent option, the counterpart of the native Some(value)
+        let none_symbol = Symbol {
+            name: "None".to_string(),
+            kind: SymbolKind::Value,
+            is_mutable: false,
+            scope_depth: 0,
+            location: SourceLocation {
+                offset: 0,
+                line: 0,
+                column: 0,
+            },
+            is_read: false,
+        };
+        let _ = symbol_table.define(none_symbol);
+
         let mut type_env = HashMap::new();
         // Track that args is an Array type for method validation
         type_env.insert("args".to_string(), "Array".to_string());
+        type_env.insert("None".to_string(), "Option".to_string());
 
         SemanticAnalyzer {
             symbol_table,
             errors: Vec::new(),
+            warnings: Vec::new(),
             type_env,
-            loop_depth: 0,
+            loop_labels: Vec::new(),
+            modules: HashMap::new(),
         }
     }
 
+    /// Makes the exports of the modules loaded for the file's imports known
+    pub(crate) fn with_modules(mut self, modules: HashMap<String, Rc<Module>>) -> Self {
+        self.modules = modules;
+        self
+    }
+
     /// Analyze the AST and return the symbol table if successful
     pub fn analyze(&mut self, statements: &[Stmt]) -> CompilationResult<SymbolTable> {
         // First: collect all top-level declarations
@@ -92,23 +194,30 @@ impl SemanticAnalyzer {
         }
     }
 
+    /// Warnings found by the last `analyze`; they never make it fail
+    pub fn warnings(&self) -> &[CompilationError] {
+        &self.warnings
+    }
+
     // ===== First: Declaration Collection =====
     // Only collect function and struct declarations
     // Variables (val/var) are defined during resolution
 
     fn collect_declarations(&mut self, statements: &[Stmt]) {
         for stmt in statements {
-            match stmt {
+            match stmt.declaration() {
                 Stmt::Fn {
                     name,
                     params,
                     location,
                     ..
                 } => {
-                    let arity = params.len() as u8;
                     self.define_symbol(
                         name.clone(),
-                        SymbolKind::Function { arity },
+                        SymbolKind::Function {
+                            arity: max_arity(params),
+                            min_arity: required_arity(params),
+                        },
                         false,
                         *location,
                     );
@@ -117,11 +226,28 @@ impl SemanticAnalyzer {
                     name,
                     fields,
                     location,
+                    ..
                 } => {
                     self.define_symbol(
                         name.clone(),
                         SymbolKind::Struct {
-                            fields: fields.clone(),
+                            fields: fields.iter().map(|f| f.name.clone()).collect(),
+                            required: fields.iter().filter(|f| f.default.is_none()).count(),
+                        },
+                        false,
+                        *location,
+                    );
+                }
+                Stmt::Enum {
+                    name,
+                    variants,
+                    location,
+                    ..
+                } => {
+                    self.define_symbol(
+                        name.clone(),
+                        SymbolKind::Enum {
+                            variants: variants.clone(),
                         },
                         false,
                         *location,
@@ -164,6 +290,7 @@ impl SemanticAnalyzer {
             Expr::MapLiteral { .. } => Some("Map".to_string()),
             Expr::SetLiteral { .. } => Some("Set".to_string()),
             Expr::Nil { .. } => Some("Nil".to_string()),
+            Expr::Lambda { .. } => Some("Function".to_string()),
 
             // Variable lookup
             Expr::Variable { name, .. } => self.type_env.get(name).cloned(),
@@ -179,6 +306,18 @@ impl SemanticAnalyzer {
             } => {
                 // Check if this is a method call: Call { callee: GetField { object, field }, arguments }
                 if let Expr::GetField { object, field, .. } = callee.as_ref() {
+                    // Static constructors have a fixed return type
+                    if let Expr::Variable { name, .. } = object.as_ref() {
+                        match (name.as_str(), field.as_str()) {
+                            ("Array", "from") => return Some("Array".to_string()),
+                            ("Array", "fill") => return Some("Array".to_string()),
+                            ("Array", "range") => return Some("Array".to_string()),
+                            ("Map", "from") => return Some("Map".to_string()),
+                            ("String", "fromBytes") => return Some("String".to_string()),
+                            _ => {}
+                        }
+                    }
+
                     // This is a method call obj.method(args)
                     let object_type = self.infer_expr_type(object)?;
                     match (object_type.as_str(), field.as_str()) {
@@ -191,16 +330,36 @@ impl SemanticAnalyzer {
                         ("String", "toLowerCase") => Some("String".to_string()),
                         ("String", "trim") => Some("String".to_string()),
                         ("String", "toString") => Some("String".to_string()),
+                        ("String", "bytes") => Some("Array".to_string()),
                         ("String", "toInt") => Some("Number".to_string()),
                         ("String", "toFloat") => Some("Number".to_string()),
                         ("Number", "toString") => Some("String".to_string()),
+                        ("Array", "push") => Some("Array".to_string()),
+                        ("Array", "unshift") => Some("Array".to_string()),
+                        ("Array", "sort") => Some("Array".to_string()),
+                        ("Array", "reverse") => Some("Array".to_string()),
+                        ("Array", "fill") => Some("Array".to_string()),
                         ("Array", "join") => Some("String".to_string()),
                         ("Array", "map") => Some("Array".to_string()),
                         ("Array", "filter") => Some("Array".to_string()),
+                        ("Array", "enumerate") => Some("Array".to_string()),
+                        ("Array", "findIndex") => Some("Number".to_string()),
+                        ("Array", "every") => Some("Boolean".to_string()),
+                        ("Array", "some") => Some("Boolean".to_string()),
+                        _ => None,
+                    }
+                } else if let Expr::Variable { name, .. } = callee.as_ref() {
+                    // Conversion builtins have a fixed return type
+                    match name.as_str() {
+                        "str" => Some("String".to_string()),
+                        "num" => Some("Number".to_string()),
+                        "type" => Some("String".to_string()),
+                        "len" => Some("Number".to_string()),
+                        "formatNumber" => Some("String".to_string()),
+                        // Regular function call - can't easily infer return type without more info
                         _ => None,
                     }
                 } else {
-                    // Regular function call - can't easily infer return type without more info
                     None
                 }
             }
@@ -215,22 +374,34 @@ impl SemanticAnalyzer {
                 use crate::compiler::ast::BinaryOp;
                 match operator {
                     BinaryOp::Add => {
-                        // Add can be either string concatenation or numeric addition
+                        // Add can be string or array concatenation, or numeric addition
                         let left_type = self.infer_expr_type(left);
                         let right_type = self.infer_expr_type(right);
 
-                        // If both operands are strings, result is string
-                        if let (Some(lt), Some(rt)) = (left_type, right_type) {
-                            if lt == "String" && rt == "String" {
-                                return Some("String".to_string());
+                        // Only operands of one known type say what the sum is: an unknown
+                        // operand may be a string or array as well as a number
+                        match (left_type, right_type) {
+                            (Some(lt), Some(rt))
+                                if lt == rt
+                                    && (lt == "Number" || lt == "String" || lt == "Array") =>
+                            {
+                                Some(lt)
+                            }
+                            _ => None,
+                        }
+                    }
+                    BinaryOp::Multiply => {
+                        // A string or array on either side repeats it
+                        let left_type = self.infer_expr_type(left);
+                        let right_type = self.infer_expr_type(right);
+                        for operand_type in [left_type, right_type].into_iter().flatten() {
+                            if operand_type == "String" || operand_type == "Array" {
+                                return Some(operand_type);
                             }
                         }
-
-                        // Otherwise, assume numeric addition
                         Some("Number".to_string())
                     }
                     BinaryOp::Subtract
-                    | BinaryOp::Multiply
                     | BinaryOp::Divide
                     | BinaryOp::FloorDivide
                     | BinaryOp::Modulo
@@ -240,6 +411,7 @@ impl SemanticAnalyzer {
                     }
                     BinaryOp::Equal
                     | BinaryOp::NotEqual
+                    | BinaryOp::Is
                     | BinaryOp::Greater
                     | BinaryOp::GreaterEqual
                     | BinaryOp::Less
@@ -257,9 +429,17 @@ impl SemanticAnalyzer {
                         // Bitwise operations return Number
                         Some("Number".to_string())
                     }
+                    BinaryOp::NilCoalesce => {
+                        // Either side may be the result, so only infer when they agree
+                        let left_type = self.infer_expr_type(left)?;
+                        let right_type = self.infer_expr_type(right)?;
+                        (left_type == right_type).then_some(left_type)
+                    }
                 }
             }
 
+            Expr::ChainedComparison { .. } => Some("Boolean".to_string()),
+
             // Unary operations
             Expr::Unary { operator, .. } => {
                 use crate::compiler::ast::UnaryOp;
@@ -294,6 +474,27 @@ impl SemanticAnalyzer {
 
     // ===== Then: Reference Resolution =====
 
+    /// Leaves the current scope, warning about the `val`s and `var`s in it that were never read.
+    /// Globals aren't checked since other modules or the REPL may use them, and names starting
+    /// with '_' are unused on purpose.
+    fn exit_scope(&mut self) {
+        for symbol in self.symbol_table.unread_symbols() {
+            let is_local_value = matches!(symbol.kind, SymbolKind::Value | SymbolKind::Variable);
+            if is_local_value && !symbol.name.starts_with('_') {
+                self.warnings.push(CompilationError::warning(
+                    CompilationPhase::Semantic,
+                    CompilationErrorKind::UnusedVariable,
+                    format!(
+                        "Variable '{}' is never read. Prefix it with '_' if that is intended.",
+                        symbol.name
+                    ),
+                    symbol.location,
+                ));
+            }
+        }
+        self.symbol_table.exit_scope();
+    }
+
     /// Helper method to check if a variable exists and is mutable
     fn check_variable_mutability(&mut self, name: &str, location: SourceLocation) {
         match self.symbol_table.resolve(name) {
@@ -340,6 +541,14 @@ impl SemanticAnalyzer {
             } => {
                 self.resolve_var_declaration(name, initializer.as_ref(), *location);
             }
+            Stmt::Destructure {
+                pattern,
+                initializer,
+                mutable,
+                location,
+            } => {
+                self.resolve_destructuring_declaration(pattern, initializer, *mutable, *location);
+            }
             Stmt::Fn {
                 params,
                 body,
@@ -348,8 +557,53 @@ impl SemanticAnalyzer {
             } => {
                 self.resolve_function_declaration(params, body, *location);
             }
-            Stmt::Struct { .. } => {
-                // Struct declarations are already collected, nothing to resolve
+            Stmt::Struct {
+                name,
+                fields,
+                methods,
+                ..
+            } => {
+                // The struct itself was already collected; only its body needs checking
+                self.check_field_defaults(fields);
+                self.resolve_struct_methods(name, fields, methods);
+            }
+            Stmt::Enum {
+                name,
+                variants,
+                location,
+                ..
+            } => {
+                for (index, variant) in variants.iter().enumerate() {
+                    if variants[..index].contains(variant) {
+                        self.errors.push(CompilationError::new(
+                            CompilationPhase::Semantic,
+                            CompilationErrorKind::DuplicateSymbol,
+                            format!("Variant '{}' is declared twice in enum '{}'", variant, name),
+                            *location,
+                        ));
+                    }
+                }
+            }
+            Stmt::Export {
+                declaration,
+                location,
+            } => {
+                if self.symbol_table.current_depth() > 0 {
+                    self.errors.push(CompilationError::new(
+                        CompilationPhase::Semantic,
+                        CompilationErrorKind::UnexpectedToken,
+                        "Only declarations at the top level of a file can be exported".to_string(),
+                        *location,
+                    ));
+                }
+                self.resolve_stmt(declaration);
+            }
+            Stmt::Import {
+                path,
+                items,
+                location,
+            } => {
+                self.resolve_import(path, items, *location);
             }
             Stmt::Expression { expr, .. } => {
                 self.resolve_expr(expr);
@@ -370,26 +624,61 @@ impl SemanticAnalyzer {
                 );
             }
             Stmt::While {
-                condition, body, ..
+                condition,
+                body,
+                label,
+                location,
             } => {
-                self.resolve_while_statement(condition, body);
+                self.resolve_while_statement(condition, body, label, *location);
+            }
+            Stmt::DoWhile {
+                body,
+                condition,
+                label,
+                location,
+            } => {
+                self.resolve_do_while_statement(body, condition, label, *location);
+            }
+            Stmt::Match {
+                scrutinee,
+                arms,
+                else_branch,
+                ..
+            } => {
+                self.resolve_expr(scrutinee);
+                for arm in arms {
+                    self.resolve_expr(&arm.pattern);
+                    self.resolve_stmt(&arm.body);
+                }
+                if let Some(else_stmt) = else_branch {
+                    self.resolve_stmt(else_stmt);
+                }
             }
-            Stmt::Return { value, .. } => {
+            Stmt::Return { value, .. } | Stmt::Throw { value, .. } => {
                 self.resolve_expr(value);
             }
-            Stmt::Break { location } => {
-                self.validate_break_statement(*location);
+            Stmt::Try {
+                body,
+                catch,
+                finally,
+                location,
+            } => {
+                self.resolve_try_statement(body, catch, finally, *location);
             }
-            Stmt::Continue { location } => {
-                self.validate_continue_statement(*location);
+            Stmt::Break { label, location } => {
+                self.validate_loop_exit("break", label, *location);
+            }
+            Stmt::Continue { label, location } => {
+                self.validate_loop_exit("continue", label, *location);
             }
             Stmt::ForIn {
                 variable,
                 collection,
                 body,
+                label,
                 location,
             } => {
-                self.resolve_for_in_statement(variable, collection, body, *location);
+                self.resolve_for_in_statement(variable, collection, body, label, *location);
             }
         }
     }
@@ -420,6 +709,11 @@ impl SemanticAnalyzer {
             } => {
                 self.resolve_binary_expr(left, right, operator, *location);
             }
+            Expr::ChainedComparison { operands, .. } => {
+                for operand in operands {
+                    self.resolve_expr(operand);
+                }
+            }
             Expr::Unary { operand, .. } => {
                 self.resolve_expr(operand);
             }
@@ -430,6 +724,13 @@ impl SemanticAnalyzer {
             } => {
                 self.resolve_call_expr(callee, arguments, *location);
             }
+            Expr::NamedCall {
+                callee,
+                arguments,
+                location,
+            } => {
+                self.resolve_named_call_expr(callee, arguments, *location);
+            }
             Expr::GetField {
                 object,
                 field,
@@ -442,6 +743,7 @@ impl SemanticAnalyzer {
                 field,
                 value,
                 location,
+                ..
             } => {
                 self.resolve_set_field(object, field, value, *location);
             }
@@ -457,9 +759,26 @@ impl SemanticAnalyzer {
             Expr::SetLiteral { elements, .. } => {
                 self.resolve_set_literal(elements);
             }
-            Expr::Index { object, index, .. } => {
+            Expr::Index { object, index, .. } | Expr::OptionalIndex { object, index, .. } => {
                 self.resolve_index_expr(object, index);
             }
+            Expr::OptionalGetField {
+                object,
+                field,
+                location,
+            } => {
+                self.resolve_get_field(object, field, *location);
+            }
+            Expr::Lambda {
+                params,
+                body,
+                location,
+            } => {
+                // break/continue inside the lambda can't target loops around it
+                let enclosing_loops = std::mem::take(&mut self.loop_labels);
+                self.resolve_function_declaration(params, body, *location);
+                self.loop_labels = enclosing_loops;
+            }
             Expr::IndexAssign {
                 object,
                 index,
@@ -506,8 +825,16 @@ impl SemanticAnalyzer {
                 self.type_env.insert(name.to_string(), inferred_type);
             }
         }
-        // Then define the variable in current scope
-        self.define_symbol(name.to_string(), SymbolKind::Value, false, location);
+        // Then define the variable in current scope; a val bound to a lambda is
+        // checked like a named function
+        let kind = match initializer {
+            Some(Expr::Lambda { params, .. }) => SymbolKind::Function {
+                arity: max_arity(params),
+                min_arity: required_arity(params),
+            },
+            _ => SymbolKind::Value,
+        };
+        self.define_symbol(name.to_string(), kind, false, location);
     }
 
     fn resolve_var_declaration(
@@ -528,19 +855,56 @@ impl SemanticAnalyzer {
         self.define_symbol(name.to_string(), SymbolKind::Variable, true, location);
     }
 
+    fn resolve_destructuring_declaration(
+        &mut self,
+        pattern: &DestructurePattern,
+        initializer: &Expr,
+        mutable: bool,
+        location: SourceLocation,
+    ) {
+        self.resolve_expr(initializer);
+
+        let (names, rest) = match pattern {
+            DestructurePattern::Array { names, rest } => (names, rest.as_ref()),
+            DestructurePattern::Struct { fields } => (fields, None),
+        };
+        let kind = if mutable {
+            SymbolKind::Variable
+        } else {
+            SymbolKind::Value
+        };
+        for name in names {
+            // Element and field types aren't tracked, so forget any earlier binding's type
+            self.type_env.remove(name);
+            self.define_symbol(name.clone(), kind.clone(), mutable, location);
+        }
+        if let Some(rest) = rest {
+            self.type_env.insert(rest.clone(), "Array".to_string());
+            self.define_symbol(rest.clone(), kind, mutable, location);
+        }
+    }
+
     fn resolve_function_declaration(
         &mut self,
-        params: &[String],
+        params: &[Parameter],
         body: &[Stmt],
         location: SourceLocation,
     ) {
         // Enter function scope
         self.symbol_table.enter_scope();
 
-        // Define parameters in function scope
+        // Define parameters in function scope; a default can refer to earlier parameters
         for param in params {
+            if let Some(default) = &param.default {
+                self.resolve_expr(default);
+            }
             let param_location = location; // Use function location for params
-            self.define_symbol(param.clone(), SymbolKind::Parameter, false, param_location);
+            self.define_symbol(
+                param.name.clone(),
+                SymbolKind::Parameter,
+                false,
+                param_location,
+            );
         }
 
         // Resolve function body
@@ -549,7 +913,58 @@ impl SemanticAnalyzer {
         }
 
         // Exit function scope
-        self.symbol_table.exit_scope();
+        self.exit_scope();
+    }
+
+    /// Defaults are evaluated once, when the struct is compiled, so they must be literals
+    fn check_field_defaults(&mut self, fields: &[StructField]) {
+        for field in fields {
+            if let Some(default) = &field.default {
+                if default.literal_value().is_none() {
+                    self.errors.push(CompilationError::new(
+                        CompilationPhase::Semantic,
+                        CompilationErrorKind::UnexpectedToken,
+                        format!("Default value of field '{}' must be a literal", field.name),
+                        *default.location(),
+                    ));
+                }
+            }
+        }
+    }
+
+    fn resolve_struct_methods(
+        &mut self,
+        struct_name: &str,
+        fields: &[StructField],
+        methods: &[Method],
+    ) {
+        for (index, method) in methods.iter().enumerate() {
+            let clashes_with_field = fields.iter().any(|f| f.name == method.name);
+            let is_duplicate = methods[..index].iter().any(|m| m.name == method.name);
+            if clashes_with_field || is_duplicate {
+                self.errors.push(CompilationError::new(
+                    CompilationPhase::Semantic,
+                    CompilationErrorKind::DuplicateSymbol,
+                    format!(
+                        "Struct '{}' already has a {} named '{}'",
+                        struct_name,
+                        if clashes_with_field {
+                            "field"
+                        } else {
+                            "method"
+                        },
+                        method.name
+                    ),
+                    method.location,
+                ));
+            }
+
+            // The receiver is passed as a hidden first parameter
+            let params: Vec<Parameter> = std::iter::once(Parameter::new("this"))
+                .chain(method.params.iter().cloned())
+                .collect();
+            self.resolve_function_declaration(&params, &method.body, method.location);
+        }
     }
 
     fn resolve_block_statement(&mut self, statements: &[Stmt]) {
@@ -557,7 +972,7 @@ impl SemanticAnalyzer {
         for stmt in statements {
             self.resolve_stmt(stmt);
         }
-        self.symbol_table.exit_scope();
+        self.exit_scope();
     }
 
     fn resolve_if_statement(
@@ -573,11 +988,43 @@ impl SemanticAnalyzer {
         }
     }
 
-    fn resolve_while_statement(&mut self, condition: &Expr, body: &Stmt) {
+    fn resolve_while_statement(
+        &mut self,
+        condition: &Expr,
+        body: &Stmt,
+        label: &Option<String>,
+        location: SourceLocation,
+    ) {
         self.resolve_expr(condition);
-        self.loop_depth += 1;
+        self.resolve_loop_body(body, label, location);
+    }
+
+    fn resolve_do_while_statement(
+        &mut self,
+        body: &Stmt,
+        condition: &Expr,
+        label: &Option<String>,
+        location: SourceLocation,
+    ) {
+        self.resolve_loop_body(body, label, location);
+        self.resolve_expr(condition);
+    }
+
+    /// Resolves a loop body with the loop's label in scope for break/continue
+    fn resolve_loop_body(&mut self, body: &Stmt, label: &Option<String>, location: SourceLocation) {
+        if let Some(name) = label {
+            if self.loop_labels.contains(label) {
+                self.errors.push(CompilationError::new(
+                    CompilationPhase::Semantic,
+                    CompilationErrorKind::Other,
+                    format!("Label '{}' is already used by an enclosing loop", name),
+                    location,
+                ));
+            }
+        }
+        self.loop_labels.push(label.clone());
         self.resolve_stmt(body);
-        self.loop_depth -= 1;
+        self.loop_labels.pop();
     }
 
     fn resolve_for_in_statement(
@@ -585,6 +1032,7 @@ impl SemanticAnalyzer {
         variable: &str,
         collection: &Expr,
         body: &Stmt,
+        label: &Option<String>,
         location: SourceLocation,
     ) {
         // Resolve the collection expression
@@ -593,44 +1041,68 @@ impl SemanticAnalyzer {
         // Enter a new scope for the loop
         self.symbol_table.enter_scope();
 
-        // Define the loop variable as immutable (always val)
+        // Define the loop variable as immutable (always val); leaving it unused is
+        // how a loop runs a number of times, so it doesn't count as unread
         self.define_symbol(variable.to_string(), SymbolKind::Value, false, location);
-
-        // Track loop depth for break/continue validation
-        self.loop_depth += 1;
+        self.symbol_table.mark_read(variable);
 
         // Resolve the loop body
-        self.resolve_stmt(body);
-
-        // Exit loop depth tracking
-        self.loop_depth -= 1;
+        self.resolve_loop_body(body, label, location);
 
         // Exit the loop scope
-        self.symbol_table.exit_scope();
+        self.exit_scope();
     }
 
-    fn validate_break_statement(&mut self, location: SourceLocation) {
-        if self.loop_depth == 0 {
-            self.errors.push(CompilationError::new(
-                CompilationPhase::Semantic,
-                CompilationErrorKind::Other,
-                "Cannot use 'break' outside of a loop".to_string(),
-                location,
-            ));
+    fn resolve_try_statement(
+        &mut self,
+        body: &[Stmt],
+        catch: &Option<CatchClause>,
+        finally: &Option<Vec<Stmt>>,
+        location: SourceLocation,
+    ) {
+        self.resolve_block_statement(body);
+
+        // The error is immutable like a loop variable; a handler that only needs to know
+        // that something failed leaves it unused
+        if let Some(catch) = catch {
+            self.symbol_table.enter_scope();
+            self.define_symbol(catch.error.clone(), SymbolKind::Value, false, location);
+            self.symbol_table.mark_read(&catch.error);
+            for stmt in &catch.body {
+                self.resolve_stmt(stmt);
+            }
+            self.exit_scope();
         }
-    }
 
-    fn validate_continue_statement(&mut self, location: SourceLocation) {
-        if self.loop_depth == 0 {
-            self.errors.push(CompilationError::new(
-                CompilationPhase::Semantic,
-                CompilationErrorKind::Other,
-                "Cannot use 'continue' outside of a loop".to_string(),
-                location,
-            ));
+        if let Some(finally) = finally {
+            self.resolve_block_statement(finally);
         }
     }
 
+    /// Checks that a `break` or `continue` is inside a loop, and inside the loop it names
+    fn validate_loop_exit(
+        &mut self,
+        keyword: &str,
+        label: &Option<String>,
+        location: SourceLocation,
+    ) {
+        let message = match label {
+            _ if self.loop_labels.is_empty() => {
+                format!("Cannot use '{}' outside of a loop", keyword)
+            }
+            Some(name) if !self.loop_labels.contains(label) => {
+                format!("Undefined loop label '{}' in '{}'", name, keyword)
+            }
+            _ => return,
+        };
+        self.errors.push(CompilationError::new(
+            CompilationPhase::Semantic,
+            CompilationErrorKind::Other,
+            message,
+            location,
+        ));
+    }
+
     // Expression resolution methods
 
     fn resolve_string_interpolation(&mut self, parts: &[crate::compiler::ast::InterpolationPart]) {
@@ -644,12 +1116,18 @@ impl SemanticAnalyzer {
     }
 
     fn resolve_variable(&mut self, name: &str, location: SourceLocation) {
+        self.symbol_table.mark_read(name);
         // Check if variable is defined
         if self.symbol_table.resolve(name).is_none() {
+            let message = if name == "this" {
+                "'this' can only be used inside a struct method".to_string()
+            } else {
+                format!("Undefined variable '{}'", name)
+            };
             self.errors.push(CompilationError::new(
                 CompilationPhase::Semantic,
                 CompilationErrorKind::UndefinedSymbol,
-                format!("Undefined variable '{}'", name),
+                message,
                 location,
             ));
         }
@@ -704,6 +1182,50 @@ impl SemanticAnalyzer {
         // For example, ensuring division by zero checks, etc.
     }
 
+    fn resolve_import(&mut self, path: &str, items: &ImportItems, location: SourceLocation) {
+        if self.symbol_table.current_depth() > 0 {
+            self.errors.push(CompilationError::new(
+                CompilationPhase::Semantic,
+                CompilationErrorKind::UnexpectedToken,
+                "Imports are only allowed at the top level of a file".to_string(),
+                location,
+            ));
+            return;
+        }
+        let exports = self.modules.get(path).map(|module| module.exports.clone());
+
+        match items {
+            ImportItems::Symbols(symbols) => {
+                for (name, symbol_location) in symbols {
+                    let kind = match exports.as_ref().map(|exports| exports.get(name)) {
+                        // Imported variables can't be assigned by the importer
+                        Some(Some(SymbolKind::Variable)) | None => SymbolKind::Value,
+                        Some(Some(kind)) => kind.clone(),
+                        Some(None) => {
+                            self.errors.push(CompilationError::new(
+                                CompilationPhase::Semantic,
+                                CompilationErrorKind::UndefinedSymbol,
+                                format!("Module '{}' does not export '{}'", path, name),
+                                *symbol_location,
+                            ));
+                            SymbolKind::Value
+                        }
+                    };
+                    self.define_symbol(name.clone(), kind, false, *symbol_location);
+                }
+            }
+            ImportItems::Namespace(name) => {
+                let kind = match exports {
+                    Some(exports) => SymbolKind::Module {
+                        exports: exports.keys().cloned().collect(),
+                    },
+                    None => SymbolKind::Value,
+                };
+                self.define_symbol(name.clone(), kind, false, location);
+            }
+        }
+    }
+
     fn resolve_call_expr(&mut self, callee: &Expr, arguments: &[Expr], location: SourceLocation) {
         // Check if this is a method call: Call { callee: GetField { object, field }, arguments }
         if let Expr::GetField { object, field, .. } = callee {
@@ -726,6 +1248,11 @@ impl SemanticAnalyzer {
             self.resolve_expr(arg);
         }
 
+        if let Some((name, exports)) = self.module_exports(object) {
+            self.check_module_export(&name, &exports, method, location);
+            return;
+        }
+
         // Check if this is a static method call (e.g., Math.abs)
         if let Expr::Variable { name, .. } = object {
             if crate::common::method_registry::is_static_namespace(name) {
@@ -775,23 +1302,142 @@ impl SemanticAnalyzer {
         }
     }
 
-    fn resolve_get_field(&mut self, object: &Expr, _field: &str, _location: SourceLocation) {
+    /// Labels are checked against the struct's fields when the callee is a known struct;
+    /// other callees are left to the VM, which rejects anything that isn't a struct
+    fn resolve_named_call_expr(
+        &mut self,
+        callee: &Expr,
+        arguments: &[(String, Expr)],
+        location: SourceLocation,
+    ) {
+        self.resolve_expr(callee);
+
+        let callee_kind = match callee {
+            Expr::Variable { name, .. } => self
+                .symbol_table
+                .resolve(name)
+                .map(|symbol| (name.clone(), symbol.kind.clone())),
+            _ => None,
+        };
+        if let Some((name, SymbolKind::Function { .. })) = &callee_kind {
+            self.errors.push(CompilationError::new(
+                CompilationPhase::Semantic,
+                CompilationErrorKind::UnexpectedToken,
+                format!(
+                    "Named arguments can only be used to construct a struct, but '{}' is a function",
+                    name
+                ),
+                location,
+            ));
+        }
+
+        for (index, (label, value)) in arguments.iter().enumerate() {
+            if arguments[..index].iter().any(|(other, _)| other == label) {
+                self.errors.push(CompilationError::new(
+                    CompilationPhase::Semantic,
+                    CompilationErrorKind::DuplicateSymbol,
+                    format!("Field '{}' is given more than once", label),
+                    *value.location(),
+                ));
+            } else if let Some((name, SymbolKind::Struct { fields, .. })) = &callee_kind {
+                if !fields.contains(label) {
+                    self.errors.push(CompilationError::new(
+                        CompilationPhase::Semantic,
+                        CompilationErrorKind::UndefinedSymbol,
+                        format!("Struct '{}' has no field named '{}'", name, label),
+                        *value.location(),
+                    ));
+                }
+            }
+            self.resolve_expr(value);
+        }
+    }
+
+    fn resolve_get_field(&mut self, object: &Expr, field: &str, location: SourceLocation) {
         self.resolve_expr(object);
+        if let Some((name, variants)) = self.enum_variants(object) {
+            if !variants.iter().any(|variant| variant == field) {
+                self.errors.push(CompilationError::new(
+                    CompilationPhase::Semantic,
+                    CompilationErrorKind::UndefinedSymbol,
+                    format!("Enum '{}' has no variant '{}'", name, field),
+                    location,
+                ));
+            }
+        }
+        if let Some((name, exports)) = self.module_exports(object) {
+            self.check_module_export(&name, &exports, field, location);
+        }
         // Field validation could be added here if we track struct types
     }
 
     fn resolve_set_field(
         &mut self,
         object: &Expr,
-        _field: &str,
+        field: &str,
         value: &Expr,
-        _location: SourceLocation,
+        location: SourceLocation,
     ) {
         self.resolve_expr(object);
         self.resolve_expr(value);
+        if let Some((name, _)) = self.enum_variants(object) {
+            self.errors.push(CompilationError::new(
+                CompilationPhase::Semantic,
+                CompilationErrorKind::ImmutableAssignment,
+                format!("Cannot assign to '{}' of enum '{}'", field, name),
+                location,
+            ));
+        }
+        if let Some((name, _)) = self.module_exports(object) {
+            self.errors.push(CompilationError::new(
+                CompilationPhase::Semantic,
+                CompilationErrorKind::ImmutableAssignment,
+                format!("Cannot assign to '{}' of module '{}'", field, name),
+                location,
+            ));
+        }
         // Field validation could be added here if we track struct types
     }
 
+    /// The name and variants of the enum an expression refers to by name
+    fn enum_variants(&self, expr: &Expr) -> Option<(String, Vec<String>)> {
+        let Expr::Variable { name, .. } = expr else {
+            return None;
+        };
+        match &self.symbol_table.resolve(name)?.kind {
+            SymbolKind::Enum { variants } => Some((name.clone(), variants.clone())),
+            _ => None,
+        }
+    }
+
+    /// The name and exports of the module an expression refers to by an `import * as` name
+    fn module_exports(&self, expr: &Expr) -> Option<(String, Vec<String>)> {
+        let Expr::Variable { name, .. } = expr else {
+            return None;
+        };
+        match &self.symbol_table.resolve(name)?.kind {
+            SymbolKind::Module { exports } => Some((name.clone(), exports.clone())),
+            _ => None,
+        }
+    }
+
+    fn check_module_export(
+        &mut self,
+        name: &str,
+        exports: &[String],
+        field: &str,
+        location: SourceLocation,
+    ) {
+        if !exports.iter().any(|export| export == field) {
+            self.errors.push(CompilationError::new(
+                CompilationPhase::Semantic,
+                CompilationErrorKind::UndefinedSymbol,
+                format!("Module '{}' has no export '{}'", name, field),
+                location,
+            ));
+        }
+    }
+
     fn resolve_map_literal(&mut self, entries: &[(Expr, Expr)]) {
         // Resolve all key-value pairs in the map literal
         for (key, value) in entries {
@@ -939,24 +1585,59 @@ impl SemanticAnalyzer {
     ) {
         if let Some(symbol) = self.symbol_table.resolve(function_name) {
             match &symbol.kind {
-                SymbolKind::Function { arity } => {
-                    // Check arity matches
-                    if arguments.len() != *arity as usize {
+                SymbolKind::Function { arity, min_arity } => {
+                    // Check the argument count is within the accepted range
+                    let count = arguments.len();
+                    let is_variadic = *arity == VARIADIC_ARITY;
+                    if count < *min_arity as usize || (!is_variadic && count > *arity as usize) {
+                        let expected = if is_variadic {
+                            format!("at least {}", min_arity)
+                        } else if min_arity == arity {
+                            arity.to_string()
+                        } else {
+                            format!("{} to {}", min_arity, arity)
+                        };
                         self.errors.push(CompilationError::new(
                             CompilationPhase::Semantic,
                             CompilationErrorKind::ArityExceeded,
                             format!(
                                 "Function '{}' expects {} arguments but got {}",
-                                function_name,
-                                arity,
-                                arguments.len()
+                                function_name, expected, count
                             ),
                             location,
                         ));
                     }
                 }
-                SymbolKind::Struct { .. } => {
-                    // Calling a struct is valid (constructor)
+                SymbolKind::Struct { fields, required } => {
+                    // Calling a struct constructs it from positional field values
+                    let count = arguments.len();
+                    if count < *required || count > fields.len() {
+                        let expected = if *required == fields.len() {
+                            required.to_string()
+                        } else {
+                            format!("{} to {}", required, fields.len())
+                        };
+                        self.errors.push(CompilationError::new(
+                            CompilationPhase::Semantic,
+                            CompilationErrorKind::ArityExceeded,
+                            format!(
+                                "Struct '{}' expects {} fields but got {}",
+                                function_name, expected, count
+                            ),
+                            location,
+                        ));
+                    }
+                }
+                SymbolKind::Parameter => {
+                    // Parameters may hold functions passed as callbacks
+                }
+                SymbolKind::Value | SymbolKind::Variable
+                    if self
+                        .type_env
+                        .get(function_name)
+                        .is_none_or(|value_type| value_type == "Function") =>
+                {
+                    // Values of unknown type may hold functions; only reject known non-functions
                 }
                 _ => {
                     self.errors.push(CompilationError::new(
@@ -8,10 +8,17 @@ pub enum SymbolKind {
     Value,
     /// Mutable variable
     Variable,
-    /// Function with arity
-    Function { arity: u8 },
-    /// Struct with field names
-    Struct { fields: Vec<String> },
+    /// Function taking between `min_arity` and `arity` arguments
+    Function { arity: u8, min_arity: u8 },
+    /// Struct with field names, of which `required` have no default
+    Struct {
+        fields: Vec<String>,
+        required: usize,
+    },
+    /// Enum with its variant names
+    Enum { variants: Vec<String> },
+    /// Module bound by `import * as name`, with the names it exports
+    Module { exports: Vec<String> },
     /// Function parameter
     Parameter,
 }
@@ -29,6 +36,8 @@ pub struct Symbol {
     pub scope_depth: u32,
     /// Source location where defined
     pub location: SourceLocation,
+    /// Whether the symbol has been referenced since it was defined
+    pub is_read: bool,
 }
 
 impl Symbol {
@@ -45,6 +54,7 @@ impl Symbol {
             is_mutable,
             scope_depth,
             location,
+            is_read: false,
         }
     }
 }
@@ -85,6 +95,10 @@ impl Scope {
     pub fn get(&self, name: &str) -> Option<&Symbol> {
         self.symbols.get(name)
     }
+
+    fn get_mut(&mut self, name: &str) -> Option<&mut Symbol> {
+        self.symbols.get_mut(name)
+    }
 }
 
 /// Symbol table managing all scopes
@@ -144,6 +158,32 @@ impl SymbolTable {
             }
         }
     }
+
+    /// Mark the symbol a name resolves to as read
+    pub fn mark_read(&mut self, name: &str) {
+        let mut scope_idx = self.current_scope;
+        loop {
+            if let Some(symbol) = self.scopes[scope_idx].get_mut(name) {
+                symbol.is_read = true;
+                return;
+            }
+            match self.scopes[scope_idx].parent {
+                Some(parent) => scope_idx = parent,
+                None => return,
+            }
+        }
+    }
+
+    /// Symbols defined in the current scope that were never read, in definition order
+    pub fn unread_symbols(&self) -> Vec<&Symbol> {
+        let mut unread: Vec<&Symbol> = self.scopes[self.current_scope]
+            .symbols
+            .values()
+            .filter(|symbol| !symbol.is_read)
+            .collect();
+        unread.sort_by_key(|symbol| symbol.location.offset);
+        unread
+    }
 }
 
 impl Default for SymbolTable {
@@ -1,5 +1,5 @@
 use crate::common::SourceLocation;
-use crate::compiler::ast::{BinaryOp, Expr, Stmt};
+use crate::compiler::ast::{BinaryOp, Expr, Parameter, Stmt};
 
 fn dummy_location() -> SourceLocation {
     SourceLocation {
@@ -60,8 +60,9 @@ fn test_stmt_val() {
 fn test_stmt_fn() {
     let stmt = Stmt::Fn {
         name: "foo".to_string(),
-        params: vec!["a".to_string(), "b".to_string()],
+        params: vec![Parameter::new("a"), Parameter::new("b")],
         body: vec![],
+        doc: None,
         location: dummy_location(),
     };
 
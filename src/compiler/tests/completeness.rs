@@ -0,0 +1,75 @@
+use crate::compiler::{input_status, InputStatus};
+
+fn assert_status(expected: InputStatus, snippets: &[&str]) {
+    for snippet in snippets {
+        assert_eq!(expected, input_status(snippet), "{:?}", snippet);
+    }
+}
+
+#[test]
+fn balanced_input_is_complete() {
+    assert_status(
+        InputStatus::Complete,
+        &[
+            "",
+            "print(1)",
+            "val x = [1, 2, {\"a\": (3)}]",
+            "fn add(a, b) {\n    return a + b\n}",
+            "if (x) {\n    print(1)\n} else {\n    print(2)\n}",
+            "print(\"{ ( [\")",
+            "// a comment with an open {",
+            "/* a closed ( comment */ print(1)",
+            "print(map?.[\"key\"])",
+        ],
+    );
+}
+
+#[test]
+fn open_brackets_need_more_input() {
+    assert_status(
+        InputStatus::Incomplete,
+        &[
+            "fn add(a, b) {",
+            "fn add(a, b) {\n    return a + b",
+            "print(1,",
+            "val xs = [\n    1,\n    2,",
+            "struct Point {\n    x\n    y",
+            "if (a && (b",
+            "try {\n    risky()\n} catch (e) {",
+            "print(map?.[\"key\"",
+        ],
+    );
+}
+
+#[test]
+fn unterminated_strings_and_comments_need_more_input() {
+    assert_status(
+        InputStatus::Incomplete,
+        &[
+            "val s = \"first line",
+            "val raw = \"\"\"\nfirst line",
+            "/* a comment\nthat goes on",
+            "/* nested /* comments */ still open",
+        ],
+    );
+}
+
+#[test]
+fn a_trailing_operator_does_not_continue_the_statement() {
+    assert_status(InputStatus::Complete, &["val x = 1 +", "val y ="]);
+}
+
+#[test]
+fn input_no_continuation_can_fix_is_invalid() {
+    assert_status(
+        InputStatus::Invalid,
+        &[
+            ")",
+            "print(1))",
+            "fn f() {\n    print(1]",
+            "val xs = [1, 2}",
+            "val x = 1 @ 2 {",
+            "val n = 0b2 + (",
+        ],
+    );
+}
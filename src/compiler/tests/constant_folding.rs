@@ -0,0 +1,158 @@
+use crate::common::stdlib::create_builtin_objects;
+use crate::common::{Chunk, Value};
+use crate::compiler::ast::{Expr, Stmt};
+use crate::compiler::codegen::CodeGenerator;
+use crate::compiler::constant_folding::fold_constants;
+use crate::compiler::parser::Parser;
+use crate::vm::{Result, VirtualMachine};
+
+fn parse(source: &str) -> Vec<Stmt> {
+    Parser::new(source).parse().expect("source should parse")
+}
+
+fn generate(ast: &[Stmt]) -> Chunk {
+    CodeGenerator::new(create_builtin_objects(vec![]))
+        .generate(ast)
+        .expect("source should compile")
+}
+
+/// Chunks compiled without and with folding
+fn compile_both(source: &str) -> (Chunk, Chunk) {
+    let mut ast = parse(source);
+    let unfolded = generate(&ast);
+    fold_constants(&mut ast);
+    (unfolded, generate(&ast))
+}
+
+fn numbers(chunk: &Chunk) -> Vec<f64> {
+    chunk
+        .constants
+        .values
+        .iter()
+        .filter_map(|value| match value {
+            Value::Number(n) => Some(*n),
+            _ => None,
+        })
+        .collect()
+}
+
+fn run(chunk: Chunk) -> String {
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.execute_chunk(chunk));
+    vm.get_output()
+}
+
+fn folded_initializer(source: &str) -> Expr {
+    let mut ast = parse(source);
+    fold_constants(&mut ast);
+    match ast.into_iter().next() {
+        Some(Stmt::Val {
+            initializer: Some(initializer),
+            ..
+        }) => initializer,
+        other => panic!("expected a val declaration, got {:?}", other),
+    }
+}
+
+#[test]
+fn folds_literal_arithmetic_into_one_constant() {
+    let (unfolded, folded) = compile_both("print(2 * 3 + 1)\n");
+
+    assert_eq!(vec![2.0, 3.0, 1.0], numbers(&unfolded));
+    assert_eq!(vec![7.0], numbers(&folded));
+    assert!(folded.instruction_count() < unfolded.instruction_count());
+    assert_eq!(run(unfolded), run(folded));
+}
+
+#[test]
+fn folded_programs_print_the_same() {
+    let source = r#"
+val words = "neon" + " " + "lang"
+print(words, 10 / 4, 7 % 3, 2 ** 10, -(3 - 5))
+print(1 < 2, 2 <= 2, 3 > 4, 3 >= 4, 1 == 1, "a" != "b", 1 == "1", nil == nil)
+print(true && false, true || false, !true, (1 + 2) * 3)
+"#;
+    let (unfolded, folded) = compile_both(source);
+
+    assert!(folded.constants.values.len() < unfolded.constants.values.len());
+    let output = run(folded);
+    assert_eq!(run(unfolded), output);
+    assert_eq!(
+        "neon lang 2.5 1 1024 2\ntrue true false false true true false true\nfalse true false 9",
+        output
+    );
+}
+
+#[test]
+fn folds_inside_functions_and_nested_expressions() {
+    let source = r#"
+fn area(r) {
+    return r * r * (3 + 0.5)
+}
+print(area(2), [1 + 1, 2 * 2][1])
+"#;
+    let (unfolded, folded) = compile_both(source);
+    assert_eq!(run(unfolded), run(folded));
+
+    let mut ast = parse(source);
+    fold_constants(&mut ast);
+    let Stmt::Fn { body, .. } = &ast[0] else {
+        panic!("expected a function declaration");
+    };
+    let Stmt::Return {
+        value: Expr::Binary { right, .. },
+        ..
+    } = &body[0]
+    else {
+        panic!("expected a returned product");
+    };
+    assert!(matches!(**right, Expr::Number { value, .. } if value == 3.5));
+}
+
+#[test]
+fn leaves_non_finite_results_to_the_vm() {
+    assert!(matches!(
+        folded_initializer("val x = 1 / 0\n"),
+        Expr::Binary { .. }
+    ));
+    assert!(matches!(
+        folded_initializer("val x = 0 % 0\n"),
+        Expr::Binary { .. }
+    ));
+
+    let (unfolded, folded) = compile_both("print(1 / 0, -1 / 0)\n");
+    assert_eq!(run(unfolded), run(folded));
+}
+
+#[test]
+fn leaves_expressions_with_variables_or_calls_alone() {
+    assert!(matches!(
+        folded_initializer("val x = args.length() + 1\n"),
+        Expr::Binary { .. }
+    ));
+    assert!(matches!(
+        folded_initializer("val x = 1 + \"a\"\n"),
+        Expr::Binary { .. }
+    ));
+}
+
+#[test]
+fn folds_partially_constant_expressions() {
+    match folded_initializer("val x = args.length() + (2 * 4)\n") {
+        Expr::Binary { right, .. } => {
+            assert!(matches!(*right, Expr::Number { value, .. } if value == 8.0))
+        }
+        other => panic!("expected a binary expression, got {:?}", other),
+    }
+}
+
+#[test]
+fn folds_string_comparisons_like_the_vm() {
+    let source = r#"print("ab" < "abc", "b" > "abc", "é" >= "z", "a" <= "a")"#;
+    let (unfolded, folded) = compile_both(source);
+
+    assert!(folded.instruction_count() < unfolded.instruction_count());
+    let output = run(folded);
+    assert_eq!(run(unfolded), output);
+    assert_eq!("true true true true", output);
+}
@@ -0,0 +1,217 @@
+use crate::common::errors::{CompilationErrorKind, CompilationPhase, Severity};
+use crate::compiler::lint::Linter;
+use crate::compiler::parser::Parser;
+use crate::vm::{Result, VirtualMachine};
+
+fn lint(source: &str) -> Vec<crate::common::errors::CompilationError> {
+    let mut parser = Parser::new(source);
+    let ast = parser.parse().expect("source should parse");
+    Linter::new().lint(&ast)
+}
+
+#[test]
+fn test_assignment_in_if_condition_warns() {
+    let warnings = lint("var x = 1\nif (x = 5) {\n    print(x)\n}");
+
+    assert_eq!(1, warnings.len());
+    let warning = &warnings[0];
+    assert_eq!(Severity::Warning, warning.severity);
+    assert_eq!(CompilationPhase::Lint, warning.phase);
+    assert_eq!(CompilationErrorKind::AssignmentInCondition, warning.kind);
+    assert!(warning.message.contains("Did you mean '=='?"));
+    assert_eq!(2, warning.location.line);
+}
+
+#[test]
+fn test_assignment_in_while_condition_warns() {
+    let warnings = lint("var x = 1\nwhile (x = 0) {\n    print(x)\n}");
+
+    assert_eq!(1, warnings.len());
+    assert!(warnings[0].message.contains("'while'"));
+}
+
+#[test]
+fn test_equality_in_condition_does_not_warn() {
+    assert!(lint("var x = 1\nif (x == 5) {\n    print(x)\n}").is_empty());
+}
+
+#[test]
+fn test_parenthesized_assignment_does_not_warn() {
+    assert!(lint("var x = 1\nif ((x = 5)) {\n    print(x)\n}").is_empty());
+}
+
+#[test]
+fn test_nested_conditions_are_linted() {
+    let source = r#"
+fn check(x) {
+    if (x > 0) {
+        while (x = 1) {
+            print(x)
+        }
+    } else {
+        if (x = 2) {
+            print(x)
+        }
+    }
+}
+"#;
+    assert_eq!(2, lint(source).len());
+}
+
+#[test]
+fn test_lint_warning_does_not_stop_compilation() {
+    let program = r#"
+        var x = 1
+        if (x = 5) {
+            print(x)
+        }
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("5", vm.get_output());
+    assert_eq!(1, vm.get_warnings().len());
+}
+
+#[test]
+fn test_formatted_warning_uses_warning_label() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret("var x = 1\nif (x = 5) {\n    print(x)\n}".to_string());
+
+    let formatted = vm.get_formatted_warnings("test.n");
+    assert!(formatted.contains("warning"));
+    assert!(formatted.contains("1 warning emitted"));
+    assert!(!formatted.contains("aborting"));
+}
+
+#[test]
+fn test_statement_after_return_warns() {
+    let source = "fn f() {\n    return 1\n    print(2)\n    print(3)\n}";
+    let warnings = lint(source);
+
+    assert_eq!(1, warnings.len());
+    assert_eq!(CompilationErrorKind::UnreachableCode, warnings[0].kind);
+    assert_eq!(Severity::Warning, warnings[0].severity);
+    assert_eq!("Unreachable code after 'return'.", warnings[0].message);
+    assert_eq!(3, warnings[0].location.line);
+}
+
+#[test]
+fn test_statement_after_break_and_continue_warns() {
+    let source = r#"
+for (i in 1..3) {
+    continue
+    print(i)
+}
+while (true) {
+    break
+    print(0)
+}
+"#;
+    let warnings = lint(source);
+
+    assert_eq!(2, warnings.len());
+    assert!(warnings[0].message.contains("'continue'"));
+    assert!(warnings[1].message.contains("'break'"));
+}
+
+#[test]
+fn test_statement_after_exiting_if_else_warns() {
+    let source = r#"
+fn sign(x) {
+    if (x < 0) {
+        return -1
+    } else {
+        return 1
+    }
+    print(x)
+}
+"#;
+    let warnings = lint(source);
+
+    assert_eq!(1, warnings.len());
+    assert_eq!(8, warnings[0].location.line);
+}
+
+#[test]
+fn test_conditional_exit_is_not_unreachable() {
+    let source = r#"
+fn f(x) {
+    if (x < 0) {
+        return -1
+    }
+    while (true) {
+        break
+    }
+    return x
+}
+"#;
+    assert!(lint(source).is_empty());
+}
+
+#[test]
+fn test_unreachable_code_in_struct_method_warns() {
+    let source = "struct S {\n    fn m() {\n        return 1\n        print(1)\n    }\n}";
+    assert_eq!(1, lint(source).len());
+}
+
+#[test]
+fn test_lambda_bodies_are_linted() {
+    let source = r#"
+val check = fn(x) {
+    if (x = 1) {
+        print(x)
+    }
+}
+[1, 2].forEach(fn(item) {
+    return item
+    print(item)
+})
+val outer = fn() {
+    return y => fn() {
+        while (y = 0) {
+            print(y)
+        }
+    }
+}
+"#;
+    let warnings = lint(source);
+    assert_eq!(3, warnings.len());
+    assert_eq!(
+        CompilationErrorKind::AssignmentInCondition,
+        warnings[0].kind
+    );
+    assert_eq!(CompilationErrorKind::UnreachableCode, warnings[1].kind);
+    assert_eq!(
+        CompilationErrorKind::AssignmentInCondition,
+        warnings[2].kind
+    );
+}
+
+#[test]
+fn test_unreachable_code_does_not_stop_compilation() {
+    let program = r#"
+        fn f() {
+            return 1
+            print(2)
+        }
+        print(f())
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("1", vm.get_output());
+    assert_eq!(1, vm.get_warnings().len());
+}
+
+#[test]
+fn test_with_block_cleanup_is_not_unreachable() {
+    let source = r#"
+fn first_line(path) {
+    with (f = File(path)) {
+        return f.readLines()
+    }
+}
+"#;
+    assert!(lint(source).is_empty());
+}
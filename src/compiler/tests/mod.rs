@@ -1,6 +1,11 @@
 mod ast;
 mod codegen;
+mod completeness;
+mod constant_folding;
+mod lint;
 mod parser;
+mod peephole;
+mod purity;
 mod scanner;
 mod semantic;
 mod symbol_table;
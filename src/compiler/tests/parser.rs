@@ -1,4 +1,8 @@
-use crate::compiler::ast::{Expr, Stmt};
+#![allow(clippy::unnecessary_unwrap)]
+
+use crate::compiler::ast::{
+    BinaryOp, DestructurePattern, Expr, ImportItems, Parameter, Stmt, UnaryOp,
+};
 use crate::compiler::parser::Parser;
 
 #[test]
@@ -46,6 +50,22 @@ fn test_parse_hex_number() {
     }
 }
 
+#[test]
+fn test_parse_hex_number_too_large_for_an_integer() {
+    let mut parser = Parser::new("0x1FFFFFFFFFFFFFFFF\n");
+    let result = parser.parse();
+    assert!(result.is_ok());
+    let stmts = result.unwrap();
+    assert_eq!(stmts.len(), 1);
+    match &stmts[0] {
+        Stmt::Expression { expr, .. } => match expr {
+            Expr::Number { value, .. } => assert_eq!(*value, 2f64.powi(65)),
+            _ => panic!("Expected Number expression"),
+        },
+        _ => panic!("Expected Expression statement"),
+    }
+}
+
 #[test]
 fn test_parse_binary_number() {
     let mut parser = Parser::new("0b1010\n");
@@ -228,6 +248,454 @@ fn test_parse_while_loop() {
     }
 }
 
+#[test]
+fn test_parse_do_while_loop() {
+    let program = r#"
+        var i = 0
+        do {
+            i = i + 1
+        } while (i < 10)
+        print(i)
+        "#;
+    let mut parser = Parser::new(program);
+    let result = parser.parse();
+    assert!(result.is_ok());
+    let stmts = result.unwrap();
+    assert_eq!(stmts.len(), 3);
+    match &stmts[1] {
+        Stmt::DoWhile { body, .. } => assert!(matches!(body.as_ref(), Stmt::Block { .. })),
+        _ => panic!("Expected DoWhile statement"),
+    }
+}
+
+#[test]
+fn test_parse_do_without_while() {
+    let mut parser = Parser::new("do {\n    print(1)\n}\nprint(2)\n");
+    assert!(parser.parse().is_err());
+}
+
+#[test]
+fn test_parse_labeled_loops() {
+    let program = r#"
+        outer: while (true) {
+            inner: for (x in [1]) {
+                continue outer
+            }
+            break outer
+        }
+        "#;
+    let mut parser = Parser::new(program);
+    let stmts = parser.parse().unwrap();
+    assert_eq!(stmts.len(), 1);
+    let Stmt::While { label, body, .. } = &stmts[0] else {
+        panic!("Expected While statement");
+    };
+    assert_eq!(label.as_deref(), Some("outer"));
+    let Stmt::Block { statements, .. } = body.as_ref() else {
+        panic!("Expected Block as while body");
+    };
+    match &statements[0] {
+        Stmt::ForIn { label, body, .. } => {
+            assert_eq!(label.as_deref(), Some("inner"));
+            assert!(matches!(
+                body.as_ref(),
+                Stmt::Block { statements, .. }
+                    if matches!(&statements[0], Stmt::Continue { label: Some(l), .. } if l == "outer")
+            ));
+        }
+        _ => panic!("Expected ForIn statement"),
+    }
+    assert!(matches!(&statements[1], Stmt::Break { label: Some(l), .. } if l == "outer"));
+}
+
+#[test]
+fn test_parse_label_without_loop() {
+    let mut parser = Parser::new("outer: print(1)\n");
+    assert!(parser.parse().is_err());
+}
+
+#[test]
+fn test_parse_destructuring_declarations() {
+    let program = r#"
+        val [a, b, ...rest] = items
+        var {x, y} = point
+        "#;
+    let mut parser = Parser::new(program);
+    let stmts = parser.parse().unwrap();
+    assert_eq!(stmts.len(), 2);
+    match &stmts[0] {
+        Stmt::Destructure {
+            pattern: DestructurePattern::Array { names, rest },
+            mutable,
+            ..
+        } => {
+            assert_eq!(names, &["a", "b"]);
+            assert_eq!(rest.as_deref(), Some("rest"));
+            assert!(!mutable);
+        }
+        _ => panic!("Expected array destructuring"),
+    }
+    match &stmts[1] {
+        Stmt::Destructure {
+            pattern: DestructurePattern::Struct { fields },
+            mutable,
+            ..
+        } => {
+            assert_eq!(fields, &["x", "y"]);
+            assert!(mutable);
+        }
+        _ => panic!("Expected struct destructuring"),
+    }
+}
+
+#[test]
+fn test_parse_destructuring_rest_must_be_last() {
+    let mut parser = Parser::new("val [...rest, last] = items\n");
+    assert!(parser.parse().is_err());
+}
+
+#[test]
+fn test_parse_destructuring_requires_initializer() {
+    let mut parser = Parser::new("val [a, b]\n");
+    assert!(parser.parse().is_err());
+}
+
+#[test]
+fn test_parse_multiple_assignment() {
+    let mut parser = Parser::new("a, b = b, a\n");
+    let stmts = parser.parse().unwrap();
+    assert_eq!(stmts.len(), 1);
+    match &stmts[0] {
+        Stmt::Block { statements, .. } => {
+            assert_eq!(statements.len(), 4);
+            assert!(matches!(&statements[0], Stmt::Val { .. }));
+            assert!(matches!(&statements[1], Stmt::Val { .. }));
+            assert!(matches!(
+                &statements[2],
+                Stmt::Expression { expr: Expr::Assign { name, .. }, .. } if name == "a"
+            ));
+            assert!(matches!(
+                &statements[3],
+                Stmt::Expression { expr: Expr::Assign { name, .. }, .. } if name == "b"
+            ));
+        }
+        _ => panic!("Expected multiple assignment block"),
+    }
+}
+
+#[test]
+fn test_parse_multiple_assignment_count_mismatch() {
+    let mut parser = Parser::new("a, b = 1\n");
+    assert!(parser.parse().is_err());
+    let mut parser = Parser::new("a, b = 1, 2, 3\n");
+    assert!(parser.parse().is_err());
+}
+
+#[test]
+fn test_parse_chained_comparison() {
+    let mut parser = Parser::new("0 <= x < 10 > y\n");
+    let stmts = parser.parse().unwrap();
+    match &stmts[0] {
+        Stmt::Expression {
+            expr:
+                Expr::ChainedComparison {
+                    operands,
+                    operators,
+                    ..
+                },
+            ..
+        } => {
+            assert_eq!(operands.len(), 4);
+            assert_eq!(
+                operators,
+                &[BinaryOp::LessEqual, BinaryOp::Less, BinaryOp::Greater]
+            );
+        }
+        _ => panic!("Expected chained comparison"),
+    }
+}
+
+#[test]
+fn test_parse_grouped_and_equality_comparisons_do_not_chain() {
+    let mut parser = Parser::new("(a < b) < c\na == b == c\n");
+    let stmts = parser.parse().unwrap();
+    for stmt in &stmts {
+        assert!(matches!(
+            stmt,
+            Stmt::Expression {
+                expr: Expr::Binary { .. },
+                ..
+            }
+        ));
+    }
+}
+
+fn parse_string_value(source: &str) -> String {
+    let mut parser = Parser::new(source);
+    let stmts = parser.parse().unwrap();
+    match &stmts[0] {
+        Stmt::Expression {
+            expr: Expr::String { value, .. },
+            ..
+        } => value.clone(),
+        _ => panic!("Expected string expression"),
+    }
+}
+
+#[test]
+fn test_parse_raw_string_is_verbatim() {
+    let value =
+        parse_string_value("\"\"\"{\"path\": \"C:\\new\\table\", \"name\": \"${name}\"}\"\"\"\n");
+    assert_eq!(
+        value,
+        "{\"path\": \"C:\\new\\table\", \"name\": \"${name}\"}"
+    );
+}
+
+#[test]
+fn test_parse_raw_string_strips_indentation() {
+    let source = "\"\"\"\n    SELECT *\n      FROM users\n\n    \"\"\"\n";
+    assert_eq!(parse_string_value(source), "SELECT *\n  FROM users\n");
+}
+
+#[test]
+fn test_parse_raw_string_keeps_text_after_opening_delimiter() {
+    let source = "\"\"\"first\n    second\n    \"\"\"\n";
+    assert_eq!(parse_string_value(source), "first\n    second\n    ");
+}
+
+#[test]
+fn test_parse_attaches_doc_comments_to_declarations() {
+    let program = r#"
+/// Adds two numbers.
+///
+/// Returns their sum.
+fn add(a, b) {
+    return a + b
+}
+
+/// A point in the plane
+struct Point {
+    x
+    y
+    /// Distance from the origin
+    fn length() {
+        return 0
+    }
+}
+"#;
+    let mut parser = Parser::new(program);
+    let stmts = parser.parse().unwrap();
+    match &stmts[0] {
+        Stmt::Fn { doc, .. } => {
+            assert_eq!(
+                doc.as_deref(),
+                Some("Adds two numbers.\n\nReturns their sum.")
+            )
+        }
+        _ => panic!("Expected Fn statement"),
+    }
+    match &stmts[1] {
+        Stmt::Struct { doc, methods, .. } => {
+            assert_eq!(doc.as_deref(), Some("A point in the plane"));
+            assert_eq!(methods[0].doc.as_deref(), Some("Distance from the origin"));
+        }
+        _ => panic!("Expected Struct statement"),
+    }
+}
+
+#[test]
+fn test_parse_doc_comment_only_attaches_to_next_declaration() {
+    let program = r#"
+/// Not about f
+val x = 1
+fn f() {
+    return x
+}
+// Plain comment
+fn g() {
+    return 1
+}
+"#;
+    let mut parser = Parser::new(program);
+    let stmts = parser.parse().unwrap();
+    for stmt in &stmts[1..] {
+        match stmt {
+            Stmt::Fn { doc, .. } => assert_eq!(doc, &None),
+            _ => panic!("Expected Fn statement"),
+        }
+    }
+}
+
+#[test]
+fn test_parse_enum_declaration() {
+    let program =
+        "/// Compass points\nenum Direction {\n    North, East\n    South\n    West,\n}\n";
+    let mut parser = Parser::new(program);
+    let stmts = parser.parse().unwrap();
+    match &stmts[0] {
+        Stmt::Enum {
+            name,
+            variants,
+            doc,
+            ..
+        } => {
+            assert_eq!(name, "Direction");
+            assert_eq!(variants, &["North", "East", "South", "West"]);
+            assert_eq!(doc.as_deref(), Some("Compass points"));
+        }
+        _ => panic!("Expected Enum statement"),
+    }
+}
+
+#[test]
+fn test_parse_enum_requires_variant_names() {
+    let mut parser = Parser::new("enum Color { Red, 1 }\n");
+    assert!(parser.parse().is_err());
+    let mut parser = Parser::new("enum { Red }\n");
+    assert!(parser.parse().is_err());
+}
+
+#[test]
+fn test_parse_import_of_symbols() {
+    let program = "import { add,\n    sub, } from \"math_utils\"\n";
+    let mut parser = Parser::new(program);
+    let stmts = parser.parse().unwrap();
+    match &stmts[0] {
+        Stmt::Import {
+            path,
+            items: ImportItems::Symbols(symbols),
+            ..
+        } => {
+            assert_eq!(path, "math_utils");
+            let names: Vec<&str> = symbols.iter().map(|(name, _)| name.as_str()).collect();
+            assert_eq!(names, ["add", "sub"]);
+        }
+        other => panic!("Expected Import statement, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_namespace_import() {
+    let mut parser = Parser::new("import * as M from \"lib/math\"\n");
+    let stmts = parser.parse().unwrap();
+    match &stmts[0] {
+        Stmt::Import {
+            path,
+            items: ImportItems::Namespace(name),
+            ..
+        } => {
+            assert_eq!(path, "lib/math");
+            assert_eq!(name, "M");
+        }
+        other => panic!("Expected Import statement, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_import_requires_names_and_source() {
+    for program in [
+        "import { } from \"m\"\n",
+        "import { a } \"m\"\n",
+        "import { a } from m\n",
+        "import * from \"m\"\n",
+        "import a from \"m\"\n",
+    ] {
+        let mut parser = Parser::new(program);
+        assert!(parser.parse().is_err(), "{} should not parse", program);
+    }
+}
+
+#[test]
+fn test_parse_export_wraps_declaration() {
+    let program = "/// Adds\nexport fn add(a, b) {\n    return a + b\n}\nexport val ZERO = 0\n";
+    let mut parser = Parser::new(program);
+    let stmts = parser.parse().unwrap();
+    match &stmts[0] {
+        Stmt::Export { declaration, .. } => match declaration.as_ref() {
+            Stmt::Fn { name, doc, .. } => {
+                assert_eq!(name, "add");
+                assert_eq!(doc.as_deref(), Some("Adds"));
+            }
+            other => panic!("Expected Fn declaration, got {:?}", other),
+        },
+        other => panic!("Expected Export statement, got {:?}", other),
+    }
+    assert!(matches!(
+        stmts[1].declaration(),
+        Stmt::Val { name, .. } if name == "ZERO"
+    ));
+}
+
+#[test]
+fn test_parse_export_requires_declaration() {
+    let mut parser = Parser::new("export print(1)\n");
+    assert!(parser.parse().is_err());
+}
+
+#[test]
+fn test_parse_try_catch() {
+    let program = "try {\n    risky()\n} catch (e) {\n    print(e)\n}\n";
+    let mut parser = Parser::new(program);
+    let stmts = parser.parse().unwrap();
+    match &stmts[0] {
+        Stmt::Try {
+            body,
+            catch: Some(catch),
+            finally: None,
+            ..
+        } => {
+            assert_eq!(body.len(), 1);
+            assert_eq!(catch.error, "e");
+            assert_eq!(catch.body.len(), 1);
+        }
+        other => panic!("Expected Try statement, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_try_finally() {
+    let program =
+        "try {\n    risky()\n} catch (e) {\n} finally {\n    close()\n}\ntry {\n} finally {\n}\n";
+    let mut parser = Parser::new(program);
+    let stmts = parser.parse().unwrap();
+    assert!(matches!(
+        &stmts[0],
+        Stmt::Try { catch: Some(_), finally: Some(finally), .. } if finally.len() == 1
+    ));
+    assert!(matches!(
+        &stmts[1],
+        Stmt::Try {
+            catch: None,
+            finally: Some(_),
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_parse_try_requires_catch_or_finally() {
+    for program in [
+        "try {\n}\n",
+        "try {\n} catch {\n}\n",
+        "try {\n} catch () {\n}\n",
+        "try {\n} finally\n",
+        "try print(1)\n",
+    ] {
+        let mut parser = Parser::new(program);
+        assert!(parser.parse().is_err(), "{} should not parse", program);
+    }
+}
+
+#[test]
+fn test_parse_throw() {
+    let mut parser = Parser::new("throw \"boom\"\n");
+    let stmts = parser.parse().unwrap();
+    assert!(
+        matches!(&stmts[0], Stmt::Throw { value: Expr::String { value, .. }, .. } if value == "boom")
+    );
+}
+
 #[test]
 fn test_parse_simple_else_if() {
     let program = r#"
@@ -2399,3 +2867,404 @@ fn test_parse_array_missing_closing_bracket() {
     assert!(!errors.is_empty());
     assert!(errors[0].message.contains("']'"));
 }
+
+#[test]
+fn test_parse_with_block_lowers_to_try_finally() {
+    let mut parser = Parser::new("with (f = File(\"x\")) {\n    print(f)\n}\n");
+    let stmts = parser.parse().unwrap();
+    assert_eq!(stmts.len(), 1);
+    match &stmts[0] {
+        Stmt::Block { statements, .. } => {
+            assert_eq!(statements.len(), 2);
+            assert!(matches!(&statements[0], Stmt::Val { name, .. } if name == "f"));
+            match &statements[1] {
+                Stmt::Try {
+                    body,
+                    catch: None,
+                    finally: Some(finally),
+                    ..
+                } => {
+                    assert_eq!(body.len(), 1);
+                    match &finally[..] {
+                        [Stmt::Expression {
+                            expr: Expr::Call { callee, .. },
+                            ..
+                        }] => assert!(
+                            matches!(callee.as_ref(), Expr::GetField { field, .. } if field == "close")
+                        ),
+                        _ => panic!("Expected cleanup call in the finally block"),
+                    }
+                }
+                _ => panic!("Expected try/finally around the body"),
+            }
+        }
+        _ => panic!("Expected Block statement"),
+    }
+}
+
+#[test]
+fn test_parse_with_block_leaves_return_to_finally() {
+    let mut parser =
+        Parser::new("fn f() {\n    with (r = File(\"x\")) {\n        return 1\n    }\n}\n");
+    let stmts = parser.parse().unwrap();
+    let body = match &stmts[0] {
+        Stmt::Fn { body, .. } => body,
+        _ => panic!("Expected Fn statement"),
+    };
+    match &body[0] {
+        Stmt::Block { statements, .. } => match &statements[1] {
+            Stmt::Try { body, .. } => assert!(matches!(&body[..], [Stmt::Return { .. }])),
+            _ => panic!("Expected try/finally around the body"),
+        },
+        _ => panic!("Expected lowered with block"),
+    }
+}
+
+fn parse_single_expression(source: &str) -> Expr {
+    let mut parser = Parser::new(source);
+    let stmts = parser.parse().expect("parse failed");
+    match stmts.into_iter().next() {
+        Some(Stmt::Expression { expr, .. }) => expr,
+        other => panic!("Expected Expression statement, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_compound_assignment_desugars_to_assign() {
+    use crate::compiler::ast::BinaryOp;
+
+    match parse_single_expression("x += 2\n") {
+        Expr::Assign { name, value, .. } => {
+            assert_eq!(name, "x");
+            match *value {
+                Expr::Binary {
+                    left,
+                    operator,
+                    right,
+                    ..
+                } => {
+                    assert!(matches!(*left, Expr::Variable { ref name, .. } if name == "x"));
+                    assert_eq!(operator, BinaryOp::Add);
+                    assert!(matches!(*right, Expr::Number { value, .. } if value == 2.0));
+                }
+                other => panic!("Expected Binary value, got {:?}", other),
+            }
+        }
+        other => panic!("Expected Assign, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_compound_index_assignment_is_tagged_with_its_operator() {
+    use crate::compiler::ast::BinaryOp;
+
+    match parse_single_expression("m[\"k\"] -= 1\n") {
+        Expr::IndexAssign {
+            operator, value, ..
+        } => {
+            assert_eq!(operator, Some(BinaryOp::Subtract));
+            assert!(matches!(*value, Expr::Number { value, .. } if value == 1.0));
+        }
+        other => panic!("Expected IndexAssign, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_compound_field_assignment_is_tagged_with_its_operator() {
+    use crate::compiler::ast::BinaryOp;
+
+    match parse_single_expression("p.count *= 3\n") {
+        Expr::SetField {
+            field,
+            operator,
+            value,
+            ..
+        } => {
+            assert_eq!(field, "count");
+            assert_eq!(operator, Some(BinaryOp::Multiply));
+            assert!(matches!(*value, Expr::Number { value, .. } if value == 3.0));
+        }
+        other => panic!("Expected SetField, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_plain_index_assignment_has_no_operator() {
+    match parse_single_expression("m[\"k\"] = m[\"k\"] - 1\n") {
+        Expr::IndexAssign { operator, .. } => assert_eq!(operator, None),
+        other => panic!("Expected IndexAssign, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_ternary_is_right_associative() {
+    match parse_single_expression("a ? b : c ? d : e\n") {
+        Expr::Conditional {
+            condition,
+            then_expr,
+            else_expr,
+            ..
+        } => {
+            assert!(matches!(*condition, Expr::Variable { ref name, .. } if name == "a"));
+            assert!(matches!(*then_expr, Expr::Variable { ref name, .. } if name == "b"));
+            assert!(matches!(*else_expr, Expr::Conditional { .. }));
+        }
+        other => panic!("Expected Conditional, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_ternary_binds_looser_than_logical_or() {
+    match parse_single_expression("a || b ? c : d\n") {
+        Expr::Conditional { condition, .. } => {
+            assert!(matches!(*condition, Expr::Binary { .. }));
+        }
+        other => panic!("Expected Conditional, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_exponent_is_right_associative() {
+    match parse_single_expression("2 ** 3 ** 2\n") {
+        Expr::Binary {
+            left,
+            operator: BinaryOp::Exponent,
+            right,
+            ..
+        } => {
+            assert!(matches!(*left, Expr::Number { value, .. } if value == 2.0));
+            assert!(matches!(
+                *right,
+                Expr::Binary {
+                    operator: BinaryOp::Exponent,
+                    ..
+                }
+            ));
+        }
+        other => panic!("Expected Binary, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_exponent_binds_tighter_than_unary_minus() {
+    match parse_single_expression("-2 ** 2\n") {
+        Expr::Unary {
+            operator: UnaryOp::Negate,
+            operand,
+            ..
+        } => {
+            assert!(matches!(
+                *operand,
+                Expr::Binary {
+                    operator: BinaryOp::Exponent,
+                    ..
+                }
+            ));
+        }
+        other => panic!("Expected Unary, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_exponent_accepts_negated_exponent() {
+    match parse_single_expression("2 ** -1\n") {
+        Expr::Binary {
+            operator: BinaryOp::Exponent,
+            right,
+            ..
+        } => {
+            assert!(matches!(
+                *right,
+                Expr::Unary {
+                    operator: UnaryOp::Negate,
+                    ..
+                }
+            ));
+        }
+        other => panic!("Expected Binary, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_match_statement() {
+    let mut parser = Parser::new("match x {\n    1 => print(\"one\"),\n    2 => {\n        print(\"two\")\n    }\n    else => print(\"other\")\n}\n");
+    let stmts = parser.parse().expect("parse failed");
+    assert_eq!(stmts.len(), 1);
+    match &stmts[0] {
+        Stmt::Match {
+            scrutinee,
+            arms,
+            else_branch,
+            ..
+        } => {
+            assert!(matches!(scrutinee, Expr::Variable { name, .. } if name == "x"));
+            assert_eq!(arms.len(), 2);
+            assert!(matches!(arms[0].pattern, Expr::Number { value, .. } if value == 1.0));
+            assert!(matches!(arms[0].body, Stmt::Expression { .. }));
+            assert!(matches!(arms[1].body, Stmt::Block { .. }));
+            assert!(else_branch.is_some());
+        }
+        other => panic!("Expected Match statement, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_match_else_must_be_last() {
+    let mut parser = Parser::new("match x {\n    else => 1\n    2 => 2\n}\n");
+    assert!(parser.parse().is_err());
+}
+
+#[test]
+fn test_parse_match_as_method_name() {
+    let mut parser = Parser::new("Regex.match(\"a\", \"a\")\n");
+    assert!(parser.parse().is_ok());
+}
+
+#[test]
+fn test_parse_nil_coalesce_binds_tighter_than_logical_or() {
+    use crate::compiler::ast::BinaryOp;
+
+    match parse_single_expression("a ?? b || c\n") {
+        Expr::Binary { left, operator, .. } => {
+            assert_eq!(operator, BinaryOp::Or);
+            assert!(matches!(
+                *left,
+                Expr::Binary {
+                    operator: BinaryOp::NilCoalesce,
+                    ..
+                }
+            ));
+        }
+        other => panic!("Expected Binary, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_lambda_forms() {
+    match parse_single_expression("apply(fn(a, b) {\nreturn a\n}, x => x * 2)\n") {
+        Expr::Call { arguments, .. } => {
+            match &arguments[0] {
+                Expr::Lambda { params, body, .. } => {
+                    assert_eq!(params, &vec![Parameter::new("a"), Parameter::new("b")]);
+                    assert_eq!(body.len(), 1);
+                }
+                other => panic!("Expected Lambda, got {:?}", other),
+            }
+            match &arguments[1] {
+                Expr::Lambda { params, body, .. } => {
+                    assert_eq!(params, &vec![Parameter::new("x")]);
+                    assert!(matches!(body[0], Stmt::Return { .. }));
+                }
+                other => panic!("Expected Lambda, got {:?}", other),
+            }
+        }
+        other => panic!("Expected Call, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_default_parameters() {
+    let mut parser = Parser::new("fn greet(name, greeting = \"Hello\") {\n  print(name)\n}\n");
+    let stmts = parser.parse().unwrap();
+    match &stmts[0] {
+        Stmt::Fn { params, .. } => {
+            assert_eq!(params[0], Parameter::new("name"));
+            assert_eq!(params[1].name, "greeting");
+            assert!(matches!(
+                params[1].default,
+                Some(Expr::String { ref value, .. }) if value == "Hello"
+            ));
+        }
+        other => panic!("Expected Fn statement, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_default_before_required_parameter_is_an_error() {
+    let mut parser = Parser::new("fn f(a = 1, b) {\n  print(b)\n}\n");
+    let errors = parser.parse().unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0]
+        .message
+        .contains("Parameter 'b' needs a default value"));
+}
+
+#[test]
+fn test_parse_rest_parameter() {
+    let mut parser = Parser::new("fn log(level, ...messages) {\n  print(level)\n}\n");
+    let stmts = parser.parse().unwrap();
+    match &stmts[0] {
+        Stmt::Fn { params, .. } => {
+            assert!(!params[0].rest);
+            assert_eq!(params[1].name, "messages");
+            assert!(params[1].rest);
+        }
+        other => panic!("Expected Fn statement, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_rest_parameter_must_be_last() {
+    let mut parser = Parser::new("fn f(...a, b) {\n  print(b)\n}\n");
+    let errors = parser.parse().unwrap_err();
+    assert!(errors[0]
+        .message
+        .contains("Rest parameter 'a' must be the last parameter"));
+}
+
+#[test]
+fn test_parse_struct_field_defaults() {
+    let mut parser = Parser::new("struct Config {\n  host = \"localhost\"\n  port = 8080\n}\n");
+    let stmts = parser.parse().unwrap();
+    match &stmts[0] {
+        Stmt::Struct { fields, .. } => {
+            assert_eq!(fields[0].name, "host");
+            assert!(matches!(
+                fields[0].default,
+                Some(Expr::String { ref value, .. }) if value == "localhost"
+            ));
+            assert!(matches!(
+                fields[1].default,
+                Some(Expr::Number { value, .. }) if value == 8080.0
+            ));
+        }
+        other => panic!("Expected Struct statement, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_struct_default_before_required_field_is_an_error() {
+    let mut parser = Parser::new("struct Point {\n  x = 0\n  y\n}\n");
+    let errors = parser.parse().unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0]
+        .message
+        .contains("Field 'y' needs a default value because an earlier field has one."));
+}
+
+#[test]
+fn test_parse_named_struct_construction() {
+    let mut parser = Parser::new("Point(y: 4, x: 3)\n");
+    let stmts = parser.parse().unwrap();
+    match &stmts[0] {
+        Stmt::Expression {
+            expr: Expr::NamedCall { arguments, .. },
+            ..
+        } => {
+            let labels: Vec<&str> = arguments.iter().map(|(label, _)| label.as_str()).collect();
+            assert_eq!(labels, vec!["y", "x"]);
+            assert!(matches!(arguments[0].1, Expr::Number { value, .. } if value == 4.0));
+        }
+        other => panic!("Expected NamedCall, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_mixed_positional_and_named_arguments_is_an_error() {
+    let mut parser = Parser::new("Point(1, y: 2)\n");
+    let errors = parser.parse().unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0]
+        .message
+        .contains("Can't mix positional and named arguments in one call."));
+}
@@ -0,0 +1,169 @@
+use crate::common::opcodes::OpCode;
+use crate::common::stdlib::create_builtin_objects;
+use crate::common::Chunk;
+use crate::compiler::codegen::CodeGenerator;
+use crate::compiler::parser::Parser;
+use crate::compiler::peephole::optimize;
+use crate::vm::{Result, VirtualMachine};
+use crate::{as_number, boolean, number};
+
+fn compile(source: &str) -> Chunk {
+    let ast = Parser::new(source).parse().expect("source should parse");
+    CodeGenerator::new(create_builtin_objects(vec![]))
+        .generate(&ast)
+        .expect("source should compile")
+}
+
+fn run(source: &str) -> String {
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(source.to_string()));
+    vm.get_output()
+}
+
+/// `if (condition) { 2; 10 } else { 20 }` with a few discarded values around it
+fn branching_chunk(condition: bool) -> Chunk {
+    let mut chunk = Chunk::new("peephole");
+    chunk.write_constant(number!(1.0), 1, 1);
+    chunk.write_op_code(OpCode::Pop, 1, 1);
+    chunk.write_op_code(OpCode::Nil, 2, 1);
+    chunk.write_op_code(OpCode::Pop, 2, 1);
+    chunk.write_constant(boolean!(condition), 3, 1);
+    let else_jump = chunk.emit_jump(OpCode::JumpIfFalse, 3, 1);
+    chunk.write_op_code(OpCode::Pop, 3, 1);
+    chunk.write_constant(number!(2.0), 4, 1);
+    chunk.write_op_code(OpCode::Pop, 4, 1);
+    chunk.write_constant(number!(10.0), 4, 1);
+    let end_jump = chunk.emit_jump(OpCode::Jump, 4, 1);
+    chunk.patch_jump(else_jump);
+    chunk.write_op_code(OpCode::Pop, 5, 1);
+    chunk.write_constant(number!(20.0), 5, 1);
+    chunk.patch_jump(end_jump);
+    chunk.write_op_code(OpCode::Return, 6, 1);
+    chunk
+}
+
+fn result_of(chunk: Chunk) -> f64 {
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.run_chunk(chunk));
+    as_number!(vm.stack.pop().unwrap())
+}
+
+#[test]
+fn removes_discarded_values_and_keeps_jumps_working() {
+    for condition in [true, false] {
+        let original = branching_chunk(condition);
+        let mut optimized = branching_chunk(condition);
+        optimize(&mut optimized);
+
+        assert_eq!(
+            original.instruction_count() - 2 * 2 - 2 - 2,
+            optimized.instruction_count()
+        );
+        let last = optimized.instruction_count() - 1;
+        assert_eq!(6, optimized.get_source_location(last).unwrap().line);
+        assert_eq!(result_of(original), result_of(optimized));
+    }
+}
+
+#[test]
+fn discarded_expression_statements_compile_to_nothing() {
+    let with_statements = r#"
+        var i = 0
+        var total = 0
+        while (i < 5) {
+            i
+            "ignored"
+            total
+            total = total + i
+            i = i + 1
+        }
+        nil
+        print(total)
+    "#;
+    let without_statements = r#"
+        var i = 0
+        var total = 0
+        while (i < 5) {
+            total = total + i
+            i = i + 1
+        }
+        print(total)
+    "#;
+
+    assert_eq!(
+        compile(without_statements).instruction_count(),
+        compile(with_statements).instruction_count()
+    );
+    assert_eq!(run(without_statements), run(with_statements));
+    assert_eq!("10", run(with_statements));
+}
+
+#[test]
+fn discarded_comparisons_and_arrays_compile_to_nothing() {
+    let with_statements = r#"
+        val a = 1
+        val b = "b"
+        a == b
+        !a
+        a != nil
+        a is b
+        [a, [b, !b]]
+        print(a)
+    "#;
+    let without_statements = r#"
+        val a = 1
+        val b = "b"
+        print(a)
+    "#;
+
+    assert_eq!(
+        compile(without_statements).instruction_count(),
+        compile(with_statements).instruction_count()
+    );
+    assert_eq!("1", run(with_statements));
+}
+
+#[test]
+fn discarded_values_that_can_fail_are_kept() {
+    let source = r#"
+        val items = [1]
+        try {
+            items == [items[5]]
+        } catch (e) {
+            print("caught")
+        }
+    "#;
+
+    let listing = compile(source).disassemble();
+    assert!(listing.contains("GetIndex"));
+    assert_eq!("caught", run(source));
+}
+
+#[test]
+fn double_negation_is_dropped_only_in_conditions() {
+    let source = r#"
+        val x = 5
+        if (!!x) {
+            print("truthy")
+        }
+        print(!!x)
+    "#;
+
+    let listing = compile(source).disassemble();
+    assert_eq!(2, listing.matches("Not").count());
+    assert_eq!("truthy\ntrue", run(source));
+}
+
+#[test]
+fn values_a_jump_lands_on_are_kept() {
+    let source = r#"
+        val a = false
+        val b = 1
+        a && b
+        print("done")
+    "#;
+
+    let listing = compile(source).disassemble();
+    assert!(listing.contains("JumpIfFalse"));
+    assert_eq!("done", run(source));
+}
@@ -0,0 +1,79 @@
+use crate::compiler::ast::{Expr, Stmt};
+use crate::compiler::parser::Parser;
+
+fn parse_expr(source: &str) -> Expr {
+    let mut parser = Parser::new(source);
+    let mut statements = parser.parse().expect("source should parse");
+    match statements.pop() {
+        Some(Stmt::Expression { expr, .. }) => expr,
+        other => panic!("expected an expression statement, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_literals_are_pure() {
+    assert!(parse_expr("42").is_pure());
+    assert!(parse_expr("\"text\"").is_pure());
+    assert!(parse_expr("true").is_pure());
+    assert!(parse_expr("nil").is_pure());
+}
+
+#[test]
+fn test_logic_and_equality_on_variables_are_pure() {
+    assert!(parse_expr("val x = 1\nx").is_pure());
+    assert!(parse_expr("!flag && x == 1").is_pure());
+    assert!(parse_expr("x != nil ? x : y ?? 0").is_pure());
+    assert!(parse_expr("(a is b) || c").is_pure());
+}
+
+#[test]
+fn test_collection_literals_with_pure_parts_are_pure() {
+    assert!(parse_expr("[1, x, 3]").is_pure());
+    assert!(parse_expr("({\"a\": 1, \"b\": x})").is_pure());
+    assert!(parse_expr("\"value: ${x}\"").is_pure());
+}
+
+#[test]
+fn test_operations_that_can_fail_are_impure() {
+    assert!(!parse_expr("1 + x").is_pure());
+    assert!(!parse_expr("-x").is_pure());
+    assert!(!parse_expr("x > 1").is_pure());
+    assert!(!parse_expr("1 < x < 10").is_pure());
+    assert!(!parse_expr("point.x").is_pure());
+    assert!(!parse_expr("items[0]").is_pure());
+    assert!(!parse_expr("1..x").is_pure());
+    assert!(!parse_expr("\"value: ${x + 1}\"").is_pure());
+    assert!(!parse_expr("({[1]: 1})").is_pure());
+}
+
+#[test]
+fn test_assignments_are_impure() {
+    assert!(!parse_expr("x = 5").is_pure());
+    assert!(!parse_expr("point.x = 5").is_pure());
+    assert!(!parse_expr("items[0] = 5").is_pure());
+    assert!(!parse_expr("x++").is_pure());
+    assert!(!parse_expr("x--").is_pure());
+}
+
+#[test]
+fn test_calls_are_impure() {
+    assert!(!parse_expr("print(1)").is_pure());
+    assert!(!parse_expr("compute(1, 2)").is_pure());
+    assert!(!parse_expr("items.push(1)").is_pure());
+    assert!(!parse_expr("Time.now()").is_pure());
+}
+
+#[test]
+fn test_static_method_calls_are_impure() {
+    assert!(!parse_expr("Math.abs(x)").is_pure());
+    assert!(!parse_expr("Math.max(1, 2, 3)").is_pure());
+}
+
+#[test]
+fn test_impurity_propagates_through_pure_operators() {
+    assert!(!parse_expr("1 + f()").is_pure());
+    assert!(!parse_expr("[1, x = 2]").is_pure());
+    assert!(!parse_expr("(x++)").is_pure());
+    assert!(!parse_expr("c ? 1 : g()").is_pure());
+    assert!(!parse_expr("\"${f()}\"").is_pure());
+}
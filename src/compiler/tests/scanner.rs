@@ -290,6 +290,47 @@ fn rejects_invalid_octal_digit() {
     assert!(tokens[0].token.contains("Invalid digit in octal literal"));
 }
 
+#[test]
+fn rejects_invalid_digit_after_binary_digits() {
+    let tokens = collect_tokens(Scanner::new("0b102"));
+
+    assert_eq!(tokens[0].token_type, TokenType::Error);
+    assert!(tokens[0].token.contains("Invalid digit in binary literal"));
+    assert_eq!(tokens[0].line, 1);
+    assert_eq!(tokens[0].column, 1);
+}
+
+#[test]
+fn rejects_binary_literal_starting_with_invalid_digit() {
+    let tokens = collect_tokens(Scanner::new("0b2"));
+
+    assert_eq!(tokens[0].token_type, TokenType::Error);
+    assert!(tokens[0].token.contains("Invalid digit in binary literal"));
+}
+
+#[test]
+fn rejects_letters_after_radix_literals() {
+    for (script, message) in [
+        ("0xFG", "Invalid digit in hexadecimal literal"),
+        ("0b10a", "Invalid digit in binary literal"),
+        ("0o17z", "Invalid digit in octal literal"),
+    ] {
+        let tokens = collect_tokens(Scanner::new(script));
+
+        assert_eq!(tokens[0].token_type, TokenType::Error, "{}", script);
+        assert!(tokens[0].token.contains(message), "{}", script);
+    }
+}
+
+#[test]
+fn rejects_misplaced_underscores() {
+    for script in ["1__000", "0x_FF", "0b1010_", "0o_17", "1_000.5_"] {
+        let tokens = collect_tokens(Scanner::new(script));
+
+        assert_eq!(tokens[0].token_type, TokenType::Error, "{}", script);
+    }
+}
+
 #[test]
 fn rejects_empty_hex_literal() {
     let scanner = Scanner::new("0x");
@@ -316,3 +357,220 @@ fn rejects_trailing_underscore_in_decimal() {
     assert_eq!(tokens[0].token_type, TokenType::Error);
     assert!(tokens[0].token.contains("underscore"));
 }
+
+#[test]
+fn can_scan_compound_assignment_operators() {
+    let script = "a += 1 -= 2 *= 3 /= 4 %= 5";
+
+    let scanner = Scanner::new(script);
+    let x: Vec<Token> = collect_tokens(scanner);
+
+    assert_eq!(x.len(), 12);
+    assert_eq!(x[1].token_type, TokenType::PlusEqual);
+    assert_eq!(x[3].token_type, TokenType::MinusEqual);
+    assert_eq!(x[5].token_type, TokenType::StarEqual);
+    assert_eq!(x[7].token_type, TokenType::SlashEqual);
+    assert_eq!(x[9].token_type, TokenType::PercentEqual);
+    assert_eq!(x[11].token_type, TokenType::Eof);
+}
+
+#[test]
+fn can_scan_match_keyword_and_fat_arrow() {
+    let script = "match x { 1 => y }";
+
+    let scanner = Scanner::new(script);
+    let x: Vec<Token> = collect_tokens(scanner);
+
+    assert_eq!(x[0].token_type, TokenType::Match);
+    assert_eq!(x[1].token_type, TokenType::Identifier);
+    assert_eq!(x[2].token_type, TokenType::LeftBrace);
+    assert_eq!(x[3].token_type, TokenType::Number);
+    assert_eq!(x[4].token_type, TokenType::FatArrow);
+    assert_eq!(x[4].token, "=>");
+}
+
+#[test]
+fn can_scan_optional_chaining_operators() {
+    let script = "a?.b?.[c]??d";
+
+    let scanner = Scanner::new(script);
+    let x: Vec<Token> = collect_tokens(scanner);
+
+    assert_eq!(x[1].token_type, TokenType::QuestionDot);
+    assert_eq!(x[3].token_type, TokenType::QuestionDot);
+    assert_eq!(x[4].token_type, TokenType::LeftBracket);
+    assert_eq!(x[7].token_type, TokenType::QuestionQuestion);
+}
+
+#[test]
+fn question_mark_before_bracket_stays_a_ternary() {
+    let scanner = Scanner::new("c ?[1] : [2]");
+    let x: Vec<Token> = collect_tokens(scanner);
+
+    assert_eq!(x[1].token_type, TokenType::Question);
+    assert_eq!(x[2].token_type, TokenType::LeftBracket);
+}
+
+#[test]
+fn can_scan_rest_parameter_dots() {
+    let script = "fn f(...xs) 1..2 1..=2";
+
+    let scanner = Scanner::new(script);
+    let x: Vec<Token> = collect_tokens(scanner);
+
+    assert_eq!(x[3].token_type, TokenType::DotDotDot);
+    assert_eq!(x[4].token_type, TokenType::Identifier);
+    assert_eq!(x[7].token_type, TokenType::DotDot);
+    assert_eq!(x[10].token_type, TokenType::DotDotEqual);
+}
+
+#[test]
+fn can_scan_raw_string() {
+    let script = "val s = \"\"\"say \"hi\" \\n ${x}\"\"\"";
+    let tokens = collect_tokens(Scanner::new(script));
+
+    assert_eq!(tokens.len(), 5);
+    assert_eq!(tokens[3].token_type, TokenType::RawString);
+    assert_eq!(tokens[3].token, "\"\"\"say \"hi\" \\n ${x}\"\"\"");
+}
+
+#[test]
+fn can_scan_multiline_raw_string() {
+    let script = "\"\"\"\nfirst\nsecond\n\"\"\"\nx";
+    let tokens = collect_tokens(Scanner::new(script));
+
+    assert_eq!(tokens[0].token_type, TokenType::RawString);
+    assert_eq!(tokens[1].token_type, TokenType::NewLine);
+    assert_eq!(tokens[2].token_type, TokenType::Identifier);
+    assert_eq!(tokens[2].line, 5);
+}
+
+#[test]
+fn rejects_unterminated_raw_string() {
+    let tokens = collect_tokens(Scanner::new("\"\"\"never closed\"\""));
+
+    assert_eq!(tokens[0].token_type, TokenType::Error);
+    assert!(tokens[0].token.contains("Unterminated raw string"));
+}
+
+#[test]
+fn can_scan_string_with_escapes() {
+    let tokens = collect_tokens(Scanner::new(r#"x = "a\"b\\c\u{1F30D}\${d}""#));
+
+    assert_eq!(tokens[2].token_type, TokenType::String);
+    assert_eq!(tokens[2].token, r#""a\"b\\c\u{1F30D}\${d}""#);
+}
+
+#[test]
+fn rejects_invalid_escape_at_its_location() {
+    let tokens = collect_tokens(Scanner::new(r#"x = "ok \q""#));
+
+    assert_eq!(tokens[2].token_type, TokenType::Error);
+    assert_eq!(tokens[2].token, "Invalid escape sequence '\\q'");
+    assert_eq!(tokens[2].line, 1);
+    assert_eq!(tokens[2].column, 9);
+    assert_eq!(tokens[3].token_type, TokenType::Eof);
+}
+
+#[test]
+fn rejects_malformed_unicode_escapes() {
+    for script in [
+        r#""\u1F30D""#,
+        r#""\u{}""#,
+        r#""\u{1F30D""#,
+        r#""\u{1234567}""#,
+    ] {
+        let tokens = collect_tokens(Scanner::new(script));
+
+        assert_eq!(tokens[0].token_type, TokenType::Error, "{}", script);
+        assert!(
+            tokens[0].token.contains("Invalid unicode escape"),
+            "{}",
+            script
+        );
+    }
+
+    let tokens = collect_tokens(Scanner::new(r#""\u{D800}""#));
+    assert!(tokens[0]
+        .token
+        .contains("Invalid unicode code point 'D800'"));
+}
+
+#[test]
+fn skips_line_comments_and_keeps_line_numbers() {
+    let script = "// first\nx // trailing\n\n// another\ny";
+    let tokens = collect_tokens(Scanner::new(script));
+
+    let types: Vec<TokenType> = tokens.iter().map(|t| t.token_type.clone()).collect();
+    assert_eq!(
+        types,
+        [
+            TokenType::Identifier,
+            TokenType::NewLine,
+            TokenType::Identifier,
+            TokenType::Eof
+        ]
+    );
+    assert_eq!(tokens[0].line, 2);
+    assert_eq!(tokens[2].line, 5);
+    assert_eq!(tokens[2].column, 1);
+}
+
+#[test]
+fn keeps_line_numbers_across_blank_lines_with_spaces() {
+    let tokens = collect_tokens(Scanner::new("x\n   \n\t\ny"));
+
+    assert_eq!(tokens[2].token, "y");
+    assert_eq!(tokens[2].line, 4);
+    assert_eq!(tokens[2].column, 1);
+}
+
+#[test]
+fn skips_block_comments() {
+    let script = "x /* inline */ + /* spans\nlines */ y";
+    let tokens = collect_tokens(Scanner::new(script));
+
+    assert_eq!(tokens.len(), 4);
+    assert_eq!(tokens[1].token_type, TokenType::Plus);
+    assert_eq!(tokens[1].column, 16);
+    assert_eq!(tokens[2].token, "y");
+    assert_eq!(tokens[2].line, 2);
+    assert_eq!(tokens[2].column, 10);
+}
+
+#[test]
+fn balances_nested_block_comments() {
+    let tokens = collect_tokens(Scanner::new("/* outer /* inner */ still outer */ x"));
+
+    assert_eq!(tokens.len(), 2);
+    assert_eq!(tokens[0].token, "x");
+}
+
+#[test]
+fn rejects_unterminated_block_comment() {
+    let tokens = collect_tokens(Scanner::new("x\n/* outer /* inner */ never closed"));
+
+    let error = &tokens[2];
+    assert_eq!(error.token_type, TokenType::Error);
+    assert_eq!(error.token, "Unterminated block comment");
+    assert_eq!(error.line, 2);
+    assert_eq!(error.column, 1);
+}
+
+#[test]
+fn can_scan_doc_comments() {
+    let tokens = collect_tokens(Scanner::new("/// Adds numbers.\nfn add"));
+
+    assert_eq!(tokens[0].token_type, TokenType::DocComment);
+    assert_eq!(tokens[0].token, "/// Adds numbers.");
+    assert_eq!(tokens[1].token_type, TokenType::Fn);
+    assert_eq!(tokens[1].line, 2);
+}
+
+#[test]
+fn floor_division_is_not_a_comment() {
+    let tokens = collect_tokens(Scanner::new("7 //2"));
+
+    assert_eq!(tokens[1].token_type, TokenType::SlashSlash);
+    assert_eq!(tokens[2].token_type, TokenType::Number);
+}
@@ -237,6 +237,39 @@ val x = [1, 2, 3].invalidMethod()
         .any(|e| e.message.contains("has no method named 'invalidMethod'")));
 }
 
+#[test]
+fn test_sum_with_an_unknown_operand_is_not_assumed_to_be_a_number() {
+    let program = r#"
+val g = [1].clone() + [2]
+g.push(3)
+val h = freeze([1]) + [2]
+h.push(3)
+"#;
+    let mut parser = Parser::new(program);
+    let ast = parser.parse().unwrap();
+
+    let mut analyzer = SemanticAnalyzer::new();
+    assert!(analyzer.analyze(&ast).is_ok());
+}
+
+#[test]
+fn test_invalid_method_on_sum_of_numbers() {
+    let program = r#"
+val x = (1 + 2).push(3)
+"#;
+    let mut parser = Parser::new(program);
+    let ast = parser.parse().unwrap();
+
+    let mut analyzer = SemanticAnalyzer::new();
+    let result = analyzer.analyze(&ast);
+
+    assert!(result.is_err());
+    assert!(result
+        .unwrap_err()
+        .iter()
+        .any(|e| e.message.contains("has no method named 'push'")));
+}
+
 #[test]
 fn test_typo_on_method_name_suggests_correction() {
     let program = r#"
@@ -273,6 +306,27 @@ val x = [1, 2, 3].notAMethod()
         .any(|e| e.message.contains("Available methods:")));
 }
 
+#[test]
+fn test_static_array_methods_are_not_instance_methods() {
+    let program = r#"
+val x = [1, 2, 3].range(1, 3)
+"#;
+    let mut parser = Parser::new(program);
+    let ast = parser.parse().unwrap();
+
+    let mut analyzer = SemanticAnalyzer::new();
+    let result = analyzer.analyze(&ast);
+
+    assert!(result.is_err());
+    let errors = result.unwrap_err();
+    let error = errors
+        .iter()
+        .find(|e| e.message.contains("has no method named 'range'"))
+        .unwrap();
+    assert_eq!(1, error.message.matches("fill").count());
+    assert!(!error.message.contains("from"));
+}
+
 #[test]
 fn test_method_on_tracked_variable_validates_correctly() {
     let program = r#"
@@ -1285,6 +1339,117 @@ fn test_continue_outside_function_in_loop() {
         .contains("Cannot use 'continue' outside of a loop"));
 }
 
+#[test]
+fn test_labeled_break_and_continue_valid() {
+    let program = r#"
+        outer: while (true) {
+            inner: for (x in [1, 2]) {
+                if (x == 1) {
+                    continue outer
+                }
+                break inner
+            }
+            break outer
+        }
+        "#;
+    let mut parser = Parser::new(program);
+    let ast = parser.parse().unwrap();
+
+    let mut analyzer = SemanticAnalyzer::new();
+    let result = analyzer.analyze(&ast);
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_break_with_undefined_label() {
+    let program = r#"
+        outer: while (true) {
+            break nope
+        }
+        "#;
+    let mut parser = Parser::new(program);
+    let ast = parser.parse().unwrap();
+
+    let mut analyzer = SemanticAnalyzer::new();
+    let result = analyzer.analyze(&ast);
+
+    assert!(result.is_err());
+    let errors = result.unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0]
+        .message
+        .contains("Undefined loop label 'nope' in 'break'"));
+}
+
+#[test]
+fn test_continue_with_label_of_finished_loop() {
+    let program = r#"
+        first: while (false) {
+        }
+        while (true) {
+            continue first
+        }
+        "#;
+    let mut parser = Parser::new(program);
+    let ast = parser.parse().unwrap();
+
+    let mut analyzer = SemanticAnalyzer::new();
+    let result = analyzer.analyze(&ast);
+
+    assert!(result.is_err());
+    let errors = result.unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0]
+        .message
+        .contains("Undefined loop label 'first' in 'continue'"));
+}
+
+#[test]
+fn test_label_of_loop_around_lambda_is_not_visible() {
+    let program = r#"
+        outer: while (true) {
+            val f = fn() {
+                break outer
+            }
+        }
+        "#;
+    let mut parser = Parser::new(program);
+    let ast = parser.parse().unwrap();
+
+    let mut analyzer = SemanticAnalyzer::new();
+    let result = analyzer.analyze(&ast);
+
+    assert!(result.is_err());
+    let errors = result.unwrap_err();
+    assert!(errors.iter().any(|error| error
+        .message
+        .contains("Cannot use 'break' outside of a loop")));
+}
+
+#[test]
+fn test_duplicate_label_in_nested_loops() {
+    let program = r#"
+        outer: while (true) {
+            outer: while (true) {
+                break outer
+            }
+        }
+        "#;
+    let mut parser = Parser::new(program);
+    let ast = parser.parse().unwrap();
+
+    let mut analyzer = SemanticAnalyzer::new();
+    let result = analyzer.analyze(&ast);
+
+    assert!(result.is_err());
+    let errors = result.unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0]
+        .message
+        .contains("Label 'outer' is already used by an enclosing loop"));
+}
+
 // =============================================================================
 // Postfix Increment/Decrement Operator Tests
 // =============================================================================
@@ -1618,3 +1783,75 @@ fn test_postfix_in_function_parameters() {
 
     assert!(result.is_ok());
 }
+
+fn unused_variable_warnings(program: &str) -> Vec<String> {
+    let mut parser = Parser::new(program);
+    let ast = parser.parse().unwrap();
+
+    let mut analyzer = SemanticAnalyzer::new();
+    assert!(analyzer.analyze(&ast).is_ok());
+    analyzer
+        .warnings()
+        .iter()
+        .map(|warning| warning.message.clone())
+        .collect()
+}
+
+#[test]
+fn test_unused_local_warns() {
+    let program =
+        "fn f() {\n    val unused = 1\n    var count = 0\n    count = 2\n    return 3\n}\n";
+    let warnings = unused_variable_warnings(program);
+
+    assert_eq!(2, warnings.len());
+    assert!(warnings[0].contains("'unused' is never read"));
+    assert!(warnings[1].contains("'count' is never read"));
+}
+
+#[test]
+fn test_read_local_does_not_warn() {
+    let program = r#"
+fn f() {
+    val a = 1
+    var b = 2
+    b = b + a
+    val add = x => x + b
+    return add
+}
+{
+    val shown = "block"
+    print(shown)
+}
+"#;
+    assert!(unused_variable_warnings(program).is_empty());
+}
+
+#[test]
+fn test_globals_params_loop_variables_and_underscores_do_not_warn() {
+    let program = r#"
+val global = 1
+fn f(param) {
+    val _ignored = 2
+    for (i in 1..3) {
+        print("tick")
+    }
+}
+"#;
+    assert!(unused_variable_warnings(program).is_empty());
+}
+
+#[test]
+fn test_unused_warning_points_at_declaration() {
+    let mut parser = Parser::new("fn f() {\n    print(1)\n    val late = 2\n}\n");
+    let ast = parser.parse().unwrap();
+
+    let mut analyzer = SemanticAnalyzer::new();
+    assert!(analyzer.analyze(&ast).is_ok());
+    let warnings = analyzer.warnings();
+    assert_eq!(1, warnings.len());
+    assert_eq!(
+        crate::common::errors::Severity::Warning,
+        warnings[0].severity
+    );
+    assert_eq!(3, warnings[0].location.line);
+}
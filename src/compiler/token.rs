@@ -12,24 +12,33 @@ pub(crate) enum TokenType {
     Dot,
     DotDot,
     DotDotEqual,
+    DotDotDot,
     Minus,
     MinusMinus,
+    MinusEqual,
     Plus,
     PlusPlus,
+    PlusEqual,
     Percent,
+    PercentEqual,
     Semicolon,
     Colon,
     Question,
+    QuestionQuestion,
+    QuestionDot,
     NewLine,
     Slash,
+    SlashEqual,
     SlashSlash,
     Star,
+    StarEqual,
     StarStar,
 
     Bang,
     BangEqual,
     Equal,
     EqualEqual,
+    FatArrow,
     Greater,
     GreaterEqual,
     Less,
@@ -46,27 +55,40 @@ pub(crate) enum TokenType {
     Identifier,
     String,
     InterpolatedString,
+    RawString,
+    DocComment,
     Number,
 
     And,
     Break,
+    Catch,
     Continue,
+    Do,
     Else,
+    Enum,
+    Export,
     False,
+    Finally,
     For,
     Fn,
     If,
+    Import,
+    Match,
     Nil,
     Or,
     Return,
     Struct,
     Super,
     This,
+    Throw,
     True,
+    Try,
     Val,
     Var,
     While,
+    With,
     In,
+    Is,
 
     Error,
 
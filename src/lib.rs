@@ -1,3 +1,4 @@
+pub mod binary;
 pub mod common;
 pub mod compiler;
 pub mod macros;
@@ -5,3 +6,18 @@ pub mod vm;
 
 #[cfg(target_arch = "wasm32")]
 pub mod wasm;
+
+use common::errors::CompilationError;
+use common::Chunk;
+
+/// Compiles source code into its top-level chunk without running it.
+///
+/// The chunk can be run later, as often as needed, with
+/// [`vm::VirtualMachine::execute_chunk`]. On failure all errors found are returned.
+pub fn compile(source: &str) -> Result<Chunk, Vec<CompilationError>> {
+    let builtin = common::stdlib::create_builtin_objects(Vec::new());
+    let mut compiler = compiler::Compiler::new(builtin);
+    compiler
+        .compile(source)
+        .ok_or_else(|| compiler.get_structured_errors())
+}
@@ -17,7 +17,7 @@ macro_rules! string {
     ($value: expr) => {
         $crate::common::Value::Object(std::rc::Rc::from($crate::common::Object::String(
             $crate::common::ObjString {
-                value: std::rc::Rc::from($value),
+                value: $crate::common::string_interner::intern(AsRef::<str>::as_ref(&$value)),
             },
         )))
     };
@@ -33,10 +33,10 @@ macro_rules! nil {
 #[macro_export]
 macro_rules! as_number {
     ($value: expr) => {
-        if let $crate::common::Value::Number(value) = $value {
-            value
-        } else {
-            panic!("Expected number, got {:?}", $value);
+        match $value {
+            $crate::common::Value::Number(value) => value,
+            $crate::common::Value::Int(value) => value as f64,
+            value => panic!("Expected number, got {:?}", value),
         }
     };
 }
@@ -1,10 +1,16 @@
 use colored::Colorize;
-use std::io::{Read, Write};
+use rustyline::error::ReadlineError;
+use rustyline::DefaultEditor;
+use std::io::Read;
 use std::process::exit;
 
+use std::env;
 use std::fs::File;
-use std::{env, io};
+use std::path::{Path, PathBuf};
 
+use neon::binary::{read_binary_file, write_binary_file, Compression};
+use neon::common::error_renderer::ErrorRenderer;
+use neon::compiler::{input_status, InputStatus};
 use neon::vm::{Result, VirtualMachine};
 
 fn main() {
@@ -20,12 +26,23 @@ fn main() {
             "help" | "--help" | "-h" => {
                 print_help();
             }
+            "compile" => compile_file(&args[2..]),
+            "run" => {
+                if args.len() < 3 {
+                    eprintln!("Usage: neon run <file.nbc> [args...]");
+                    exit(64);
+                }
+                run_bytecode_file(&args[2], args[3..].to_vec());
+            }
             _ => {
                 // Auto-detection: check if the file has .nbc extension
                 let file_path = &args[1];
-                // Interpret as source
                 let script_args = args[2..].to_vec();
-                run_file(file_path, script_args);
+                if file_path.ends_with(".nbc") {
+                    run_bytecode_file(file_path, script_args);
+                } else {
+                    run_file(file_path, script_args);
+                }
             }
         }
     }
@@ -58,16 +75,30 @@ fn print_tagline() {
 fn run_repl() {
     println!("Type 'exit' or Ctrl+C to quit");
 
+    let mut editor = match DefaultEditor::new() {
+        Ok(editor) => editor,
+        Err(error) => {
+            eprintln!("{} {}", "error:".red().bold(), error);
+            exit(74);
+        }
+    };
+    let history = history_path();
+    if let Some(history) = &history {
+        // There is no history yet on the first run
+        let _ = editor.load_history(history);
+    }
+
     // REPL has no command-line arguments
     let mut vm = VirtualMachine::new();
-    loop {
-        print_prompt();
-        let line = read_line();
-        if line == "exit" {
-            println!("Ciao 👋 - May your coffee be strong");
+    while let Some(input) = read_input(&mut editor) {
+        if input.trim() == "exit" {
             break;
         }
-        let result = vm.interpret(line);
+        let _ = editor.add_history_entry(input.as_str());
+        let result = vm.interpret(input);
+        if !vm.get_warnings().is_empty() {
+            eprintln!("{}", vm.get_formatted_warnings("<repl>"));
+        }
         match result {
             Result::Ok => {}
             Result::CompileError => {
@@ -75,22 +106,55 @@ fn run_repl() {
                 eprintln!("{}", formatted_errors);
             }
             Result::RuntimeError => eprintln!("{}", "Runtime error.".red()),
+            Result::Exit(code) => {
+                save_history(&mut editor, &history);
+                exit(code)
+            }
         }
         println!();
     }
+    save_history(&mut editor, &history);
+    println!("Ciao 👋 - May your coffee be strong");
 }
 
-fn read_line() -> String {
+/// Reads lines until they form a complete statement, prompting with `..` for continuation
+/// lines. Ctrl+C discards the lines read so far, or ends the session if there are none;
+/// Ctrl+D ends it.
+fn read_input(editor: &mut DefaultEditor) -> Option<String> {
     let mut input = String::new();
-    io::stdin()
-        .read_line(&mut input)
-        .expect("Failed to read line");
-    String::from(input.trim())
+    loop {
+        let prompt = if input.is_empty() { ">> " } else { ".. " };
+        match editor.readline(prompt) {
+            Ok(line) => {
+                if !input.is_empty() {
+                    input.push('\n');
+                }
+                input.push_str(&line);
+                if input_status(&input) != InputStatus::Incomplete {
+                    return Some(input);
+                }
+            }
+            Err(ReadlineError::Interrupted) if !input.is_empty() => input.clear(),
+            Err(ReadlineError::Interrupted | ReadlineError::Eof) => return None,
+            Err(error) => {
+                eprintln!("{} {}", "error:".red().bold(), error);
+                return None;
+            }
+        }
+    }
 }
 
-fn print_prompt() {
-    print!(">> ");
-    io::stdout().flush().unwrap();
+/// `~/.neon_history`, or None without a home directory
+fn history_path() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| Path::new(&home).join(".neon_history"))
+}
+
+fn save_history(editor: &mut DefaultEditor, history: &Option<PathBuf>) {
+    if let Some(history) = history {
+        if let Err(error) = editor.save_history(history) {
+            eprintln!("{} {}: {}", "error:".red().bold(), history.display(), error);
+        }
+    }
 }
 
 fn run_file(path: &String, args: Vec<String>) {
@@ -98,8 +162,12 @@ fn run_file(path: &String, args: Vec<String>) {
 
     let source = read_file(path);
     let mut vm = VirtualMachine::with_args(args);
+    vm.set_script_path(path);
 
     let result: Result = vm.interpret(source);
+    if !vm.get_warnings().is_empty() {
+        eprintln!("{}", vm.get_formatted_warnings(path));
+    }
     match result {
         Result::Ok => (),
         Result::CompileError => {
@@ -109,7 +177,62 @@ fn run_file(path: &String, args: Vec<String>) {
             exit(65);
         }
         Result::RuntimeError => exit(70),
+        Result::Exit(code) => exit(code),
+    }
+}
+
+fn run_bytecode_file(path: &str, args: Vec<String>) {
+    println!("Running file: {} ", path);
+
+    let chunk = match read_binary_file(path) {
+        Ok(chunk) => chunk,
+        Err(error) => {
+            eprintln!("{} {}: {}", "error:".red().bold(), path, error);
+            exit(65);
+        }
+    };
+    let mut vm = VirtualMachine::with_args(args);
+
+    match vm.execute_chunk(chunk) {
+        Result::Ok => (),
+        Result::CompileError => exit(65),
+        Result::RuntimeError => exit(70),
+        Result::Exit(code) => exit(code),
+    }
+}
+
+/// `neon compile <input.n> [-o <output.nbc>] [--compress]`; the output defaults to the input
+/// with `.nbc`
+fn compile_file(args: &[String]) {
+    let compression = if args.iter().any(|arg| arg == "--compress") {
+        Compression::Deflate
+    } else {
+        Compression::None
+    };
+    let args: Vec<&String> = args.iter().filter(|arg| *arg != "--compress").collect();
+    let (input, output) = match args.as_slice() {
+        [input] => (*input, Path::new(input).with_extension("nbc")),
+        [input, flag, output] if *flag == "-o" => (*input, PathBuf::from(output)),
+        _ => {
+            eprintln!("Usage: neon compile <input.n> [-o <output.nbc>] [--compress]");
+            exit(64);
+        }
+    };
+
+    let source = read_file(input);
+    let chunk = match neon::compile(&source) {
+        Ok(chunk) => chunk,
+        Err(errors) => {
+            let renderer = ErrorRenderer::default();
+            eprintln!("{}", renderer.render_errors(&errors, &source, input));
+            exit(65);
+        }
+    };
+    if let Err(error) = write_binary_file(&output, &chunk, compression) {
+        eprintln!("{} {}: {}", "error:".red().bold(), output.display(), error);
+        exit(74);
     }
+    println!("Compiled {} -> {}", input, output.display());
 }
 
 fn read_file(path: &str) -> String {
@@ -132,8 +255,8 @@ fn print_help() {
     println!("  neon                     Start interactive REPL");
     println!("  neon <file.n>           Interpret source file");
     println!("  neon <file.nbc>         Execute compiled binary");
-    println!("  neon compile <input.n> [-o <output.nbc>]");
-    println!("                          Compile source to binary");
+    println!("  neon compile <input.n> [-o <output.nbc>] [--compress]");
+    println!("                          Compile source to binary, optionally deflated");
     println!("  neon run <file.nbc> [args...]");
     println!("                          Execute compiled binary");
     println!("  neon help               Show this help message");
@@ -0,0 +1,384 @@
+//! Instruction dispatch for the run loop.
+//!
+//! The opcode byte indexes a table of small handler functions, built once at compile time
+//! from an exhaustive match over `OpCode`. This keeps the run loop itself tiny, which is
+//! considerably faster than one large match with every handler inlined into it. All 256
+//! byte values have an entry; bytes that aren't opcodes stop the VM with a runtime error.
+
+use crate::common::opcodes::OpCode;
+use crate::common::BitsSize;
+use crate::vm::{Result, VirtualMachine};
+use crate::{boolean, nil};
+
+/// What the run loop does after an instruction's handler returns
+pub(in crate::vm) enum Flow {
+    /// Continue with the instruction after this one
+    Next,
+    /// The handler already moved the instruction pointer, e.g. into a new call frame
+    Jumped,
+    /// Stop running and return this result
+    Stop(Result),
+}
+
+type Handler = fn(&mut VirtualMachine) -> Flow;
+
+pub(in crate::vm) static DISPATCH: [Handler; 256] = dispatch_table();
+
+const fn dispatch_table() -> [Handler; 256] {
+    let mut table: [Handler; 256] = [invalid_opcode; 256];
+    let mut byte = 0;
+    while byte < OpCode::COUNT {
+        table[byte] = handler(OpCode::from_u8(byte as u8));
+        byte += 1;
+    }
+    table
+}
+
+const fn handler(op_code: OpCode) -> Handler {
+    match op_code {
+        OpCode::Return => |vm| match vm.fn_return() {
+            Some(result) => Flow::Stop(result),
+            None => Flow::Jumped,
+        },
+        OpCode::Constant => |vm| {
+            vm.fn_constant();
+            Flow::Next
+        },
+        OpCode::Constant2 => |vm| {
+            vm.fn_constant2();
+            Flow::Next
+        },
+        OpCode::Constant4 => |vm| {
+            vm.fn_constant4();
+            Flow::Next
+        },
+        OpCode::Negate => |vm| match vm.fn_negate() {
+            Some(result) => Flow::Stop(result),
+            None => Flow::Next,
+        },
+        OpCode::Add => |vm| match vm.fn_add() {
+            Some(result) => Flow::Stop(result),
+            None => Flow::Next,
+        },
+        OpCode::Subtract => |vm| match vm.fn_subtract() {
+            Some(result) => Flow::Stop(result),
+            None => Flow::Next,
+        },
+        OpCode::Multiply => |vm| match vm.fn_multiply() {
+            Some(result) => Flow::Stop(result),
+            None => Flow::Next,
+        },
+        OpCode::Divide => |vm| match vm.fn_divide() {
+            Some(result) => Flow::Stop(result),
+            None => Flow::Next,
+        },
+        OpCode::FloorDivide => |vm| match vm.fn_floor_divide() {
+            Some(result) => Flow::Stop(result),
+            None => Flow::Next,
+        },
+        OpCode::Modulo => |vm| match vm.fn_modulo() {
+            Some(result) => Flow::Stop(result),
+            None => Flow::Next,
+        },
+        OpCode::Exponent => |vm| match vm.fn_exponent() {
+            Some(result) => Flow::Stop(result),
+            None => Flow::Next,
+        },
+        OpCode::Nil => |vm| {
+            vm.push(nil!());
+            Flow::Next
+        },
+        OpCode::True => |vm| {
+            vm.push(boolean!(true));
+            Flow::Next
+        },
+        OpCode::False => |vm| {
+            vm.push(boolean!(false));
+            Flow::Next
+        },
+        OpCode::Equal => |vm| {
+            vm.fn_equal();
+            Flow::Next
+        },
+        OpCode::Same => |vm| {
+            vm.fn_same();
+            Flow::Next
+        },
+        OpCode::UnpackElement => |vm| match vm.fn_unpack_element() {
+            Some(result) => Flow::Stop(result),
+            None => Flow::Next,
+        },
+        OpCode::UnpackRest => |vm| match vm.fn_unpack_rest() {
+            Some(result) => Flow::Stop(result),
+            None => Flow::Next,
+        },
+        OpCode::Greater => |vm| match vm.fn_greater() {
+            Some(result) => Flow::Stop(result),
+            None => Flow::Next,
+        },
+        OpCode::Less => |vm| match vm.fn_less() {
+            Some(result) => Flow::Stop(result),
+            None => Flow::Next,
+        },
+        OpCode::Not => |vm| {
+            vm.fn_not();
+            Flow::Next
+        },
+        OpCode::String => |vm| {
+            vm.fn_string();
+            Flow::Next
+        },
+        OpCode::String2 => |vm| {
+            vm.fn_string2();
+            Flow::Next
+        },
+        OpCode::String4 => |vm| {
+            vm.fn_string4();
+            Flow::Next
+        },
+        OpCode::Pop => |vm| {
+            vm.pop();
+            Flow::Next
+        },
+        OpCode::GetLocal => |vm| {
+            vm.fn_get_local(BitsSize::Eight);
+            Flow::Next
+        },
+        OpCode::GetLocal2 => |vm| {
+            vm.fn_get_local(BitsSize::Sixteen);
+            Flow::Next
+        },
+        OpCode::GetLocal4 => |vm| {
+            vm.fn_get_local(BitsSize::ThirtyTwo);
+            Flow::Next
+        },
+        OpCode::SetLocal => |vm| {
+            vm.fn_set_local(BitsSize::Eight);
+            Flow::Next
+        },
+        OpCode::SetLocal2 => |vm| {
+            vm.fn_set_local(BitsSize::Sixteen);
+            Flow::Next
+        },
+        OpCode::SetLocal4 => |vm| {
+            vm.fn_set_local(BitsSize::ThirtyTwo);
+            Flow::Next
+        },
+        OpCode::GetBuiltin => |vm| {
+            vm.fn_get_builtin(BitsSize::Eight);
+            Flow::Next
+        },
+        OpCode::GetBuiltin2 => |vm| {
+            vm.fn_get_builtin(BitsSize::Sixteen);
+            Flow::Next
+        },
+        OpCode::GetBuiltin4 => |vm| {
+            vm.fn_get_builtin(BitsSize::ThirtyTwo);
+            Flow::Next
+        },
+        OpCode::GetGlobal => |vm| {
+            vm.fn_get_global(BitsSize::Eight);
+            Flow::Next
+        },
+        OpCode::GetGlobal2 => |vm| {
+            vm.fn_get_global(BitsSize::Sixteen);
+            Flow::Next
+        },
+        OpCode::GetGlobal4 => |vm| {
+            vm.fn_get_global(BitsSize::ThirtyTwo);
+            Flow::Next
+        },
+        OpCode::SetGlobal => |vm| {
+            vm.fn_set_global(BitsSize::Eight);
+            Flow::Next
+        },
+        OpCode::SetGlobal2 => |vm| {
+            vm.fn_set_global(BitsSize::Sixteen);
+            Flow::Next
+        },
+        OpCode::SetGlobal4 => |vm| {
+            vm.fn_set_global(BitsSize::ThirtyTwo);
+            Flow::Next
+        },
+        OpCode::JumpIfFalse => |vm| {
+            vm.fn_jump_if_false();
+            Flow::Next
+        },
+        OpCode::Jump => |vm| {
+            vm.fn_jump();
+            Flow::Next
+        },
+        OpCode::Loop => |vm| {
+            vm.fn_loop();
+            Flow::Next
+        },
+        OpCode::Call => |vm| match vm.fn_call() {
+            Some(result) => Flow::Stop(result),
+            None => Flow::Jumped,
+        },
+        OpCode::GetField => |vm| match vm.fn_get_field(BitsSize::Eight) {
+            Some(result) => Flow::Stop(result),
+            None => Flow::Next,
+        },
+        OpCode::GetField2 => |vm| match vm.fn_get_field(BitsSize::Sixteen) {
+            Some(result) => Flow::Stop(result),
+            None => Flow::Next,
+        },
+        OpCode::GetField4 => |vm| match vm.fn_get_field(BitsSize::ThirtyTwo) {
+            Some(result) => Flow::Stop(result),
+            None => Flow::Next,
+        },
+        OpCode::SetField => |vm| match vm.fn_set_field(BitsSize::Eight) {
+            Some(result) => Flow::Stop(result),
+            None => Flow::Next,
+        },
+        OpCode::SetField2 => |vm| match vm.fn_set_field(BitsSize::Sixteen) {
+            Some(result) => Flow::Stop(result),
+            None => Flow::Next,
+        },
+        OpCode::SetField4 => |vm| match vm.fn_set_field(BitsSize::ThirtyTwo) {
+            Some(result) => Flow::Stop(result),
+            None => Flow::Next,
+        },
+        OpCode::CreateMap => |vm| {
+            vm.fn_create_map();
+            Flow::Next
+        },
+        OpCode::CreateArray => |vm| {
+            vm.fn_create_array();
+            Flow::Next
+        },
+        OpCode::CreateSet => |vm| {
+            vm.fn_create_set();
+            Flow::Next
+        },
+        OpCode::GetIndex => |vm| match vm.fn_get_index() {
+            Some(result) => Flow::Stop(result),
+            None => Flow::Next,
+        },
+        OpCode::SetIndex => |vm| match vm.fn_set_index() {
+            Some(result) => Flow::Stop(result),
+            None => Flow::Next,
+        },
+        OpCode::GetIterator => |vm| match vm.fn_get_iterator() {
+            Some(result) => Flow::Stop(result),
+            None => Flow::Next,
+        },
+        OpCode::IteratorNext => |vm| match vm.fn_iterator_next() {
+            Some(result) => Flow::Stop(result),
+            None => Flow::Next,
+        },
+        OpCode::IteratorDone => |vm| {
+            vm.fn_iterator_done();
+            Flow::Next
+        },
+        OpCode::PopIterator => |vm| {
+            if vm.iterator_stack.pop().is_none() {
+                vm.runtime_error("No iterator to pop");
+                return Flow::Stop(Result::RuntimeError);
+            }
+            Flow::Next
+        },
+        OpCode::CreateRange => |vm| match vm.fn_create_range() {
+            Some(result) => Flow::Stop(result),
+            None => Flow::Next,
+        },
+        OpCode::ToString => |vm| {
+            vm.fn_to_string();
+            Flow::Next
+        },
+        OpCode::BitwiseAnd => |vm| match vm.fn_bitwise_and() {
+            Some(result) => Flow::Stop(result),
+            None => Flow::Next,
+        },
+        OpCode::BitwiseOr => |vm| match vm.fn_bitwise_or() {
+            Some(result) => Flow::Stop(result),
+            None => Flow::Next,
+        },
+        OpCode::BitwiseXor => |vm| match vm.fn_bitwise_xor() {
+            Some(result) => Flow::Stop(result),
+            None => Flow::Next,
+        },
+        OpCode::BitwiseNot => |vm| match vm.fn_bitwise_not() {
+            Some(result) => Flow::Stop(result),
+            None => Flow::Next,
+        },
+        OpCode::LeftShift => |vm| match vm.fn_left_shift() {
+            Some(result) => Flow::Stop(result),
+            None => Flow::Next,
+        },
+        OpCode::RightShift => |vm| match vm.fn_right_shift() {
+            Some(result) => Flow::Stop(result),
+            None => Flow::Next,
+        },
+        OpCode::Dup => |vm| {
+            vm.push(vm.peek(0));
+            Flow::Next
+        },
+        OpCode::Dup2 => |vm| {
+            vm.push(vm.peek(1));
+            vm.push(vm.peek(1));
+            Flow::Next
+        },
+        OpCode::Tuck => |vm| {
+            let top = vm.pop();
+            let below = vm.pop();
+            vm.push(top.clone());
+            vm.push(below);
+            vm.push(top);
+            Flow::Next
+        },
+        OpCode::Import => |vm| {
+            vm.fn_import();
+            Flow::Jumped
+        },
+        OpCode::ExportModule => |vm| {
+            vm.fn_export_module();
+            Flow::Next
+        },
+        OpCode::Closure => |vm| {
+            vm.fn_closure();
+            Flow::Next
+        },
+        OpCode::GetUpvalue => |vm| {
+            vm.fn_get_upvalue();
+            Flow::Next
+        },
+        OpCode::SetUpvalue => |vm| {
+            vm.fn_set_upvalue();
+            Flow::Next
+        },
+        OpCode::JumpIfArgSupplied => |vm| {
+            vm.fn_jump_if_arg_supplied();
+            Flow::Next
+        },
+        OpCode::CallNamed => |vm| match vm.fn_call_named() {
+            Some(result) => Flow::Stop(result),
+            None => Flow::Jumped,
+        },
+        OpCode::TailCall => |vm| match vm.fn_tail_call() {
+            Some(result) => Flow::Stop(result),
+            None => Flow::Jumped,
+        },
+        OpCode::TryBegin => |vm| {
+            vm.fn_try_begin();
+            Flow::Next
+        },
+        OpCode::TryEnd => |vm| {
+            vm.fn_try_end();
+            Flow::Next
+        },
+        OpCode::Throw => |vm| Flow::Stop(vm.fn_throw()),
+        OpCode::CloseUpvalues => |vm| {
+            vm.fn_close_upvalues();
+            Flow::Next
+        },
+    }
+}
+
+fn invalid_opcode(vm: &mut VirtualMachine) -> Flow {
+    let frame = vm.current_frame();
+    let byte = frame.function.chunk.read_u8(frame.ip);
+    vm.runtime_error(&format!("Invalid opcode 0x{:02x}.", byte));
+    Flow::Stop(Result::RuntimeError)
+}
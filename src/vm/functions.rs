@@ -1,15 +1,17 @@
+use crate::common::constants::MAX_SEQUENCE_LENGTH;
 use crate::common::method_registry::NativeCallable;
 use crate::common::{BitsSize, CallFrame, ObjInstance, ObjNativeFunction, ObjStruct, Value};
-use crate::common::{ObjFunction, Object};
+use crate::common::{MapKey, SetKey};
+use crate::common::{ObjError, ObjFunction, ObjModule, Object, Upvalue};
+use crate::vm::dispatch::{Flow, DISPATCH};
 use crate::vm::Result;
-use crate::vm::VirtualMachine;
-use crate::{as_number, boolean, is_false_like, number, string};
+use crate::vm::{RaisedError, TryHandler, VirtualMachine, MAX_CALLBACK_DEPTH};
+use crate::{boolean, is_false_like, number, string};
+use std::cell::RefCell;
+use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::rc::Rc;
 
-/// Registry index for the print() function (always at index 0)
-const PRINT_METHOD_INDEX: u32 = 0;
-
 impl VirtualMachine {
     #[inline(always)]
     pub(in crate::vm) fn fn_to_string(&mut self) {
@@ -66,8 +68,11 @@ impl VirtualMachine {
 
         let frame = self.current_frame_mut();
         frame.ip += 2; // Skip CALL opcode and arg_count byte
+        self.call_value(arg_count)
+    }
 
-        // Get the callable from the stack
+    /// Calls the value on top of the stack with the `arg_count` arguments below it
+    fn call_value(&mut self, arg_count: usize) -> Option<Result> {
         let callable_value = self.peek(0);
 
         let result = match &callable_value {
@@ -75,8 +80,26 @@ impl VirtualMachine {
                 Object::Function(callable) => return self.call_function(arg_count, &callable),
                 Object::Struct(r#struct) => return self.instantiate_struct(arg_count, r#struct),
                 Object::NativeFunction(callable) => {
+                    if let Some(method) = self.find_struct_method(arg_count, callable) {
+                        return self.call_struct_method(arg_count, &method);
+                    }
+                    if let Some(export) = self.find_module_export(arg_count, callable) {
+                        return match export {
+                            Ok(export) => self.call_module_export(arg_count, export),
+                            Err(error) => {
+                                self.runtime_error(&error);
+                                Some(Result::RuntimeError)
+                            }
+                        };
+                    }
                     match self.call_native_function(arg_count, callable) {
+                        Ok(_) if self.exit_code.is_some() => {
+                            return self.exit_code.take().map(Result::Exit);
+                        }
                         Ok(value) => value,
+                        Err(_) if self.callback_stop.is_some() => {
+                            return self.callback_stop.take();
+                        }
                         Err(error) => {
                             self.runtime_error(&error);
                             return Some(Result::RuntimeError);
@@ -101,6 +124,229 @@ impl VirtualMachine {
         None
     }
 
+    /// `return callee(args)`: a function called here takes over the frame of the returning
+    /// function, so recursion in tail position runs in constant frame depth. Anything else is
+    /// called like with `Call`, and the `Return` that follows returns its result.
+    pub(in crate::vm) fn fn_tail_call(&mut self) -> Option<Result> {
+        let (arg_count, slot_start) = {
+            let frame = self.current_frame();
+            (
+                frame.function.chunk.read_u8(frame.ip + 1) as usize,
+                frame.slot_start,
+            )
+        };
+        // The script's frame isn't a function's, there is nothing to return to
+        if self.call_frames.len() == 1 {
+            return self.fn_call();
+        }
+
+        let function = match self.peek(0) {
+            Value::Object(obj) => match obj.as_ref() {
+                Object::Function(function) => Rc::clone(function),
+                _ => return self.fn_call(),
+            },
+            _ => return self.fn_call(),
+        };
+        if !self.check_arity(&function, arg_count, 0) {
+            return Some(Result::RuntimeError);
+        }
+
+        // Move [args..., callee] down over the returning function's slots and drop its frame
+        let frame_start = (slot_start + 1) as usize;
+        self.close_upvalues(frame_start);
+        let call_start = self.stack.len() - arg_count - 1;
+        self.stack.drain(frame_start..call_start);
+        self.call_frames.pop();
+        self.call_function(arg_count, &&function)
+    }
+
+    /// For a by-name method call on a struct instance, returns the struct's method of that name
+    fn find_struct_method(
+        &self,
+        arg_count: usize,
+        callable: &ObjNativeFunction,
+    ) -> Option<Rc<ObjFunction>> {
+        if callable.method_index != u32::MAX || arg_count == 0 {
+            return None;
+        }
+        let receiver = &self.stack[self.stack.len() - arg_count - 1];
+        match receiver {
+            Value::Object(obj) => match obj.as_ref() {
+                Object::Instance(instance) => instance
+                    .borrow()
+                    .r#struct
+                    .methods
+                    .get(&callable.method_name)
+                    .cloned(),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Calls a struct method; the receiver already sits in the slot of its `this` parameter
+    fn call_struct_method(&mut self, arg_count: usize, method: &Rc<ObjFunction>) -> Option<Result> {
+        if !self.check_arity(method, arg_count, 1) {
+            return Some(Result::RuntimeError);
+        }
+        let callable_slot = self.stack.len() - 1;
+        self.stack[callable_slot] = Value::Object(Rc::new(Object::Function(Rc::clone(method))));
+        self.call_function(arg_count, &method)
+    }
+
+    /// For a by-name call on a module, `M.add(1, 2)`, returns the export of that name
+    fn find_module_export(
+        &self,
+        arg_count: usize,
+        callable: &ObjNativeFunction,
+    ) -> Option<std::result::Result<Value, String>> {
+        if callable.method_index != u32::MAX || arg_count == 0 {
+            return None;
+        }
+        let receiver = &self.stack[self.stack.len() - arg_count - 1];
+        match receiver {
+            Value::Object(obj) => match obj.as_ref() {
+                Object::Module(module) => Some(
+                    module
+                        .exports
+                        .get(&callable.method_name)
+                        .cloned()
+                        .ok_or_else(|| {
+                            format!(
+                                "Module '{}' has no export '{}'.",
+                                module.name, callable.method_name
+                            )
+                        }),
+                ),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Calls a module's export with the arguments after the module, which is dropped
+    fn call_module_export(&mut self, arg_count: usize, export: Value) -> Option<Result> {
+        let receiver_slot = self.stack.len() - arg_count - 1;
+        self.stack.remove(receiver_slot);
+        let callable_slot = self.stack.len() - 1;
+        self.stack[callable_slot] = export;
+        self.call_value(arg_count - 1)
+    }
+
+    /// `Import`: pushes the cached module, or runs the module's function in a frame of its
+    /// own. The module's code ends with `ExportModule`, which caches it, and returns it.
+    pub(in crate::vm) fn fn_import(&mut self) {
+        self.current_frame_mut().ip += 1;
+        let function = match self.pop() {
+            Value::Object(obj) => match obj.as_ref() {
+                Object::Function(function) => Rc::clone(function),
+                _ => unreachable!("Import operand must be a function"),
+            },
+            _ => unreachable!("Import operand must be a function"),
+        };
+        if let Some(module) = self.module_cache.get(&function.name) {
+            self.push(module.clone());
+            return;
+        }
+
+        // Like the script frame, the module frame has no function object on the stack
+        self.call_frames.push(CallFrame {
+            function,
+            ip: 0,
+            slot_start: self.stack.len() as isize - 1,
+            arg_count: 0,
+        });
+    }
+
+    pub(in crate::vm) fn fn_export_module(&mut self) {
+        let (count, name) = {
+            let frame = self.current_frame();
+            (
+                frame.function.chunk.read_u16(frame.ip + 1) as usize,
+                frame.function.name.clone(),
+            )
+        };
+        self.current_frame_mut().ip += 2;
+
+        let pairs = self.stack.split_off(self.stack.len() - count * 2);
+        let exports = pairs
+            .chunks(2)
+            .map(|pair| (pair[0].to_string(), pair[1].clone()))
+            .collect();
+        let module = Value::Object(Rc::new(Object::Module(Rc::new(ObjModule {
+            name: name.clone(),
+            exports,
+        }))));
+        self.module_cache.insert(name, module.clone());
+        self.push(module);
+    }
+
+    pub(in crate::vm) fn fn_try_begin(&mut self) {
+        let frame = self.current_frame();
+        let offset = frame.function.chunk.read_u32(frame.ip + 1) as usize;
+        let handler = TryHandler {
+            frame_depth: self.call_frames.len(),
+            stack_len: self.stack.len(),
+            iterators: self.iterator_stack.len(),
+            catch_ip: frame.ip + 5 + offset,
+        };
+        self.try_handlers.push(handler);
+        self.current_frame_mut().ip += 4;
+    }
+
+    pub(in crate::vm) fn fn_try_end(&mut self) {
+        self.try_handlers.pop();
+    }
+
+    /// Unwinds to the `catch` block of the innermost `try` block and pushes the raised error
+    /// for it. Only `try` blocks running in a frame above `base_depth` are considered, so
+    /// a callback run by a native doesn't unwind into the frames of the native's caller.
+    pub(in crate::vm) fn catch_runtime_error(&mut self, base_depth: usize) -> bool {
+        if self
+            .try_handlers
+            .last()
+            .is_none_or(|handler| handler.frame_depth <= base_depth)
+        {
+            return false;
+        }
+        let handler = self.try_handlers.pop().unwrap();
+        self.close_upvalues(handler.stack_len);
+        self.stack.truncate(handler.stack_len);
+        self.call_frames.truncate(handler.frame_depth);
+        self.iterator_stack.truncate(handler.iterators);
+
+        let error = match self.raised_error.take() {
+            Some(RaisedError {
+                thrown: Some(value),
+                ..
+            }) => value,
+            error => Value::Object(Rc::new(Object::Error(Rc::new(ObjError {
+                message: error.map(|error| error.message).unwrap_or_default(),
+            })))),
+        };
+        self.push(error);
+        self.current_frame_mut().ip = handler.catch_ip;
+        true
+    }
+
+    /// Raises the popped value. Rethrowing a caught error object keeps its message for the
+    /// report if nothing catches it again.
+    pub(in crate::vm) fn fn_throw(&mut self) -> Result {
+        let value = self.pop();
+        let message = match &value {
+            Value::Object(object) => match object.as_ref() {
+                Object::Error(error) => error.message.clone(),
+                _ => format!("Uncaught error: {}", value),
+            },
+            _ => format!("Uncaught error: {}", value),
+        };
+        self.runtime_error(&message);
+        if let Some(error) = &mut self.raised_error {
+            error.thrown = Some(value);
+        }
+        Result::RuntimeError
+    }
+
     fn call_native_function(
         &mut self,
         arg_count: usize,
@@ -119,45 +365,97 @@ impl VirtualMachine {
         let args_end = stack_len - 1;
         let args: Vec<Value> = self.stack[args_start..args_end].to_vec();
 
-        #[cfg(any(test, debug_assertions, target_arch = "wasm32"))]
-        {
-            if callable.method_index == PRINT_METHOD_INDEX {
-                self.print_to_vm_buffer(arg_count);
-            }
+        native_callable.call(self, &args)
+    }
+
+    /// Calls a function value on behalf of a native, such as the predicate of `find`, and
+    /// runs it until it returns. When the callback stops the program instead, the outcome is
+    /// kept in `callback_stop` and the error carries no message; the native should return
+    /// it as is. Callbacks nested deeper than `MAX_CALLBACK_DEPTH` are a stack overflow.
+    pub(crate) fn call_callback(
+        &mut self,
+        callee: &Value,
+        args: &[Value],
+    ) -> std::result::Result<Value, String> {
+        if self.callback_depth >= MAX_CALLBACK_DEPTH {
+            self.runtime_error(&format!(
+                "Stack overflow: callbacks nested more than {} deep.",
+                MAX_CALLBACK_DEPTH
+            ));
+            self.callback_stop = Some(Result::RuntimeError);
+            return Err(String::new());
         }
-        native_callable.function()(&args)
+        self.callback_depth += 1;
+        let result = self.run_callback(callee, args);
+        self.callback_depth -= 1;
+        result
     }
 
-    #[cfg(any(test, debug_assertions, target_arch = "wasm32"))]
-    fn print_to_vm_buffer(&mut self, arg_count: usize) {
-        let stack_len = self.stack.len();
-        let args_start = stack_len - arg_count - 1;
-        let args_end = stack_len - 1;
-        let args = &self.stack[args_start..args_end];
-        if !args.is_empty() {
-            use std::fmt::Write;
-            writeln!(self.string_buffer, "{}", args[0]).ok();
+    fn run_callback(
+        &mut self,
+        callee: &Value,
+        args: &[Value],
+    ) -> std::result::Result<Value, String> {
+        let function = match callee {
+            Value::Object(obj) => match obj.as_ref() {
+                Object::Function(function) => Rc::clone(function),
+                _ => return Err(format!("{} is not a function", callee)),
+            },
+            _ => return Err(format!("{} is not a function", callee)),
+        };
+
+        let depth = self.call_frames.len();
+        self.stack.extend_from_slice(args);
+        self.push(callee.clone());
+        if let Some(result) = self.call_function(args.len(), &&function) {
+            self.callback_stop = Some(result);
+            return Err(String::new());
+        }
+        while self.call_frames.len() > depth {
+            let op_code = {
+                let frame = self.current_frame();
+                frame.function.chunk.read_u8(frame.ip)
+            };
+            match DISPATCH[op_code as usize](self) {
+                Flow::Next => self.current_frame_mut().ip += 1,
+                Flow::Jumped => {}
+                Flow::Stop(Result::RuntimeError) if self.catch_runtime_error(depth) => {}
+                Flow::Stop(result) => {
+                    self.callback_stop = Some(result);
+                    return Err(String::new());
+                }
+            }
         }
+        Ok(self.pop())
     }
 
     fn instantiate_struct(&mut self, arg_count: usize, r#struct: &Rc<ObjStruct>) -> Option<Result> {
-        if arg_count != r#struct.fields.len() {
+        let field_count = r#struct.fields.len();
+        let required = field_count - r#struct.defaults.len();
+        if arg_count < required || arg_count > field_count {
+            let expected = if required == field_count {
+                field_count.to_string()
+            } else {
+                format!("{} to {}", required, field_count)
+            };
             self.runtime_error(&format!(
                 "Expected {} fields but got {}.",
-                r#struct.fields.len(),
-                arg_count
+                expected, arg_count
             ));
             return Some(Result::RuntimeError);
         }
 
-        let field_count = r#struct.fields.len();
         let mut fields = HashMap::with_capacity(field_count);
         let stack_len = self.stack.len();
 
         // Unified calling convention: [args..., struct_obj]
-        // Extract arguments, excluding the struct object at the top
+        // Extract arguments, excluding the struct object at the top; omitted trailing
+        // fields take their defaults
         let stack_slice = &self.stack[stack_len - arg_count - 1..stack_len - 1];
-        for (field_name, value) in r#struct.fields.iter().zip(stack_slice.iter()) {
+        let values = stack_slice
+            .iter()
+            .chain(r#struct.defaults[arg_count - required..].iter());
+        for (field_name, value) in r#struct.fields.iter().zip(values) {
             fields.insert(field_name.clone(), value.clone());
         }
 
@@ -178,26 +476,151 @@ impl VirtualMachine {
         None
     }
 
+    /// Named struct construction: `CallNamed count (label:u32)*count` with [values..., struct]
+    pub(in crate::vm) fn fn_call_named(&mut self) -> Option<Result> {
+        let labels: Vec<String> = {
+            let frame = self.current_frame();
+            let chunk = &frame.function.chunk;
+            let arg_count = chunk.read_u8(frame.ip + 1) as usize;
+            (0..arg_count)
+                .map(|arg| {
+                    let label = chunk.read_u32(frame.ip + 2 + arg * 4) as usize;
+                    chunk.read_string(label).to_string()
+                })
+                .collect()
+        };
+        self.current_frame_mut().ip += 2 + labels.len() * 4;
+
+        let r#struct = match self.peek(0) {
+            Value::Object(obj) => match obj.as_ref() {
+                Object::Struct(r#struct) => Rc::clone(r#struct),
+                _ => {
+                    self.runtime_error("Named arguments can only be used to construct a struct.");
+                    return Some(Result::RuntimeError);
+                }
+            },
+            _ => {
+                self.runtime_error("Named arguments can only be used to construct a struct.");
+                return Some(Result::RuntimeError);
+            }
+        };
+
+        let stack_len = self.stack.len();
+        let values = &self.stack[stack_len - labels.len() - 1..stack_len - 1];
+        let fields = match Self::named_fields(&r#struct, &labels, values) {
+            Ok(fields) => fields,
+            Err(error) => {
+                self.runtime_error(&error);
+                return Some(Result::RuntimeError);
+            }
+        };
+
+        self.stack.truncate(stack_len - labels.len() - 1);
+        self.push(Value::new_object(ObjInstance { r#struct, fields }));
+        None
+    }
+
+    /// Matches labeled values to the struct's fields, filling the rest from defaults
+    fn named_fields(
+        r#struct: &ObjStruct,
+        labels: &[String],
+        values: &[Value],
+    ) -> std::result::Result<HashMap<String, Value>, String> {
+        let mut fields = HashMap::with_capacity(r#struct.fields.len());
+        for (label, value) in labels.iter().zip(values) {
+            if !r#struct.fields.contains(label) {
+                return Err(format!(
+                    "Struct '{}' has no field named '{}'.",
+                    r#struct.name, label
+                ));
+            }
+            if fields.insert(label.clone(), value.clone()).is_some() {
+                return Err(format!("Field '{}' is given more than once.", label));
+            }
+        }
+
+        let required = r#struct.fields.len() - r#struct.defaults.len();
+        for (index, name) in r#struct.fields.iter().enumerate() {
+            if fields.contains_key(name) {
+                continue;
+            }
+            if index < required {
+                return Err(format!("Missing value for field '{}'.", name));
+            }
+            fields.insert(name.clone(), r#struct.defaults[index - required].clone());
+        }
+        Ok(fields)
+    }
+
+    /// Reports a runtime error unless `func` accepts `arg_count` arguments.
+    /// `hidden` leading arguments (a method's receiver) are left out of the message.
+    fn check_arity(&mut self, func: &ObjFunction, arg_count: usize, hidden: u8) -> bool {
+        let fixed_params = func.arity as usize - func.variadic as usize;
+        if arg_count >= func.min_arity as usize && (func.variadic || arg_count <= fixed_params) {
+            return true;
+        }
+
+        let (min_arity, arity) = (func.min_arity - hidden, func.arity - hidden);
+        let expected = if func.variadic {
+            format!("at least {}", min_arity)
+        } else if min_arity == arity {
+            arity.to_string()
+        } else {
+            format!("{} to {}", min_arity, arity)
+        };
+        self.runtime_error(&format!(
+            "Expected {} arguments but got {}.",
+            expected,
+            arg_count - hidden as usize
+        ));
+        false
+    }
+
     fn call_function(&mut self, arg_count: usize, func: &&Rc<ObjFunction>) -> Option<Result> {
-        if arg_count != func.arity as usize {
-            self.runtime_error(&format!(
-                "Expected {} arguments but got {}.",
-                func.arity, arg_count
-            ));
+        if !self.check_arity(func, arg_count, 0) {
+            return Some(Result::RuntimeError);
+        }
+        // The script's own frame doesn't count as a call
+        if self.call_frames.len() > self.max_call_depth {
+            let message = format!(
+                "Stack overflow: recursion limit of {} calls exceeded.",
+                self.max_call_depth
+            );
+            self.runtime_error(&message);
             return Some(Result::RuntimeError);
         }
 
+        // A rest parameter takes the last slot and isn't filled by a single argument
+        let fixed_params = func.arity as usize - func.variadic as usize;
+
+        // Lay the parameters out below the function object: omitted ones get slots the
+        // function's prologue fills with their defaults, extra arguments become the rest array
+        let missing = fixed_params.saturating_sub(arg_count);
+        if missing > 0 || func.variadic {
+            let function = self.pop();
+            let rest = func.variadic.then(|| {
+                self.stack
+                    .split_off(self.stack.len() - arg_count.saturating_sub(fixed_params))
+            });
+            self.stack.extend(std::iter::repeat_n(Value::Nil, missing));
+            if let Some(rest) = rest {
+                self.push(Value::new_array(rest));
+            }
+            self.push(function);
+        }
+
         // Calculate slot_start for unified calling convention [args..., func]
         // The function object is still on the stack at this point
         // Stack layout: [...previous..., arg0, arg1, ..., argN, func_obj]
         // slot_start should point just BEFORE the first argument
-        // So: slot_start = current_len - arg_count - 1 (for func) - 1 (to go before first arg)
-        let slot_start = (self.stack.len() - arg_count - 1 - 1) as isize;
+        // So: slot_start = current_len - arity - 1 (for func) - 1 (to go before first arg)
+        let slot_start = (self.stack.len() - func.arity as usize - 1 - 1) as isize;
 
         let new_frame = CallFrame {
             function: Rc::clone(func),
             ip: 0,
             slot_start,
+            arg_count: arg_count.min(fixed_params) as u8,
         };
 
         // NOTE: IP increment is handled by the caller (fn_call_unified)
@@ -211,6 +634,16 @@ impl VirtualMachine {
     pub(in crate::vm) fn fn_return(&mut self) -> Option<Result> {
         let return_value = self.pop();
         let slot_start = self.current_frame().slot_start;
+        self.close_upvalues((slot_start + 1) as usize);
+        // `try` blocks the return leaves
+        let depth = self.call_frames.len();
+        while self
+            .try_handlers
+            .last()
+            .is_some_and(|handler| handler.frame_depth == depth)
+        {
+            self.try_handlers.pop();
+        }
         self.call_frames.pop();
 
         if self.call_frames.is_empty() {
@@ -225,18 +658,175 @@ impl VirtualMachine {
         None
     }
 
+    /// Turns the function on top of the stack into a closure.
+    /// Operands: capture count (u8), then per capture an is_local flag (u8) and an index (u32).
+    /// Local captures refer to a slot of the current frame, the others to one of its upvalues.
+    pub(in crate::vm) fn fn_closure(&mut self) {
+        let (capture_count, slot_start, enclosing) = {
+            let frame = self.current_frame();
+            (
+                frame.function.chunk.read_u8(frame.ip + 1) as usize,
+                frame.slot_start,
+                Rc::clone(&frame.function),
+            )
+        };
+
+        let mut upvalues = Vec::with_capacity(capture_count);
+        for capture in 0..capture_count {
+            let operand = self.current_frame().ip + 2 + capture * 5;
+            let is_local = enclosing.chunk.read_u8(operand) != 0;
+            let index = enclosing.chunk.read_u32(operand + 1) as usize;
+            if is_local {
+                upvalues.push(self.capture_upvalue((slot_start + 1) as usize + index));
+            } else {
+                upvalues.push(Rc::clone(&enclosing.upvalues[index]));
+            }
+        }
+        self.current_frame_mut().ip += 1 + capture_count * 5;
+
+        let function = match self.pop() {
+            Value::Object(obj) => match obj.as_ref() {
+                Object::Function(function) => Rc::clone(function),
+                _ => unreachable!("Closure operand must be a function"),
+            },
+            _ => unreachable!("Closure operand must be a function"),
+        };
+        self.push(Value::Object(Rc::new(Object::Function(Rc::new(
+            ObjFunction {
+                name: function.name.clone(),
+                arity: function.arity,
+                min_arity: function.min_arity,
+                variadic: function.variadic,
+                chunk: Rc::clone(&function.chunk),
+                upvalues,
+            },
+        )))));
+    }
+
+    /// Returns the open upvalue for `slot`, creating it if no closure has captured it yet
+    fn capture_upvalue(&mut self, slot: usize) -> Rc<RefCell<Upvalue>> {
+        let position = self
+            .open_upvalues
+            .iter()
+            .position(|upvalue| matches!(*upvalue.borrow(), Upvalue::Open(open) if open >= slot));
+        if let Some(position) = position {
+            let existing = &self.open_upvalues[position];
+            if *existing.borrow() == Upvalue::Open(slot) {
+                return Rc::clone(existing);
+            }
+        }
+
+        let upvalue = Rc::new(RefCell::new(Upvalue::Open(slot)));
+        let position = position.unwrap_or(self.open_upvalues.len());
+        self.open_upvalues.insert(position, Rc::clone(&upvalue));
+        upvalue
+    }
+
+    /// Moves the values of all upvalues at or above `first_slot` off the stack
+    fn close_upvalues(&mut self, first_slot: usize) {
+        while let Some(upvalue) = self.open_upvalues.last() {
+            let slot = match *upvalue.borrow() {
+                Upvalue::Open(slot) if slot >= first_slot => slot,
+                _ => break,
+            };
+            let value = self.stack[slot].clone();
+            *upvalue.borrow_mut() = Upvalue::Closed(value);
+            self.open_upvalues.pop();
+        }
+    }
+
+    pub(in crate::vm) fn fn_close_upvalues(&mut self) {
+        let first_slot = {
+            let frame = self.current_frame();
+            (frame.slot_start + 1) as usize + frame.function.chunk.read_u32(frame.ip + 1) as usize
+        };
+        self.current_frame_mut().ip += 4;
+        self.close_upvalues(first_slot);
+    }
+
+    pub(in crate::vm) fn fn_get_upvalue(&mut self) {
+        let upvalue = {
+            let frame = self.current_frame();
+            let index = frame.function.chunk.read_u8(frame.ip + 1) as usize;
+            Rc::clone(&frame.function.upvalues[index])
+        };
+        self.current_frame_mut().ip += 1;
+
+        let value = match &*upvalue.borrow() {
+            Upvalue::Open(slot) => self.stack[*slot].clone(),
+            Upvalue::Closed(value) => value.clone(),
+        };
+        self.push(value);
+    }
+
+    pub(in crate::vm) fn fn_set_upvalue(&mut self) {
+        let upvalue = {
+            let frame = self.current_frame();
+            let index = frame.function.chunk.read_u8(frame.ip + 1) as usize;
+            Rc::clone(&frame.function.upvalues[index])
+        };
+        self.current_frame_mut().ip += 1;
+
+        let value = self.peek(0);
+        let open_slot = match &mut *upvalue.borrow_mut() {
+            Upvalue::Open(slot) => Some(*slot),
+            Upvalue::Closed(closed) => {
+                *closed = value.clone();
+                None
+            }
+        };
+        if let Some(slot) = open_slot {
+            self.stack[slot] = value;
+        }
+    }
+
     #[inline(always)]
-    pub(in crate::vm) fn fn_less(&mut self) {
-        let b = self.pop();
-        let a = self.pop();
-        self.push(boolean!(as_number!(a) < as_number!(b)));
+    pub(in crate::vm) fn fn_less(&mut self) -> Option<Result> {
+        self.ordering_comparison(Ordering::Less)
     }
 
     #[inline(always)]
-    pub(in crate::vm) fn fn_greater(&mut self) {
+    pub(in crate::vm) fn fn_greater(&mut self) -> Option<Result> {
+        self.ordering_comparison(Ordering::Greater)
+    }
+
+    /// Pops two operands and pushes whether the first one orders as `expected` against the
+    /// second. Numbers compare by value and strings lexicographically by code point; `<=` and
+    /// `>=` are compiled as the negation of `>` and `<`. Any other pair is a runtime error.
+    #[inline(always)]
+    fn ordering_comparison(&mut self, expected: Ordering) -> Option<Result> {
         let b = self.pop();
         let a = self.pop();
-        self.push(boolean!(as_number!(a) > as_number!(b)));
+        let ordering = match (&a, &b) {
+            (Value::Int(a), Value::Int(b)) => Some(a.cmp(b)),
+            (Value::Object(obj_a), Value::Object(obj_b)) => {
+                match (obj_a.unfrozen(), obj_b.unfrozen()) {
+                    (Object::String(a), Object::String(b)) => Some(a.value.cmp(&b.value)),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        let ordering = match ordering {
+            Some(ordering) => Some(ordering),
+            None if Self::is_string(&a) || Self::is_string(&b) => {
+                self.runtime_error("Operands must be two numbers or two strings");
+                return Some(Result::RuntimeError);
+            }
+            // A nan operand isn't ordered, which makes every comparison with it false
+            None => {
+                let Some((a, b)) = self.number_operands(&a, &b) else {
+                    return Some(Result::RuntimeError);
+                };
+                a.partial_cmp(&b)
+            }
+        };
+        self.push(boolean!(ordering == Some(expected)));
+        None
+    }
+
+    fn is_string(value: &Value) -> bool {
+        matches!(value, Value::Object(obj) if matches!(obj.unfrozen(), Object::String(_)))
     }
 
     #[inline(always)]
@@ -247,32 +837,148 @@ impl VirtualMachine {
     }
 
     #[inline(always)]
-    pub(in crate::vm) fn fn_divide(&mut self) {
+    pub(in crate::vm) fn fn_same(&mut self) {
         let b = self.pop();
         let a = self.pop();
-        self.push(Value::Number(as_number!(a) / as_number!(b)));
+        self.push(boolean!(a.is_same(&b)));
+    }
+
+    /// Numeric values of the operands of an arithmetic or comparison operator.
+    /// Reports a runtime error for the first operand that isn't a number.
+    fn number_operands(&mut self, a: &Value, b: &Value) -> Option<(f64, f64)> {
+        for operand in [a, b] {
+            if operand.as_f64().is_none() {
+                self.runtime_error(&format!(
+                    "Operand must be a number, got {}",
+                    operand.type_name()
+                ));
+                return None;
+            }
+        }
+        a.as_f64().zip(b.as_f64())
     }
 
+    /// Pops two operands and pushes `int_op` of them if both are ints, `float_op` otherwise.
+    /// An int result that doesn't fit (`int_op` returns None) and an operand that isn't a
+    /// number are runtime errors.
     #[inline(always)]
-    pub(in crate::vm) fn fn_floor_divide(&mut self) {
+    fn binary_arithmetic(
+        &mut self,
+        int_op: fn(i64, i64) -> Option<i64>,
+        float_op: fn(f64, f64) -> f64,
+    ) -> Option<Result> {
+        let b = self.pop();
+        let a = self.pop();
+        match (a, b) {
+            (Value::Int(a), Value::Int(b)) => match int_op(a, b) {
+                Some(result) => self.push(Value::Int(result)),
+                None => {
+                    self.runtime_error("Integer overflow.");
+                    return Some(Result::RuntimeError);
+                }
+            },
+            (a, b) => {
+                let Some((a, b)) = self.number_operands(&a, &b) else {
+                    return Some(Result::RuntimeError);
+                };
+                self.push(Value::Number(float_op(a, b)));
+            }
+        }
+        None
+    }
+
+    /// Reports a division by zero that has no result: int division and remainder, whose
+    /// float counterparts give `inf` or `nan`, and any division with strict arithmetic.
+    /// `int_result` tells whether the operation on two ints gives an int.
+    fn is_division_by_zero(&mut self, int_result: bool) -> bool {
+        let divisor_is_zero = match self.peek(0) {
+            Value::Int(b) => b == 0,
+            Value::Number(b) => b == 0.0,
+            _ => false,
+        };
+        let ints = matches!((self.peek(1), self.peek(0)), (Value::Int(_), Value::Int(_)));
+        if divisor_is_zero && (self.strict_arithmetic || (int_result && ints)) {
+            self.runtime_error("Division by zero.");
+            return true;
+        }
+        false
+    }
+
+    #[inline(always)]
+    pub(in crate::vm) fn fn_divide(&mut self) -> Option<Result> {
+        if self.is_division_by_zero(false) {
+            return Some(Result::RuntimeError);
+        }
         let b = self.pop();
         let a = self.pop();
-        let result = (as_number!(a) / as_number!(b)).floor();
-        self.push(Value::Number(result));
+        let Some((a, b)) = self.number_operands(&a, &b) else {
+            return Some(Result::RuntimeError);
+        };
+        self.push(Value::Number(a / b));
+        None
     }
 
+    /// Always produces an int, except for results with no int equivalent (nan, inf, too large)
     #[inline(always)]
-    pub(in crate::vm) fn fn_modulo(&mut self) {
+    pub(in crate::vm) fn fn_floor_divide(&mut self) -> Option<Result> {
+        if self.is_division_by_zero(true) {
+            return Some(Result::RuntimeError);
+        }
         let b = self.pop();
         let a = self.pop();
-        self.push(Value::Number(as_number!(a) % as_number!(b)));
+        let result = match (a, b) {
+            (Value::Int(a), Value::Int(b)) => match a.checked_div(b) {
+                // Round towards negative infinity rather than zero
+                Some(quotient) if a % b != 0 && (a < 0) != (b < 0) => Value::Int(quotient - 1),
+                Some(quotient) => Value::Int(quotient),
+                None => {
+                    self.runtime_error("Integer overflow.");
+                    return Some(Result::RuntimeError);
+                }
+            },
+            (a, b) => {
+                let Some((a, b)) = self.number_operands(&a, &b) else {
+                    return Some(Result::RuntimeError);
+                };
+                let quotient = (a / b).floor();
+                if quotient.is_finite() && quotient >= i64::MIN as f64 && quotient < i64::MAX as f64
+                {
+                    Value::Int(quotient as i64)
+                } else {
+                    Value::Number(quotient)
+                }
+            }
+        };
+        self.push(result);
+        None
+    }
+
+    #[inline(always)]
+    pub(in crate::vm) fn fn_modulo(&mut self) -> Option<Result> {
+        if self.is_division_by_zero(true) {
+            return Some(Result::RuntimeError);
+        }
+        self.binary_arithmetic(|a, b| Some(a.wrapping_rem(b)), |a, b| a % b)
     }
 
     #[inline(always)]
-    pub(in crate::vm) fn fn_exponent(&mut self) {
+    pub(in crate::vm) fn fn_exponent(&mut self) -> Option<Result> {
+        // A negative int exponent has a fractional result, so it is computed as a float
+        if let (Value::Int(_), Value::Int(exponent)) = (self.peek(1), self.peek(0)) {
+            if exponent >= 0 {
+                return self.binary_arithmetic(
+                    |a, b| u32::try_from(b).ok().and_then(|b| a.checked_pow(b)),
+                    f64::powf,
+                );
+            }
+        }
         let b = self.pop();
         let a = self.pop();
-        self.push(Value::Number(as_number!(a).powf(as_number!(b))));
+        let Some((a, b)) = self.number_operands(&a, &b) else {
+            return Some(Result::RuntimeError);
+        };
+        self.push(Value::Number(a.powf(b)));
+        None
     }
 
     /// Helper: Convert f64 to i64 for bitwise operations
@@ -285,36 +991,56 @@ impl VirtualMachine {
         }
     }
 
+    /// Helper: Bitwise operand of either numeric kind
     #[inline(always)]
-    pub(in crate::vm) fn fn_bitwise_and(&mut self) {
-        let b = self.pop();
-        let a = self.pop();
-        let result = Self::to_integer(as_number!(a)) & Self::to_integer(as_number!(b));
-        self.push(Value::Number(result as f64));
+    fn bitwise_operand(value: Value) -> i64 {
+        match value {
+            Value::Int(i) => i,
+            value => Self::to_integer(value.as_f64().unwrap_or_default()),
+        }
     }
 
+    /// Helper: Pops the two operands of a binary bitwise operator, which must be numbers
     #[inline(always)]
-    pub(in crate::vm) fn fn_bitwise_or(&mut self) {
+    fn bitwise_operands(&mut self) -> Option<(i64, i64)> {
         let b = self.pop();
         let a = self.pop();
-        let result = Self::to_integer(as_number!(a)) | Self::to_integer(as_number!(b));
-        self.push(Value::Number(result as f64));
+        self.number_operands(&a, &b)?;
+        Some((Self::bitwise_operand(a), Self::bitwise_operand(b)))
     }
 
     #[inline(always)]
-    pub(in crate::vm) fn fn_bitwise_xor(&mut self) {
-        let b = self.pop();
-        let a = self.pop();
-        let result = Self::to_integer(as_number!(a)) ^ Self::to_integer(as_number!(b));
-        self.push(Value::Number(result as f64));
+    pub(in crate::vm) fn fn_bitwise_and(&mut self) -> Option<Result> {
+        let Some((a, b)) = self.bitwise_operands() else {
+            return Some(Result::RuntimeError);
+        };
+        self.push(Value::Int(a & b));
+        None
+    }
+
+    #[inline(always)]
+    pub(in crate::vm) fn fn_bitwise_or(&mut self) -> Option<Result> {
+        let Some((a, b)) = self.bitwise_operands() else {
+            return Some(Result::RuntimeError);
+        };
+        self.push(Value::Int(a | b));
+        None
+    }
+
+    #[inline(always)]
+    pub(in crate::vm) fn fn_bitwise_xor(&mut self) -> Option<Result> {
+        let Some((a, b)) = self.bitwise_operands() else {
+            return Some(Result::RuntimeError);
+        };
+        self.push(Value::Int(a ^ b));
+        None
     }
 
     #[inline(always)]
     pub(in crate::vm) fn fn_bitwise_not(&mut self) -> Option<Result> {
-        if let Value::Number(..) = self.peek(0) {
+        if let Value::Number(..) | Value::Int(..) = self.peek(0) {
             let value = self.pop();
-            let int_val = Self::to_integer(as_number!(value));
-            self.push(Value::Number((!int_val) as f64));
+            self.push(Value::Int(!Self::bitwise_operand(value)));
             return None;
         }
         self.runtime_error("Operand must be a number for bitwise NOT");
@@ -322,56 +1048,136 @@ impl VirtualMachine {
     }
 
     #[inline(always)]
-    pub(in crate::vm) fn fn_left_shift(&mut self) {
-        let b = self.pop();
-        let a = self.pop();
-        let shift_amount = (Self::to_integer(as_number!(b)) & 0x3F) as u32; // Mask to 6 bits (0-63)
-        let result = Self::to_integer(as_number!(a)) << shift_amount;
-        self.push(Value::Number(result as f64));
+    pub(in crate::vm) fn fn_left_shift(&mut self) -> Option<Result> {
+        let Some((a, b)) = self.bitwise_operands() else {
+            return Some(Result::RuntimeError);
+        };
+        let shift_amount = (b & 0x3F) as u32; // Mask to 6 bits (0-63)
+        let result = a << shift_amount;
+        self.push(Value::Int(result));
+        None
     }
 
     #[inline(always)]
-    pub(in crate::vm) fn fn_right_shift(&mut self) {
-        let b = self.pop();
-        let a = self.pop();
-        let shift_amount = (Self::to_integer(as_number!(b)) & 0x3F) as u32; // Mask to 6 bits (0-63)
-        let result = Self::to_integer(as_number!(a)) >> shift_amount; // Arithmetic right shift
-        self.push(Value::Number(result as f64));
+    pub(in crate::vm) fn fn_right_shift(&mut self) -> Option<Result> {
+        let Some((a, b)) = self.bitwise_operands() else {
+            return Some(Result::RuntimeError);
+        };
+        let shift_amount = (b & 0x3F) as u32; // Mask to 6 bits (0-63)
+        let result = a >> shift_amount; // Arithmetic right shift
+        self.push(Value::Int(result));
+        None
     }
 
     #[inline(always)]
-    pub(in crate::vm) fn fn_multiply(&mut self) {
-        let b = self.pop();
-        let a = self.pop();
-        self.push(Value::Number(as_number!(a) * as_number!(b)));
+    pub(in crate::vm) fn fn_multiply(&mut self) -> Option<Result> {
+        let object_operand = match (self.peek(1), self.peek(0)) {
+            (Value::Object(object), count) | (count, Value::Object(object)) => {
+                Some((object, count))
+            }
+            _ => None,
+        };
+        if let Some((object, count)) = object_operand {
+            self.pop();
+            self.pop();
+            return self.fn_repeat(object.unfrozen(), &count);
+        }
+        self.binary_arithmetic(i64::checked_mul, |a, b| a * b)
+    }
+
+    /// `"ab" * 3` and `3 * "ab"` repeat the string, `[0] * 3` the array.
+    /// The count has to be a non-negative whole number, and the result can't be longer than
+    /// `MAX_SEQUENCE_LENGTH`.
+    fn fn_repeat(&mut self, object: &Object, count: &Value) -> Option<Result> {
+        let times = match *count {
+            Value::Int(n) if n >= 0 => Some(n as usize),
+            Value::Number(n) if n >= 0.0 && n.fract() == 0.0 => Some(n as usize),
+            _ => None,
+        };
+        match (object, times) {
+            (Object::String(text), Some(times)) => {
+                if !self.fits_repeated("a string", text.value.len(), times) {
+                    return Some(Result::RuntimeError);
+                }
+                self.push(string!(text.value.repeat(times)));
+                None
+            }
+            (Object::Array(elements), Some(times)) => {
+                let elements = elements.borrow();
+                if !self.fits_repeated("an array", elements.len(), times) {
+                    return Some(Result::RuntimeError);
+                }
+                // An empty array stays empty however large the count
+                let times = if elements.is_empty() { 0 } else { times };
+                let repeated = (0..times).flat_map(|_| elements.iter().cloned()).collect();
+                self.push(Value::new_array(repeated));
+                None
+            }
+            (Object::String(_), None) => self.invalid_repeat_count("a string", count),
+            (Object::Array(_), None) => self.invalid_repeat_count("an array", count),
+            _ => {
+                self.runtime_error(
+                    "Operands must be two numbers, or a string or array and a number",
+                );
+                Some(Result::RuntimeError)
+            }
+        }
+    }
+
+    /// Whether `times` copies of something `len` long stay within `MAX_SEQUENCE_LENGTH`,
+    /// reporting a runtime error if they don't
+    fn fits_repeated(&mut self, repeated: &str, len: usize, times: usize) -> bool {
+        if len
+            .checked_mul(times)
+            .is_some_and(|total| total <= MAX_SEQUENCE_LENGTH)
+        {
+            return true;
+        }
+        self.runtime_error(&format!(
+            "Can't repeat {} that many times, the result would be longer than {}",
+            repeated, MAX_SEQUENCE_LENGTH
+        ));
+        false
+    }
+
+    fn invalid_repeat_count(&mut self, repeated: &str, count: &Value) -> Option<Result> {
+        self.runtime_error(&format!(
+            "Can't repeat {} {} times, the count must be a non-negative whole number",
+            repeated, count
+        ));
+        Some(Result::RuntimeError)
     }
 
     #[inline(always)]
-    pub(in crate::vm) fn fn_subtract(&mut self) {
-        let b = self.pop();
-        let a = self.pop();
-        self.push(Value::Number(as_number!(a) - as_number!(b)));
+    pub(in crate::vm) fn fn_subtract(&mut self) -> Option<Result> {
+        self.binary_arithmetic(i64::checked_sub, |a, b| a - b)
     }
 
     #[inline(always)]
     pub(in crate::vm) fn fn_add(&mut self) -> Option<Result> {
-        let b = self.pop();
-        let a = self.pop();
-        match (a, b) {
-            (Value::Number(a), Value::Number(b)) => self.push(Value::Number(a + b)),
-            (Value::Object(a), Value::Object(b)) => {
-                let obj_a = a.as_ref();
-                let obj_b = b.as_ref();
-                if let Some(result) = self.fn_add_object(obj_a, obj_b) {
-                    return Some(result);
-                }
-            }
-            _ => {
-                self.runtime_error("Operands must be two numbers or two strings");
-                return Some(Result::RuntimeError);
+        if let (Value::Object(_), Value::Object(_)) = (self.peek(1), self.peek(0)) {
+            let b = self.pop();
+            let a = self.pop();
+            if let (Value::Object(a), Value::Object(b)) = (a, b) {
+                return self.fn_add_object(a.unfrozen(), b.unfrozen());
             }
         }
-        None
+        if self.peek(0).as_f64().is_none() || self.peek(1).as_f64().is_none() {
+            let is_array = |value: Value| matches!(value, Value::Object(object) if matches!(object.unfrozen(), Object::Array(_)));
+            let involves_array = is_array(self.peek(1)) || is_array(self.peek(0));
+            self.runtime_error(Self::add_operands_error(involves_array));
+            return Some(Result::RuntimeError);
+        }
+        self.binary_arithmetic(i64::checked_add, |a, b| a + b)
+    }
+
+    /// Arrays only concatenate with arrays, which gets its own message when mixed up
+    fn add_operands_error(involves_array: bool) -> &'static str {
+        if involves_array {
+            "Arrays can only be concatenated with other arrays"
+        } else {
+            "Operands must be two numbers or two strings"
+        }
     }
 
     fn fn_add_object(&mut self, a: &Object, b: &Object) -> Option<Result> {
@@ -383,8 +1189,19 @@ impl VirtualMachine {
                 self.push(string!(combined));
                 None
             }
+            (Object::Array(elements_a), Object::Array(elements_b)) => {
+                let combined = elements_a
+                    .borrow()
+                    .iter()
+                    .chain(elements_b.borrow().iter())
+                    .cloned()
+                    .collect();
+                self.push(Value::new_array(combined));
+                None
+            }
             _ => {
-                self.runtime_error("Operands must be two numbers or two strings");
+                let involves_array = matches!(a, Object::Array(_)) || matches!(b, Object::Array(_));
+                self.runtime_error(Self::add_operands_error(involves_array));
                 Some(Result::RuntimeError)
             }
         }
@@ -392,13 +1209,28 @@ impl VirtualMachine {
 
     #[inline(always)]
     pub(in crate::vm) fn fn_negate(&mut self) -> Option<Result> {
-        if let Value::Number(..) = self.peek(0) {
-            let value = self.pop();
-            self.push(number!(-as_number!(value)));
-            return None;
+        match self.peek(0) {
+            Value::Int(i) => match i.checked_neg() {
+                Some(negated) => {
+                    self.pop();
+                    self.push(Value::Int(negated));
+                    None
+                }
+                None => {
+                    self.runtime_error("Integer overflow.");
+                    Some(Result::RuntimeError)
+                }
+            },
+            Value::Number(n) => {
+                self.pop();
+                self.push(number!(-n));
+                None
+            }
+            _ => {
+                self.runtime_error("Operand must be a number");
+                Some(Result::RuntimeError)
+            }
         }
-        self.runtime_error("Operand must be a number");
-        Some(Result::RuntimeError)
     }
 
     #[inline(always)]
@@ -477,6 +1309,16 @@ impl VirtualMachine {
         }
     }
 
+    pub(in crate::vm) fn fn_jump_if_arg_supplied(&mut self) {
+        let frame = self.current_frame_mut();
+        let param = frame.function.chunk.read_u8(frame.ip + 1);
+        let offset = frame.function.chunk.read_u32(frame.ip + 2);
+        frame.ip += 5;
+        if param < frame.arg_count {
+            frame.ip += offset as usize;
+        }
+    }
+
     #[inline(always)]
     pub(in crate::vm) fn fn_jump(&mut self) {
         let frame = self.current_frame_mut();
@@ -541,7 +1383,7 @@ impl VirtualMachine {
     }
 
     #[inline(always)]
-    pub(in crate::vm) fn fn_get_field(&mut self, bits: BitsSize) {
+    pub(in crate::vm) fn fn_get_field(&mut self, bits: BitsSize) -> Option<Result> {
         let field_name_index = self.read_bits(&bits);
         let instance_value = self.peek(0);
 
@@ -553,12 +1395,12 @@ impl VirtualMachine {
                     Object::String(s) => s.value.to_string(),
                     _ => {
                         self.runtime_error("Field name must be a string.");
-                        return;
+                        return Some(Result::RuntimeError);
                     }
                 },
                 _ => {
                     self.runtime_error("Field name must be a string.");
-                    return;
+                    return Some(Result::RuntimeError);
                 }
             }
         };
@@ -574,26 +1416,55 @@ impl VirtualMachine {
                         self.push(value);
                     } else {
                         self.runtime_error(&format!("Undefined field '{}'.", field_name));
-                        return;
+                        return Some(Result::RuntimeError);
                     }
                 }
+                Object::Enum(r#enum) => {
+                    let Some(index) = r#enum.variants.iter().position(|v| *v == field_name) else {
+                        self.runtime_error(&format!(
+                            "Enum '{}' has no variant '{}'.",
+                            r#enum.name, field_name
+                        ));
+                        return Some(Result::RuntimeError);
+                    };
+                    let variant = Object::EnumVariant(Rc::clone(r#enum), index);
+                    self.pop();
+                    self.push(Value::Object(Rc::new(variant)));
+                }
+                Object::Module(module) => {
+                    let Some(value) = module.exports.get(&field_name).cloned() else {
+                        self.runtime_error(&format!(
+                            "Module '{}' has no export '{}'.",
+                            module.name, field_name
+                        ));
+                        return Some(Result::RuntimeError);
+                    };
+                    self.pop();
+                    self.push(value);
+                }
+                Object::Error(error) if field_name == "message" => {
+                    let message = string!(error.message.as_str());
+                    self.pop();
+                    self.push(message);
+                }
                 _ => {
                     self.runtime_error("Only instances have fields.");
-                    return;
+                    return Some(Result::RuntimeError);
                 }
             },
             _ => {
                 self.runtime_error("Only instances have fields.");
-                return;
+                return Some(Result::RuntimeError);
             }
         }
 
         let frame = self.current_frame_mut();
         frame.ip += bits.as_bytes();
+        None
     }
 
     #[inline(always)]
-    pub(in crate::vm) fn fn_set_field(&mut self, bits: BitsSize) {
+    pub(in crate::vm) fn fn_set_field(&mut self, bits: BitsSize) -> Option<Result> {
         let field_name_index = self.read_bits(&bits);
         let value = self.peek(0);
         let instance_value = self.peek(1);
@@ -606,12 +1477,12 @@ impl VirtualMachine {
                     Object::String(s) => s.value.to_string(),
                     _ => {
                         self.runtime_error("Field name must be a string.");
-                        return;
+                        return Some(Result::RuntimeError);
                     }
                 },
                 _ => {
                     self.runtime_error("Field name must be a string.");
-                    return;
+                    return Some(Result::RuntimeError);
                 }
             }
         };
@@ -622,7 +1493,7 @@ impl VirtualMachine {
                     let mut instance = instance_ref.borrow_mut();
                     if !instance.r#struct.fields.contains(&field_name) {
                         self.runtime_error(&format!("Undefined field '{}'.", field_name));
-                        return;
+                        return Some(Result::RuntimeError);
                     }
 
                     instance.fields.insert(field_name, value.clone());
@@ -633,17 +1504,18 @@ impl VirtualMachine {
                 }
                 _ => {
                     self.runtime_error("Only instances have fields.");
-                    return;
+                    return Some(Result::RuntimeError);
                 }
             },
             _ => {
                 self.runtime_error("Only instances have fields.");
-                return;
+                return Some(Result::RuntimeError);
             }
         }
 
         let frame = self.current_frame_mut();
         frame.ip += bits.as_bytes();
+        None
     }
 
     #[inline(always)]
@@ -661,11 +1533,11 @@ impl VirtualMachine {
             let key_value = &self.stack[pairs_start + i];
             let value = &self.stack[pairs_start + count + i];
 
-            let key = match Self::value_to_map_key(key_value) {
+            let key = match MapKey::from_value(key_value) {
                 Some(k) => k,
                 None => {
                     self.runtime_error(&format!(
-                        "Invalid map key type: {}. Only strings, numbers, and booleans can be used as map keys.",
+                        "Invalid map key type: {}. Only strings, numbers, booleans and tuples can be used as map keys.",
                         key_value
                     ));
                     return;
@@ -716,11 +1588,11 @@ impl VirtualMachine {
         for i in 0..count {
             let element_value = &self.stack[elements_start + i];
 
-            let key = match Self::value_to_map_key(element_value) {
+            let key = match MapKey::from_value(element_value) {
                 Some(k) => k,
                 None => {
                     self.runtime_error(&format!(
-                        "Invalid set element type: {}. Only strings, numbers, and booleans can be used as set elements.",
+                        "Invalid set element type: {}. Only strings, numbers, booleans and tuples can be used as set elements.",
                         element_value
                     ));
                     return;
@@ -749,6 +1621,7 @@ impl VirtualMachine {
 
         let start = match start_value {
             Value::Number(n) => n,
+            Value::Int(i) => i as f64,
             _ => {
                 self.runtime_error(&format!(
                     "Range start must be a number, got {}",
@@ -760,6 +1633,7 @@ impl VirtualMachine {
 
         let end = match end_value {
             Value::Number(n) => n,
+            Value::Int(i) => i as f64,
             _ => {
                 self.runtime_error(&format!("Range end must be a number, got {}", end_value));
                 return Some(Result::RuntimeError);
@@ -804,22 +1678,67 @@ impl VirtualMachine {
     }
 
     #[inline(always)]
-    pub(in crate::vm) fn fn_get_index(&mut self) {
+    /// Element of a `val [a, b] = ...` binding; nil when the collection is too short
+    pub(in crate::vm) fn fn_unpack_element(&mut self) -> Option<Result> {
+        self.unpack(|elements, index| elements.get(index).cloned().unwrap_or(Value::Nil))
+    }
+
+    /// Rest of a `val [a, ...rest] = ...` binding, as a new array
+    pub(in crate::vm) fn fn_unpack_rest(&mut self) -> Option<Result> {
+        self.unpack(|elements, index| {
+            Value::new_array(elements.get(index..).unwrap_or_default().to_vec())
+        })
+    }
+
+    /// Pops the index and the array or tuple being destructured and pushes `take` of its
+    /// elements. Other values can't be destructured with an array pattern.
+    fn unpack(&mut self, take: fn(&[Value], usize) -> Value) -> Option<Result> {
+        let index = match self.pop() {
+            Value::Number(n) => n as usize,
+            Value::Int(i) => i as usize,
+            other => unreachable!("destructuring index is a number literal, got {}", other),
+        };
+        let collection = self.pop();
+
+        let value = match &collection {
+            Value::Object(obj) => match obj.unfrozen() {
+                Object::Array(array_ref) => Some(take(&array_ref.borrow(), index)),
+                Object::Tuple(elements) => Some(take(elements, index)),
+                _ => None,
+            },
+            _ => None,
+        };
+        match value {
+            Some(value) => {
+                self.push(value);
+                None
+            }
+            None => {
+                self.runtime_error(&format!(
+                    "Only arrays and tuples can be destructured with [...], got {}.",
+                    collection
+                ));
+                Some(Result::RuntimeError)
+            }
+        }
+    }
+
+    pub(in crate::vm) fn fn_get_index(&mut self) -> Option<Result> {
         let index_value = self.pop();
         let collection_value = self.pop();
 
         match &collection_value {
-            Value::Object(obj) => match obj.as_ref() {
+            Value::Object(obj) => match obj.unfrozen() {
                 Object::Map(map_ref) => {
                     // Convert index to MapKey
-                    let key = match Self::value_to_map_key(&index_value) {
+                    let key = match MapKey::from_value(&index_value) {
                         Some(k) => k,
                         None => {
                             self.runtime_error(&format!(
-                                "Invalid map key type: {}. Only strings, numbers, and booleans can be used as map keys.",
+                                "Invalid map key type: {}. Only strings, numbers, booleans and tuples can be used as map keys.",
                                 index_value
                             ));
-                            return;
+                            return Some(Result::RuntimeError);
                         }
                     };
 
@@ -828,51 +1747,70 @@ impl VirtualMachine {
                     self.push(result);
                 }
                 Object::Array(array_ref) => {
-                    let index = match index_value {
-                        Value::Number(n) => n as i32,
-                        _ => {
-                            self.runtime_error(&format!(
-                                "Array index must be a number, got {}.",
-                                index_value
-                            ));
-                            return;
-                        }
+                    let len = array_ref.borrow().len();
+                    let Some(index) = self.sequence_index(&index_value, len, "Array") else {
+                        return Some(Result::RuntimeError);
                     };
-
-                    let array = array_ref.borrow();
-                    let len = array.len() as i32;
-
-                    let actual_index = if index < 0 { len + index } else { index };
-
-                    if actual_index < 0 || actual_index >= len {
-                        self.runtime_error(&format!(
-                            "Array index out of bounds: index {} (normalized: {}) on array of length {}.",
-                            index, actual_index, len
-                        ));
-                        return;
-                    }
-
-                    let result = array[actual_index as usize].clone();
+                    let result = array_ref.borrow()[index].clone();
                     self.push(result);
                 }
+                Object::Tuple(elements) => {
+                    let Some(index) = self.sequence_index(&index_value, elements.len(), "Tuple")
+                    else {
+                        return Some(Result::RuntimeError);
+                    };
+                    self.push(elements[index].clone());
+                }
                 _ => {
                     self.runtime_error(&format!(
-                        "Only arrays and maps support index access, got {}.",
+                        "Only arrays, maps and tuples support index access, got {}.",
                         collection_value
                     ));
+                    return Some(Result::RuntimeError);
                 }
             },
             _ => {
                 self.runtime_error(&format!(
-                    "Only arrays and maps support index access, got {}.",
+                    "Only arrays, maps and tuples support index access, got {}.",
                     collection_value
                 ));
+                return Some(Result::RuntimeError);
             }
         }
+        None
     }
 
     #[inline(always)]
-    pub(in crate::vm) fn fn_set_index(&mut self) {
+    /// Position an index refers to in an array or tuple of `len` elements, counting from the
+    /// end for a negative index. Reports a runtime error and returns `None` for an index that
+    /// isn't a number or lies outside the collection.
+    fn sequence_index(&mut self, index: &Value, len: usize, kind: &str) -> Option<usize> {
+        let position = match index {
+            Value::Int(i) => Some(*i),
+            Value::Number(n) => n.is_finite().then_some(*n as i64),
+            _ => {
+                self.runtime_error(&format!("{} index must be a number, got {}.", kind, index));
+                return None;
+            }
+        };
+        let len_i64 = len as i64;
+        let position = position
+            .map(|position| match position {
+                _ if position < 0 => position.saturating_add(len_i64),
+                _ => position,
+            })
+            .filter(|position| (0..len_i64).contains(position));
+        if position.is_none() {
+            self.runtime_error(&format!(
+                "{} index {} out of bounds, length {}.",
+                kind, index, len
+            ));
+        }
+        position.map(|position| position as usize)
+    }
+
+    /// Assigning past the end of an array is an error rather than growing it; `push` appends
+    pub(in crate::vm) fn fn_set_index(&mut self) -> Option<Result> {
         let value = self.pop();
         let index_value = self.pop();
         let collection_value = self.pop();
@@ -881,14 +1819,14 @@ impl VirtualMachine {
             Value::Object(obj) => match obj.as_ref() {
                 Object::Map(map_ref) => {
                     // Convert index to MapKey
-                    let key = match Self::value_to_map_key(&index_value) {
+                    let key = match MapKey::from_value(&index_value) {
                         Some(k) => k,
                         None => {
                             self.runtime_error(&format!(
-                                "Invalid map key type: {}. Only strings, numbers, and booleans can be used as map keys.",
+                                "Invalid map key type: {}. Only strings, numbers, booleans and tuples can be used as map keys.",
                                 index_value
                             ));
-                            return;
+                            return Some(Result::RuntimeError);
                         }
                     };
 
@@ -898,39 +1836,33 @@ impl VirtualMachine {
                     self.push(value);
                 }
                 Object::Array(array_ref) => {
-                    let index = match index_value {
-                        Value::Number(n) => n as i32,
-                        _ => {
-                            self.runtime_error(&format!(
-                                "Array index must be a number, got {}.",
-                                index_value
-                            ));
-                            return;
-                        }
+                    let len = array_ref.borrow().len();
+                    let Some(index) = self.sequence_index(&index_value, len, "Array") else {
+                        return Some(Result::RuntimeError);
                     };
-
-                    let mut array = array_ref.borrow_mut();
-                    let len = array.len() as i32;
-
-                    let actual_index = if index < 0 { len + index } else { index };
-
-                    if actual_index < 0 || actual_index >= len {
-                        self.runtime_error(&format!(
-                            "Array index out of bounds: index {} (normalized: {}) on array of length {}.",
-                            index, actual_index, len
-                        ));
-                        return;
-                    }
-
-                    array[actual_index as usize] = value.clone();
-
+                    array_ref.borrow_mut()[index] = value.clone();
                     self.push(value);
                 }
+                Object::Tuple(_) => {
+                    self.runtime_error(&format!(
+                        "Tuples are immutable, cannot assign to an index of {}.",
+                        collection_value
+                    ));
+                    return Some(Result::RuntimeError);
+                }
+                Object::Frozen(_) => {
+                    self.runtime_error(&format!(
+                        "Cannot mutate frozen collection {}.",
+                        collection_value
+                    ));
+                    return Some(Result::RuntimeError);
+                }
                 _ => {
                     self.runtime_error(&format!(
                         "Only arrays and maps support index assignment, got {}.",
                         collection_value
                     ));
+                    return Some(Result::RuntimeError);
                 }
             },
             _ => {
@@ -938,23 +1870,10 @@ impl VirtualMachine {
                     "Only arrays and maps support index assignment, got {}.",
                     collection_value
                 ));
+                return Some(Result::RuntimeError);
             }
         }
-    }
-
-    fn value_to_map_key(value: &Value) -> Option<crate::common::MapKey> {
-        use crate::common::MapKey;
-        use ordered_float::OrderedFloat;
-
-        match value {
-            Value::Object(obj) => match obj.as_ref() {
-                Object::String(s) => Some(MapKey::String(Rc::clone(&s.value))),
-                _ => None,
-            },
-            Value::Number(n) => Some(MapKey::Number(OrderedFloat(*n))),
-            Value::Boolean(b) => Some(MapKey::Boolean(*b)),
-            Value::Nil => None,
-        }
+        None
     }
 
     /// GetIterator: Convert a collection to an iterator
@@ -967,45 +1886,24 @@ impl VirtualMachine {
         let collection = self.pop();
 
         let iterator_value = match &collection {
-            Value::Object(obj) => match obj.as_ref() {
+            Value::Object(obj) => match obj.unfrozen() {
                 Object::Array(_) => collection,
                 Object::Map(map_ref) => {
                     let map = map_ref.borrow();
-                    let keys: Vec<Value> = map
-                        .keys()
-                        .map(|k| match k {
-                            crate::common::MapKey::String(s) => {
-                                Value::Object(Rc::new(Object::String(crate::common::ObjString {
-                                    value: Rc::clone(s),
-                                })))
-                            }
-                            crate::common::MapKey::Number(n) => Value::Number(n.into_inner()),
-                            crate::common::MapKey::Boolean(b) => Value::Boolean(*b),
-                        })
-                        .collect();
+                    let keys: Vec<Value> = map.keys().map(MapKey::to_value).collect();
 
                     Value::new_array(keys)
                 }
                 Object::Set(set_ref) => {
                     let set = set_ref.borrow();
-                    let elements: Vec<Value> = set
-                        .iter()
-                        .map(|k| match k {
-                            crate::common::SetKey::String(s) => {
-                                Value::Object(Rc::new(Object::String(crate::common::ObjString {
-                                    value: Rc::clone(s),
-                                })))
-                            }
-                            crate::common::SetKey::Number(n) => Value::Number(n.into_inner()),
-                            crate::common::SetKey::Boolean(b) => Value::Boolean(*b),
-                        })
-                        .collect();
+                    let elements: Vec<Value> = set.iter().map(SetKey::to_value).collect();
 
                     Value::new_array(elements)
                 }
+                Object::Tuple(elements) => Value::new_array(elements.to_vec()),
                 _ => {
                     self.runtime_error(&format!(
-                        "Cannot iterate over type: {}. Only arrays, maps, and sets are iterable.",
+                        "Cannot iterate over type: {}. Only arrays, maps, sets and tuples are iterable.",
                         collection
                     ));
                     return Some(Result::RuntimeError);
@@ -1031,7 +1929,7 @@ impl VirtualMachine {
     pub(in crate::vm) fn fn_iterator_done(&mut self) {
         if let Some((index, collection)) = self.iterator_stack.last() {
             let has_more = match collection {
-                Value::Object(obj) => match obj.as_ref() {
+                Value::Object(obj) => match obj.unfrozen() {
                     Object::Array(array_ref) => {
                         let array = array_ref.borrow();
                         *index < array.len()
@@ -1055,7 +1953,7 @@ impl VirtualMachine {
     pub(in crate::vm) fn fn_iterator_next(&mut self) -> Option<Result> {
         let (value, new_index) = if let Some((index, collection)) = self.iterator_stack.last() {
             match collection {
-                Value::Object(obj) => match obj.as_ref() {
+                Value::Object(obj) => match obj.unfrozen() {
                     Object::Array(array_ref) => {
                         let array = array_ref.borrow();
                         if *index < array.len() {
@@ -1095,16 +1993,18 @@ impl VirtualMachine {
     /// Helper: Extract type name from a value for method dispatch
     fn get_type_name(&self, value: &Value) -> Option<String> {
         match value {
-            Value::Object(obj) => match obj.as_ref() {
+            Value::Object(obj) => match obj.unfrozen() {
                 Object::Array(_) => Some("Array".to_string()),
                 Object::String(_) => Some("String".to_string()),
                 Object::Map(_) => Some("Map".to_string()),
                 Object::Set(_) => Some("Set".to_string()),
                 Object::File(_) => Some("File".to_string()),
+                Object::Result(_) => Some("Result".to_string()),
+                Object::Option(_) => Some("Option".to_string()),
                 Object::Instance(inst) => Some(inst.borrow().r#struct.name.clone()),
                 _ => None,
             },
-            Value::Number(_) => Some("Number".to_string()),
+            Value::Number(_) | Value::Int(_) => Some("Number".to_string()),
             Value::Boolean(_) => Some("Boolean".to_string()),
             _ => None,
         }
@@ -1,10 +1,16 @@
-use crate::common::opcodes::OpCode;
-use crate::common::{BitsSize, CallFrame, Chunk, ObjFunction, Value};
+use crate::common;
+use crate::common::{CallFrame, Chunk, ObjFunction, Value};
 use crate::compiler::Compiler;
-use crate::vm::{Result, VirtualMachine};
-use crate::{boolean, common, nil};
+use crate::vm::dispatch::{Flow, DISPATCH};
+use crate::vm::{
+    EvalOutcome, FrameLocation, OutputBuffering, RaisedError, Result, VirtualMachine,
+    REGEX_CACHE_CAPACITY,
+};
 #[cfg(not(target_arch = "wasm32"))]
 use log::info;
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::rc::Rc;
 
 impl Default for VirtualMachine {
@@ -19,14 +25,30 @@ impl VirtualMachine {
             call_frames: Vec::new(),
             stack: Vec::new(),
             chunk: None,
-            builtin: common::stdlib::create_builtin_objects(args),
-            #[cfg(any(test, debug_assertions, target_arch = "wasm32"))]
+            builtin: common::stdlib::create_builtin_objects(args.clone()),
+            args,
             string_buffer: String::new(),
+            capture_output: cfg!(any(test, debug_assertions, target_arch = "wasm32")),
+            output: super::output::default_output(),
+            output_buffering: OutputBuffering::default(),
+            input: super::input::default_input(),
             compilation_errors: String::new(),
             structured_errors: Vec::new(),
+            warnings: Vec::new(),
             runtime_errors: String::new(),
             source: String::new(),
             iterator_stack: Vec::new(),
+            open_upvalues: Vec::new(),
+            exit_code: None,
+            callback_stop: None,
+            callback_depth: 0,
+            regex_cache: indexmap::IndexMap::new(),
+            max_call_depth: super::DEFAULT_MAX_CALL_DEPTH,
+            strict_arithmetic: false,
+            script_path: None,
+            module_cache: HashMap::new(),
+            try_handlers: Vec::new(),
+            raised_error: None,
         }
     }
 
@@ -35,7 +57,7 @@ impl VirtualMachine {
     }
 
     pub fn interpret(&mut self, source: String) -> Result {
-        self.reset();
+        self.reset_run_state();
 
         self.source = source.clone();
 
@@ -43,7 +65,11 @@ impl VirtualMachine {
         let start = std::time::Instant::now();
 
         let mut compiler = Compiler::new(self.builtin.clone());
+        if let Some(path) = &self.script_path {
+            compiler.set_script_path(path);
+        }
         let chunk = compiler.compile(&source);
+        self.warnings = compiler.get_warnings();
 
         #[cfg(not(target_arch = "wasm32"))]
         info!("Compile time: {}ms", start.elapsed().as_millis());
@@ -57,11 +83,122 @@ impl VirtualMachine {
         }
 
         let chunk = chunk.unwrap();
+        let result = self.run_script(chunk);
+
+        #[cfg(not(target_arch = "wasm32"))]
+        info!("Run time: {}ms", start.elapsed().as_millis());
+
+        result
+    }
+
+    /// Evaluates a single REPL input and returns a structured outcome.
+    ///
+    /// Behaves like [`VirtualMachine::interpret`], but when the input ends in an
+    /// expression statement its value is returned in [`EvalOutcome::value`].
+    /// Output printed during evaluation is captured in [`EvalOutcome::output`].
+    pub fn eval(&mut self, line: &str) -> EvalOutcome {
+        self.reset_run_state();
+        self.source = line.to_string();
+
+        let mut compiler = Compiler::new(self.builtin.clone());
+        let compiled = compiler.compile_for_eval(line);
+        self.warnings = compiler.get_warnings();
+        let Some((chunk, returns_value)) = compiled else {
+            self.compilation_errors = compiler.get_compilation_errors();
+            self.structured_errors = compiler.get_structured_errors();
+            return EvalOutcome {
+                result: Result::CompileError,
+                value: None,
+                output: String::new(),
+                errors: self.structured_errors.clone(),
+                warnings: self.warnings.clone(),
+                runtime_error: None,
+            };
+        };
+
+        let capture_output = self.capture_output;
+        let output_start = self.string_buffer.len();
+        self.capture_output = true;
+        let result = self.run_script(chunk);
+        self.capture_output = capture_output;
+
+        let output = self.string_buffer[output_start..].to_string();
+        if !capture_output {
+            self.string_buffer.truncate(output_start);
+        }
+
+        let value = match result {
+            Result::Ok if returns_value => self.stack.last().cloned(),
+            _ => None,
+        };
+        let runtime_error = match result {
+            Result::RuntimeError => Some(self.runtime_errors.clone()),
+            _ => None,
+        };
 
+        EvalOutcome {
+            result,
+            value,
+            output,
+            errors: Vec::new(),
+            warnings: self.warnings.clone(),
+            runtime_error,
+        }
+    }
+
+    /// Compiles the source without running it and returns its bytecode listing
+    /// (see [`Chunk::disassemble`]). Returns None if it doesn't compile; the errors
+    /// are then available the same way as after [`VirtualMachine::interpret`].
+    pub fn disassemble(&mut self, source: &str) -> Option<String> {
+        self.reset_run_state();
+        self.source = source.to_string();
+
+        let mut compiler = Compiler::new(self.builtin.clone());
+        let chunk = compiler.compile(source);
+        self.warnings = compiler.get_warnings();
+        if chunk.is_none() {
+            self.compilation_errors = compiler.get_compilation_errors();
+            self.structured_errors = compiler.get_structured_errors();
+        }
+        chunk.map(|chunk| chunk.disassemble())
+    }
+
+    /// Command-line arguments passed to the script
+    pub(crate) fn script_args(&self) -> &[String] {
+        &self.args
+    }
+
+    /// Asks the VM to stop after the current native call, returning `Result::Exit(code)`
+    pub(crate) fn request_exit(&mut self, code: i32) {
+        self.exit_code = Some(code);
+    }
+
+    /// Returns the compiled regex for `pattern`, compiling and caching it on first use.
+    /// Once the cache is full, the least recently used pattern makes room for a new one.
+    pub(crate) fn compiled_regex(&mut self, pattern: &str) -> std::result::Result<&Regex, String> {
+        match self.regex_cache.get_index_of(pattern) {
+            Some(index) => self
+                .regex_cache
+                .move_index(index, self.regex_cache.len() - 1),
+            None => {
+                let regex = Regex::new(pattern).map_err(|e| e.to_string())?;
+                if self.regex_cache.len() >= REGEX_CACHE_CAPACITY {
+                    self.regex_cache.shift_remove_index(0);
+                }
+                self.regex_cache.insert(pattern.to_string(), regex);
+            }
+        }
+        Ok(&self.regex_cache[self.regex_cache.len() - 1])
+    }
+
+    fn run_script(&mut self, chunk: Chunk) -> Result {
         let script_function = Rc::new(ObjFunction {
             name: "<script>".to_string(),
             arity: 0,
+            min_arity: 0,
+            variadic: false,
             chunk: Rc::new(chunk),
+            upvalues: Vec::new(),
         });
 
         // Use -1 for slot_start since the script has no function object on the stack
@@ -69,15 +206,13 @@ impl VirtualMachine {
             function: script_function,
             ip: 0,
             slot_start: -1,
+            arg_count: 0,
         };
         self.call_frames.push(frame);
 
         let result = self.run(&Chunk::new("dummy"));
         self.chunk = None;
-
-        #[cfg(not(target_arch = "wasm32"))]
-        info!("Run time: {}ms", start.elapsed().as_millis());
-
+        self.flush_output();
         result
     }
 
@@ -96,6 +231,7 @@ impl VirtualMachine {
     ///
     /// * `Result::Ok` - Execution completed successfully
     /// * `Result::RuntimeError` - A runtime error occurred during execution
+    /// * `Result::Exit(code)` - The script called `System.exit(code)`
     ///
     /// # Example
     ///
@@ -105,7 +241,7 @@ impl VirtualMachine {
     /// let result = vm.execute_chunk(chunk);
     /// ```
     pub fn execute_chunk(&mut self, chunk: Chunk) -> Result {
-        self.reset();
+        self.reset_run_state();
 
         // Use a placeholder for source since we're executing pre-compiled bytecode
         self.source = "<compiled>".to_string();
@@ -117,7 +253,10 @@ impl VirtualMachine {
         let script_function = Rc::new(ObjFunction {
             name: "<compiled>".to_string(),
             arity: 0,
+            min_arity: 0,
+            variadic: false,
             chunk: Rc::new(chunk),
+            upvalues: Vec::new(),
         });
 
         // Create the initial call frame
@@ -126,11 +265,13 @@ impl VirtualMachine {
             function: script_function,
             ip: 0,
             slot_start: -1,
+            arg_count: 0,
         };
         self.call_frames.push(frame);
 
         let result = self.run(&Chunk::new("dummy"));
         self.chunk = None;
+        self.flush_output();
 
         #[cfg(not(target_arch = "wasm32"))]
         info!("Run time: {}ms", start.elapsed().as_millis());
@@ -148,119 +289,18 @@ impl VirtualMachine {
         loop {
             let op_code = {
                 let frame = self.current_frame();
-                let ip = frame.ip;
-                OpCode::from_u8(frame.function.chunk.read_u8(ip))
+                frame.function.chunk.read_u8(frame.ip)
             };
 
-            match op_code {
-                OpCode::Return => {
-                    if let Some(result) = self.fn_return() {
-                        return result;
-                    }
-                    continue;
-                }
-                OpCode::Constant => self.fn_constant(),
-                OpCode::Constant2 => self.fn_constant2(),
-                OpCode::Constant4 => self.fn_constant4(),
-                OpCode::Negate => {
-                    if let Some(value) = self.fn_negate() {
-                        return value;
-                    }
-                }
-                OpCode::Add => {
-                    if let Some(value) = self.fn_add() {
-                        return value;
-                    }
-                }
-                OpCode::Subtract => self.fn_subtract(),
-                OpCode::Multiply => self.fn_multiply(),
-                OpCode::Divide => self.fn_divide(),
-                OpCode::FloorDivide => self.fn_floor_divide(),
-                OpCode::Modulo => self.fn_modulo(),
-                OpCode::Exponent => self.fn_exponent(),
-                OpCode::Nil => self.push(nil!()),
-                OpCode::True => self.push(boolean!(true)),
-                OpCode::False => self.push(boolean!(false)),
-                OpCode::Equal => self.fn_equal(),
-                OpCode::Greater => self.fn_greater(),
-                OpCode::Less => self.fn_less(),
-                OpCode::Not => self.fn_not(),
-                OpCode::String => self.fn_string(),
-                OpCode::String2 => self.fn_string2(),
-                OpCode::String4 => self.fn_string4(),
-                OpCode::Pop => _ = self.pop(),
-                OpCode::GetLocal => self.fn_get_local(BitsSize::Eight),
-                OpCode::GetLocal2 => self.fn_get_local(BitsSize::Sixteen),
-                OpCode::GetLocal4 => self.fn_get_local(BitsSize::ThirtyTwo),
-                OpCode::SetLocal => self.fn_set_local(BitsSize::Eight),
-                OpCode::SetLocal2 => self.fn_set_local(BitsSize::Sixteen),
-                OpCode::SetLocal4 => self.fn_set_local(BitsSize::ThirtyTwo),
-                OpCode::GetBuiltin => self.fn_get_builtin(BitsSize::Eight),
-                OpCode::GetBuiltin2 => self.fn_get_builtin(BitsSize::Sixteen),
-                OpCode::GetBuiltin4 => self.fn_get_builtin(BitsSize::ThirtyTwo),
-                OpCode::GetGlobal => self.fn_get_global(BitsSize::Eight),
-                OpCode::GetGlobal2 => self.fn_get_global(BitsSize::Sixteen),
-                OpCode::GetGlobal4 => self.fn_get_global(BitsSize::ThirtyTwo),
-                OpCode::SetGlobal => self.fn_set_global(BitsSize::Eight),
-                OpCode::SetGlobal2 => self.fn_set_global(BitsSize::Sixteen),
-                OpCode::SetGlobal4 => self.fn_set_global(BitsSize::ThirtyTwo),
-                OpCode::JumpIfFalse => self.fn_jump_if_false(),
-                OpCode::Jump => self.fn_jump(),
-                OpCode::Loop => self.fn_loop(),
-                OpCode::Call => {
-                    if let Some(result) = self.fn_call() {
-                        return result;
-                    }
-                    continue;
-                }
-                OpCode::GetField => self.fn_get_field(BitsSize::Eight),
-                OpCode::GetField2 => self.fn_get_field(BitsSize::Sixteen),
-                OpCode::GetField4 => self.fn_get_field(BitsSize::ThirtyTwo),
-                OpCode::SetField => self.fn_set_field(BitsSize::Eight),
-                OpCode::SetField2 => self.fn_set_field(BitsSize::Sixteen),
-                OpCode::SetField4 => self.fn_set_field(BitsSize::ThirtyTwo),
-
-                OpCode::CreateMap => self.fn_create_map(),
-                OpCode::CreateArray => self.fn_create_array(),
-                OpCode::CreateSet => self.fn_create_set(),
-                OpCode::GetIndex => self.fn_get_index(),
-                OpCode::SetIndex => self.fn_set_index(),
-                OpCode::GetIterator => {
-                    if let Some(result) = self.fn_get_iterator() {
-                        return result;
-                    }
-                }
-                OpCode::IteratorNext => {
-                    if let Some(result) = self.fn_iterator_next() {
-                        return result;
-                    }
-                }
-                OpCode::IteratorDone => self.fn_iterator_done(),
-                OpCode::PopIterator => {
-                    if self.iterator_stack.is_empty() {
-                        self.runtime_error("No iterator to pop");
-                        return Result::RuntimeError;
-                    }
-                    self.iterator_stack.pop();
-                }
-                OpCode::CreateRange => {
-                    if let Some(result) = self.fn_create_range() {
-                        return result;
-                    }
-                }
-                OpCode::ToString => self.fn_to_string(),
-                OpCode::BitwiseAnd => self.fn_bitwise_and(),
-                OpCode::BitwiseOr => self.fn_bitwise_or(),
-                OpCode::BitwiseXor => self.fn_bitwise_xor(),
-                OpCode::BitwiseNot => {
-                    if let Some(value) = self.fn_bitwise_not() {
-                        return value;
-                    }
+            match DISPATCH[op_code as usize](self) {
+                Flow::Next => self.current_frame_mut().ip += 1,
+                Flow::Jumped => {}
+                Flow::Stop(Result::RuntimeError) if self.catch_runtime_error(0) => {}
+                Flow::Stop(result) => {
+                    self.report_raised_error();
+                    return result;
                 }
-                OpCode::LeftShift => self.fn_left_shift(),
-                OpCode::RightShift => self.fn_right_shift(),
             }
-            self.current_frame_mut().ip += 1;
         }
     }
 
@@ -293,24 +333,45 @@ impl VirtualMachine {
         self.stack[self.stack.len() - 1 - distance].clone()
     }
 
+    /// Raises a runtime error at the current instruction. It is reported once it stops the
+    /// program, so an error caught by a `try` block is never printed.
     pub(in crate::vm) fn runtime_error(&mut self, error: &str) {
-        let source_location = self.get_current_source_location();
-        let error_message = format!("[{}] {}", source_location, error);
+        // An earlier error that didn't stop the program
+        self.report_raised_error();
 
-        eprintln!("{}", error_message);
+        self.raised_error = Some(RaisedError {
+            message: error.to_string(),
+            thrown: None,
+            frames: self.frame_locations(),
+        });
+    }
+
+    fn report_raised_error(&mut self) {
+        let Some(error) = self.raised_error.take() else {
+            return;
+        };
+        let source_location = match error.frames.first() {
+            Some(frame) => Self::source_location_at(&frame.function, frame.offset),
+            None => "unknown".to_string(),
+        };
+        let report = format!(
+            "[{}] {}{}",
+            source_location,
+            error.message,
+            Self::stack_trace(&error.frames)
+        );
+        eprintln!("{}", report);
 
         if !self.runtime_errors.is_empty() {
             self.runtime_errors.push('\n');
         }
-        self.runtime_errors.push_str(&error_message);
+        self.runtime_errors.push_str(&report);
     }
 
-    #[cfg(any(test, debug_assertions, target_arch = "wasm32"))]
     pub fn get_output(&self) -> String {
         self.string_buffer.trim().to_string()
     }
 
-    #[cfg(target_arch = "wasm32")]
     pub fn clear_output(&mut self) {
         self.string_buffer.clear();
     }
@@ -327,6 +388,36 @@ impl VirtualMachine {
         renderer.render_errors(&self.structured_errors, &self.source, filename)
     }
 
+    pub fn get_warnings(&self) -> Vec<crate::common::errors::CompilationError> {
+        self.warnings.clone()
+    }
+
+    pub fn get_formatted_warnings(&self, filename: &str) -> String {
+        use crate::common::error_renderer::ErrorRenderer;
+
+        let renderer = ErrorRenderer::default();
+        renderer.render_errors(&self.warnings, &self.source, filename)
+    }
+
+    /// Sets the file the source passed to [`VirtualMachine::interpret`] comes from. Its
+    /// imports are resolved relative to the file's directory instead of the working directory.
+    pub fn set_script_path(&mut self, path: impl Into<PathBuf>) {
+        self.script_path = Some(path.into());
+    }
+
+    /// Sets how many function calls may be active at once. A script that recurses deeper stops
+    /// with a runtime error; calls in tail position don't add to the depth.
+    pub fn set_max_call_depth(&mut self, depth: usize) {
+        self.max_call_depth = depth;
+    }
+
+    /// Makes `/`, `//` and `%` with a zero divisor a runtime error. Without it only ints
+    /// can't be divided by zero; numbers follow IEEE 754, so `1 / 0` is `inf`, `0 / 0` is
+    /// `nan` and `x % 0` is `nan`.
+    pub fn set_strict_arithmetic(&mut self, strict: bool) {
+        self.strict_arithmetic = strict;
+    }
+
     pub fn get_runtime_errors(&self) -> String {
         self.runtime_errors.clone()
     }
@@ -335,22 +426,94 @@ impl VirtualMachine {
         self.runtime_errors.clear();
     }
 
-    fn get_current_source_location(&self) -> String {
-        if let Some(frame) = self.call_frames.last() {
-            if let Some(location) = frame.function.chunk.get_source_location(frame.ip) {
-                format!("{}:{}", location.line, location.column)
-            } else {
-                "unknown".to_string()
+    fn source_location_at(function: &ObjFunction, offset: usize) -> String {
+        match function.chunk.get_source_location(offset) {
+            Some(location) => format!("{}:{}", location.line, location.column),
+            None => "unknown".to_string(),
+        }
+    }
+
+    /// Where every active call frame is at, innermost first. Consecutive identical frames, as
+    /// in recursion, are kept once with their count, so a deep stack stays cheap to copy.
+    fn frame_locations(&self) -> Vec<FrameLocation> {
+        let innermost = self.call_frames.len().saturating_sub(1);
+        let mut frames: Vec<FrameLocation> = Vec::new();
+        for (index, frame) in self.call_frames.iter().enumerate().rev() {
+            // The frames below the innermost one are already past the call they wait on
+            let offset = match index {
+                _ if index == innermost => frame.ip,
+                _ => frame.ip.saturating_sub(1),
+            };
+            match frames.last_mut() {
+                Some(last)
+                    if Rc::ptr_eq(&last.function, &frame.function) && last.offset == offset =>
+                {
+                    last.count += 1
+                }
+                _ => frames.push(FrameLocation {
+                    function: frame.function.clone(),
+                    offset,
+                    count: 1,
+                }),
+            }
+        }
+        frames
+    }
+
+    /// One line per active call frame, innermost first, with the function's name and where it
+    /// is at. Empty for an error in the script's own code. Consecutive identical lines, as in
+    /// recursion, are listed once with their count.
+    fn stack_trace(frames: &[FrameLocation]) -> String {
+        if frames.iter().map(|frame| frame.count).sum::<usize>() < 2 {
+            return String::new();
+        }
+        let mut lines: Vec<(String, usize)> = Vec::new();
+        for frame in frames {
+            let line = format!(
+                "    at {} [{}]",
+                frame.function.name,
+                Self::source_location_at(&frame.function, frame.offset)
+            );
+            match lines.last_mut() {
+                Some((last, count)) if *last == line => *count += frame.count,
+                _ => lines.push((line, frame.count)),
             }
-        } else {
-            "unknown".to_string()
         }
+        lines
+            .iter()
+            .map(|(line, count)| match count {
+                1 => format!("\n{}", line),
+                _ => format!("\n{} (x{})", line, count),
+            })
+            .collect()
+    }
+
+    /// Returns the VM to the state of a fresh instance so it can run an unrelated program:
+    /// globals, the stack, captured output and all errors and warnings are dropped. The
+    /// builtin objects, script arguments, regex cache, call depth limit and
+    /// arithmetic mode are kept.
+    pub fn reset(&mut self) {
+        self.reset_run_state();
+        self.string_buffer.clear();
+        self.source.clear();
     }
 
-    fn reset(&mut self) {
+    /// Clears what a previous run left behind before the next one starts
+    fn reset_run_state(&mut self) {
         self.call_frames.clear();
         self.stack.clear();
+        self.iterator_stack.clear();
+        self.open_upvalues.clear();
         self.chunk = None;
+        self.compilation_errors.clear();
+        self.structured_errors.clear();
         self.runtime_errors.clear();
+        self.warnings.clear();
+        self.exit_code = None;
+        self.callback_stop = None;
+        self.callback_depth = 0;
+        self.module_cache.clear();
+        self.try_handlers.clear();
+        self.raised_error = None;
     }
 }
@@ -0,0 +1,43 @@
+use crate::vm::VirtualMachine;
+use std::io::{BufRead, Write};
+
+/// Default input source: the process's standard input.
+#[cfg(not(target_arch = "wasm32"))]
+pub(in crate::vm) fn default_input() -> Box<dyn BufRead> {
+    Box::new(std::io::BufReader::new(std::io::stdin()))
+}
+
+/// The browser has no stdin, so every read reports end of input
+#[cfg(target_arch = "wasm32")]
+pub(in crate::vm) fn default_input() -> Box<dyn BufRead> {
+    Box::new(std::io::empty())
+}
+
+impl VirtualMachine {
+    /// Replaces the source that input() reads lines from.
+    pub fn set_input_source(&mut self, source: Box<dyn BufRead>) {
+        self.input = source;
+    }
+
+    /// Writes `prompt` without a trailing newline and flushes it so it shows before input is read
+    pub(crate) fn write_prompt(&mut self, prompt: &str) -> Result<(), String> {
+        if self.capture_output {
+            self.string_buffer.push_str(prompt);
+        }
+
+        write!(self.output, "{}", prompt).map_err(|e| format!("input() failed: {}", e))?;
+        self.output
+            .flush()
+            .map_err(|e| format!("input() failed: {}", e))
+    }
+
+    /// Reads the next line without surrounding whitespace, or None at end of input
+    pub(crate) fn read_input_line(&mut self) -> Result<Option<String>, String> {
+        let mut line = String::new();
+        match self.input.read_line(&mut line) {
+            Ok(0) => Ok(None),
+            Ok(_) => Ok(Some(line.trim().to_string())),
+            Err(e) => Err(format!("input() failed: {}", e)),
+        }
+    }
+}
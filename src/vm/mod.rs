@@ -1,16 +1,61 @@
-use crate::common::{CallFrame, Chunk, Value};
+use crate::common::errors::CompilationError;
+use crate::common::{CallFrame, Chunk, ObjFunction, Upvalue, Value};
+use regex::Regex;
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::fmt::Debug;
+use std::io::{BufRead, BufWriter, Write};
+use std::path::PathBuf;
+use std::rc::Rc;
 
+pub use output::{OutputBuffering, StdoutSink};
+
+mod dispatch;
 mod functions;
 mod r#impl;
+mod input;
+mod output;
 #[cfg(test)]
 mod tests;
 
+/// Nested function calls a VM allows unless told otherwise, see
+/// [`VirtualMachine::set_max_call_depth`]
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 10_000;
+
+/// Compiled regular expressions a VM keeps; the least recently used one is dropped for a new one
+pub(crate) const REGEX_CACHE_CAPACITY: usize = 64;
+
+/// Callbacks run by natives that may be active inside each other at once. Each one runs its
+/// own dispatch loop on the native stack, which this keeps from overflowing.
+const MAX_CALLBACK_DEPTH: usize = 200;
+
 #[derive(Debug, PartialEq)]
 pub enum Result {
     Ok,
     CompileError,
     RuntimeError,
+    /// The script called System.exit(code); execution stopped with this status code
+    Exit(i32),
+}
+
+/// Structured outcome of evaluating a single REPL input via [`VirtualMachine::eval`].
+///
+/// Unlike [`Result`], this carries everything an embedder needs to render the
+/// evaluation: the value of a trailing expression, the output printed while
+/// running, and the errors that stopped evaluation.
+#[derive(Debug)]
+pub struct EvalOutcome {
+    pub result: Result,
+    /// Value of the input when it ends in an expression statement
+    pub value: Option<Value>,
+    /// Output printed while evaluating the input
+    pub output: String,
+    /// Compilation errors (empty unless `result` is `CompileError`)
+    pub errors: Vec<CompilationError>,
+    /// Lint warnings reported while compiling the input
+    pub warnings: Vec<CompilationError>,
+    /// Runtime error message (set only if `result` is `RuntimeError`)
+    pub runtime_error: Option<String>,
 }
 
 pub struct VirtualMachine {
@@ -25,30 +70,98 @@ pub struct VirtualMachine {
     chunk: Option<Chunk>,
     /// Global built-in values (like Math) stored separately from the call stack
     builtin: indexmap::IndexMap<String, Value>,
-    #[cfg(any(test, debug_assertions, target_arch = "wasm32"))]
+    /// Raw command-line arguments the VM was constructed with (also exposed as `args`)
+    args: Vec<String>,
     string_buffer: String,
+    /// Whether print() output is also captured into `string_buffer`
+    capture_output: bool,
+    /// Sink that print() writes to (stdout by default)
+    output: BufWriter<Box<dyn Write>>,
+    output_buffering: OutputBuffering,
+    /// Source that input() reads lines from (stdin by default)
+    input: Box<dyn BufRead>,
     compilation_errors: String,
     structured_errors: Vec<crate::common::errors::CompilationError>,
+    /// Lint warnings from the most recent compilation
+    warnings: Vec<crate::common::errors::CompilationError>,
     runtime_errors: String,
     source: String,
     /// Iterator stack: Vec of (current_index, collection_value)
     /// Used for for-in loops to track iteration progress
     /// Supports nested for-in loops by maintaining a stack of iterators
     iterator_stack: Vec<(usize, Value)>,
+    /// Upvalues still pointing at live stack slots, ordered by slot.
+    /// Shared by every closure capturing the same variable and closed when its frame returns.
+    open_upvalues: Vec<Rc<RefCell<Upvalue>>>,
+    /// Status code requested by System.exit(), checked after each native call
+    exit_code: Option<i32>,
+    /// How a callback run by a native stopped the program (runtime error or exit), returned
+    /// once the native that called it fails
+    callback_stop: Option<Result>,
+    /// Number of `call_callback` calls currently running
+    callback_depth: usize,
+    /// Compiled regular expressions keyed by pattern, reused across Regex calls.
+    /// Ordered from least to most recently used.
+    regex_cache: indexmap::IndexMap<String, Regex>,
+    /// Most function calls that may be active at once; deeper recursion is a runtime error
+    max_call_depth: usize,
+    /// Whether dividing a number by zero is a runtime error rather than `inf` or `nan`
+    strict_arithmetic: bool,
+    /// File the interpreted source was read from; its imports are resolved from its directory
+    script_path: Option<PathBuf>,
+    /// Modules imported during the current run, keyed by name, so each one runs only once
+    module_cache: HashMap<String, Value>,
+    /// `try` blocks being run, innermost last
+    try_handlers: Vec<TryHandler>,
+    /// Runtime error not reported yet, as a `try` block may still catch it
+    raised_error: Option<RaisedError>,
+}
+
+/// Where a runtime error inside a `try` block unwinds to
+struct TryHandler {
+    /// Number of call frames, the last one being the frame running the `try` block
+    frame_depth: usize,
+    stack_len: usize,
+    /// Number of for-in iterators
+    iterators: usize,
+    /// Start of the `catch` block
+    catch_ip: usize,
+}
+
+struct RaisedError {
+    message: String,
+    /// The value a `throw` raised, which a `catch` block gets instead of an error object
+    thrown: Option<Value>,
+    /// Where the call frames were at when the error was raised, for the report if nothing
+    /// catches the error
+    frames: Vec<FrameLocation>,
+}
+
+/// A run of identical call frames, as kept for the stack trace of a raised error
+struct FrameLocation {
+    function: Rc<ObjFunction>,
+    offset: usize,
+    count: usize,
 }
 
 // Test-only methods
 #[cfg(test)]
 impl VirtualMachine {
+    pub(crate) fn regex_cache_len(&self) -> usize {
+        self.regex_cache.len()
+    }
+
     pub(crate) fn run_chunk(&mut self, chunk: Chunk) -> Result {
         use crate::common::ObjFunction;
-        use std::rc::Rc;
 
         // Create a synthetic function for the test chunk
         let test_function = Rc::new(ObjFunction {
             name: "<test>".to_string(),
             arity: 0,
+            min_arity: 0,
+            variadic: false,
             chunk: Rc::new(chunk),
+            upvalues: Vec::new(),
         });
 
         // Create the initial call frame
@@ -56,6 +169,7 @@ impl VirtualMachine {
             function: test_function,
             ip: 0,
             slot_start: -1, // Like script frame, no function object on stack
+            arg_count: 0,
         };
         self.call_frames.push(frame);
 
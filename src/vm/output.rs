@@ -0,0 +1,69 @@
+use crate::vm::VirtualMachine;
+use std::io::{BufWriter, Write};
+
+/// Controls when print() output is handed to the VM's output sink.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputBuffering {
+    /// Flush after every print, so consumers see each line as soon as it is printed
+    #[default]
+    Line,
+    /// Buffer output and flush when the buffer fills up or the script finishes
+    Full,
+}
+
+/// Default output sink that writes to the process's standard output.
+pub struct StdoutSink;
+
+impl Write for StdoutSink {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        // print! (rather than io::stdout) so test harnesses can capture the output
+        #[cfg(not(target_arch = "wasm32"))]
+        print!("{}", String::from_utf8_lossy(buf));
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        #[cfg(not(target_arch = "wasm32"))]
+        std::io::stdout().flush()?;
+        Ok(())
+    }
+}
+
+pub(in crate::vm) fn default_output() -> BufWriter<Box<dyn Write>> {
+    BufWriter::new(Box::new(StdoutSink))
+}
+
+impl VirtualMachine {
+    /// Replaces the sink that print() writes to. Pending output is flushed to the old sink first.
+    pub fn set_output_sink(&mut self, sink: Box<dyn Write>) {
+        self.flush_output();
+        self.output = BufWriter::new(sink);
+    }
+
+    pub fn set_output_buffering(&mut self, buffering: OutputBuffering) {
+        self.output_buffering = buffering;
+        if buffering == OutputBuffering::Line {
+            self.flush_output();
+        }
+    }
+
+    /// Writes one line of print() output, honouring the buffering policy
+    pub(crate) fn write_output_line(&mut self, line: &str) -> Result<(), String> {
+        if self.capture_output {
+            self.string_buffer.push_str(line);
+            self.string_buffer.push('\n');
+        }
+
+        writeln!(self.output, "{}", line).map_err(|e| format!("print() failed: {}", e))?;
+        if self.output_buffering == OutputBuffering::Line {
+            self.output
+                .flush()
+                .map_err(|e| format!("print() failed: {}", e))?;
+        }
+        Ok(())
+    }
+
+    pub(in crate::vm) fn flush_output(&mut self) {
+        let _ = self.output.flush();
+    }
+}
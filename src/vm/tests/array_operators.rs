@@ -0,0 +1,120 @@
+use crate::vm::{Result, VirtualMachine};
+
+#[test]
+fn plus_concatenates_arrays() {
+    let program = r#"
+        val a = [1, 2]
+        val b = [3, 4]
+        val c = a + b
+        print(c)
+        print(a, b)
+        print(([] + [1]).length())
+        "#;
+
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret(program.to_string());
+    assert_eq!(Result::Ok, result);
+    assert_eq!("[1, 2, 3, 4]\n[1, 2] [3, 4]\n1", vm.get_output());
+}
+
+#[test]
+fn concatenated_array_is_a_new_array() {
+    let program = r#"
+        val a = [1]
+        val c = a + [2]
+        c.push(3)
+        print(a)
+        print(c)
+        "#;
+
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret(program.to_string());
+    assert_eq!(Result::Ok, result);
+    assert_eq!("[1]\n[1, 2, 3]", vm.get_output());
+}
+
+#[test]
+fn times_repeats_arrays() {
+    let program = r#"
+        val zeros = [0]
+        print(zeros * 3)
+        print(2 * [1, 2])
+        print([1, 2] * 0)
+        print([7] * (6 //3))
+        print(zeros)
+        "#;
+
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret(program.to_string());
+    assert_eq!(Result::Ok, result);
+    assert_eq!("[0, 0, 0]\n[1, 2, 1, 2]\n[]\n[7, 7]\n[0]", vm.get_output());
+}
+
+#[test]
+fn adding_array_and_non_array_is_an_error() {
+    let program = r#"
+        print([1, 2] + 3)
+        "#;
+
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret(program.to_string());
+    assert_eq!(Result::RuntimeError, result);
+    assert!(vm
+        .get_runtime_errors()
+        .contains("Arrays can only be concatenated with other arrays"));
+
+    let program = r#"
+        print("a" + [1])
+        "#;
+
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret(program.to_string());
+    assert_eq!(Result::RuntimeError, result);
+    assert!(vm
+        .get_runtime_errors()
+        .contains("Arrays can only be concatenated with other arrays"));
+}
+
+#[test]
+fn array_repeat_rejects_invalid_count() {
+    let program = r#"
+        print([0] * -2)
+        "#;
+
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret(program.to_string());
+    assert_eq!(Result::RuntimeError, result);
+    assert!(vm
+        .get_runtime_errors()
+        .contains("Can't repeat an array -2 times"));
+
+    let program = r#"
+        print([0] * 1.5)
+        "#;
+
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret(program.to_string());
+    assert_eq!(Result::RuntimeError, result);
+    assert!(vm
+        .get_runtime_errors()
+        .contains("Can't repeat an array 1.5 times"));
+}
+
+#[test]
+fn repetition_rejects_results_that_are_too_long() {
+    for program in [
+        "print([1] * 10 ** 300)",
+        "print(10 ** 7 * [1, 2, 3, 4, 5, 6, 7])",
+    ] {
+        let mut vm = VirtualMachine::new();
+        assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+        assert!(vm.get_runtime_errors().contains(
+            "Can't repeat an array that many times, the result would be longer than 67108864"
+        ));
+    }
+
+    let mut vm = VirtualMachine::new();
+    let program = "print([] * 10 ** 300, len([0] * 1000))";
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("[] 1000", vm.get_output());
+}
@@ -33,6 +33,21 @@ fn can_execute_simple_arithmetics() {
     assert_eq!(3.5, as_number!(vm.pop()));
 }
 
+#[test]
+fn invalid_opcode_is_a_runtime_error() {
+    let mut chunk = Chunk::new("ZeChunk");
+
+    chunk.write_constant(number!(1.0), 0, 0);
+    chunk.write_u8(0xff);
+    chunk.write_op_code(OpCode::Return, 0, 0);
+
+    let mut vm = VirtualMachine::new();
+
+    let result = vm.run_chunk(chunk);
+    assert_eq!(Result::RuntimeError, result);
+    assert!(vm.get_runtime_errors().contains("Invalid opcode 0xff."));
+}
+
 #[test]
 fn can_print_hello_world() {
     let program = r#"
@@ -538,7 +553,7 @@ fn can_calculate_fibonacci() {
     let start = std::time::Instant::now();
     let result = vm.interpret(program.to_string());
     let elapsed = start.elapsed();
-    println!("Fibonacci test (fib 0-30) took: {:?}", elapsed);
+    println!("Fibonacci test (fib 10) took: {:?}", elapsed);
     assert_eq!(Result::Ok, result);
     assert_eq!("55", vm.get_output());
 }
@@ -866,6 +881,93 @@ fn test_logical_or_short_circuit() {
     assert_eq!("Should reach here\n10", vm.get_output());
 }
 
+#[test]
+fn test_nil_coalesce_uses_right_side_for_nil() {
+    let program = r#"
+        val m = {"name": "neon"}
+        print(m["missing"] ?? "anon")
+        print(m["name"] ?? "anon")
+        print(nil ?? nil ?? 3)
+        "#;
+
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret(program.to_string());
+    assert_eq!(Result::Ok, result);
+    assert_eq!("anon\nneon\n3", vm.get_output());
+}
+
+#[test]
+fn test_nil_coalesce_keeps_falsy_non_nil_values() {
+    let program = r#"
+        print(false ?? true)
+        print(0 ?? 1)
+        print("" ?? "fallback")
+        "#;
+
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret(program.to_string());
+    assert_eq!(Result::Ok, result);
+    assert_eq!("false\n0", vm.get_output());
+}
+
+#[test]
+fn test_nil_coalesce_short_circuit() {
+    let program = r#"
+        var x = 10
+        val a = 1 ?? (x = 20)
+        print(x)
+        val b = nil ?? (x = 30)
+        print(x)
+        print(a + b)
+        "#;
+
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret(program.to_string());
+    assert_eq!(Result::Ok, result);
+    // The right side only runs when the left side is nil
+    assert_eq!("10\n30\n31", vm.get_output());
+}
+
+#[test]
+fn test_ternary_evaluates_only_taken_branch() {
+    let program = r#"
+        var hits = 0
+        fn hit(value) {
+            hits = hits + 1
+            return value
+        }
+        val a = true ? hit("then") : hit("else")
+        val b = false ? hit("then") : hit("else")
+        print(a)
+        print(b)
+        print(hits)
+        "#;
+
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret(program.to_string());
+    assert_eq!(Result::Ok, result);
+    // Each ternary calls hit() exactly once
+    assert_eq!("then\nelse\n2", vm.get_output());
+}
+
+#[test]
+fn test_ternary_chained_is_right_associative() {
+    let program = r#"
+        fn grade(score) {
+            return score >= 90 ? "A" : score >= 80 ? "B" : score >= 70 ? "C" : "F"
+        }
+        print(grade(95))
+        print(grade(85))
+        print(grade(75))
+        print(grade(10))
+        "#;
+
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret(program.to_string());
+    assert_eq!(Result::Ok, result);
+    assert_eq!("A\nB\nC\nF", vm.get_output());
+}
+
 #[test]
 fn test_logical_complex_expression() {
     let program = r#"
@@ -1990,6 +2092,66 @@ fn test_array_index_assignment_negative() {
     assert_eq!("88\n99", vm.get_output());
 }
 
+#[test]
+fn test_array_index_out_of_bounds_is_runtime_error() {
+    for (index, expected) in [
+        ("99", "Array index 99 out of bounds, length 3."),
+        ("3", "Array index 3 out of bounds, length 3."),
+        ("-4", "Array index -4 out of bounds, length 3."),
+        ("-99", "Array index -99 out of bounds, length 3."),
+    ] {
+        let program = format!(
+            r#"
+            val arr = [10, 20, 30]
+            print(arr[{}])
+            print("not reached")
+            "#,
+            index
+        );
+
+        let mut vm = VirtualMachine::new();
+        assert_eq!(Result::RuntimeError, vm.interpret(program));
+        assert!(vm.get_runtime_errors().contains(expected), "{}", index);
+        assert_eq!("", vm.get_output());
+    }
+}
+
+#[test]
+fn test_array_index_assignment_out_of_bounds_is_runtime_error() {
+    // Assigning past the end doesn't grow the array; push() appends
+    for index in ["3", "99", "-4"] {
+        let program = format!(
+            r#"
+            var arr = [1, 2, 3]
+            arr[{}] = 4
+            print("not reached")
+            "#,
+            index
+        );
+
+        let mut vm = VirtualMachine::new();
+        assert_eq!(Result::RuntimeError, vm.interpret(program));
+        assert!(vm
+            .get_runtime_errors()
+            .contains(&format!("Array index {} out of bounds, length 3.", index)));
+        assert_eq!("", vm.get_output());
+    }
+}
+
+#[test]
+fn test_array_index_that_is_not_finite_is_out_of_bounds() {
+    let program = r#"
+        val arr = [1, 2, 3]
+        print(arr[0 / 0])
+        "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm
+        .get_runtime_errors()
+        .contains("Array index nan out of bounds, length 3."));
+}
+
 #[test]
 fn test_array_push() {
     let program = r#"
@@ -2866,3 +3028,35 @@ fn debug_simple_param() {
     assert_eq!(Result::Ok, result);
     assert_eq!("42", vm.get_output());
 }
+
+#[test]
+fn can_raise_to_a_power() {
+    let program = r#"
+        print(2 ** 10)
+        print(2 ** 3 ** 2)
+        print((2 ** 3) ** 2)
+        print(4 ** 0.5)
+        print(2 ** -1)
+        "#;
+
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret(program.to_string());
+    assert_eq!(Result::Ok, result);
+    assert_eq!("1024\n512\n64\n2\n0.5", vm.get_output());
+}
+
+#[test]
+fn exponent_binds_tighter_than_unary_minus() {
+    let program = r#"
+        val x = 3
+        print(-2 ** 2)
+        print((-2) ** 2)
+        print(-x ** 2)
+        print(2 * -x ** 2)
+        "#;
+
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret(program.to_string());
+    assert_eq!(Result::Ok, result);
+    assert_eq!("-4\n4\n-9\n-18", vm.get_output());
+}
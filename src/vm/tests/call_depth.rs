@@ -0,0 +1,118 @@
+use crate::vm::{Result, VirtualMachine, DEFAULT_MAX_CALL_DEPTH};
+
+#[test]
+fn infinite_recursion_is_a_runtime_error() {
+    let program = r#"
+        fn forever(n) {
+            return 1 + forever(n + 1)
+        }
+        fn start() {
+            return 1 + forever(0)
+        }
+        start()
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    let errors = vm.get_runtime_errors();
+    assert!(errors.contains(&format!(
+        "Stack overflow: recursion limit of {} calls exceeded.",
+        DEFAULT_MAX_CALL_DEPTH
+    )));
+    assert!(errors.contains("    at start ["));
+    assert!(!errors.contains("Call chain"));
+}
+
+#[test]
+fn recursion_within_the_limit_runs() {
+    let program = r#"
+        fn depth(n) {
+            if (n == 0) {
+                return 0
+            }
+            return 1 + depth(n - 1)
+        }
+        print(depth(5000))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("5000", vm.get_output());
+}
+
+#[test]
+fn limit_can_be_changed() {
+    let program = r#"
+        fn depth(n) {
+            if (n == 0) {
+                return 0
+            }
+            return 1 + depth(n - 1)
+        }
+        print(depth(20))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    vm.set_max_call_depth(10);
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm
+        .get_runtime_errors()
+        .contains("recursion limit of 10 calls exceeded"));
+
+    vm.set_max_call_depth(50);
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("20", vm.get_output());
+}
+
+#[test]
+fn tail_calls_do_not_count_towards_the_limit() {
+    let program = r#"
+        fn countdown(n) {
+            if (n == 0) {
+                return "done"
+            }
+            return countdown(n - 1)
+        }
+        print(countdown(100))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    vm.set_max_call_depth(5);
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("done", vm.get_output());
+}
+
+#[test]
+fn recursion_through_callbacks_is_a_runtime_error() {
+    let program = r#"
+        fn recurse(n) {
+            [1].forEach(x => recurse(n + 1))
+        }
+        recurse(0)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm
+        .get_runtime_errors()
+        .contains("Stack overflow: callbacks nested more than 200 deep."));
+}
+
+#[test]
+fn callback_overflow_can_be_caught() {
+    let program = r#"
+        fn recurse(n) {
+            [1].forEach(x => recurse(n + 1))
+        }
+        try {
+            recurse(0)
+        } catch (e) {
+            print(e.message.startsWith("Stack overflow"))
+        }
+        print([1, 2, 3].some(x => x > 2))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("true\ntrue", vm.get_output());
+}
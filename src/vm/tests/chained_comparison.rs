@@ -0,0 +1,80 @@
+use crate::vm::{Result, VirtualMachine};
+
+#[test]
+fn chained_comparison_checks_every_pair() {
+    let program = r#"
+        val x = 5
+        print(0 <= x < 10)
+        print(0 <= x < 5)
+        print(10 > x >= 5)
+        print(1 < 2 < 3 < 4)
+        print(1 < 2 < 3 < 3)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("true\nfalse\ntrue\ntrue\nfalse", vm.get_output());
+}
+
+#[test]
+fn chained_comparison_evaluates_middle_term_once() {
+    let program = r#"
+        var calls = 0
+        fn middle(value) {
+            calls = calls + 1
+            return value
+        }
+        print(0 <= middle(5) < 10)
+        print(0 <= middle(15) < 10)
+        print(calls)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("true\nfalse\n2", vm.get_output());
+}
+
+#[test]
+fn chained_comparison_short_circuits() {
+    let program = r#"
+        fn never() {
+            print("evaluated")
+            return 0
+        }
+        print(3 < 2 < never())
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("false", vm.get_output());
+}
+
+#[test]
+fn chained_comparison_in_condition() {
+    let program = r#"
+        var inside = 0
+        for (i in 0..20) {
+            if (5 <= i < 10) {
+                inside = inside + 1
+            }
+        }
+        print(inside)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("5", vm.get_output());
+}
+
+#[test]
+fn equality_still_compares_left_to_right() {
+    let program = r#"
+        print(1 == 1 == true)
+        print(1 == 2 == false)
+        print((1 < 2) == true)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("true\ntrue\ntrue", vm.get_output());
+}
@@ -0,0 +1,174 @@
+use crate::vm::{Result, VirtualMachine};
+
+fn run(program: &str) -> String {
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    vm.get_output()
+}
+
+#[test]
+fn counters_capture_independent_variables() {
+    let output = run(r#"
+        fn makeCounter() {
+            var n = 0
+            return fn() {
+                n = n + 1
+                return n
+            }
+        }
+        val a = makeCounter()
+        val b = makeCounter()
+        print(a())
+        print(a())
+        print(b())
+        print(a())
+    "#);
+    assert_eq!("1\n2\n1\n3", output);
+}
+
+#[test]
+fn closures_from_the_same_call_share_captured_variable() {
+    let output = run(r#"
+        fn makeAccount() {
+            var balance = 0
+            return {
+                "deposit": amount => {
+                    balance = balance + amount
+                    return balance
+                },
+                "balance": fn() {
+                    return balance
+                }
+            }
+        }
+        val account = makeAccount()
+        account["deposit"](10)
+        account["deposit"](5)
+        print(account["balance"]())
+    "#);
+    assert_eq!("15", output);
+}
+
+#[test]
+fn closure_sees_changes_made_by_enclosing_function() {
+    let output = run(r#"
+        fn run() {
+            var label = "before"
+            val show = fn() {
+                return label
+            }
+            label = "after"
+            return show()
+        }
+        print(run())
+    "#);
+    assert_eq!("after", output);
+}
+
+#[test]
+fn captures_parameters() {
+    let output = run(r#"
+        fn makeAdder(n) {
+            return x => x + n
+        }
+        val addTwo = makeAdder(2)
+        val addTen = makeAdder(10)
+        print(addTwo(1))
+        print(addTen(1))
+    "#);
+    assert_eq!("3\n11", output);
+}
+
+#[test]
+fn captures_through_several_enclosing_functions() {
+    let output = run(r#"
+        fn adder(x) {
+            return y => z => x + y + z
+        }
+        print(adder(1)(20)(300))
+    "#);
+    assert_eq!("321", output);
+}
+
+#[test]
+fn captured_variable_outlives_the_stack_slot() {
+    let output = run(r#"
+        fn makeGreeter(name) {
+            val greeting = "hello ${name}"
+            return fn() {
+                return greeting
+            }
+        }
+        val greet = makeGreeter("neon")
+        val unrelated = [1, 2, 3]
+        print(greet())
+    "#);
+    assert_eq!("hello neon", output);
+}
+
+#[test]
+fn top_level_variables_are_not_captured_by_value() {
+    let output = run(r#"
+        var total = 1
+        val read = fn() {
+            return total
+        }
+        total = 42
+        print(read())
+    "#);
+    assert_eq!("42", output);
+}
+
+#[test]
+fn closures_made_in_loop_iterations_capture_their_own_variables() {
+    let output = run(r#"
+        fn make() {
+            val out = []
+            var k = 0
+            while (k < 3) {
+                val kk = k
+                out.push(fn() {
+                    return kk
+                })
+                k = k + 1
+            }
+            for (i in [3, 4, 5]) {
+                if (i == 4) {
+                    continue
+                }
+                out.push(fn() {
+                    return i
+                })
+            }
+            return out
+        }
+        for (f in make()) {
+            print(f())
+        }
+    "#);
+    assert_eq!("0\n1\n2\n3\n5", output);
+}
+
+#[test]
+fn block_scoped_top_level_variables_are_captured() {
+    let output = run(r#"
+        val gs = []
+        var n = 0
+        while (n < 2) {
+            val m = n * 10
+            gs.push(fn() {
+                return m
+            })
+            n = n + 1
+        }
+        for (j in [1, 2, 3]) {
+            gs.push(fn() {
+                return j
+            })
+        }
+        for (g in gs) {
+            print(g())
+        }
+    "#);
+    assert_eq!("0\n10\n1\n2\n3", output);
+}
@@ -0,0 +1,106 @@
+use crate::vm::{Result, VirtualMachine};
+
+fn run(program: &str) -> String {
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    vm.get_output()
+}
+
+#[test]
+fn compound_assignment_on_variables() {
+    let output = run(r#"
+        var x = 10
+        x += 5
+        print(x)
+        x -= 3
+        print(x)
+        x *= 2
+        print(x)
+        x /= 4
+        print(x)
+        x %= 4
+        print(x)
+        var s = "ab"
+        s += "cd"
+        print(s)
+    "#);
+    assert_eq!("15\n12\n24\n6\n2\nabcd", output);
+}
+
+#[test]
+fn compound_assignment_on_index_targets() {
+    let output = run(r#"
+        val m = {"k": 1}
+        m["k"] += 41
+        print(m["k"])
+        val arr = [1, 2, 3]
+        arr[1] *= 10
+        print(arr)
+    "#);
+    assert_eq!("42\n[1, 20, 3]", output);
+}
+
+#[test]
+fn compound_assignment_on_field_targets() {
+    let output = run(r#"
+        struct Counter {
+            count
+        }
+        val c = Counter(1)
+        c.count += 2
+        c.count *= 5
+        print(c.count)
+    "#);
+    assert_eq!("15", output);
+}
+
+#[test]
+fn compound_assignment_evaluates_index_target_once() {
+    let output = run(r#"
+        val m = {"k": 1}
+        fn key() {
+            print("key")
+            return "k"
+        }
+        fn target() {
+            print("target")
+            return m
+        }
+        target()[key()] += 1
+        print(m["k"])
+    "#);
+    assert_eq!("target\nkey\n2", output);
+}
+
+#[test]
+fn compound_assignment_evaluates_field_target_once() {
+    let output = run(r#"
+        struct Counter {
+            count
+        }
+        val c = Counter(1)
+        fn target() {
+            print("target")
+            return c
+        }
+        target().count += 1
+        print(c.count)
+    "#);
+    assert_eq!("target\n2", output);
+}
+
+#[test]
+fn compound_assignment_is_an_expression() {
+    let output = run(r#"
+        var x = 1
+        print(x += 2)
+    "#);
+    assert_eq!("3", output);
+}
+
+#[test]
+fn compound_assignment_to_val_is_rejected() {
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret("val x = 1\nx += 1\n".to_string());
+    assert_eq!(Result::CompileError, result);
+}
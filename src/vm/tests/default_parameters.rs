@@ -0,0 +1,105 @@
+use crate::vm::{Result, VirtualMachine};
+
+fn run(program: &str) -> String {
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    vm.get_output()
+}
+
+#[test]
+fn omitted_partially_supplied_and_fully_supplied_arguments() {
+    let output = run(r#"
+        fn greet(name, greeting = "Hello", punctuation = "!") {
+            return "${greeting}, ${name}${punctuation}"
+        }
+        print(greet("Ann"))
+        print(greet("Ann", "Hi"))
+        print(greet("Ann", "Hi", "?"))
+    "#);
+    assert_eq!("Hello, Ann!\nHi, Ann!\nHi, Ann?", output);
+}
+
+#[test]
+fn default_can_use_earlier_parameters() {
+    let output = run(r#"
+        fn area(width, height = width) {
+            return width * height
+        }
+        print(area(3))
+        print(area(3, 4))
+    "#);
+    assert_eq!("9\n12", output);
+}
+
+#[test]
+fn default_is_evaluated_on_every_call() {
+    let output = run(r#"
+        fn append(value, items = []) {
+            items.push(value)
+            return items
+        }
+        print(append(1))
+        print(append(2))
+    "#);
+    assert_eq!("[1]\n[2]", output);
+}
+
+#[test]
+fn explicit_nil_is_not_replaced_by_default() {
+    let output = run(r#"
+        fn show(value = "default") {
+            return value
+        }
+        print(show(nil))
+    "#);
+    assert_eq!("nil", output);
+}
+
+#[test]
+fn lambda_defaults_apply_when_called_indirectly() {
+    let output = run(r#"
+        val add = fn(x, y = 10) {
+            return x + y
+        }
+        fn callWithFive(f) {
+            return f(5)
+        }
+        print(add(1))
+        print(callWithFive(add))
+    "#);
+    assert_eq!("11\n15", output);
+}
+
+#[test]
+fn too_few_arguments_without_defaults_is_a_compile_error() {
+    let program = r#"
+        fn greet(name, greeting = "Hello") {
+            return greeting + name
+        }
+        greet()
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::CompileError, vm.interpret(program.to_string()));
+    assert!(vm
+        .get_compiler_error()
+        .contains("expects 1 to 2 arguments but got 0"));
+}
+
+#[test]
+fn too_few_arguments_is_a_runtime_error_for_indirect_calls() {
+    let program = r#"
+        fn callWithNothing(f) {
+            return f()
+        }
+        callWithNothing(fn(x, y = 1) {
+            return x
+        })
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm
+        .get_runtime_errors()
+        .contains("Expected 1 to 2 arguments but got 0."));
+}
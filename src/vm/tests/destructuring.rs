@@ -0,0 +1,215 @@
+use crate::vm::{Result, VirtualMachine};
+
+#[test]
+fn array_destructuring_binds_elements_in_order() {
+    let program = r#"
+        val [a, b, c] = [1, "two", [3]]
+        print(a)
+        print(b)
+        print(c)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("1\ntwo\n[3]", vm.get_output());
+}
+
+#[test]
+fn array_destructuring_of_short_array_binds_nil() {
+    let program = r#"
+        val [a, b, c] = [1]
+        print(a)
+        print(b)
+        print(c)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("1\nnil\nnil", vm.get_output());
+}
+
+#[test]
+fn array_destructuring_ignores_extra_elements() {
+    let program = r#"
+        val [first, second] = [1, 2, 3, 4]
+        print(first + second)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("3", vm.get_output());
+}
+
+#[test]
+fn array_destructuring_with_rest() {
+    let program = r#"
+        val [head, ...tail] = [1, 2, 3]
+        print(head)
+        print(tail)
+        val [x, y, ...none] = [1]
+        print(none)
+        print(tail.length())
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("1\n[2, 3]\n[]\n2", vm.get_output());
+}
+
+#[test]
+fn rest_is_a_copy() {
+    let program = r#"
+        val numbers = [1, 2, 3]
+        val [...all] = numbers
+        all.push(4)
+        print(numbers)
+        print(all)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("[1, 2, 3]\n[1, 2, 3, 4]", vm.get_output());
+}
+
+#[test]
+fn array_destructuring_of_tuples() {
+    let program = r#"
+        val [key, value] = tuple("a", 1)
+        print("${key}=${value}")
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("a=1", vm.get_output());
+}
+
+#[test]
+fn array_destructuring_in_function_and_loop() {
+    let program = r#"
+        fn swap(pair) {
+            val [a, b] = pair
+            return [b, a]
+        }
+        var total = 0
+        for (entry in [[1, 2], [3, 4]]) {
+            val [a, b] = swap(entry)
+            total = total + a * 10 + b
+        }
+        print(total)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("64", vm.get_output());
+}
+
+#[test]
+fn var_destructuring_is_mutable() {
+    let program = r#"
+        var [a, b] = [1, 2]
+        a = a + 10
+        print(a + b)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("13", vm.get_output());
+}
+
+#[test]
+fn val_destructuring_is_immutable() {
+    let program = r#"
+        val [a, b] = [1, 2]
+        a = 3
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::CompileError, vm.interpret(program.to_string()));
+}
+
+#[test]
+fn array_destructuring_of_non_array() {
+    let program = r#"
+        val [a, b] = 5
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm
+        .get_runtime_errors()
+        .contains("Only arrays and tuples can be destructured with [...], got 5."));
+}
+
+#[test]
+fn struct_destructuring_binds_fields() {
+    let program = r#"
+        struct Point {
+            x
+            y
+        }
+        val {x, y} = Point(3, 4)
+        print(x)
+        print(y)
+        fn yOf(point) {
+            val {y} = point
+            return y
+        }
+        print(yOf(Point(5, 6)))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("3\n4\n6", vm.get_output());
+}
+
+#[test]
+fn struct_destructuring_captured_by_closure() {
+    let program = r#"
+        struct Size {
+            width
+            height
+        }
+        fn areaOf(size) {
+            val {width, height} = size
+            val area = fn() {
+                return width * height
+            }
+            return area()
+        }
+        print(areaOf(Size(2, 5)))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("10", vm.get_output());
+}
+
+#[test]
+fn struct_destructuring_of_missing_field() {
+    let program = r#"
+        struct Point {
+            x
+            y
+        }
+        val {x, z} = Point(1, 2)
+        print("not reached")
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert_eq!("", vm.get_output());
+    assert!(vm.get_runtime_errors().contains("Undefined field 'z'."));
+}
+
+#[test]
+fn struct_destructuring_of_non_instance() {
+    let program = r#"
+        val {x} = [1, 2]
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm
+        .get_runtime_errors()
+        .contains("Only instances have fields."));
+}
@@ -0,0 +1,141 @@
+use crate::vm::{Result, VirtualMachine};
+
+#[test]
+fn do_while_runs_body_once_when_condition_is_false() {
+    let program = r#"
+        var runs = 0
+        do {
+            runs = runs + 1
+        } while (false)
+        print(runs)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("1", vm.get_output());
+}
+
+#[test]
+fn do_while_repeats_until_condition_is_false() {
+    let program = r#"
+        var i = 0
+        do {
+            print(i)
+            i = i + 1
+        } while (i < 3)
+        print("done")
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("0\n1\n2\ndone", vm.get_output());
+}
+
+#[test]
+fn do_while_with_single_statement_body() {
+    let program = r#"
+        var i = 10
+        do i = i + 1
+        while (i < 5)
+        print(i)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("11", vm.get_output());
+}
+
+#[test]
+fn do_while_continue_checks_the_condition() {
+    let program = r#"
+        var i = 0
+        do {
+            i = i + 1
+            if (i % 2 == 0) {
+                continue
+            }
+            print(i)
+        } while (i < 5)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("1\n3\n5", vm.get_output());
+}
+
+#[test]
+fn do_while_continue_on_false_condition_leaves_the_loop() {
+    let program = r#"
+        var runs = 0
+        do {
+            runs = runs + 1
+            continue
+        } while (false)
+        print(runs)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("1", vm.get_output());
+}
+
+#[test]
+fn do_while_break_leaves_the_loop() {
+    let program = r#"
+        var i = 0
+        do {
+            i = i + 1
+            if (i == 3) {
+                break
+            }
+        } while (true)
+        print(i)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("3", vm.get_output());
+}
+
+#[test]
+fn do_while_with_locals_in_body_inside_function() {
+    let program = r#"
+        fn sumDigits(n) {
+            var rest = n
+            var sum = 0
+            do {
+                val digit = rest % 10
+                sum = sum + digit
+                rest = (rest - digit) / 10
+            } while (rest > 0)
+            return sum
+        }
+        print(sumDigits(0))
+        print(sumDigits(1234))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("0\n10", vm.get_output());
+}
+
+#[test]
+fn do_while_nested_in_while() {
+    let program = r#"
+        var outer = 0
+        var total = 0
+        while (outer < 3) {
+            var inner = 0
+            do {
+                total = total + 1
+                inner = inner + 1
+            } while (inner < outer)
+            outer = outer + 1
+        }
+        print(total)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("4", vm.get_output());
+}
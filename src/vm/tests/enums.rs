@@ -0,0 +1,139 @@
+use crate::vm::{Result, VirtualMachine};
+
+#[test]
+fn enum_variants_compare_by_variant() {
+    let program = r#"
+        enum Color { Red, Green, Blue }
+        print(Color.Red == Color.Red)
+        print(Color.Red == Color.Green)
+        print(Color.Red != Color.Blue)
+        print(Color.Red is Color.Red)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("true\nfalse\ntrue\ntrue", vm.get_output());
+}
+
+#[test]
+fn variants_of_different_enums_are_not_equal() {
+    let program = r#"
+        enum Light { Red, Green }
+        enum Color { Red, Green }
+        print(Light.Red == Color.Red)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("false", vm.get_output());
+}
+
+#[test]
+fn enum_variants_display_with_enum_name() {
+    let program = r#"
+        enum Color { Red, Green, Blue }
+        print(Color.Red)
+        print("favorite: ${Color.Blue}")
+        print([Color.Green])
+        print(Color)
+        print(type(Color), type(Color.Red))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!(
+        "Color.Red\nfavorite: Color.Blue\n[Color.Green]\n<enum Color>\nenum variant",
+        vm.get_output()
+    );
+}
+
+#[test]
+fn enum_variants_work_as_values() {
+    let program = r#"
+        enum Direction {
+            North
+            East
+            South
+            West
+        }
+        fn turn_right(direction) {
+            var next = Direction.North
+            match direction {
+                Direction.North => next = Direction.East
+                Direction.East => next = Direction.South
+                Direction.South => next = Direction.West
+            }
+            return next
+        }
+        var heading = Direction.North
+        for (i in 0..3) {
+            heading = turn_right(heading)
+        }
+        print(heading)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("Direction.West", vm.get_output());
+}
+
+#[test]
+fn enum_can_be_used_before_its_declaration() {
+    let program = r#"
+        fn primary() {
+            return Color.Red
+        }
+        enum Color { Red }
+        print(primary())
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("Color.Red", vm.get_output());
+}
+
+#[test]
+fn unknown_variant_is_compile_error() {
+    let program = r#"
+        enum Color { Red }
+        print(Color.Purple)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::CompileError, vm.interpret(program.to_string()));
+}
+
+#[test]
+fn assigning_to_variant_is_compile_error() {
+    let program = r#"
+        enum Color { Red }
+        Color.Red = 1
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::CompileError, vm.interpret(program.to_string()));
+}
+
+#[test]
+fn duplicate_variant_is_compile_error() {
+    let program = r#"
+        enum Color { Red, Red }
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::CompileError, vm.interpret(program.to_string()));
+}
+
+#[test]
+fn unknown_variant_of_enum_passed_around_is_runtime_error() {
+    let program = r#"
+        enum Color { Red }
+        fn pick(e) {
+            return e.Purple
+        }
+        pick(Color)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+}
@@ -0,0 +1,63 @@
+use crate::number;
+use crate::vm::{Result, VirtualMachine};
+
+#[test]
+fn eval_returns_value_of_expression() {
+    let mut vm = VirtualMachine::new();
+    let outcome = vm.eval("1 + 2");
+
+    assert_eq!(Result::Ok, outcome.result);
+    assert_eq!(Some(number!(3.0)), outcome.value);
+    assert!(outcome.errors.is_empty());
+    assert_eq!(None, outcome.runtime_error);
+}
+
+#[test]
+fn eval_returns_value_of_trailing_expression() {
+    let mut vm = VirtualMachine::new();
+    let outcome = vm.eval("val x = 20\nx * 2 + 2");
+
+    assert_eq!(Result::Ok, outcome.result);
+    assert_eq!(Some(number!(42.0)), outcome.value);
+}
+
+#[test]
+fn eval_statement_has_no_value() {
+    let mut vm = VirtualMachine::new();
+    let outcome = vm.eval("val x = 5");
+
+    assert_eq!(Result::Ok, outcome.result);
+    assert_eq!(None, outcome.value);
+}
+
+#[test]
+fn eval_captures_printed_output() {
+    let mut vm = VirtualMachine::new();
+    let outcome = vm.eval("print(\"hello\")\nprint(42)");
+
+    assert_eq!(Result::Ok, outcome.result);
+    assert_eq!("hello\n42\n", outcome.output);
+}
+
+#[test]
+fn eval_compile_error_returns_structured_errors() {
+    let mut vm = VirtualMachine::new();
+    let outcome = vm.eval("val x = ");
+
+    assert_eq!(Result::CompileError, outcome.result);
+    assert_eq!(None, outcome.value);
+    assert!(!outcome.errors.is_empty());
+}
+
+#[test]
+fn eval_runtime_error_is_reported() {
+    let mut vm = VirtualMachine::new();
+    let outcome = vm.eval("num(\"abc\")");
+
+    assert_eq!(Result::RuntimeError, outcome.result);
+    assert_eq!(None, outcome.value);
+    assert!(outcome
+        .runtime_error
+        .unwrap()
+        .contains("num() failed: 'abc' is not a valid number"));
+}
@@ -0,0 +1,74 @@
+use crate::vm::{Result, VirtualMachine};
+
+#[test]
+fn equality_is_structural_and_is_compares_references() {
+    let program = r#"
+        val a = [1, 2]
+        val b = [1, 2]
+        val c = a
+        print(a == b)
+        print(a is b)
+        print(a is c)
+        print({"k": 1} is {"k": 1})
+        print({1, 2} == {1, 2})
+        "#;
+
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret(program.to_string());
+    assert_eq!(Result::Ok, result);
+    assert_eq!("true\nfalse\ntrue\nfalse\ntrue", vm.get_output());
+}
+
+#[test]
+fn is_sees_mutation_through_shared_reference() {
+    let program = r#"
+        val a = [1]
+        val b = a
+        val copy = a.clone()
+        b.push(2)
+        print(a)
+        print(a is b)
+        print(copy is a)
+        "#;
+
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret(program.to_string());
+    assert_eq!(Result::Ok, result);
+    assert_eq!("[1, 2]\ntrue\nfalse", vm.get_output());
+}
+
+#[test]
+fn is_compares_instances_by_reference() {
+    let program = r#"
+        struct Point {
+            x
+            y
+        }
+        val p = Point(1, 2)
+        val q = Point(1, 2)
+        print(p == q)
+        print(p is q)
+        print(p is p)
+        "#;
+
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret(program.to_string());
+    assert_eq!(Result::Ok, result);
+    assert_eq!("true\nfalse\ntrue", vm.get_output());
+}
+
+#[test]
+fn is_compares_other_values_like_equality() {
+    let program = r#"
+        print(1 is 1)
+        print(4 //2 is 2)
+        print("ab" is "a" + "b")
+        print(nil is nil)
+        print(nil is false)
+        "#;
+
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret(program.to_string());
+    assert_eq!(Result::Ok, result);
+    assert_eq!("true\ntrue\ntrue\ntrue\nfalse", vm.get_output());
+}
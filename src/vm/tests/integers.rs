@@ -0,0 +1,169 @@
+use crate::vm::{Result, VirtualMachine};
+
+fn run(program: &str) -> String {
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    vm.get_output()
+}
+
+fn runtime_error(program: &str) -> String {
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    vm.get_runtime_errors()
+}
+
+#[test]
+fn floor_division_produces_int() {
+    let output = run(r#"
+        print(7 //2)
+        print(type(7 //2))
+        print(-7 //2)
+        print(7.5 //2)
+        print(type(7.5 //2))
+        print(type(1 //0))
+    "#);
+    assert_eq!("3\nint\n-4\n3\nint\nnumber", output);
+}
+
+#[test]
+fn int_floor_division_rounds_towards_negative_infinity() {
+    let output = run(r#"
+        val seven = "7".toInt()
+        val two = "2".toInt()
+        print(seven //two)
+        print(-seven //two)
+        print(seven //-two)
+        print(-seven //-two)
+    "#);
+    assert_eq!("3\n-4\n-4\n3", output);
+}
+
+#[test]
+fn int_arithmetic_stays_int() {
+    let output = run(r#"
+        val a = "6".toInt()
+        val b = "4".toInt()
+        print(type(a + b), a + b)
+        print(type(a - b), a - b)
+        print(type(a * b), a * b)
+        print(type(a % b), a % b)
+        print(type(a ** b), a ** b)
+        print(type(-a), -a)
+    "#);
+    assert_eq!("int 10\nint 2\nint 24\nint 2\nint 1296\nint -6", output);
+}
+
+#[test]
+fn mixing_int_and_number_gives_number() {
+    let output = run(r#"
+        val a = "6".toInt()
+        print(type(a + 0.5), a + 0.5)
+        print(type(a * 1), a * 1)
+        print(type(a / "4".toInt()), a / "4".toInt())
+        print(type(a ** -1))
+    "#);
+    assert_eq!("number 6.5\nnumber 6\nnumber 1.5\nnumber", output);
+}
+
+#[test]
+fn ints_keep_precision_beyond_float_range() {
+    let output = run(r#"
+        val big = "9007199254740993".toInt()
+        print(big)
+        print(big + "1".toInt())
+        print(big.toString())
+    "#);
+    assert_eq!(
+        "9007199254740993\n9007199254740994\n9007199254740993",
+        output
+    );
+}
+
+#[test]
+fn ints_compare_with_numbers() {
+    let output = run(r#"
+        val two = 4 //2
+        print(two == 2)
+        print(two != 2.5)
+        print(two < 2.5)
+        print(two > 1)
+        print(two >= "2".toInt())
+    "#);
+    assert_eq!("true\ntrue\ntrue\ntrue\ntrue", output);
+}
+
+#[test]
+fn ints_work_as_indexes_and_keys() {
+    let output = run(r#"
+        val items = [10, 20, 30]
+        print(items[5 //2])
+        val names = {1: "one"}
+        print(names[3 //2])
+        for (i in 0..(6 //2)) {
+            print(i)
+        }
+    "#);
+    assert_eq!("30\none\n0\n1\n2", output);
+}
+
+#[test]
+fn bitwise_operators_produce_ints() {
+    let output = run(r#"
+        print(type(6 & 3), 6 & 3)
+        print(type(1 << 4), 1 << 4)
+        print(type(~0), ~0)
+    "#);
+    assert_eq!("int 2\nint 16\nint -1", output);
+}
+
+#[test]
+fn number_to_int_truncates() {
+    let output = run(r#"
+        val positive = 3.9
+        val negative = -3.9
+        print(positive.toInt(), type(positive.toInt()))
+        print(negative.toInt())
+        print("42".toInt() + "1".toInt())
+    "#);
+    assert_eq!("3 int\n-3\n43", output);
+}
+
+#[test]
+fn number_to_int_rejects_non_finite() {
+    let errors = runtime_error(
+        r#"
+        val nan = 0 / 0
+        nan.toInt()
+    "#,
+    );
+    assert!(errors.contains("toInt() cannot convert nan to an int"));
+}
+
+#[test]
+fn int_overflow_is_runtime_error() {
+    let errors = runtime_error(
+        r#"
+        val max = "9223372036854775807".toInt()
+        print(max + "1".toInt())
+    "#,
+    );
+    assert!(errors.contains("Integer overflow."));
+}
+
+#[test]
+fn int_division_by_zero_is_runtime_error() {
+    let errors = runtime_error(
+        r#"
+        val zero = "0".toInt()
+        print("5".toInt() //zero)
+    "#,
+    );
+    assert!(errors.contains("Division by zero."));
+
+    let errors = runtime_error(
+        r#"
+        print("5".toInt() % "0".toInt())
+    "#,
+    );
+    assert!(errors.contains("Division by zero."));
+}
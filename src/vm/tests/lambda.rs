@@ -0,0 +1,93 @@
+use crate::vm::{Result, VirtualMachine};
+
+fn run(program: &str) -> String {
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    vm.get_output()
+}
+
+#[test]
+fn lambda_passed_to_function_parameter() {
+    let output = run(r#"
+        fn apply(f, x) {
+            return f(x)
+        }
+        print(apply(fn(x) {
+            return x * 2
+        }, 21))
+        print(apply(x => x + 1, 41))
+    "#);
+    assert_eq!("42\n42", output);
+}
+
+#[test]
+fn lambda_bound_to_val() {
+    let output = run(r#"
+        val square = fn(x) {
+            return x * x
+        }
+        val greet = name => "hello ${name}"
+        print(square(7))
+        print(greet("neon"))
+    "#);
+    assert_eq!("49\nhello neon", output);
+}
+
+#[test]
+fn arrow_lambda_with_block_body() {
+    let output = run(r#"
+        fn apply(f, x) {
+            return f(x)
+        }
+        print(apply(n => {
+            val doubled = n * 2
+            return doubled + 1
+        }, 5))
+    "#);
+    assert_eq!("11", output);
+}
+
+#[test]
+fn lambda_can_use_top_level_variables() {
+    let output = run(r#"
+        val offset = 100
+        val shift = x => x + offset
+        print(shift(1))
+    "#);
+    assert_eq!("101", output);
+}
+
+#[test]
+fn lambda_called_from_loop() {
+    let output = run(r#"
+        fn each(arr, f) {
+            for (item in arr) {
+                f(item)
+            }
+        }
+        each([1, 2, 3], fn(x) {
+            print(x * 10)
+        })
+    "#);
+    assert_eq!("10\n20\n30", output);
+}
+
+#[test]
+fn val_bound_lambda_arity_is_checked() {
+    let program = r#"
+        val add = fn(a, b) {
+            return a + b
+        }
+        add(1)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::CompileError, vm.interpret(program.to_string()));
+}
+
+#[test]
+fn calling_a_known_non_function_value_is_still_rejected() {
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret("val x = 10\nx()\n".to_string());
+    assert_eq!(Result::CompileError, result);
+}
@@ -0,0 +1,152 @@
+use crate::vm::{Result, VirtualMachine};
+
+#[test]
+fn break_outer_loop_from_inner_loop() {
+    let program = r#"
+        var i = 0
+        outer: while (i < 3) {
+            var j = 0
+            while (j < 3) {
+                if (i == 1 && j == 1) {
+                    break outer
+                }
+                print("${i} ${j}")
+                j = j + 1
+            }
+            i = i + 1
+        }
+        print("done")
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("0 0\n0 1\n0 2\n1 0\ndone", vm.get_output());
+}
+
+#[test]
+fn continue_outer_loop_from_inner_loop() {
+    let program = r#"
+        var i = 0
+        outer: while (i < 3) {
+            i = i + 1
+            var j = 0
+            while (j < 3) {
+                j = j + 1
+                if (j == 2) {
+                    continue outer
+                }
+                print("${i} ${j}")
+            }
+            print("not reached")
+        }
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("1 1\n2 1\n3 1", vm.get_output());
+}
+
+#[test]
+fn continue_outer_c_style_for_runs_its_increment() {
+    let program = r#"
+        outer: for (var i = 0; i < 3; i = i + 1) {
+            for (var j = 0; j < 3; j = j + 1) {
+                if (j > i) {
+                    continue outer
+                }
+                print("${i} ${j}")
+            }
+        }
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("0 0\n1 0\n1 1\n2 0\n2 1\n2 2", vm.get_output());
+}
+
+#[test]
+fn labeled_break_and_continue_across_for_in_loops() {
+    let program = r#"
+        outer: for (x in [1, 2, 3, 4]) {
+            for (y in ["a", "b", "c"]) {
+                if (x == 2) {
+                    continue outer
+                }
+                if (x == 4) {
+                    break outer
+                }
+                if (y == "b") {
+                    break
+                }
+                print("${x}${y}")
+            }
+        }
+        for (z in [7, 8]) {
+            print(z)
+        }
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("1a\n3a\n7\n8", vm.get_output());
+}
+
+#[test]
+fn break_inner_for_in_keeps_outer_iterator() {
+    let program = r#"
+        var total = 0
+        for (x in [1, 2, 3]) {
+            for (y in [10, 20, 30]) {
+                if (y == 20) {
+                    break
+                }
+                total = total + x * y
+            }
+        }
+        print(total)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("60", vm.get_output());
+}
+
+#[test]
+fn labeled_break_through_three_loops() {
+    let program = r#"
+        fn find(target) {
+            var found = "none"
+            search: for (a in 0..3) {
+                for (b in 0..3) {
+                    var c = 0
+                    do {
+                        if (a * 100 + b * 10 + c == target) {
+                            found = "${a}${b}${c}"
+                            break search
+                        }
+                        c = c + 1
+                    } while (c < 3)
+                }
+            }
+            return found
+        }
+        print(find(121))
+        print(find(999))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("121\nnone", vm.get_output());
+}
+
+#[test]
+fn break_with_undefined_label_is_a_compile_error() {
+    let program = r#"
+        while (true) {
+            break missing
+        }
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::CompileError, vm.interpret(program.to_string()));
+}
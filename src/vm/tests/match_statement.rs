@@ -0,0 +1,122 @@
+use crate::vm::{Result, VirtualMachine};
+
+fn run(program: &str) -> String {
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    vm.get_output()
+}
+
+#[test]
+fn match_on_numbers() {
+    let output = run(r#"
+        fn describe(n) {
+            match n {
+                1 => print("one"),
+                2 => print("two"),
+                else => print("many")
+            }
+        }
+        describe(1)
+        describe(2)
+        describe(7)
+    "#);
+    assert_eq!("one\ntwo\nmany", output);
+}
+
+#[test]
+fn match_on_strings() {
+    let output = run(r#"
+        val command = "stop"
+        match command {
+            "go" => print("going")
+            "stop" => {
+                val message = "stopping"
+                print(message)
+            }
+        }
+    "#);
+    assert_eq!("stopping", output);
+}
+
+#[test]
+fn match_on_booleans() {
+    let output = run(r#"
+        for (flag in [true, false]) {
+            match flag {
+                true => print("yes"),
+                false => print("no")
+            }
+        }
+    "#);
+    assert_eq!("yes\nno", output);
+}
+
+#[test]
+fn match_does_not_fall_through() {
+    let output = run(r#"
+        val x = 1
+        match x {
+            1 => print("first"),
+            1 => print("second"),
+            else => print("else")
+        }
+    "#);
+    assert_eq!("first", output);
+}
+
+#[test]
+fn match_without_else_and_no_match_does_nothing() {
+    let output = run(r#"
+        match 5 {
+            1 => print("one")
+        }
+        print("after")
+    "#);
+    assert_eq!("after", output);
+}
+
+#[test]
+fn match_patterns_can_be_values() {
+    let output = run(r#"
+        val limit = 10
+        match 5 + 5 {
+            limit => print("at limit"),
+            else => print("not at limit")
+        }
+    "#);
+    assert_eq!("at limit", output);
+}
+
+#[test]
+fn match_evaluates_scrutinee_once() {
+    let output = run(r#"
+        var calls = 0
+        fn next() {
+            calls = calls + 1
+            return calls
+        }
+        match next() {
+            2 => print("two"),
+            1 => print("one")
+        }
+        print(calls)
+    "#);
+    assert_eq!("one\n1", output);
+}
+
+#[test]
+fn match_arms_can_break_out_of_loops() {
+    let output = run(r#"
+        var i = 0
+        while (true) {
+            i = i + 1
+            match i {
+                3 => {
+                    break
+                }
+            }
+        }
+        print(i)
+    "#);
+    assert_eq!("3", output);
+}
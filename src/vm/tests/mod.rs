@@ -1 +1,34 @@
+mod array_operators;
 mod basic;
+mod call_depth;
+mod chained_comparison;
+mod closures;
+mod compound_assignment;
+mod default_parameters;
+mod destructuring;
+mod do_while;
+mod enums;
+mod eval;
+mod identity;
+mod integers;
+mod lambda;
+mod loop_labels;
+mod match_statement;
+mod modules;
+mod multiple_assignment;
+mod named_construction;
+mod optional_chaining;
+mod output;
+mod reset;
+mod rest_parameters;
+mod stack_trace;
+mod strict_arithmetic;
+mod string_comparison;
+mod string_escapes;
+mod string_repeat;
+mod struct_defaults;
+mod struct_methods;
+mod tail_calls;
+mod try_catch;
+mod type_errors;
+mod with_block;
@@ -0,0 +1,507 @@
+use crate::vm::{Result, VirtualMachine};
+use std::fs;
+use std::path::PathBuf;
+
+/// Directory the files of a test are written to, canonical like the paths of modules
+fn test_directory(test: &str) -> PathBuf {
+    let temp = fs::canonicalize(std::env::temp_dir()).unwrap();
+    temp.join(format!("neon_modules_{}_{}", std::process::id(), test))
+}
+
+/// Writes the files into a fresh directory and returns the directory
+fn write_files(test: &str, files: &[(&str, &str)]) -> PathBuf {
+    let directory = test_directory(test);
+    let _ = fs::remove_dir_all(&directory);
+    for (name, source) in files {
+        let path = directory.join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, source).unwrap();
+    }
+    directory
+}
+
+/// Interprets `main.n` of the directory the files were written to
+fn run(test: &str, files: &[(&str, &str)]) -> (Result, VirtualMachine) {
+    let directory = write_files(test, files);
+    let main = directory.join("main.n");
+    let mut vm = VirtualMachine::new();
+    vm.set_script_path(&main);
+    let result = vm.interpret(fs::read_to_string(&main).unwrap());
+    fs::remove_dir_all(&directory).unwrap();
+    (result, vm)
+}
+
+const MATH_UTILS: &str = r#"
+export fn add(a, b) {
+    return a + b
+}
+
+export fn sub(a, b) {
+    return a - b
+}
+
+export fn double(x) {
+    return add(x, x)
+}
+"#;
+
+#[test]
+fn imported_functions_can_be_called() {
+    let main = r#"
+import { add, sub } from "math_utils"
+print(add(2, 3))
+print(sub(10, 4))
+"#;
+    let (result, vm) = run("symbols", &[("main.n", main), ("math_utils.n", MATH_UTILS)]);
+    assert_eq!(Result::Ok, result);
+    assert_eq!("5\n6", vm.get_output());
+}
+
+#[test]
+fn imported_functions_call_functions_of_their_module() {
+    let main = r#"
+import { double } from "math_utils"
+fn quadruple(x) {
+    return double(double(x))
+}
+print(quadruple(3))
+"#;
+    let (result, vm) = run(
+        "internal",
+        &[("main.n", main), ("math_utils.n", MATH_UTILS)],
+    );
+    assert_eq!(Result::Ok, result);
+    assert_eq!("12", vm.get_output());
+}
+
+#[test]
+fn namespace_import_reads_exports_as_fields() {
+    let main = r#"
+import * as M from "math_utils"
+print(M.add(1, 2))
+val sub = M.sub
+print(sub(3, 1))
+print(M)
+"#;
+    let (result, vm) = run(
+        "namespace",
+        &[("main.n", main), ("math_utils.n", MATH_UTILS)],
+    );
+    assert_eq!(Result::Ok, result);
+    let output = vm.get_output();
+    assert!(output.starts_with("3\n2\n<module "), "{}", output);
+    assert!(output.ends_with("math_utils.n>"), "{}", output);
+}
+
+#[test]
+fn structs_enums_and_values_can_be_imported() {
+    let shapes = r#"
+export val UNIT = 1
+export struct Point {
+    x
+    y
+}
+export enum Corner { TopLeft, BottomRight }
+"#;
+    let main = r#"
+import { UNIT, Point, Corner } from "shapes"
+val p = Point(UNIT, 2)
+print(p.x + p.y)
+print(Corner.BottomRight)
+"#;
+    let (result, vm) = run("kinds", &[("main.n", main), ("shapes.n", shapes)]);
+    assert_eq!(Result::Ok, result);
+    assert_eq!("3\nCorner.BottomRight", vm.get_output());
+}
+
+#[test]
+fn module_runs_once_and_shares_its_state() {
+    let counter = r#"
+print("loading counter")
+var count = 0
+export fn increment() {
+    count = count + 1
+    return count
+}
+"#;
+    let left = r#"
+import { increment } from "counter"
+export fn left() {
+    return increment()
+}
+"#;
+    let main = r#"
+import { left } from "left"
+import { increment } from "counter"
+import * as C from "counter"
+print(left())
+print(increment())
+print(C.increment())
+"#;
+    let (result, vm) = run(
+        "once",
+        &[("main.n", main), ("left.n", left), ("counter.n", counter)],
+    );
+    assert_eq!(Result::Ok, result);
+    assert_eq!("loading counter\n1\n2\n3", vm.get_output());
+}
+
+#[test]
+fn importing_a_name_the_module_does_not_export_is_a_compile_error() {
+    let main = r#"
+import { add, multiply } from "math_utils"
+"#;
+    let (result, vm) = run(
+        "unexported",
+        &[("main.n", main), ("math_utils.n", MATH_UTILS)],
+    );
+    assert_eq!(Result::CompileError, result);
+    let errors = &vm.structured_errors;
+    assert_eq!(1, errors.len());
+    assert_eq!(
+        "Module 'math_utils' does not export 'multiply'",
+        errors[0].message
+    );
+    assert_eq!(
+        (2, 15),
+        (errors[0].location.line, errors[0].location.column)
+    );
+}
+
+#[test]
+fn reading_a_missing_export_of_a_namespace_is_a_compile_error() {
+    let main = r#"
+import * as M from "math_utils"
+print(M.multiply(2, 3))
+"#;
+    let (result, vm) = run(
+        "namespace_missing",
+        &[("main.n", main), ("math_utils.n", MATH_UTILS)],
+    );
+    assert_eq!(Result::CompileError, result);
+    assert!(vm
+        .compilation_errors
+        .contains("Module 'M' has no export 'multiply'"));
+}
+
+#[test]
+fn imported_names_cannot_be_assigned() {
+    let main = r#"
+import { add } from "math_utils"
+import * as M from "math_utils"
+add = nil
+M.sub = nil
+"#;
+    let (result, vm) = run("assign", &[("main.n", main), ("math_utils.n", MATH_UTILS)]);
+    assert_eq!(Result::CompileError, result);
+    let errors = &vm.compilation_errors;
+    assert!(
+        errors.contains("Cannot assign to immutable variable 'add'"),
+        "{}",
+        errors
+    );
+    assert!(
+        errors.contains("Cannot assign to 'sub' of module 'M'"),
+        "{}",
+        errors
+    );
+}
+
+#[test]
+fn missing_module_is_a_compile_error() {
+    let main = r#"
+import { add } from "nowhere"
+"#;
+    let (result, vm) = run("missing", &[("main.n", main)]);
+    assert_eq!(Result::CompileError, result);
+    let tried = test_directory("missing").join("nowhere.n");
+    assert!(
+        vm.compilation_errors.contains(&format!(
+            "Cannot find module 'nowhere', tried {}",
+            tried.display()
+        )),
+        "{}",
+        vm.compilation_errors
+    );
+}
+
+#[test]
+fn imports_resolve_relative_to_the_importing_file() {
+    let counter = r#"
+var count = 0
+export fn increment() {
+    count = count + 1
+    return count
+}
+"#;
+    let format = r#"
+import { increment } from "./counter"
+export fn next() {
+    return "n${increment()}"
+}
+"#;
+    let main = r#"
+import { next } from "lib/format"
+import { increment } from "lib/counter"
+print(next())
+print(increment())
+print(next())
+"#;
+    let (result, vm) = run(
+        "relative",
+        &[
+            ("main.n", main),
+            ("lib/format.n", format),
+            ("lib/counter.n", counter),
+        ],
+    );
+    assert_eq!(Result::Ok, result, "{}", vm.compilation_errors);
+    assert_eq!("n1\n2\nn3", vm.get_output());
+}
+
+#[test]
+fn imports_fall_back_to_the_search_path() {
+    let search = write_files(
+        "search_path_lib",
+        &[
+            ("text.n", "export val greeting = \"from the search path\"\n"),
+            ("shadowed.n", "export val origin = \"search path\"\n"),
+        ],
+    );
+    std::env::set_var("NEON_PATH", &search);
+    let main = r#"
+import { greeting } from "text"
+import { origin } from "shadowed"
+print(greeting)
+print(origin)
+"#;
+    let shadowed = "export val origin = \"next to main\"\n";
+    let (result, vm) = run("search_path", &[("main.n", main), ("shadowed.n", shadowed)]);
+    std::env::remove_var("NEON_PATH");
+    fs::remove_dir_all(&search).unwrap();
+    assert_eq!(Result::Ok, result, "{}", vm.compilation_errors);
+    assert_eq!("from the search path\nnext to main", vm.get_output());
+}
+
+#[test]
+fn errors_in_a_module_are_reported_at_the_import() {
+    let broken = r#"
+export fn broken() {
+    return missing
+}
+"#;
+    let main = r#"
+import { broken } from "broken"
+"#;
+    let (result, vm) = run("broken", &[("main.n", main), ("broken.n", broken)]);
+    assert_eq!(Result::CompileError, result);
+    let errors = &vm.structured_errors;
+    assert_eq!(1, errors.len());
+    assert!(
+        errors[0].message.contains("(in module 'broken' at 3:12)"),
+        "{}",
+        errors[0].message
+    );
+    assert_eq!(2, errors[0].location.line);
+}
+
+#[test]
+fn circular_imports_are_a_compile_error() {
+    let a = r#"
+import { b } from "b"
+export fn a() {
+    return "a"
+}
+"#;
+    let b = r#"
+import { a } from "a"
+export fn b() {
+    return a()
+}
+"#;
+    let main = r#"
+import { a } from "a"
+print(a())
+"#;
+    let (result, vm) = run("cycle", &[("main.n", main), ("a.n", a), ("b.n", b)]);
+    assert_eq!(Result::CompileError, result);
+    let directory = test_directory("cycle");
+    let (a, b) = (directory.join("a.n"), directory.join("b.n"));
+    let errors = &vm.structured_errors;
+    assert_eq!(1, errors.len());
+    assert_eq!(
+        format!(
+            "Circular import: {} -> {} -> {} (in module 'b' at 2:1) (in module 'a' at 2:1)",
+            a.display(),
+            b.display(),
+            a.display()
+        ),
+        errors[0].message
+    );
+    assert_eq!(2, errors[0].location.line);
+}
+
+#[test]
+fn module_importing_itself_is_a_compile_error() {
+    let main = r#"
+import { me } from "me"
+"#;
+    let me = r#"
+import { me } from "me"
+export val me = 1
+"#;
+    let (result, vm) = run("self_import", &[("main.n", main), ("me.n", me)]);
+    assert_eq!(Result::CompileError, result);
+    let me = test_directory("self_import").join("me.n");
+    assert!(
+        vm.compilation_errors.contains(&format!(
+            "Circular import: {} -> {}",
+            me.display(),
+            me.display()
+        )),
+        "{}",
+        vm.compilation_errors
+    );
+}
+
+#[test]
+fn module_imported_along_two_paths_is_not_a_cycle() {
+    let shared = r#"
+export val value = 21
+"#;
+    let left = r#"
+import { value } from "shared"
+export fn left() {
+    return value
+}
+"#;
+    let right = r#"
+import { value } from "shared"
+export fn right() {
+    return value
+}
+"#;
+    let main = r#"
+import { left } from "left"
+import { right } from "right"
+print(left() + right())
+"#;
+    let (result, vm) = run(
+        "diamond",
+        &[
+            ("main.n", main),
+            ("shared.n", shared),
+            ("left.n", left),
+            ("right.n", right),
+        ],
+    );
+    assert_eq!(Result::Ok, result, "{}", vm.compilation_errors);
+    assert_eq!("42", vm.get_output());
+}
+
+#[test]
+fn imports_are_only_allowed_at_the_top_level() {
+    let main = r#"
+fn f() {
+    import { add } from "math_utils"
+}
+"#;
+    let (result, vm) = run("nested", &[("main.n", main), ("math_utils.n", MATH_UTILS)]);
+    assert_eq!(Result::CompileError, result);
+    assert!(vm
+        .compilation_errors
+        .contains("Imports are only allowed at the top level of a file"));
+}
+
+const GREETER: &str = r#"
+val greeting = "Hello"
+
+fn decorate(name) {
+    return "${greeting}, ${name}!"
+}
+
+export fn greet(name) {
+    return decorate(name)
+}
+"#;
+
+#[test]
+fn exported_functions_use_private_declarations_of_their_module() {
+    let main = r#"
+import { greet } from "greeter"
+print(greet("Neon"))
+"#;
+    let (result, vm) = run("private_use", &[("main.n", main), ("greeter.n", GREETER)]);
+    assert_eq!(Result::Ok, result);
+    assert_eq!("Hello, Neon!", vm.get_output());
+}
+
+#[test]
+fn private_declarations_cannot_be_imported() {
+    let main = r#"
+import { greet, decorate, greeting } from "greeter"
+"#;
+    let (result, vm) = run(
+        "private_import",
+        &[("main.n", main), ("greeter.n", GREETER)],
+    );
+    assert_eq!(Result::CompileError, result);
+    let errors: Vec<(&str, u32, u32)> = vm
+        .structured_errors
+        .iter()
+        .map(|e| (e.message.as_str(), e.location.line, e.location.column))
+        .collect();
+    assert_eq!(
+        vec![
+            ("Module 'greeter' does not export 'decorate'", 2, 17),
+            ("Module 'greeter' does not export 'greeting'", 2, 27),
+        ],
+        errors
+    );
+}
+
+#[test]
+fn private_declarations_are_not_in_the_namespace() {
+    let main = r#"
+import * as G from "greeter"
+print(G.decorate("Neon"))
+"#;
+    let (result, vm) = run(
+        "private_namespace",
+        &[("main.n", main), ("greeter.n", GREETER)],
+    );
+    assert_eq!(Result::CompileError, result);
+    assert!(vm
+        .compilation_errors
+        .contains("Module 'G' has no export 'decorate'"));
+}
+
+#[test]
+fn only_top_level_declarations_can_be_exported() {
+    let program = r#"
+fn outer() {
+    export val inner = 1
+}
+"#;
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::CompileError, vm.interpret(program.to_string()));
+    assert!(vm
+        .compilation_errors
+        .contains("Only declarations at the top level of a file can be exported"));
+}
+
+#[test]
+fn exported_declarations_behave_like_plain_ones_in_their_own_file() {
+    let program = r#"
+export fn twice(x) {
+    return x * 2
+}
+print(twice(4))
+export var total = 1
+total = total + 1
+print(total)
+"#;
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("8\n2", vm.get_output());
+}
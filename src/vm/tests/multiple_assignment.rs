@@ -0,0 +1,81 @@
+use crate::vm::{Result, VirtualMachine};
+
+#[test]
+fn multiple_assignment_swaps_two_variables() {
+    let program = r#"
+        var a = 1
+        var b = 2
+        a, b = b, a
+        print("${a} ${b}")
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("2 1", vm.get_output());
+}
+
+#[test]
+fn multiple_assignment_rotates_three_variables() {
+    let program = r#"
+        var x = "x"
+        var y = "y"
+        var z = "z"
+        x, y, z = y, z, x
+        print(x + y + z)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("yzx", vm.get_output());
+}
+
+#[test]
+fn multiple_assignment_evaluates_values_in_order() {
+    let program = r#"
+        fn next(n) {
+            print(n)
+            return n
+        }
+        var a = 0
+        var b = 0
+        a, b = next(1), next(2)
+        print(a + b)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("1\n2\n3", vm.get_output());
+}
+
+#[test]
+fn multiple_assignment_in_function_and_loop() {
+    let program = r#"
+        fn fibonacci(n) {
+            var a = 0
+            var b = 1
+            var i = 0
+            while (i < n) {
+                a, b = b, a + b
+                i = i + 1
+            }
+            return a
+        }
+        print(fibonacci(10))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("55", vm.get_output());
+}
+
+#[test]
+fn multiple_assignment_to_val_is_compile_error() {
+    let program = r#"
+        val a = 1
+        var b = 2
+        a, b = b, a
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::CompileError, vm.interpret(program.to_string()));
+}
@@ -0,0 +1,169 @@
+use crate::vm::{Result, VirtualMachine};
+
+fn run(program: &str) -> String {
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    vm.get_output()
+}
+
+fn runtime_error(program: &str) -> String {
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    vm.get_runtime_errors()
+}
+
+fn compile_error(program: &str) -> String {
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::CompileError, vm.interpret(program.to_string()));
+    vm.get_compiler_error()
+}
+
+#[test]
+fn labels_bind_fields_regardless_of_order() {
+    let output = run(r#"
+        struct Point {
+            x
+            y
+        }
+        val p = Point(y: 4, x: 3)
+        print("${p.x},${p.y}")
+    "#);
+    assert_eq!("3,4", output);
+}
+
+#[test]
+fn omitted_labels_take_field_defaults() {
+    let output = run(r#"
+        struct Config {
+            host = "localhost"
+            port = 8080
+            secure = false
+        }
+        val config = Config(secure: true)
+        print("${config.host}:${config.port} ${config.secure}")
+    "#);
+    assert_eq!("localhost:8080 true", output);
+}
+
+#[test]
+fn labeled_values_are_evaluated_in_call_order() {
+    let output = run(r#"
+        struct Pair {
+            first
+            second
+        }
+        var n = 0
+        val pair = Pair(second: n++, first: n++)
+        print("${pair.first},${pair.second}")
+    "#);
+    assert_eq!("1,0", output);
+}
+
+#[test]
+fn labels_work_with_ternary_values() {
+    let output = run(r#"
+        struct Point {
+            x
+            y
+        }
+        val flag = true
+        print(Point(x: flag ? 1 : 2, y: 0).x)
+    "#);
+    assert_eq!("1", output);
+}
+
+#[test]
+fn missing_required_field_is_runtime_error() {
+    let errors = runtime_error(
+        r#"
+        struct Point {
+            x
+            y
+        }
+        Point(x: 1)
+    "#,
+    );
+    assert!(errors.contains("Missing value for field 'y'."));
+}
+
+#[test]
+fn unknown_label_on_unresolved_struct_is_runtime_error() {
+    let errors = runtime_error(
+        r#"
+        struct Point {
+            x
+        }
+        val alias = Point
+        alias(w: 1)
+    "#,
+    );
+    assert!(errors.contains("Struct 'Point' has no field named 'w'."));
+}
+
+#[test]
+fn labels_on_non_struct_value_is_runtime_error() {
+    let errors = runtime_error(
+        r#"
+        fn build(make) {
+            return make(a: 1)
+        }
+        build(fn(a) {
+            return a
+        })
+    "#,
+    );
+    assert!(errors.contains("Named arguments can only be used to construct a struct."));
+}
+
+#[test]
+fn unknown_label_is_compile_error() {
+    let errors = compile_error(
+        r#"
+        struct Point {
+            x
+        }
+        Point(w: 1)
+    "#,
+    );
+    assert!(errors.contains("Struct 'Point' has no field named 'w'"));
+}
+
+#[test]
+fn duplicate_label_is_compile_error() {
+    let errors = compile_error(
+        r#"
+        struct Point {
+            x
+        }
+        Point(x: 1, x: 2)
+    "#,
+    );
+    assert!(errors.contains("Field 'x' is given more than once"));
+}
+
+#[test]
+fn labels_on_function_is_compile_error() {
+    let errors = compile_error(
+        r#"
+        fn f(a) {
+            return a
+        }
+        f(a: 1)
+    "#,
+    );
+    assert!(errors.contains("Named arguments can only be used to construct a struct"));
+}
+
+#[test]
+fn mixing_positional_and_named_is_compile_error() {
+    let errors = compile_error(
+        r#"
+        struct Point {
+            x
+            y
+        }
+        Point(1, y: 2)
+    "#,
+    );
+    assert!(errors.contains("Can't mix positional and named arguments in one call."));
+}
@@ -0,0 +1,114 @@
+use crate::vm::{Result, VirtualMachine};
+
+fn run(program: &str) -> String {
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    vm.get_output()
+}
+
+#[test]
+fn optional_field_access_on_struct_chain() {
+    let output = run(r#"
+        struct Node {
+            value
+            next
+        }
+        val tail = Node(2, nil)
+        val head = Node(1, tail)
+        print(head?.next?.value)
+        print(tail?.next?.value)
+        val missing = nil
+        print(missing?.next?.value)
+    "#);
+    assert_eq!("2\nnil\nnil", output);
+}
+
+#[test]
+fn optional_index_on_nested_maps() {
+    let output = run(r#"
+        val config = {"db": {"host": "localhost"}}
+        print(config?.["db"]?.["host"])
+        print(config?.["cache"]?.["host"])
+        print(config["cache"]?.["host"] ?? "none")
+    "#);
+    assert_eq!("localhost\nnil\nnone", output);
+}
+
+#[test]
+fn optional_chain_skips_index_evaluation_when_nil() {
+    let output = run(r#"
+        var calls = 0
+        fn key() {
+            calls = calls + 1
+            return "k"
+        }
+        val m = nil
+        print(m?.[key()])
+        print(calls)
+    "#);
+    assert_eq!("nil\n0", output);
+}
+
+#[test]
+fn optional_field_access_on_absent_field_is_still_an_error() {
+    let program = r#"
+        struct Point {
+            x
+        }
+        val p = Point(1)
+        print(p?.y)
+    "#;
+
+    // `?.` only guards against a nil object; a missing field reports the same error as `.`
+    let mut vm = VirtualMachine::new();
+    vm.interpret(program.to_string());
+    assert!(vm.get_runtime_errors().contains("Undefined field 'y'"));
+}
+
+#[test]
+fn optional_chain_cannot_be_assigned() {
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret("val m = {}\nm?.[\"k\"] = 1\n".to_string());
+    assert_eq!(Result::CompileError, result);
+}
+
+#[test]
+fn nil_before_optional_link_skips_the_rest_of_the_chain() {
+    let output = run(r#"
+        struct Node {
+            value
+            next
+        }
+        val missing = nil
+        print(missing?.next.value)
+        print(missing?.next.value[0])
+        val config = {"db": nil}
+        print(config?.["db"]?.["host"].size)
+    "#);
+    assert_eq!("nil\nnil\nnil", output);
+}
+
+#[test]
+fn plain_link_after_optional_one_still_fails_on_nil() {
+    let program = r#"
+        struct Node {
+            value
+            next
+        }
+        val node = Node(1, nil)
+        print(node?.next.value)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+}
+
+#[test]
+fn question_mark_before_bracket_is_a_ternary() {
+    let output = run(r#"
+        val c = true
+        print(c ?[1] : [2])
+        print(!c ?[1] : [2])
+    "#);
+    assert_eq!("[1]\n[2]", output);
+}
@@ -0,0 +1,82 @@
+use crate::vm::{OutputBuffering, Result, VirtualMachine};
+use std::cell::RefCell;
+use std::io::Write;
+use std::rc::Rc;
+
+/// Sink that records every write it receives
+#[derive(Clone, Default)]
+struct RecordingSink {
+    writes: Rc<RefCell<Vec<String>>>,
+}
+
+impl Write for RecordingSink {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.writes
+            .borrow_mut()
+            .push(String::from_utf8_lossy(buf).to_string());
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn line_buffering_writes_each_print_to_the_sink() {
+    let sink = RecordingSink::default();
+    let mut vm = VirtualMachine::new();
+    vm.set_output_sink(Box::new(sink.clone()));
+
+    let program = r#"
+        print("one")
+        print(2)
+        print("three", 3)
+    "#;
+
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!(vec!["one\n", "2\n", "three 3\n"], *sink.writes.borrow());
+}
+
+#[test]
+fn line_buffering_delivers_output_before_the_script_finishes() {
+    let sink = RecordingSink::default();
+    let mut vm = VirtualMachine::new();
+    vm.set_output_sink(Box::new(sink.clone()));
+
+    // The runtime error stops the script, but the earlier print must already be delivered
+    let program = r#"
+        print("before")
+        num("abc")
+    "#;
+
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert_eq!(vec!["before\n"], *sink.writes.borrow());
+}
+
+#[test]
+fn full_buffering_writes_once_when_the_script_finishes() {
+    let sink = RecordingSink::default();
+    let mut vm = VirtualMachine::new();
+    vm.set_output_sink(Box::new(sink.clone()));
+    vm.set_output_buffering(OutputBuffering::Full);
+
+    let program = r#"
+        print("one")
+        print("two")
+        print("three")
+    "#;
+
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!(vec!["one\ntwo\nthree\n"], *sink.writes.borrow());
+}
+
+#[test]
+fn captured_output_is_unaffected_by_the_sink() {
+    let sink = RecordingSink::default();
+    let mut vm = VirtualMachine::new();
+    vm.set_output_sink(Box::new(sink.clone()));
+
+    assert_eq!(Result::Ok, vm.interpret("print(\"hi\", 1)".to_string()));
+    assert_eq!("hi 1", vm.get_output());
+}
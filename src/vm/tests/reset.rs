@@ -0,0 +1,75 @@
+use crate::vm::{Result, VirtualMachine};
+
+#[test]
+fn reset_vm_runs_programs_independently() {
+    let mut vm = VirtualMachine::new();
+    let first = r#"
+        fn forever(n) {
+            return 1 + forever(n + 1)
+        }
+        var counter = 10
+        for (item in [1, 2, 3]) {
+            counter = counter + item
+            print(counter)
+            if (item == 2) {
+                forever(0)
+            }
+        }
+    "#;
+    assert_eq!(Result::RuntimeError, vm.interpret(first.to_string()));
+    assert_eq!("11\n13", vm.get_output());
+    assert!(!vm.get_runtime_errors().is_empty());
+
+    vm.reset();
+    assert!(vm.stack.is_empty());
+    assert!(vm.call_frames.is_empty());
+    assert!(vm.iterator_stack.is_empty());
+    assert_eq!("", vm.get_output());
+    assert_eq!("", vm.get_runtime_errors());
+
+    let second = r#"
+        var counter = 0
+        for (item in [5, 6]) {
+            counter = counter + item
+        }
+        print(counter)
+    "#;
+    assert_eq!(Result::Ok, vm.interpret(second.to_string()));
+    assert_eq!("11", vm.get_output());
+    assert_eq!("", vm.get_runtime_errors());
+}
+
+#[test]
+fn reset_drops_compilation_errors_and_warnings() {
+    let mut vm = VirtualMachine::new();
+    assert_eq!(
+        Result::CompileError,
+        vm.interpret("print(undefined_name)".to_string())
+    );
+    assert!(!vm.get_compiler_error().is_empty());
+
+    vm.reset();
+    assert_eq!("", vm.get_compiler_error());
+    assert!(vm.get_warnings().is_empty());
+}
+
+#[test]
+fn reset_keeps_builtins_and_settings() {
+    let mut vm = VirtualMachine::new();
+    vm.set_max_call_depth(10);
+    vm.reset();
+
+    assert_eq!(Result::Ok, vm.interpret("print(Math.abs(-3))".to_string()));
+    assert_eq!("3", vm.get_output());
+
+    let deep = r#"
+        fn depth(n) {
+            if (n == 0) {
+                return 0
+            }
+            return 1 + depth(n - 1)
+        }
+        print(depth(20))
+    "#;
+    assert_eq!(Result::RuntimeError, vm.interpret(deep.to_string()));
+}
@@ -0,0 +1,91 @@
+use crate::vm::{Result, VirtualMachine};
+
+fn run(program: &str) -> String {
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    vm.get_output()
+}
+
+#[test]
+fn sums_variadic_arguments() {
+    let output = run(r#"
+        fn sum(...nums) {
+            var total = 0
+            for (n in nums) {
+                total = total + n
+            }
+            return total
+        }
+        print(sum())
+        print(sum(5))
+        print(sum(1, 2, 3, 4))
+    "#);
+    assert_eq!("0\n5\n10", output);
+}
+
+#[test]
+fn fixed_parameters_come_before_rest() {
+    let output = run(r#"
+        fn join(separator, ...parts) {
+            return parts.join(separator)
+        }
+        val empty = join("-")
+        print("[${empty}]")
+        print(join("-", "a", "b", "c"))
+    "#);
+    assert_eq!("[]\na-b-c", output);
+}
+
+#[test]
+fn exactly_the_fixed_arguments_give_an_empty_rest_array() {
+    let output = run(r#"
+        fn describe(first, ...others) {
+            return "${first} ${others} ${others.length()}"
+        }
+        print(describe(1))
+    "#);
+    assert_eq!("1 [] 0", output);
+}
+
+#[test]
+fn rest_combines_with_default_parameters() {
+    let output = run(r#"
+        fn tag(label, separator = ":", ...values) {
+            return "${label}${separator}${values}"
+        }
+        print(tag("a"))
+        print(tag("a", "="))
+        print(tag("a", "=", 1, 2))
+    "#);
+    assert_eq!("a:[]\na=[]\na=[1, 2]", output);
+}
+
+#[test]
+fn variadic_lambda_called_indirectly() {
+    let output = run(r#"
+        val count = fn(...items) {
+            return items.length()
+        }
+        fn callWithFour(f) {
+            return f(1, 2, 3, 4)
+        }
+        print(callWithFour(count))
+    "#);
+    assert_eq!("4", output);
+}
+
+#[test]
+fn too_few_fixed_arguments_is_an_error() {
+    let program = r#"
+        fn join(separator, ...parts) {
+            return parts.join(separator)
+        }
+        join()
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::CompileError, vm.interpret(program.to_string()));
+    assert!(vm
+        .get_compiler_error()
+        .contains("expects at least 1 arguments but got 0"));
+}
@@ -0,0 +1,56 @@
+use crate::vm::{Result, VirtualMachine};
+
+#[test]
+fn error_in_nested_call_reports_every_frame() {
+    let program = "fn inner(items) {
+    return items[5]
+}
+fn outer(items) {
+    val found = inner(items)
+    return found
+}
+outer([1])
+";
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert_eq!(
+        "[2:17] Array index 5 out of bounds, length 1.\n\
+         \x20   at inner [2:17]\n\
+         \x20   at outer [5:22]\n\
+         \x20   at <script> [8:6]",
+        vm.get_runtime_errors()
+    );
+}
+
+#[test]
+fn error_in_script_code_has_no_trace() {
+    let program = "val items = [1]\nprint(items[5])\n";
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(!vm.get_runtime_errors().contains("    at "));
+}
+
+#[test]
+fn recursive_frames_are_counted_once() {
+    let program = "fn countdown(n) {
+    if (n == 0) {
+        return [][0]
+    }
+    return 1 + countdown(n - 1)
+}
+countdown(3)
+";
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    let errors = vm.get_runtime_errors();
+    assert!(
+        errors.contains(
+            "    at countdown [3:18]\n    at countdown [5:25] (x3)\n    at <script> [7:10]"
+        ),
+        "{}",
+        errors
+    );
+}
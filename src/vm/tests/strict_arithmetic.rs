@@ -0,0 +1,70 @@
+use crate::vm::{Result, VirtualMachine};
+
+const DIVISIONS_BY_ZERO: &str = r#"
+    val zero = 0
+    print(1 / zero)
+    print(-1 / zero)
+    print(0 / zero)
+    print(5 % zero)
+    print(5 //zero)
+    print(-5 //zero)
+"#;
+
+#[test]
+fn division_by_zero_follows_ieee_by_default() {
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(DIVISIONS_BY_ZERO.to_string()));
+    assert_eq!("inf\n-inf\nnan\nnan\ninf\n-inf", vm.get_output());
+}
+
+#[test]
+fn int_division_by_zero_is_an_error_in_either_mode() {
+    for strict in [false, true] {
+        let mut vm = VirtualMachine::new();
+        vm.set_strict_arithmetic(strict);
+        assert_eq!(
+            Result::RuntimeError,
+            vm.interpret(r#"print("5".toInt() % "0".toInt())"#.to_string())
+        );
+        assert!(vm.get_runtime_errors().contains("Division by zero."));
+    }
+}
+
+#[test]
+fn strict_arithmetic_makes_division_by_zero_an_error() {
+    for operator in ["/", "%", "//"] {
+        let program = format!(
+            r#"
+            val zero = 0
+            print(7 {}2)
+            print(1 {}zero)
+            print("not reached")
+            "#,
+            operator, operator
+        );
+
+        let mut vm = VirtualMachine::new();
+        vm.set_strict_arithmetic(true);
+        assert_eq!(Result::RuntimeError, vm.interpret(program), "{}", operator);
+        assert!(vm.get_runtime_errors().contains("Division by zero."));
+        assert!(!vm.get_output().contains("not reached"));
+    }
+}
+
+#[test]
+fn strict_division_by_zero_can_be_caught() {
+    let program = r#"
+        val zero = -0
+        try {
+            print(10 / zero)
+        } catch (e) {
+            print(e.message)
+        }
+        print(10 / 4)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    vm.set_strict_arithmetic(true);
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("Division by zero.\n2.5", vm.get_output());
+}
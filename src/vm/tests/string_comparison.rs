@@ -0,0 +1,65 @@
+use crate::vm::{Result, VirtualMachine};
+
+fn output_of(program: &str) -> String {
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    vm.get_output()
+}
+
+#[test]
+fn strings_order_lexicographically() {
+    let program = r#"
+        val [a, b] = ["apple", "banana"]
+        print(a < b, a > b, a <= b, a >= b)
+        print(b < a, "Zebra" < "apple", "" < "a")
+    "#;
+    assert_eq!("true false true false\nfalse true true", output_of(program));
+}
+
+#[test]
+fn a_prefix_orders_before_the_longer_string() {
+    let program = r#"
+        val [short, long] = ["ab", "abc"]
+        print(short < long, long > short, short <= long, long < short)
+    "#;
+    assert_eq!("true true true false", output_of(program));
+}
+
+#[test]
+fn equal_strings_are_neither_less_nor_greater() {
+    let program = r#"
+        val [a, b] = ["neon", "ne" + "on"]
+        print(a < b, a > b, a <= b, a >= b)
+    "#;
+    assert_eq!("false false true true", output_of(program));
+}
+
+#[test]
+fn unicode_strings_order_by_code_point() {
+    let program = r#"
+        val [e, e_acute, z, snowman] = ["e", "é", "z", "☃"]
+        print(e < e_acute, z < e_acute, e_acute < snowman)
+        print(0 <= 1 < 2, "a" < "ä" < "☃")
+    "#;
+    assert_eq!("true true true\ntrue true", output_of(program));
+}
+
+#[test]
+fn numbers_still_order_by_value() {
+    let program = r#"
+        val [one, half, nan] = [1, 0.5, 0 / 0.0]
+        print(half < one, one > half, one <= 1.0, nan < one, nan > one)
+    "#;
+    assert_eq!("true true true false false", output_of(program));
+}
+
+#[test]
+fn comparing_a_string_with_a_number_is_an_error() {
+    for program in [r#"print("1" < 2)"#, r#"print(2 >= "1")"#] {
+        let mut vm = VirtualMachine::new();
+        assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+        assert!(vm
+            .get_runtime_errors()
+            .contains("Operands must be two numbers or two strings"));
+    }
+}
@@ -0,0 +1,65 @@
+use crate::vm::{Result, VirtualMachine};
+
+#[test]
+fn escapes_decode_to_single_characters() {
+    let program = r#"
+        print("a\tb".len())
+        print("a\nb".len())
+        print("\r\0\\\"".len())
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("3\n3\n4", vm.get_output());
+}
+
+#[test]
+fn escapes_print_as_the_characters_they_stand_for() {
+    let program = r#"
+        print("say \"hi\"\tC:\\temp")
+        print("one\ntwo")
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("say \"hi\"\tC:\\temp\none\ntwo", vm.get_output());
+}
+
+#[test]
+fn unicode_escape_produces_the_code_point() {
+    let program = r#"
+        val globe = "\u{1F30D}"
+        print(globe)
+        print(globe.len())
+        print("\u{48}\u{69}")
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("🌍\n1\nHi", vm.get_output());
+}
+
+#[test]
+fn escapes_in_interpolated_strings() {
+    let program = r#"
+        val name = "neon"
+        print("[\t${name}]\n\${name}")
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("[\tneon]\n${name}", vm.get_output());
+}
+
+#[test]
+fn invalid_escape_is_compile_error() {
+    let mut vm = VirtualMachine::new();
+    assert_eq!(
+        Result::CompileError,
+        vm.interpret(r#"print("\q")"#.to_string())
+    );
+    assert_eq!(
+        Result::CompileError,
+        vm.interpret(r#"print("\u{110000}")"#.to_string())
+    );
+}
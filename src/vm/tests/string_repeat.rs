@@ -0,0 +1,89 @@
+use crate::vm::{Result, VirtualMachine};
+
+#[test]
+fn string_times_number_repeats() {
+    let program = r#"
+        print("ab" * 3)
+        print(3 * "ab")
+        print("[" + "-" * 0 + "]")
+        print(type("=" * 2))
+        "#;
+
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret(program.to_string());
+    assert_eq!(Result::Ok, result);
+    assert_eq!("ababab\nababab\n[]\nstring", vm.get_output());
+}
+
+#[test]
+fn string_repeat_accepts_int_count_and_compound_assignment() {
+    let program = r#"
+        var line = "-"
+        line *= 4
+        print(line)
+        print("xy" * (5 //2))
+        "#;
+
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret(program.to_string());
+    assert_eq!(Result::Ok, result);
+    assert_eq!("----\nxyxy", vm.get_output());
+}
+
+#[test]
+fn string_repeat_rejects_negative_count() {
+    let program = r#"
+        print("ab" * -1)
+        "#;
+
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret(program.to_string());
+    assert_eq!(Result::RuntimeError, result);
+    assert!(vm
+        .get_runtime_errors()
+        .contains("Can't repeat a string -1 times"));
+}
+
+#[test]
+fn string_repeat_rejects_fractional_count() {
+    let program = r#"
+        print(1.5 * "ab")
+        "#;
+
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret(program.to_string());
+    assert_eq!(Result::RuntimeError, result);
+    assert!(vm
+        .get_runtime_errors()
+        .contains("Can't repeat a string 1.5 times"));
+}
+
+#[test]
+fn string_repeat_rejects_string_count() {
+    let program = r#"
+        print("ab" * "cd")
+        "#;
+
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret(program.to_string());
+    assert_eq!(Result::RuntimeError, result);
+    assert!(vm
+        .get_runtime_errors()
+        .contains("Can't repeat a string cd times"));
+}
+
+#[test]
+fn string_repeat_rejects_results_that_are_too_long() {
+    for program in [r#"print("ab" * 10 ** 300)"#, r#"print(10 ** 8 * "ab")"#] {
+        let mut vm = VirtualMachine::new();
+        assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+        assert!(vm.get_runtime_errors().contains(
+            "Can't repeat a string that many times, the result would be longer than 67108864"
+        ));
+    }
+
+    let mut vm = VirtualMachine::new();
+    let program = r#"print(len("" * 10 ** 300), len("ab" * 1000))"#;
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("0 2000", vm.get_output());
+}
@@ -0,0 +1,152 @@
+use crate::vm::{Result, VirtualMachine};
+
+fn run(program: &str) -> String {
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    vm.get_output()
+}
+
+#[test]
+fn zero_arguments_use_all_defaults() {
+    let output = run(r#"
+        struct Config {
+            host = "localhost"
+            port = 8080
+        }
+        val config = Config()
+        print("${config.host}:${config.port}")
+    "#);
+    assert_eq!("localhost:8080", output);
+}
+
+#[test]
+fn positional_arguments_override_defaults_left_to_right() {
+    let output = run(r#"
+        struct Config {
+            host = "localhost"
+            port = 8080
+            secure = false
+        }
+        val config = Config("example.com")
+        print("${config.host}:${config.port} ${config.secure}")
+    "#);
+    assert_eq!("example.com:8080 false", output);
+}
+
+#[test]
+fn full_construction_ignores_defaults() {
+    let output = run(r#"
+        struct Config {
+            host = "localhost"
+            port = 8080
+        }
+        val config = Config("example.com", 443)
+        print("${config.host}:${config.port}")
+    "#);
+    assert_eq!("example.com:443", output);
+}
+
+#[test]
+fn required_fields_come_before_defaults() {
+    let output = run(r#"
+        struct Point {
+            x
+            y
+            z = -1
+        }
+        val p = Point(1, 2)
+        print(p.x + p.y + p.z)
+    "#);
+    assert_eq!("2", output);
+}
+
+#[test]
+fn instances_get_independent_default_fields() {
+    let output = run(r#"
+        struct Counter {
+            count = 0
+        }
+        val a = Counter()
+        val b = Counter()
+        a.count = 5
+        print(b.count)
+    "#);
+    assert_eq!("0", output);
+}
+
+#[test]
+fn missing_required_field_is_compile_error() {
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret(
+        r#"
+        struct Point {
+            x
+            y = 0
+        }
+        Point()
+    "#
+        .to_string(),
+    );
+    assert_eq!(Result::CompileError, result);
+    assert!(vm
+        .get_compiler_error()
+        .contains("Struct 'Point' expects 1 to 2 fields but got 0"));
+}
+
+#[test]
+fn too_many_arguments_is_compile_error() {
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret(
+        r#"
+        struct Point {
+            x
+            y
+        }
+        Point(1, 2, 3)
+    "#
+        .to_string(),
+    );
+    assert_eq!(Result::CompileError, result);
+    assert!(vm
+        .get_compiler_error()
+        .contains("Struct 'Point' expects 2 fields but got 3"));
+}
+
+#[test]
+fn too_many_arguments_with_defaults_is_runtime_error_for_indirect_calls() {
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret(
+        r#"
+        struct Config {
+            host = "localhost"
+        }
+        fn build(make) {
+            return make("a", "b")
+        }
+        build(Config)
+    "#
+        .to_string(),
+    );
+    assert_eq!(Result::RuntimeError, result);
+    assert!(vm
+        .get_runtime_errors()
+        .contains("Expected 0 to 1 fields but got 2."));
+}
+
+#[test]
+fn non_literal_default_is_compile_error() {
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret(
+        r#"
+        val start = 1
+        struct Range {
+            from = start
+        }
+    "#
+        .to_string(),
+    );
+    assert_eq!(Result::CompileError, result);
+    assert!(vm
+        .get_compiler_error()
+        .contains("Default value of field 'from' must be a literal"));
+}
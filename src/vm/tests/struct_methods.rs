@@ -0,0 +1,148 @@
+use crate::vm::{Result, VirtualMachine};
+
+fn run(program: &str) -> String {
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    vm.get_output()
+}
+
+#[test]
+fn method_reads_fields_through_this() {
+    let output = run(r#"
+        struct Rect {
+            width
+            height
+
+            fn area() {
+                return this.width * this.height
+            }
+        }
+        print(Rect(3, 4).area())
+    "#);
+    assert_eq!("12", output);
+}
+
+#[test]
+fn method_mutates_fields_through_this() {
+    let output = run(r#"
+        struct Account {
+            balance
+
+            fn deposit(amount) {
+                this.balance = this.balance + amount
+            }
+        }
+        val account = Account(10)
+        account.deposit(5)
+        account.deposit(7)
+        print(account.balance)
+    "#);
+    assert_eq!("22", output);
+}
+
+#[test]
+fn methods_are_shared_by_instances_but_fields_are_not() {
+    let output = run(r#"
+        struct Named {
+            name
+
+            fn greet(greeting) {
+                return "${greeting}, ${this.name}"
+            }
+        }
+        val a = Named("a")
+        val b = Named("b")
+        print(a.greet("hi"))
+        print(b.greet("yo"))
+    "#);
+    assert_eq!("hi, a\nyo, b", output);
+}
+
+#[test]
+fn method_can_use_top_level_functions_and_structs() {
+    let output = run(r#"
+        fn double(n) {
+            return n * 2
+        }
+        struct Vec2 {
+            x
+            y
+
+            fn scaled() {
+                return Vec2(double(this.x), double(this.y))
+            }
+        }
+        val v = Vec2(1, 2).scaled()
+        print("${v.x} ${v.y}")
+    "#);
+    assert_eq!("2 4", output);
+}
+
+#[test]
+fn unknown_method_is_a_runtime_error() {
+    let program = r#"
+        struct Empty {
+            value
+
+            fn get() {
+                return this.value
+            }
+        }
+        Empty(1).missing()
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm
+        .get_runtime_errors()
+        .contains("Unknown method 'missing' for type Empty"));
+}
+
+#[test]
+fn wrong_method_argument_count_is_a_runtime_error() {
+    let program = r#"
+        struct Box {
+            value
+
+            fn set(value) {
+                this.value = value
+            }
+        }
+        Box(1).set()
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm
+        .get_runtime_errors()
+        .contains("Expected 1 arguments but got 0."));
+}
+
+#[test]
+fn this_outside_a_method_is_a_compile_error() {
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret("print(this)\n".to_string());
+    assert_eq!(Result::CompileError, result);
+    assert!(vm
+        .get_compiler_error()
+        .contains("'this' can only be used inside a struct method"));
+}
+
+#[test]
+fn method_name_clashing_with_field_is_a_compile_error() {
+    let program = r#"
+        struct Point {
+            x
+
+            fn x() {
+                return 1
+            }
+        }
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::CompileError, vm.interpret(program.to_string()));
+    assert!(vm
+        .get_compiler_error()
+        .contains("Struct 'Point' already has a field named 'x'"));
+}
@@ -0,0 +1,154 @@
+use crate::vm::{Result, VirtualMachine};
+
+fn run(program: &str) -> String {
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    vm.get_output()
+}
+
+#[test]
+fn tail_recursion_runs_in_constant_frame_depth() {
+    // The error at the bottom leaves the frames of the recursion in place
+    let program = r#"
+        fn countdown(n) {
+            if (n == 0) {
+                return Math.sqrt(-1)
+            }
+            return countdown(n - 1)
+        }
+        countdown(100000)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert_eq!(2, vm.call_frames.len());
+}
+
+#[test]
+fn tail_recursive_countdown_to_a_million() {
+    let output = run(r#"
+        fn countdown(n, steps) {
+            if (n == 0) {
+                return steps
+            }
+            return countdown(n - 1, steps + 1)
+        }
+        print(countdown(1000000, 0))
+    "#);
+    assert_eq!("1000000", output);
+}
+
+#[test]
+fn mutual_tail_recursion() {
+    let output = run(r#"
+        fn isEven(n) {
+            if (n == 0) {
+                return true
+            }
+            return isOdd(n - 1)
+        }
+        fn isOdd(n) {
+            if (n == 0) {
+                return false
+            }
+            return isEven(n - 1)
+        }
+        print(isEven(100001), isOdd(100001))
+    "#);
+    assert_eq!("false true", output);
+}
+
+#[test]
+fn calls_that_are_not_functions_still_return_their_result() {
+    let output = run(r#"
+        struct Point {
+            x
+            y
+        }
+        fn label(n) {
+            return str(n)
+        }
+        fn origin() {
+            return Point(0, 0)
+        }
+        fn root(n) {
+            return Math.sqrt(n)
+        }
+        print(label(7) + "!", origin().x, root(16))
+    "#);
+    assert_eq!("7! 0 4", output);
+}
+
+#[test]
+fn tail_call_keeps_captured_variables_alive() {
+    let output = run(r#"
+        fn offsetBy(n) {
+            val add = fn(x) {
+                return x + n
+            }
+            return add(1)
+        }
+        print(offsetBy(41))
+    "#);
+    assert_eq!("42", output);
+}
+
+#[test]
+fn tail_call_with_defaults_and_rest_parameters() {
+    let output = run(r#"
+        fn greet(name, greeting = "Hello", ...marks) {
+            return "${greeting} ${name} ${marks.length()}"
+        }
+        fn welcome(name) {
+            return greet(name)
+        }
+        fn shout(name) {
+            return greet(name, "Hey", "!", "!")
+        }
+        print(welcome("neon"))
+        print(shout("neon"))
+    "#);
+    assert_eq!("Hello neon 0\nHey neon 2", output);
+}
+
+#[test]
+fn tail_call_with_wrong_arity_is_a_runtime_error() {
+    let program = r#"
+        fn pair(a, b) {
+            return a + b
+        }
+        fn call(f) {
+            return f(1)
+        }
+        call(pair)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm
+        .get_runtime_errors()
+        .contains("Expected 2 arguments but got 1."));
+}
+
+#[test]
+fn non_tail_recursion_is_unchanged() {
+    let output = run(r#"
+        fn fib(n) {
+            if (n < 2) {
+                return n
+            }
+            return fib(n - 1) + fib(n - 2)
+        }
+        print(fib(20))
+    "#);
+    assert_eq!("6765", output);
+}
+
+#[test]
+fn return_of_a_call_compiles_to_a_tail_call() {
+    let mut vm = VirtualMachine::new();
+    let listing = vm
+        .disassemble("fn f(n) {\n    return g(n)\n}\nfn g(n) {\n    return n\n}\nprint(f(1))\n")
+        .expect("program should compile");
+    assert!(listing.contains("TailCall (args: 1)"));
+}
@@ -0,0 +1,541 @@
+use crate::vm::{Result, VirtualMachine};
+
+#[test]
+fn out_of_bounds_index_is_caught() {
+    let program = r#"
+        val numbers = [1, 2, 3]
+        try {
+            print(numbers[99])
+            print("not reached")
+        } catch (e) {
+            print(e.message)
+        }
+        print("after")
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!(
+        "Array index 99 out of bounds, length 3.\nafter",
+        vm.get_output()
+    );
+    assert_eq!("", vm.get_runtime_errors());
+}
+
+#[test]
+fn errors_of_unused_values_are_still_raised() {
+    let program = r#"
+        val numbers = [1, 2, 3]
+        try {
+            numbers[99]
+            print("not reached")
+        } catch (e) {
+            print(e.message)
+        }
+        try {
+            numbers.size + "s"
+        } catch (e) {
+            print("caught")
+        }
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!(
+        "Array index 99 out of bounds, length 3.\ncaught",
+        vm.get_output()
+    );
+}
+
+#[test]
+fn type_error_of_a_native_function_is_caught() {
+    let program = r#"
+        val input = "seven"
+        try {
+            print(Math.sqrt(input))
+        } catch (e) {
+            print(type(e))
+            print(e)
+        }
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("error\nError: sqrt() x must be a number", vm.get_output());
+}
+
+#[test]
+fn calling_a_non_function_is_caught() {
+    let program = r#"
+        val callbacks = [nil]
+        try {
+            callbacks[0]()
+        } catch (e) {
+            print(e.message)
+        }
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("Value is not callable", vm.get_output());
+}
+
+#[test]
+fn error_unwinds_through_function_calls() {
+    let program = r#"
+        fn inner(items) {
+            return items[5]
+        }
+        fn outer(items) {
+            val found = inner(items)
+            print("not reached")
+            return found
+        }
+        try {
+            outer([1])
+        } catch (e) {
+            print("caught")
+        }
+        print(outer is outer)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("caught\ntrue", vm.get_output());
+}
+
+#[test]
+fn handler_inside_a_function_returns_a_value() {
+    let program = r#"
+        fn first(items) {
+            try {
+                return items[0]
+            } catch (e) {
+                return "empty"
+            }
+        }
+        print(first([7]))
+        print(first([]))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("7\nempty", vm.get_output());
+}
+
+#[test]
+fn returning_from_a_try_block_leaves_it() {
+    let program = r#"
+        fn early() {
+            try {
+                return 1
+            } catch (e) {
+                return 2
+            }
+        }
+        early()
+        val items = []
+        print(items[0])
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert!(vm
+        .get_runtime_errors()
+        .contains("Array index 0 out of bounds, length 0."));
+}
+
+#[test]
+fn break_and_continue_leave_the_try_block() {
+    let program = r#"
+        for (i in 0..5) {
+            try {
+                if (i == 1) {
+                    continue
+                }
+                if (i == 3) {
+                    break
+                }
+                print(i)
+            } catch (e) {
+                print("caught in loop")
+            }
+        }
+        val items = []
+        print(items[0])
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert_eq!("0\n2", vm.get_output());
+}
+
+#[test]
+fn nested_try_blocks_catch_innermost_first() {
+    let program = r#"
+        try {
+            try {
+                print([][0])
+            } catch (inner) {
+                print("inner")
+                print(nil())
+            }
+        } catch (outer) {
+            print("outer: ${outer.message}")
+        }
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("inner\nouter: Value is not callable", vm.get_output());
+}
+
+#[test]
+fn error_in_a_callback_is_caught_around_the_native_call() {
+    let program = r#"
+        try {
+            [1, 2].forEach(x => [x][5])
+        } catch (e) {
+            print("caught")
+        }
+        [1, 2].forEach(x => {
+            try {
+                print([x][5])
+            } catch (e) {
+                print("callback ${x}")
+            }
+        })
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("caught\ncallback 1\ncallback 2", vm.get_output());
+}
+
+#[test]
+fn error_variable_is_scoped_to_the_handler() {
+    let program = r#"
+        val e = "outer"
+        try {
+            print([][1])
+        } catch (e) {
+            print(type(e))
+        }
+        print(e)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("error\nouter", vm.get_output());
+}
+
+#[test]
+fn uncaught_error_still_stops_the_program() {
+    let program = r#"
+        try {
+            print("fine")
+        } catch (e) {
+            print("not reached")
+        }
+        print([][0])
+        print("not reached")
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert_eq!("fine", vm.get_output());
+    assert!(vm
+        .get_runtime_errors()
+        .contains("Array index 0 out of bounds, length 0."));
+}
+
+#[test]
+fn finally_runs_after_normal_completion() {
+    let program = r#"
+        try {
+            print("body")
+        } catch (e) {
+            print("not reached")
+        } finally {
+            print("finally")
+        }
+        try {
+            print("no catch")
+        } finally {
+            print("finally again")
+        }
+        print("after")
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!(
+        "body\nfinally\nno catch\nfinally again\nafter",
+        vm.get_output()
+    );
+}
+
+#[test]
+fn thrown_values_are_caught_as_is() {
+    let program = r#"
+        struct Failure {
+            code
+        }
+        for (value in [42, "text", Failure(7)]) {
+            try {
+                throw value
+            } catch (e) {
+                print(type(e))
+            } finally {
+                print("finally")
+            }
+        }
+        try {
+            throw Failure(404)
+        } catch (e) {
+            print(e.code)
+        }
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!(
+        "number\nfinally\nstring\nfinally\ninstance\nfinally\n404",
+        vm.get_output()
+    );
+}
+
+#[test]
+fn rethrown_error_keeps_its_message() {
+    let program = r#"
+        try {
+            try {
+                print([][3])
+            } catch (e) {
+                throw e
+            }
+        } catch (outer) {
+            print(outer.message)
+        }
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("Array index 3 out of bounds, length 0.", vm.get_output());
+}
+
+#[test]
+fn uncaught_throw_propagates_after_finally() {
+    let program = r#"
+        fn fail() {
+            try {
+                throw "boom"
+            } finally {
+                print("cleanup")
+            }
+            print("not reached")
+        }
+        try {
+            fail()
+        } catch (e) {
+            print("caught ${e}")
+        }
+        fail()
+        print("not reached")
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert_eq!("cleanup\ncaught boom\ncleanup", vm.get_output());
+    assert!(vm.get_runtime_errors().contains("Uncaught error: boom"));
+}
+
+#[test]
+fn error_in_catch_block_runs_finally() {
+    let program = r#"
+        try {
+            try {
+                throw 1
+            } catch (e) {
+                throw e + 1
+            } finally {
+                print("finally")
+            }
+        } catch (e) {
+            print(e)
+        }
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("finally\n2", vm.get_output());
+}
+
+#[test]
+fn return_runs_finally_before_returning() {
+    let program = r#"
+        var log = ""
+        fn compute() {
+            try {
+                log = log + "body "
+                return log + "result"
+            } finally {
+                log = log + "finally"
+            }
+        }
+        fn recover() {
+            try {
+                throw "x"
+            } catch (e) {
+                return "recovered"
+            } finally {
+                print("recover finally")
+            }
+        }
+        print(compute())
+        print(log)
+        print(recover())
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!(
+        "body result\nbody finally\nrecover finally\nrecovered",
+        vm.get_output()
+    );
+}
+
+#[test]
+fn break_and_continue_run_finally() {
+    let program = r#"
+        for (i in [1, 2, 3]) {
+            try {
+                if (i == 1) {
+                    continue
+                }
+                if (i == 2) {
+                    break
+                }
+            } finally {
+                print("leaving ${i}")
+            }
+        }
+        print("after")
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("leaving 1\nleaving 2\nafter", vm.get_output());
+}
+
+#[test]
+fn nested_finally_blocks_run_innermost_first() {
+    let program = r#"
+        fn run() {
+            try {
+                try {
+                    return 1
+                } finally {
+                    print("inner")
+                }
+            } finally {
+                print("outer")
+            }
+        }
+        print(run())
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("inner\nouter\n1", vm.get_output());
+}
+
+#[test]
+fn locals_declared_after_a_caught_error_get_their_own_slots() {
+    let program = r#"
+        try {
+            val y = 1
+            val q = [1][5]
+            print(y, q)
+        } catch (e) {
+            val message = e.message
+            print(message)
+        }
+        val z = 5
+        val w = z + 1
+        print(z, w)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!(
+        "Array index 5 out of bounds, length 1.\n5 6",
+        vm.get_output()
+    );
+}
+
+#[test]
+fn locals_after_a_try_statement_work_on_every_path() {
+    let program = r#"
+        fn attempt(index) {
+            try {
+                val items = [10, 20]
+                val item = items[index]
+                print(item)
+            } catch (e) {
+                val fallback = -1
+                print(fallback)
+            } finally {
+                val done = "done"
+                print(done)
+            }
+            val after = index * 2
+            val again = after + 1
+            return [after, again]
+        }
+        print(attempt(1))
+        print(attempt(7))
+        try {
+            val a = 1
+        } catch (e) {
+            val b = 2
+        }
+        val c = 3
+        print(c)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("20\ndone\n[2, 3]\n-1\ndone\n[14, 15]\n3", vm.get_output());
+}
+
+#[test]
+fn locals_after_an_error_in_the_catch_block_work() {
+    let program = r#"
+        fn attempt() {
+            try {
+                try {
+                    val first = [][0]
+                } catch (e) {
+                    val second = nil * 2
+                } finally {
+                    print("finally")
+                }
+            } catch (e) {
+                val caught = e.message
+                print(caught)
+            }
+            val later = "later"
+            val last = later + "!"
+            return last
+        }
+        print(attempt())
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!(
+        "finally\nOperand must be a number, got nil\nlater!",
+        vm.get_output()
+    );
+}
@@ -0,0 +1,58 @@
+use crate::vm::{Result, VirtualMachine};
+
+fn assert_runtime_error(program: &str, message: &str) {
+    let mut vm = VirtualMachine::new();
+    assert_eq!(
+        Result::RuntimeError,
+        vm.interpret(program.to_string()),
+        "{}",
+        program
+    );
+    assert!(
+        vm.get_runtime_errors().contains(message),
+        "{}: {}",
+        program,
+        vm.get_runtime_errors()
+    );
+}
+
+#[test]
+fn arithmetic_on_a_boolean_is_an_error() {
+    assert_runtime_error("print(true * 2)", "Operand must be a number, got boolean");
+}
+
+#[test]
+fn comparing_nil_is_an_error() {
+    assert_runtime_error("print(nil < 1)", "Operand must be a number, got nil");
+}
+
+#[test]
+fn subtracting_from_a_string_is_an_error() {
+    assert_runtime_error(r#"print("a" - 1)"#, "Operand must be a number, got string");
+}
+
+#[test]
+fn every_numeric_operator_checks_its_operands() {
+    for operator in [
+        "-", "*", "/", "//", "%", "**", "<", ">", "<=", ">=", "&", "|", "^", "<<", ">>",
+    ] {
+        assert_runtime_error(
+            &format!("val x = 1 {}false", operator),
+            "Operand must be a number, got boolean",
+        );
+    }
+}
+
+#[test]
+fn the_type_error_can_be_caught() {
+    let program = r#"
+        try {
+            print(nil * 2)
+        } catch (e) {
+            print(e.message)
+        }
+    "#;
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("Operand must be a number, got nil", vm.get_output());
+}
@@ -0,0 +1,132 @@
+use crate::vm::{Result, VirtualMachine};
+
+// A resource without a close() method makes the cleanup call observable:
+// it fails at runtime exactly where the cleanup runs.
+
+#[test]
+fn with_cleanup_runs_after_the_block() {
+    let program = r#"
+        fn use(resource) {
+            with (r = resource) {
+                print("body")
+            }
+            print("after cleanup")
+        }
+        use(42)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert_eq!("body", vm.get_output());
+    assert!(vm.get_runtime_errors().contains("Unknown method 'close'"));
+}
+
+#[test]
+fn with_cleanup_runs_before_early_return() {
+    let program = r#"
+        fn use(resource) {
+            with (r = resource) {
+                print("body")
+                return 1
+            }
+            print("unreachable")
+        }
+        use(42)
+        print("caller continued")
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert_eq!("body", vm.get_output());
+    assert!(vm.get_runtime_errors().contains("Unknown method 'close'"));
+}
+
+#[test]
+fn with_early_return_keeps_the_return_value() {
+    let program = r#"
+        fn first(path) {
+            with (f = File(path)) {
+                return path + "!"
+            }
+            return "late"
+        }
+        print(first("data.txt"))
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.interpret(program.to_string()));
+    assert_eq!("data.txt!", vm.get_output());
+}
+
+#[test]
+fn with_break_inside_nested_loop_does_not_close() {
+    let program = r#"
+        fn use(resource) {
+            with (r = resource) {
+                while (true) {
+                    break
+                }
+                print("still open")
+                return 0
+            }
+        }
+        use(42)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert_eq!("still open", vm.get_output());
+}
+
+#[test]
+fn with_labeled_break_past_the_block_closes() {
+    let program = r#"
+        fn use(resource) {
+            outer: while (true) {
+                with (r = resource) {
+                    while (true) {
+                        print("body")
+                        break outer
+                    }
+                    print("unreachable")
+                }
+            }
+            print("after loop")
+        }
+        use(42)
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert_eq!("body", vm.get_output());
+    assert!(vm.get_runtime_errors().contains("Unknown method 'close'"));
+}
+
+#[test]
+fn with_closes_when_the_body_throws() {
+    let program = r#"
+        struct Resource {
+            name
+
+            fn close() {
+                print("closed " + this.name)
+            }
+        }
+        try {
+            with (r = Resource("a")) {
+                print("body")
+                throw "failed"
+            }
+        } catch (e) {
+            print("caught " + e)
+        }
+        with (r = Resource("b")) {
+            print([1, 2][5])
+        }
+    "#;
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::RuntimeError, vm.interpret(program.to_string()));
+    assert_eq!("body\nclosed a\ncaught failed\nclosed b", vm.get_output());
+    assert!(vm.get_runtime_errors().contains("out of bounds"));
+}
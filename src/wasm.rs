@@ -48,6 +48,16 @@ impl NeonVM {
                 })
                 .unwrap()
             }
+            Result::Exit(code) => {
+                let output = self.vm.get_output();
+                self.vm.clear_output();
+                serde_wasm_bindgen::to_value(&InterpretResult {
+                    success: code == 0,
+                    output: Some(output),
+                    error: (code != 0).then(|| format!("Exited with status code {}", code)),
+                })
+                .unwrap()
+            }
         }
     }
 }
@@ -93,5 +103,14 @@ pub fn interpret_once(source: String) -> JsValue {
             })
             .unwrap()
         }
+        Result::Exit(code) => {
+            let output = vm.get_output();
+            serde_wasm_bindgen::to_value(&InterpretResult {
+                success: code == 0,
+                output: Some(output),
+                error: (code != 0).then(|| format!("Exited with status code {}", code)),
+            })
+            .unwrap()
+        }
     }
 }
@@ -0,0 +1,98 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("neon_cli_test_{}", name))
+}
+
+fn neon(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_neon"))
+        .args(args)
+        .output()
+        .expect("failed to run neon")
+}
+
+#[test]
+fn test_compile_then_run_bytecode() {
+    let source = temp_path("greet.n");
+    let bytecode = temp_path("greet.nbc");
+    fs::write(
+        &source,
+        "fn greet(name) {\n    return \"Hello, ${name}!\"\n}\nprint(greet(args[0]))\n",
+    )
+    .unwrap();
+
+    let compiled = neon(&[
+        "compile",
+        source.to_str().unwrap(),
+        "-o",
+        bytecode.to_str().unwrap(),
+    ]);
+    assert!(compiled.status.success());
+
+    let run = neon(&["run", bytecode.to_str().unwrap(), "Neon"]);
+    assert!(run.status.success());
+    assert!(String::from_utf8_lossy(&run.stdout).contains("Hello, Neon!"));
+
+    // A .nbc file is recognised without the run subcommand too
+    let run = neon(&[bytecode.to_str().unwrap(), "again"]);
+    assert!(String::from_utf8_lossy(&run.stdout).contains("Hello, again!"));
+
+    let _ = fs::remove_file(&source);
+    let _ = fs::remove_file(&bytecode);
+}
+
+#[test]
+fn test_compile_defaults_output_next_to_source() {
+    let source = temp_path("default_output.n");
+    let bytecode = temp_path("default_output.nbc");
+    let _ = fs::remove_file(&bytecode);
+    fs::write(&source, "print(1 + 2)\n").unwrap();
+
+    assert!(neon(&["compile", source.to_str().unwrap()])
+        .status
+        .success());
+    assert!(bytecode.exists());
+
+    let _ = fs::remove_file(&source);
+    let _ = fs::remove_file(&bytecode);
+}
+
+#[test]
+fn test_compile_reports_source_errors() {
+    let source = temp_path("broken.n");
+    fs::write(&source, "print(missing)\n").unwrap();
+
+    let compiled = neon(&["compile", source.to_str().unwrap()]);
+    assert_eq!(Some(65), compiled.status.code());
+    assert!(String::from_utf8_lossy(&compiled.stderr).contains("missing"));
+
+    let _ = fs::remove_file(&source);
+}
+
+#[test]
+fn test_run_rejects_other_format_versions() {
+    let source = temp_path("versioned.n");
+    let bytecode = temp_path("versioned.nbc");
+    fs::write(&source, "print(1)\n").unwrap();
+    assert!(neon(&[
+        "compile",
+        source.to_str().unwrap(),
+        "-o",
+        bytecode.to_str().unwrap()
+    ])
+    .status
+    .success());
+
+    let mut bytes = fs::read(&bytecode).unwrap();
+    bytes[4] = bytes[4].wrapping_add(1);
+    fs::write(&bytecode, bytes).unwrap();
+
+    let run = neon(&["run", bytecode.to_str().unwrap()]);
+    assert_eq!(Some(65), run.status.code());
+    assert!(String::from_utf8_lossy(&run.stderr).contains("recompile"));
+
+    let _ = fs::remove_file(&source);
+    let _ = fs::remove_file(&bytecode);
+}
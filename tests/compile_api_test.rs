@@ -0,0 +1,38 @@
+use neon::vm::{Result, VirtualMachine};
+
+#[test]
+fn test_compiled_chunk_runs_in_a_vm() {
+    let chunk = neon::compile(
+        r#"
+        fn greet(name) {
+            return "Hello, ${name}!"
+        }
+        print(greet("Neon"))
+        print([1, 2, 3].sum())
+    "#,
+    )
+    .expect("program should compile");
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(Result::Ok, vm.execute_chunk(chunk));
+    assert_eq!("Hello, Neon!\n6", vm.get_output());
+}
+
+#[test]
+fn test_compiled_chunk_sees_script_args() {
+    let chunk = neon::compile("print(args)\n").expect("program should compile");
+
+    let mut vm = VirtualMachine::with_args(vec!["a".to_string(), "b".to_string()]);
+    assert_eq!(Result::Ok, vm.execute_chunk(chunk));
+    assert_eq!("[a, b]", vm.get_output());
+}
+
+#[test]
+fn test_compile_returns_all_errors() {
+    let errors = neon::compile("print(missing)\nprint(alsoMissing)\n")
+        .expect_err("program should not compile");
+
+    assert_eq!(2, errors.len());
+    assert!(errors[0].message.contains("missing"));
+    assert!(errors[1].message.contains("alsoMissing"));
+}